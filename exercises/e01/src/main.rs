@@ -5,19 +5,186 @@
 //!
 //! ## Features
 //!
-//! - **Input Validation**: Ensures names contain only alphabetic characters and spaces
+//! - **Name Parsing**: Splits input into title, given, and family names, accepting hyphenated
+//!   surnames, apostrophes, and surname particles such as "van" or "de la"
 //! - **Personalized Greetings**: Provides different greetings based on the first letter of the name
 //! - **Case Handling**: Processes names in a case-insensitive manner
 //! - **Unicode Support**: Properly handles non-ASCII characters in names
 //! - **Whitespace Handling**: Trims excess whitespace from user input
 //! - **Error Handling**: Provides clear feedback for invalid inputs
+//! - **Forms of Address**: Optionally asks first-time visitors for an honorific and pronouns,
+//!   remembering them alongside each name's visit history for later greetings
+//! - **Greeting Rules**: Loads greeting groups from a JSON rules file (`--rules <file>`) that
+//!   matches names by first-letter range or wildcard pattern and fills in custom templates
+//! - **Localized Greetings**: Detects the system locale from `LC_ALL`, `LC_MESSAGES`, or
+//!   `LANG` and loads greetings from per-locale rules files, falling back from region to
+//!   language to English
+//! - **Time-Aware Greetings**: Opens with good morning, afternoon, or evening based on the local
+//!   time, and welcomes returning visitors back (`--reset-history` forgets past visitors)
+use chrono::Timelike;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io::Write;
+use std::path::Path;
 
-fn is_valid_name(name: &str) -> bool {
-    !name.trim().is_empty() && name.chars().all(|c| c.is_alphabetic() || c.is_whitespace())
+const VISITORS_FILE: &str = "exercises/e01/visitors.json";
+const NAME_PLACEHOLDER: &str = "{name}";
+const LOCALES_DIR: &str = "exercises/e01/locales";
+const FALLBACK_LOCALE: &str = "en";
+const LOCALE_VARS: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+/// How a visitor prefers to be addressed.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+struct Preferences {
+    honorific: Option<String>,
+    pronouns: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+struct Visitor {
+    visits: u32,
+    #[serde(default)]
+    preferences: Preferences,
+}
+
+/// Visit history and preferences keyed by lowercase name.
+type VisitorStore = HashMap<String, Visitor>;
+
+fn visitor_key(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn load_visitors(file_path: &Path) -> Result<VisitorStore, Box<dyn std::error::Error>> {
+    if !file_path.exists() {
+        return Ok(VisitorStore::new());
+    }
+    let file = std::fs::File::open(file_path)?;
+    Ok(serde_json::from_reader(file)?)
+}
+
+fn save_visitors(
+    file_path: &Path,
+    visitors: &VisitorStore,
+) -> Result<(), Box<dyn std::error::Error>> {
+    persist::write_json(file_path, visitors)?;
+    Ok(())
+}
+
+const TITLES: [&str; 9] = ["Mr", "Mrs", "Ms", "Mx", "Dr", "Prof", "Rev", "Sir", "Dame"];
+// Lowercase words that belong to the family name that follows them.
+const SURNAME_PARTICLES: [&str; 11] = [
+    "van", "von", "der", "den", "de", "la", "del", "da", "di", "du", "le",
+];
+
+/// A parsed name. Middle names are kept with the given name.
+#[derive(Debug, Clone, PartialEq)]
+struct Name {
+    title: Option<String>,
+    given: String,
+    family: Option<String>,
+}
+
+impl Name {
+    /// The name without its title, as typed.
+    fn full(&self) -> String {
+        match &self.family {
+            Some(family) => format!("{} {}", self.given, family),
+            None => self.given.clone(),
+        }
+    }
+
+    /// The name to use in a greeting: the family name when addressing someone formally with an
+    /// honorific, otherwise the full name.
+    fn greeting_name(&self, formal: bool) -> String {
+        match &self.family {
+            Some(family) if formal => family.clone(),
+            _ => self.full(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum NameError {
+    Empty,
+    OnlyTitle,
+    InvalidWord(String),
+}
+
+impl std::fmt::Display for NameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NameError::Empty => write!(f, "Please enter a name."),
+            NameError::OnlyTitle => write!(f, "Please enter a name after the title."),
+            NameError::InvalidWord(word) => write!(
+                f,
+                "'{}' is not a valid name. Names may contain letters, hyphens, and apostrophes.",
+                word
+            ),
+        }
+    }
+}
+
+/// Returns the canonical title if `word` is one, with or without a trailing period.
+fn parse_title(word: &str) -> Option<String> {
+    let bare = word.strip_suffix('.').unwrap_or(word);
+    TITLES
+        .iter()
+        .find(|title| title.eq_ignore_ascii_case(bare))
+        .map(|title| match *title {
+            "Sir" | "Dame" => title.to_string(),
+            _ => format!("{}.", title),
+        })
+}
+
+/// A name word is runs of letters joined by single hyphens or apostrophes, e.g. "O'Reilly" or
+/// "Smith-Jones".
+fn is_valid_name_word(word: &str) -> bool {
+    word.split(['-', '\'', '’'])
+        .all(|part| !part.is_empty() && part.chars().all(char::is_alphabetic))
 }
 
-fn prompt_for_name() -> String {
+fn parse_name(input: &str) -> Result<Name, NameError> {
+    let mut words: Vec<&str> = input.split_whitespace().collect();
+    if words.is_empty() {
+        return Err(NameError::Empty);
+    }
+
+    let title = parse_title(words[0]);
+    if title.is_some() {
+        words.remove(0);
+        if words.is_empty() {
+            return Err(NameError::OnlyTitle);
+        }
+    }
+    if let Some(word) = words.iter().find(|word| !is_valid_name_word(word)) {
+        return Err(NameError::InvalidWord(word.to_string()));
+    }
+
+    // The family name is the last word plus any particles before it, but the first word is
+    // always part of the given name.
+    let mut family_start = words.len() - 1;
+    while family_start > 1 && SURNAME_PARTICLES.contains(&words[family_start - 1]) {
+        family_start -= 1;
+    }
+    let (given, family) = if words.len() == 1 {
+        (words[0].to_string(), None)
+    } else {
+        (
+            words[..family_start].join(" "),
+            Some(words[family_start..].join(" ")),
+        )
+    };
+    Ok(Name {
+        title,
+        given,
+        family,
+    })
+}
+
+fn prompt_for_name() -> Name {
     loop {
         print!("What is your name? ");
         let mut input = String::new();
@@ -31,29 +198,429 @@ fn prompt_for_name() -> String {
             continue;
         }
 
-        if is_valid_name(&input) {
-            return input.trim().to_string();
-        } else {
-            println!("Invalid name. Please enter a valid name containing only alphabetic characters and spaces.");
+        match parse_name(&input) {
+            Ok(name) => return name,
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
+fn prompt_for_optional(prompt: &str, is_valid: fn(&str) -> bool) -> Option<String> {
+    loop {
+        print!("{} ", prompt);
+        let mut input = String::new();
+        if let Err(e) = std::io::stdout().flush() {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        if let Err(e) = std::io::stdin().read_line(&mut input) {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+        if is_valid(input) {
+            return Some(input.to_string());
+        }
+        println!("Invalid input. Please try again or leave it blank to skip.");
+    }
+}
+
+fn is_valid_honorific(honorific: &str) -> bool {
+    honorific.chars().all(|c| c.is_alphabetic() || c == '.')
+}
+
+fn is_valid_pronouns(pronouns: &str) -> bool {
+    pronouns
+        .chars()
+        .all(|c| c.is_alphabetic() || c == '/' || c.is_whitespace())
+}
+
+/// Asks for a form of address, skipping the honorific question when the name came with a
+/// title.
+fn prompt_for_preferences(title: Option<&str>) -> Preferences {
+    Preferences {
+        honorific: match title {
+            Some(title) => Some(title.to_string()),
+            None => prompt_for_optional(
+                "How should we address you (e.g. Dr., Ms., Mx.)? Leave blank to skip:",
+                is_valid_honorific,
+            ),
+        },
+        pronouns: prompt_for_optional(
+            "What are your pronouns (e.g. she/her, they/them)? Leave blank to skip:",
+            is_valid_pronouns,
+        ),
+    }
+}
+
+/// Formats the name with the visitor's honorific and pronouns, when they gave any.
+fn form_of_address(name: &str, preferences: &Preferences) -> String {
+    let mut address = match &preferences.honorific {
+        Some(honorific) => format!("{} {}", honorific, name),
+        None => name.to_string(),
+    };
+    if let Some(pronouns) = &preferences.pronouns {
+        address.push_str(&format!(" ({})", pronouns));
+    }
+    address
+}
+
+/// A rule as written in a rules file. Exactly one of `letters` or `pattern` must be set.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleSpec {
+    letters: Option<String>,
+    pattern: Option<String>,
+    template: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Salutations {
+    morning: String,
+    afternoon: String,
+    evening: String,
+}
+
+impl Default for Salutations {
+    fn default() -> Self {
+        Self {
+            morning: "Good morning!".to_string(),
+            afternoon: "Good afternoon!".to_string(),
+            evening: "Good evening!".to_string(),
+        }
+    }
+}
+
+fn default_returning() -> String {
+    "Welcome back, {name}!".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    rules: Vec<RuleSpec>,
+    default: String,
+    #[serde(default = "default_returning")]
+    returning: String,
+    #[serde(default)]
+    salutations: Salutations,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TimeOfDay {
+    Morning,
+    Afternoon,
+    Evening,
+}
+
+impl TimeOfDay {
+    /// Morning runs from 5 AM to noon and afternoon until 5 PM; the rest is evening.
+    fn from_hour(hour: u32) -> TimeOfDay {
+        match hour {
+            5..=11 => TimeOfDay::Morning,
+            12..=16 => TimeOfDay::Afternoon,
+            _ => TimeOfDay::Evening,
         }
     }
 }
 
-fn generate_greeting(name: &str) -> String {
-    // Provide one of two greetings based on where the first character of the name falls in the
-    // alphabet. Names starting with letters A-M get one greeting, and names starting with N-Z get
-    // another.
-    let first_char = name.chars().next().unwrap_or(' ');
-    if first_char.is_alphabetic() && first_char.to_ascii_lowercase() < 'n' {
-        format!("Hello, {}! Nice to meet you!", name)
+#[derive(Debug, Clone, PartialEq)]
+enum Matcher {
+    /// An inclusive, case-insensitive range for the first letter of the name.
+    Letters(char, char),
+    /// A case-insensitive wildcard pattern over the whole name, where `*` matches any run of
+    /// characters and `?` matches exactly one.
+    Pattern(Vec<char>),
+}
+
+impl Matcher {
+    fn parse_letters(range: &str) -> Result<Matcher, String> {
+        let letters: Vec<char> = range.trim().chars().flat_map(char::to_lowercase).collect();
+        let (start, end) = match letters[..] {
+            [letter] => (letter, letter),
+            [start, '-', end] => (start, end),
+            _ => {
+                return Err(format!(
+                    "letters '{}' must be a letter or a range like 'A-M'",
+                    range
+                ))
+            }
+        };
+        if !start.is_alphabetic() || !end.is_alphabetic() {
+            return Err(format!("letters '{}' must only contain letters", range));
+        }
+        if start > end {
+            return Err(format!("letters '{}' is a reversed range", range));
+        }
+        Ok(Matcher::Letters(start, end))
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        let name: Vec<char> = name.chars().flat_map(char::to_lowercase).collect();
+        match self {
+            Matcher::Letters(start, end) => name
+                .first()
+                .is_some_and(|first| (start..=end).contains(&first)),
+            Matcher::Pattern(pattern) => wildcard_match(pattern, &name),
+        }
+    }
+}
+
+fn wildcard_match(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| wildcard_match(rest, &name[skip..])),
+        Some(('?', rest)) => !name.is_empty() && wildcard_match(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && wildcard_match(rest, &name[1..]),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct GreetingRule {
+    matcher: Matcher,
+    template: String,
+}
+
+/// Ordered greeting rules; the first rule matching a name picks its template.
+#[derive(Debug, Clone, PartialEq)]
+struct GreetingRules {
+    rules: Vec<GreetingRule>,
+    default: String,
+    returning: String,
+    salutations: Salutations,
+}
+
+impl Default for GreetingRules {
+    /// Names starting with letters A-M get one greeting, and everyone else gets another.
+    fn default() -> Self {
+        Self {
+            rules: vec![GreetingRule {
+                matcher: Matcher::Letters('a', 'm'),
+                template: "Hello, {name}! Nice to meet you!".to_string(),
+            }],
+            default: "Hello, {name}! It's great to see you!".to_string(),
+            returning: default_returning(),
+            salutations: Salutations::default(),
+        }
+    }
+}
+
+fn validate_template(template: &str) -> Result<(), String> {
+    if template.contains(NAME_PLACEHOLDER) {
+        Ok(())
     } else {
-        format!("Hello, {}! It's great to see you!", name)
+        Err(format!(
+            "template '{}' is missing the {} placeholder",
+            template, NAME_PLACEHOLDER
+        ))
+    }
+}
+
+impl GreetingRules {
+    fn parse(json: &str) -> Result<GreetingRules, String> {
+        let file: RulesFile = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        validate_template(&file.default).map_err(|e| format!("default: {}", e))?;
+        validate_template(&file.returning).map_err(|e| format!("returning: {}", e))?;
+
+        let mut rules = Vec::new();
+        for (i, spec) in file.rules.into_iter().enumerate() {
+            let compile = || -> Result<GreetingRule, String> {
+                let matcher = match (&spec.letters, &spec.pattern) {
+                    (Some(letters), None) => Matcher::parse_letters(letters)?,
+                    (None, Some(pattern)) if !pattern.trim().is_empty() => Matcher::Pattern(
+                        pattern
+                            .trim()
+                            .chars()
+                            .flat_map(char::to_lowercase)
+                            .collect(),
+                    ),
+                    (None, Some(_)) => return Err("pattern must not be empty".to_string()),
+                    _ => {
+                        return Err("exactly one of 'letters' or 'pattern' is required".to_string())
+                    }
+                };
+                validate_template(&spec.template)?;
+                Ok(GreetingRule {
+                    matcher,
+                    template: spec.template.clone(),
+                })
+            };
+            rules.push(compile().map_err(|e| format!("rule {}: {}", i + 1, e))?);
+        }
+        Ok(GreetingRules {
+            rules,
+            default: file.default,
+            returning: file.returning,
+            salutations: file.salutations,
+        })
+    }
+
+    fn load(file_path: &Path) -> Result<GreetingRules, String> {
+        let json = std::fs::read_to_string(file_path)
+            .map_err(|e| format!("{}: {}", file_path.display(), e))?;
+        GreetingRules::parse(&json).map_err(|e| format!("{}: {}", file_path.display(), e))
+    }
+
+    fn salutation(&self, time_of_day: TimeOfDay) -> &str {
+        match time_of_day {
+            TimeOfDay::Morning => &self.salutations.morning,
+            TimeOfDay::Afternoon => &self.salutations.afternoon,
+            TimeOfDay::Evening => &self.salutations.evening,
+        }
+    }
+
+    fn template_for(&self, name: &str) -> &str {
+        self.rules
+            .iter()
+            .find(|rule| rule.matcher.matches(name))
+            .map_or(&self.default, |rule| &rule.template)
+    }
+}
+
+fn generate_greeting(name: &str, preferences: &Preferences, rules: &GreetingRules) -> String {
+    rules
+        .template_for(name)
+        .replace(NAME_PLACEHOLDER, &form_of_address(name, preferences))
+}
+
+/// Opens with a salutation for the time of day, then welcomes returning visitors back or greets
+/// new ones by the matching rule.
+fn personalized_greeting(
+    name: &str,
+    preferences: &Preferences,
+    rules: &GreetingRules,
+    time_of_day: TimeOfDay,
+    returning: bool,
+) -> String {
+    let greeting = if returning {
+        rules
+            .returning
+            .replace(NAME_PLACEHOLDER, &form_of_address(name, preferences))
+    } else {
+        generate_greeting(name, preferences, rules)
+    };
+    format!("{} {}", rules.salutation(time_of_day), greeting)
+}
+
+fn parse_rules_path(args: &[String]) -> Result<Option<&str>, String> {
+    match args.iter().position(|arg| arg == "--rules") {
+        Some(i) => args
+            .get(i + 1)
+            .map(|path| Some(path.as_str()))
+            .ok_or_else(|| "--rules requires a file path".to_string()),
+        None => Ok(None),
+    }
+}
+
+/// Returns the first locale set in the environment, skipping the untranslated "C" and
+/// "POSIX" locales.
+fn detect_locale(var: impl Fn(&str) -> Option<String>) -> Option<String> {
+    LOCALE_VARS
+        .iter()
+        .filter_map(|name| var(name))
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+}
+
+/// Lists the resource files to try for `locale`, most specific first: "pt_BR.UTF-8" becomes
+/// `["pt-br", "pt", "en"]`.
+fn locale_chain(locale: Option<&str>) -> Vec<String> {
+    let mut chain = Vec::new();
+    if let Some(locale) = locale {
+        let tag = locale
+            .split(['.', '@'])
+            .next()
+            .unwrap_or_default()
+            .replace('_', "-")
+            .to_lowercase();
+        if let Some((language, _)) = tag.split_once('-') {
+            chain.push(tag.clone());
+            chain.push(language.to_string());
+        } else if !tag.is_empty() {
+            chain.push(tag);
+        }
+    }
+    if !chain.iter().any(|tag| tag == FALLBACK_LOCALE) {
+        chain.push(FALLBACK_LOCALE.to_string());
     }
+    chain
+}
+
+/// Loads the first locale in `chain` with a resource file in `dir`, or the built-in English
+/// rules when none exist.
+fn load_localized_rules(dir: &Path, chain: &[String]) -> Result<GreetingRules, String> {
+    chain
+        .iter()
+        .map(|tag| dir.join(format!("{}.json", tag)))
+        .find(|path| path.exists())
+        .map_or_else(
+            || Ok(GreetingRules::default()),
+            |path| GreetingRules::load(&path),
+        )
 }
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let rules = match parse_rules_path(&args) {
+        Ok(Some(path)) => GreetingRules::load(Path::new(path)),
+        Ok(None) => {
+            let locale = detect_locale(|name| std::env::var(name).ok());
+            load_localized_rules(Path::new(LOCALES_DIR), &locale_chain(locale.as_deref()))
+        }
+        Err(e) => Err(e),
+    };
+    let rules = match rules {
+        Ok(rules) => rules,
+        Err(e) => {
+            eprintln!("Error loading greeting rules: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let file_path = Path::new(VISITORS_FILE);
+    if args.iter().any(|arg| arg == "--reset-history") {
+        match std::fs::remove_file(file_path) {
+            Ok(()) => println!("Visitor history cleared."),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => eprintln!("Error clearing visitor history: {}", e),
+        }
+    }
+    let mut visitors = match load_visitors(file_path) {
+        Ok(visitors) => visitors,
+        Err(e) => {
+            eprintln!("Error loading visitor history: {}", e);
+            VisitorStore::new()
+        }
+    };
+
     let name = prompt_for_name();
-    println!("{}", generate_greeting(&name));
+    let visitor = visitors.entry(visitor_key(&name.full())).or_default();
+    let returning = visitor.visits > 0;
+    if !returning {
+        visitor.preferences = prompt_for_preferences(name.title.as_deref());
+    }
+    visitor.visits += 1;
+    let greeting_name = name.greeting_name(visitor.preferences.honorific.is_some());
+    println!(
+        "{}",
+        personalized_greeting(
+            &greeting_name,
+            &visitor.preferences,
+            &rules,
+            TimeOfDay::from_hour(chrono::Local::now().hour()),
+            returning,
+        )
+    );
+
+    if let Err(e) = save_visitors(file_path, &visitors) {
+        eprintln!("Error saving visitor history: {}", e);
+    }
 }
 
 #[cfg(test)]
@@ -61,97 +628,351 @@ mod tests {
     use super::*;
 
     #[test]
-    fn is_valid_name_accepts_single_word_name() {
-        assert!(is_valid_name("John"));
-        assert!(is_valid_name("Mary"));
-        assert!(is_valid_name("Alexandria"));
+    fn generate_greeting_uses_form_of_address() {
+        let preferences = Preferences {
+            honorific: Some("Dr.".to_string()),
+            pronouns: Some("they/them".to_string()),
+        };
+        assert_eq!(
+            generate_greeting("Nancy", &preferences, &GreetingRules::default()),
+            "Hello, Dr. Nancy (they/them)! It's great to see you!"
+        );
+        let honorific_only = Preferences {
+            honorific: Some("Ms.".to_string()),
+            pronouns: None,
+        };
+        assert_eq!(
+            generate_greeting("Mary", &honorific_only, &GreetingRules::default()),
+            "Hello, Ms. Mary! Nice to meet you!"
+        );
+    }
+
+    #[test]
+    fn greeting_rules_pick_first_matching_template() {
+        let rules = GreetingRules::load(Path::new("inputs/greeting_rules.json")).unwrap();
+        let none = Preferences::default();
+        assert_eq!(
+            generate_greeting("Anderson", &none, &rules),
+            "Ahoy, Anderson! Welcome aboard!"
+        );
+        assert_eq!(
+            generate_greeting("Beth", &none, &rules),
+            "Hello, Beth! Nice to meet you!"
+        );
+        assert_eq!(
+            generate_greeting("helen", &none, &rules),
+            "Hi there, helen! Glad you could make it!"
+        );
+        assert_eq!(
+            generate_greeting("Q", &none, &rules),
+            "Hey, Q! Short and sweet!"
+        );
+        assert_eq!(
+            generate_greeting("Zoe", &none, &rules),
+            "Hello, Zoe! It's great to see you!"
+        );
+    }
+
+    #[test]
+    fn greeting_rules_report_malformed_files() {
+        let parse = |json: &str| GreetingRules::parse(json).unwrap_err();
+        assert_eq!(
+            parse(
+                r#"{"rules": [{"letters": "M-A", "template": "Hi {name}"}], "default": "{name}"}"#
+            ),
+            "rule 1: letters 'M-A' is a reversed range"
+        );
+        assert_eq!(
+            parse(r#"{"rules": [{"template": "Hi {name}"}], "default": "{name}"}"#),
+            "rule 1: exactly one of 'letters' or 'pattern' is required"
+        );
+        assert_eq!(
+            parse(r#"{"rules": [], "default": "Hi"}"#),
+            "default: template 'Hi' is missing the {name} placeholder"
+        );
+        assert!(parse(r#"{"rules": [], "default": "{name}", "extra": 1}"#).contains("line 1"));
+    }
+
+    #[test]
+    fn detect_locale_follows_variable_precedence() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(
+            detect_locale(env(&[("LANG", "fr_FR.UTF-8"), ("LC_ALL", "de_DE")])),
+            Some("de_DE".to_string())
+        );
+        assert_eq!(
+            detect_locale(env(&[("LC_ALL", ""), ("LANG", "es_MX.UTF-8")])),
+            Some("es_MX.UTF-8".to_string())
+        );
+        assert_eq!(detect_locale(env(&[("LANG", "C")])), None);
+    }
+
+    #[test]
+    fn locale_chain_falls_back_to_language_then_english() {
+        assert_eq!(locale_chain(Some("pt_BR.UTF-8")), vec!["pt-br", "pt", "en"]);
+        assert_eq!(locale_chain(Some("de_DE@euro")), vec!["de-de", "de", "en"]);
+        assert_eq!(locale_chain(Some("en_US")), vec!["en-us", "en"]);
+        assert_eq!(locale_chain(None), vec!["en"]);
+    }
+
+    #[test]
+    fn load_localized_rules_uses_first_available_locale() {
+        let dir = Path::new("locales");
+        let none = Preferences::default();
+        let german = load_localized_rules(dir, &locale_chain(Some("de_AT.UTF-8"))).unwrap();
+        assert_eq!(
+            generate_greeting("Anna", &none, &german),
+            "Hallo, Anna! Schön, dich kennenzulernen!"
+        );
+        let unknown = load_localized_rules(dir, &locale_chain(Some("xx_YY"))).unwrap();
+        assert_eq!(
+            unknown,
+            load_localized_rules(dir, &["en".to_string()]).unwrap()
+        );
+        assert_eq!(
+            load_localized_rules(Path::new("missing"), &locale_chain(Some("fr"))).unwrap(),
+            GreetingRules::default()
+        );
+
+        for tag in ["en", "es", "fr", "de", "it", "pt", "pt-br"] {
+            assert!(
+                load_localized_rules(dir, &[tag.to_string()]).is_ok(),
+                "{}",
+                tag
+            );
+        }
+    }
+
+    #[test]
+    fn time_of_day_splits_the_day() {
+        assert_eq!(TimeOfDay::from_hour(4), TimeOfDay::Evening);
+        assert_eq!(TimeOfDay::from_hour(5), TimeOfDay::Morning);
+        assert_eq!(TimeOfDay::from_hour(12), TimeOfDay::Afternoon);
+        assert_eq!(TimeOfDay::from_hour(17), TimeOfDay::Evening);
+        assert_eq!(TimeOfDay::from_hour(23), TimeOfDay::Evening);
     }
 
     #[test]
-    fn is_valid_name_accepts_multi_word_names() {
-        assert!(is_valid_name("John Doe"));
-        assert!(is_valid_name("Mary Jane Watson"));
-        assert!(is_valid_name("James Robert Smith"));
+    fn personalized_greeting_welcomes_returning_visitors() {
+        let rules = GreetingRules::default();
+        let none = Preferences::default();
+        assert_eq!(
+            personalized_greeting("Adam", &none, &rules, TimeOfDay::Morning, false),
+            "Good morning! Hello, Adam! Nice to meet you!"
+        );
+        assert_eq!(
+            personalized_greeting("Adam", &none, &rules, TimeOfDay::Evening, true),
+            "Good evening! Welcome back, Adam!"
+        );
+
+        let spanish = GreetingRules::load(Path::new("locales/es.json")).unwrap();
+        assert_eq!(
+            personalized_greeting("Zoe", &none, &spanish, TimeOfDay::Afternoon, true),
+            "¡Buenas tardes! ¡Bienvenido de nuevo, Zoe!"
+        );
     }
 
     #[test]
-    fn is_valid_name_rejects_empty_strings() {
-        assert!(!is_valid_name(""));
-        assert!(!is_valid_name("   "));
-        assert!(!is_valid_name("\t\n"));
+    fn visitors_round_trip_through_store() {
+        let path = std::env::temp_dir().join(format!("e01_visitors_{}.json", std::process::id()));
+        assert!(load_visitors(&path).unwrap().is_empty());
+
+        let mut visitors = VisitorStore::new();
+        visitors.insert(
+            visitor_key("  Mary   Jane "),
+            Visitor {
+                visits: 2,
+                preferences: Preferences {
+                    honorific: Some("Ms.".to_string()),
+                    pronouns: None,
+                },
+            },
+        );
+        save_visitors(&path, &visitors).unwrap();
+        let loaded = load_visitors(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, visitors);
+        assert!(loaded.contains_key("mary jane"));
+    }
+
+    fn parsed(input: &str) -> (Option<String>, String, Option<String>) {
+        let name = parse_name(input).unwrap();
+        (name.title, name.given, name.family)
     }
 
     #[test]
-    fn is_valid_name_rejects_names_with_numbers() {
-        assert!(!is_valid_name("John2"));
-        assert!(!is_valid_name("Mary 123"));
-        assert!(!is_valid_name("Agent007"));
+    fn parse_name_accepts_single_word_name() {
+        assert_eq!(parsed("John"), (None, "John".to_string(), None));
+        assert_eq!(parsed("Alexandria").1, "Alexandria");
     }
 
     #[test]
-    fn is_valid_name_rejects_names_with_symbols() {
-        assert!(!is_valid_name("John!"));
-        assert!(!is_valid_name("Mary-Jane"));
-        assert!(!is_valid_name("Smith@example.com"));
-        assert!(!is_valid_name("O'Reilly"));
+    fn parse_name_splits_multi_word_names() {
+        assert_eq!(
+            parsed("John Doe"),
+            (None, "John".to_string(), Some("Doe".to_string()))
+        );
+        assert_eq!(
+            parsed("James Robert Smith"),
+            (None, "James Robert".to_string(), Some("Smith".to_string()))
+        );
+        assert_eq!(
+            parsed("Ludwig van Beethoven"),
+            (
+                None,
+                "Ludwig".to_string(),
+                Some("van Beethoven".to_string())
+            )
+        );
+        assert_eq!(parsed("Juan de la Cruz").2, Some("de la Cruz".to_string()));
     }
 
     #[test]
-    fn is_valid_name_handles_whitespace_correctly() {
-        assert!(is_valid_name("John Doe"));
-        assert!(is_valid_name("   John   ")); // Will be trimmed
-        assert!(is_valid_name("\tJohn\n")); // Will be trimmed
+    fn parse_name_recognizes_titles() {
+        assert_eq!(
+            parsed("dr Jane Smith"),
+            (
+                Some("Dr.".to_string()),
+                "Jane".to_string(),
+                Some("Smith".to_string())
+            )
+        );
+        assert_eq!(parsed("Ms. Mary").0.as_deref(), Some("Ms."));
+        assert_eq!(parsed("Sir Patrick Stewart").0.as_deref(), Some("Sir"));
+        assert_eq!(parse_name("Dr."), Err(NameError::OnlyTitle));
     }
 
     #[test]
-    fn is_valid_name_handles_non_ascii_letters_correctly() {
-        assert!(is_valid_name("José"));
-        assert!(is_valid_name("Søren"));
-        assert!(is_valid_name("Naïve"));
+    fn parse_name_accepts_hyphens_and_apostrophes() {
+        assert_eq!(
+            parsed("Mary-Jane O'Reilly"),
+            (None, "Mary-Jane".to_string(), Some("O'Reilly".to_string()))
+        );
+        assert_eq!(
+            parsed("Anna Smith-Jones").2,
+            Some("Smith-Jones".to_string())
+        );
+        assert_eq!(parsed("D’Angelo").1, "D’Angelo");
+    }
+
+    #[test]
+    fn parse_name_rejects_empty_strings() {
+        assert_eq!(parse_name(""), Err(NameError::Empty));
+        assert_eq!(parse_name("   "), Err(NameError::Empty));
+        assert_eq!(parse_name("\t\n"), Err(NameError::Empty));
+    }
+
+    #[test]
+    fn parse_name_rejects_names_with_numbers() {
+        assert!(parse_name("John2").is_err());
+        assert_eq!(
+            parse_name("Mary 123"),
+            Err(NameError::InvalidWord("123".to_string()))
+        );
+        assert!(parse_name("Agent007").is_err());
+    }
+
+    #[test]
+    fn parse_name_rejects_names_with_symbols() {
+        assert!(parse_name("John!").is_err());
+        assert!(parse_name("Smith@example.com").is_err());
+        assert!(parse_name("-Mary").is_err());
+        assert!(parse_name("O''Reilly").is_err());
+        assert!(parse_name("Mary-").is_err());
+    }
+
+    #[test]
+    fn parse_name_handles_whitespace_correctly() {
+        assert_eq!(parsed("   John   ").1, "John");
+        assert_eq!(parsed("\tJohn\n").1, "John");
+        assert_eq!(parse_name("  John   Doe ").unwrap().full(), "John Doe");
+    }
+
+    #[test]
+    fn parse_name_handles_non_ascii_letters_correctly() {
+        assert_eq!(parsed("José").1, "José");
+        assert_eq!(parsed("Søren Kierkegaard").1, "Søren");
+        assert_eq!(parsed("Naïve").1, "Naïve");
+    }
+
+    #[test]
+    fn greeting_name_uses_family_name_when_formal() {
+        let name = parse_name("Dr. Jane Smith").unwrap();
+        assert_eq!(name.greeting_name(true), "Smith");
+        assert_eq!(name.greeting_name(false), "Jane Smith");
+        assert_eq!(parse_name("Cher").unwrap().greeting_name(true), "Cher");
     }
 
     #[test]
     fn generate_greeting_provides_first_greeting_for_a_to_m_names() {
-        assert_eq!(generate_greeting("Adam"), "Hello, Adam! Nice to meet you!");
-        assert_eq!(generate_greeting("John"), "Hello, John! Nice to meet you!");
-        assert_eq!(generate_greeting("Mary"), "Hello, Mary! Nice to meet you!");
+        assert_eq!(
+            generate_greeting("Adam", &Preferences::default(), &GreetingRules::default()),
+            "Hello, Adam! Nice to meet you!"
+        );
+        assert_eq!(
+            generate_greeting("John", &Preferences::default(), &GreetingRules::default()),
+            "Hello, John! Nice to meet you!"
+        );
+        assert_eq!(
+            generate_greeting("Mary", &Preferences::default(), &GreetingRules::default()),
+            "Hello, Mary! Nice to meet you!"
+        );
     }
 
     #[test]
     fn generate_greeting_provides_second_greeting_for_n_to_z_names() {
         assert_eq!(
-            generate_greeting("Nancy"),
+            generate_greeting("Nancy", &Preferences::default(), &GreetingRules::default()),
             "Hello, Nancy! It's great to see you!"
         );
         assert_eq!(
-            generate_greeting("Peter"),
+            generate_greeting("Peter", &Preferences::default(), &GreetingRules::default()),
             "Hello, Peter! It's great to see you!"
         );
         assert_eq!(
-            generate_greeting("Zoe"),
+            generate_greeting("Zoe", &Preferences::default(), &GreetingRules::default()),
             "Hello, Zoe! It's great to see you!"
         );
     }
 
     #[test]
     fn generate_greeting_handles_case_insensitive_comparisons() {
-        assert_eq!(generate_greeting("adam"), "Hello, adam! Nice to meet you!");
-        assert_eq!(generate_greeting("MARY"), "Hello, MARY! Nice to meet you!");
         assert_eq!(
-            generate_greeting("Nathan"),
+            generate_greeting("adam", &Preferences::default(), &GreetingRules::default()),
+            "Hello, adam! Nice to meet you!"
+        );
+        assert_eq!(
+            generate_greeting("MARY", &Preferences::default(), &GreetingRules::default()),
+            "Hello, MARY! Nice to meet you!"
+        );
+        assert_eq!(
+            generate_greeting("Nathan", &Preferences::default(), &GreetingRules::default()),
             "Hello, Nathan! It's great to see you!"
         );
     }
 
     #[test]
     fn generate_greeting_handles_empty_and_non_alphabetic_first_characters() {
-        assert_eq!(generate_greeting(""), "Hello, ! It's great to see you!");
         assert_eq!(
-            generate_greeting("123John"),
+            generate_greeting("", &Preferences::default(), &GreetingRules::default()),
+            "Hello, ! It's great to see you!"
+        );
+        assert_eq!(
+            generate_greeting(
+                "123John",
+                &Preferences::default(),
+                &GreetingRules::default()
+            ),
             "Hello, 123John! It's great to see you!"
         );
         assert_eq!(
-            generate_greeting(" Alice"),
+            generate_greeting(" Alice", &Preferences::default(), &GreetingRules::default()),
             "Hello,  Alice! It's great to see you!"
         );
     }
@@ -0,0 +1,190 @@
+//! # Mad Libs GUI
+//!
+//! This module implements a graphical front-end for the Mad Libs adventure game that plays
+//! the same story files as the command-line version.
+//!
+//! ## Features
+//!
+//! - **Blank Entry**: A labelled text field for every blank in the current story node
+//! - **Word Suggestions**: A button next to each field fills in a random word from the word bank
+//! - **Branching Buttons**: Yes/No buttons follow the story's decision tree
+//! - **Story Display**: Shows the story so far and the finished tale with a "Play again" button
+//! - **Shared Logic**: Uses the same `MadLib` and story graph implementation as the CLI
+use e04::{
+    follow_answer, is_ending, load_story, parse_story_path, pick_suggestions, MadLib, StoryGraph,
+};
+use eframe::egui::{self};
+use petgraph::graph::NodeIndex;
+use std::collections::HashMap;
+use std::path::Path;
+
+enum Stage {
+    Blanks,
+    Question(MadLib),
+    Finished,
+}
+
+struct MadLibsApp {
+    root: NodeIndex,
+    decision_tree: StoryGraph,
+    current: NodeIndex,
+    remembered: HashMap<String, String>,
+    inputs: HashMap<String, String>,
+    stage: Stage,
+    /// Rendered passages of the story so far, including the answer given to each question.
+    passages: Vec<String>,
+}
+
+impl MadLibsApp {
+    fn new(root: NodeIndex, decision_tree: StoryGraph) -> Self {
+        Self {
+            root,
+            decision_tree,
+            current: root,
+            remembered: HashMap::new(),
+            inputs: HashMap::new(),
+            stage: Stage::Blanks,
+            passages: Vec::new(),
+        }
+    }
+
+    fn restart(&mut self) {
+        *self = Self::new(self.root, std::mem::take(&mut self.decision_tree));
+    }
+
+    fn submit_blanks(&mut self) {
+        let mut madlib = self.decision_tree[self.current].clone();
+        let inputs = std::mem::take(&mut self.inputs);
+        madlib.fill_blanks(&mut self.remembered, |placeholder| {
+            Some(
+                inputs
+                    .get(&placeholder.key)
+                    .map_or(String::new(), |word| word.trim().to_string()),
+            )
+        });
+
+        if is_ending(&self.decision_tree, self.current) {
+            self.passages.push(madlib.to_string());
+            self.stage = Stage::Finished;
+        } else {
+            self.stage = Stage::Question(madlib);
+        }
+    }
+
+    fn answer(&mut self, answer: bool) {
+        if let Stage::Question(madlib) = std::mem::replace(&mut self.stage, Stage::Blanks) {
+            self.passages.push(format!(
+                "{} {}",
+                madlib,
+                if answer { "Yes." } else { "No." }
+            ));
+            self.current = follow_answer(&self.decision_tree, self.current, answer);
+        }
+    }
+
+    fn show_blanks(&mut self, ui: &mut egui::Ui) {
+        let node = &self.decision_tree[self.current];
+        let pending: Vec<_> = node
+            .pending_blanks(&self.remembered)
+            .into_iter()
+            .cloned()
+            .collect();
+        if pending.is_empty() {
+            self.submit_blanks();
+            return;
+        }
+
+        let mut complete = true;
+        egui::Grid::new("blanks").num_columns(3).show(ui, |ui| {
+            for placeholder in &pending {
+                let input = self.inputs.entry(placeholder.key.clone()).or_default();
+                ui.label(format!("Enter {}:", placeholder.label()));
+                ui.text_edit_singleline(input);
+                if ui.button("Suggest").clicked() {
+                    if let Some(word) = pick_suggestions(placeholder.kind).first() {
+                        *input = word.to_string();
+                    }
+                }
+                ui.end_row();
+
+                let word = input.trim();
+                if word.is_empty() {
+                    complete = false;
+                } else if !placeholder.accepts(word) {
+                    complete = false;
+                    ui.label("");
+                    ui.colored_label(egui::Color32::RED, format!("'{}' is not a number.", word));
+                    ui.end_row();
+                }
+            }
+        });
+
+        if ui
+            .add_enabled(complete, egui::Button::new("Continue"))
+            .clicked()
+        {
+            self.submit_blanks();
+        }
+    }
+}
+
+impl eframe::App for MadLibsApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for passage in &self.passages {
+                    ui.label(passage);
+                }
+                if !self.passages.is_empty() {
+                    ui.separator();
+                }
+
+                match &self.stage {
+                    Stage::Blanks => self.show_blanks(ui),
+                    Stage::Question(madlib) => {
+                        ui.strong(madlib.to_string());
+                        let mut answer = None;
+                        ui.horizontal(|ui| {
+                            if ui.button("Yes").clicked() {
+                                answer = Some(true);
+                            }
+                            if ui.button("No").clicked() {
+                                answer = Some(false);
+                            }
+                        });
+                        if let Some(answer) = answer {
+                            self.answer(answer);
+                        }
+                    }
+                    Stage::Finished => {
+                        if ui.button("Play again").clicked() {
+                            self.restart();
+                        }
+                    }
+                }
+            });
+        });
+    }
+}
+
+fn main() -> eframe::Result {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (root, decision_tree) =
+        match parse_story_path(&args).and_then(|path| load_story(Path::new(path))) {
+            Ok(story) => story,
+            Err(e) => {
+                eprintln!("Error loading story: {}", e);
+                std::process::exit(1);
+            }
+        };
+
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([520.0, 420.0]),
+        ..Default::default()
+    };
+    eframe::run_native(
+        "Mad Libs",
+        options,
+        Box::new(move |_| Ok(Box::new(MadLibsApp::new(root, decision_tree)))),
+    )
+}
@@ -0,0 +1,576 @@
+//! # Mad Libs Library
+//!
+//! Story loading, placeholder parsing, and rendering shared by the command-line and GUI
+//! front-ends of the Mad Libs game.
+use petgraph::{
+    graph::{DefaultIx, NodeIndex},
+    visit::EdgeRef,
+    Graph,
+};
+use rand::seq::IndexedRandom;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+
+pub const NUM_SUGGESTIONS: usize = 3;
+pub const PLACEHOLDER_KINDS: [&str; 8] = [
+    "noun",
+    "verb",
+    "adjective",
+    "adverb",
+    "place",
+    "name",
+    "number",
+    "exclamation",
+];
+pub const DEFAULT_STORY: &str = "exercises/e04/stories/wizard.json";
+const DOT_LABEL_LENGTH: usize = 40;
+
+pub fn word_bank(part_of_speech: &str) -> &'static [&'static str] {
+    match part_of_speech {
+        "noun" => &[
+            "teapot", "dragon", "umbrella", "llama", "castle", "pickle", "trombone", "wizard",
+        ],
+        "verb" => &[
+            "juggle", "tickle", "befriend", "launch", "paint", "serenade", "chase", "polish",
+        ],
+        "adjective" => &[
+            "grumpy",
+            "sparkly",
+            "enormous",
+            "soggy",
+            "mysterious",
+            "fluffy",
+            "ancient",
+            "wobbly",
+        ],
+        "adverb" => &[
+            "quickly",
+            "sheepishly",
+            "loudly",
+            "gracefully",
+            "reluctantly",
+            "boldly",
+            "sleepily",
+        ],
+        "place" => &[
+            "Paris",
+            "the moon",
+            "a haunted library",
+            "Narnia",
+            "the dentist's office",
+            "a submarine",
+        ],
+        "name" => &[
+            "Gertrude",
+            "Sir Reginald",
+            "Zorp",
+            "Mildred",
+            "Captain Noodle",
+        ],
+        "number" => &["3", "7", "42", "99", "1000"],
+        "exclamation" => &["Yikes", "Hooray", "Great Scott", "Oh bother", "Zoinks"],
+        _ => &[],
+    }
+}
+
+pub fn pick_suggestions(part_of_speech: &str) -> Vec<&'static str> {
+    word_bank(part_of_speech)
+        .choose_multiple(&mut rand::rng(), NUM_SUGGESTIONS)
+        .copied()
+        .collect()
+}
+
+/// Resolves the player's input to a suggestion when they enter its number, or to their own
+/// word otherwise.
+pub fn resolve_word(input: &str, suggestions: &[&str]) -> String {
+    match input.parse::<usize>() {
+        Ok(n) if (1..=suggestions.len()).contains(&n) => suggestions[n - 1].to_string(),
+        _ => input.to_string(),
+    }
+}
+
+/// Reads a yes/no reply, accepting `y` and `n` as well and ignoring case.
+pub fn parse_yes_no(input: &str) -> Option<bool> {
+    match input.trim().to_lowercase().as_str() {
+        "yes" | "y" => Some(true),
+        "no" | "n" => Some(false),
+        _ => None,
+    }
+}
+
+/// A blank in a story template such as `{noun}` or `{name2}`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    pub key: String,
+    pub kind: &'static str,
+    pub numbered: bool,
+}
+
+impl Placeholder {
+    pub fn label(&self) -> String {
+        let article = if self.kind.starts_with(['a', 'e', 'i', 'o', 'u']) {
+            "an"
+        } else {
+            "a"
+        };
+        if self.numbered {
+            format!(
+                "{} {} (#{})",
+                article,
+                self.kind,
+                &self.key[self.kind.len()..]
+            )
+        } else {
+            format!("{} {}", article, self.kind)
+        }
+    }
+
+    /// Checks that `word` fits the blank; numbers must parse, anything else is accepted.
+    pub fn accepts(&self, word: &str) -> bool {
+        self.kind != "number" || word.parse::<f64>().is_ok()
+    }
+}
+
+/// Finds the placeholders in a template in order of first appearance. A placeholder repeated
+/// within the template is listed once so its word is only asked for once.
+pub fn parse_placeholders(template: &str) -> Result<Vec<Placeholder>, String> {
+    let mut placeholders: Vec<Placeholder> = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| "unclosed placeholder".to_string())?
+            + start;
+        let key = &rest[start + 1..end];
+        let name = key.trim_end_matches(|c: char| c.is_ascii_digit());
+        let kind = PLACEHOLDER_KINDS
+            .iter()
+            .find(|kind| **kind == name)
+            .ok_or_else(|| format!("unknown placeholder '{{{}}}'", key))?;
+        if !placeholders.iter().any(|p| p.key == key) {
+            placeholders.push(Placeholder {
+                key: key.to_string(),
+                kind,
+                numbered: key.len() > name.len(),
+            });
+        }
+        rest = &rest[end + 1..];
+    }
+    Ok(placeholders)
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct MadLib {
+    pub id: String,
+    pub story_template: String,
+    pub placeholders: Vec<Placeholder>,
+    words: HashMap<String, String>,
+}
+
+impl MadLib {
+    pub fn new(id: String, story_template: String) -> Result<Self, String> {
+        Ok(MadLib {
+            id,
+            placeholders: parse_placeholders(&story_template)?,
+            story_template,
+            words: HashMap::new(),
+        })
+    }
+
+    /// Fills every blank, asking `ask` only for words that haven't been given yet. Numbered
+    /// words are shared through `remembered` so they carry over to later story nodes. Returns
+    /// `None` as soon as `ask` does, leaving the remaining blanks unfilled.
+    pub fn fill_blanks(
+        &mut self,
+        remembered: &mut HashMap<String, String>,
+        mut ask: impl FnMut(&Placeholder) -> Option<String>,
+    ) -> Option<()> {
+        for placeholder in &self.placeholders {
+            let word = match remembered.get(&placeholder.key) {
+                Some(word) if placeholder.numbered => word.clone(),
+                _ => ask(placeholder)?,
+            };
+            if placeholder.numbered {
+                remembered.insert(placeholder.key.clone(), word.clone());
+            }
+            self.words.insert(placeholder.key.clone(), word);
+        }
+        Some(())
+    }
+
+    /// The blanks that still need a word, skipping numbered words already given earlier in
+    /// the story.
+    pub fn pending_blanks(&self, remembered: &HashMap<String, String>) -> Vec<&Placeholder> {
+        self.placeholders
+            .iter()
+            .filter(|p| !(p.numbered && remembered.contains_key(&p.key)))
+            .collect()
+    }
+}
+
+impl Display for MadLib {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut final_story = self.story_template.clone();
+        for placeholder in &self.placeholders {
+            let word = self.words.get(&placeholder.key).map_or("", String::as_str);
+            final_story = final_story.replace(&format!("{{{}}}", placeholder.key), word);
+        }
+        write!(f, "{}", final_story)
+    }
+}
+
+pub type StoryGraph = Graph<MadLib, bool>;
+
+/// A story node as written in a story file. Questions name the nodes their yes and no answers
+/// lead to; endings have neither.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NodeSpec {
+    id: String,
+    template: String,
+    yes: Option<String>,
+    no: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StoryFile {
+    start: String,
+    nodes: Vec<NodeSpec>,
+}
+
+/// Builds the story graph, reporting duplicate ids, dangling references, and questions
+/// missing a branch.
+pub fn parse_story(json: &str) -> Result<(NodeIndex<DefaultIx>, StoryGraph), String> {
+    let story: StoryFile = serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+    let mut decision_tree = StoryGraph::new();
+    let mut indices = HashMap::new();
+    for node in &story.nodes {
+        let madlib = MadLib::new(node.id.clone(), node.template.clone())
+            .map_err(|e| format!("node '{}': {}", node.id, e))?;
+        let index = decision_tree.add_node(madlib);
+        if indices.insert(node.id.as_str(), index).is_some() {
+            return Err(format!("duplicate node id '{}'", node.id));
+        }
+    }
+
+    let lookup = |from: &str, to: &str| {
+        indices
+            .get(to)
+            .copied()
+            .ok_or_else(|| format!("node '{}' leads to unknown node '{}'", from, to))
+    };
+    for node in &story.nodes {
+        match (&node.yes, &node.no) {
+            (Some(yes), Some(no)) => {
+                let from = indices[node.id.as_str()];
+                decision_tree.add_edge(from, lookup(&node.id, yes)?, true);
+                decision_tree.add_edge(from, lookup(&node.id, no)?, false);
+            }
+            (None, None) => {}
+            (Some(_), None) => return Err(format!("node '{}' has no 'no' branch", node.id)),
+            (None, Some(_)) => return Err(format!("node '{}' has no 'yes' branch", node.id)),
+        }
+    }
+
+    let start = indices
+        .get(story.start.as_str())
+        .copied()
+        .ok_or_else(|| format!("start node '{}' does not exist", story.start))?;
+    Ok((start, decision_tree))
+}
+
+pub fn load_story(file_path: &Path) -> Result<(NodeIndex<DefaultIx>, StoryGraph), String> {
+    let json = std::fs::read_to_string(file_path)
+        .map_err(|e| format!("{}: {}", file_path.display(), e))?;
+    parse_story(&json).map_err(|e| format!("{}: {}", file_path.display(), e))
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn truncate_label(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        let truncated: String = text.chars().take(max_chars - 3).collect();
+        format!("{}...", truncated.trim_end())
+    }
+}
+
+/// Renders the story graph as Graphviz DOT. Nodes are labelled with their id and the start of
+/// their template; the start node is drawn bold and endings with a double border.
+pub fn story_to_dot(root: NodeIndex<DefaultIx>, decision_tree: &StoryGraph) -> String {
+    let mut dot = String::from("digraph story {\n    node [shape=box];\n");
+    for index in decision_tree.node_indices() {
+        let madlib = &decision_tree[index];
+        let mut attributes = format!(
+            "label=\"{}\\n{}\"",
+            escape_dot(&madlib.id),
+            escape_dot(&truncate_label(&madlib.story_template, DOT_LABEL_LENGTH))
+        );
+        if index == root {
+            attributes.push_str(", style=bold");
+        }
+        if decision_tree.edges(index).count() == 0 {
+            attributes.push_str(", peripheries=2");
+        }
+        dot.push_str(&format!(
+            "    \"{}\" [{}];\n",
+            escape_dot(&madlib.id),
+            attributes
+        ));
+    }
+    // Edges are listed in insertion order so yes always comes before no.
+    for edge in decision_tree.raw_edges() {
+        dot.push_str(&format!(
+            "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+            escape_dot(&decision_tree[edge.source()].id),
+            escape_dot(&decision_tree[edge.target()].id),
+            if edge.weight { "yes" } else { "no" }
+        ));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+pub fn is_ending(decision_tree: &StoryGraph, index: NodeIndex<DefaultIx>) -> bool {
+    decision_tree.edges(index).count() == 0
+}
+
+/// Follows the yes or no branch out of a question node.
+pub fn follow_answer(
+    decision_tree: &StoryGraph,
+    index: NodeIndex<DefaultIx>,
+    answer: bool,
+) -> NodeIndex<DefaultIx> {
+    // Story validation guarantees every question has both branches.
+    decision_tree
+        .edges(index)
+        .find(|edge| *edge.weight() == answer)
+        .map(|edge| edge.target())
+        .expect("No matching edge found")
+}
+
+pub fn flag_value<'a>(args: &'a [String], flag: &str) -> Result<Option<&'a str>, String> {
+    match args.iter().position(|arg| arg == flag) {
+        Some(i) => args
+            .get(i + 1)
+            .map(|value| Some(value.as_str()))
+            .ok_or_else(|| format!("{} requires a file path", flag)),
+        None => Ok(None),
+    }
+}
+
+pub fn parse_story_path(args: &[String]) -> Result<&str, String> {
+    Ok(flag_value(args, "--story")?.unwrap_or(DEFAULT_STORY))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pick_suggestions_draws_distinct_words_from_the_bank() {
+        for kind in PLACEHOLDER_KINDS {
+            let suggestions = pick_suggestions(kind);
+            assert_eq!(suggestions.len(), NUM_SUGGESTIONS);
+            assert!(suggestions
+                .iter()
+                .all(|word| word_bank(kind).contains(word)));
+            let mut unique = suggestions.clone();
+            unique.sort();
+            unique.dedup();
+            assert_eq!(unique.len(), suggestions.len());
+        }
+        assert!(pick_suggestions("pronoun").is_empty());
+    }
+
+    #[test]
+    fn resolve_word_maps_numbers_to_suggestions() {
+        let suggestions = ["teapot", "dragon", "llama"];
+        assert_eq!(resolve_word("1", &suggestions), "teapot");
+        assert_eq!(resolve_word("3", &suggestions), "llama");
+        assert_eq!(resolve_word("0", &suggestions), "0");
+        assert_eq!(resolve_word("4", &suggestions), "4");
+        assert_eq!(resolve_word("castle", &suggestions), "castle");
+        // Without suggestions a number is just the player's word.
+        assert_eq!(resolve_word("2", &[]), "2");
+    }
+
+    #[test]
+    fn parse_yes_no_accepts_short_answers() {
+        assert_eq!(parse_yes_no("yes"), Some(true));
+        assert_eq!(parse_yes_no(" Y "), Some(true));
+        assert_eq!(parse_yes_no("No"), Some(false));
+        assert_eq!(parse_yes_no("n"), Some(false));
+        assert_eq!(parse_yes_no("maybe"), None);
+    }
+
+    #[test]
+    fn load_story_reads_bundled_story() {
+        let (root, decision_tree) = load_story(Path::new("stories/wizard.json")).unwrap();
+        assert_eq!(decision_tree.edges(root).count(), 2);
+        assert_eq!(decision_tree.node_count(), 16);
+        assert_eq!(decision_tree.edge_count(), 20);
+        for node in decision_tree.node_indices() {
+            assert!(matches!(decision_tree.edges(node).count(), 0 | 2));
+        }
+    }
+
+    #[test]
+    fn parse_story_rejects_invalid_graphs() {
+        let missing_branch = r#"{"start": "a", "nodes": [
+            {"id": "a", "template": "Q?", "yes": "b"},
+            {"id": "b", "template": "THE END"}
+        ]}"#;
+        assert_eq!(
+            parse_story(missing_branch).unwrap_err(),
+            "node 'a' has no 'no' branch"
+        );
+
+        let dangling = r#"{"start": "a", "nodes": [
+            {"id": "a", "template": "Q?", "yes": "b", "no": "c"},
+            {"id": "b", "template": "THE END"}
+        ]}"#;
+        assert_eq!(
+            parse_story(dangling).unwrap_err(),
+            "node 'a' leads to unknown node 'c'"
+        );
+
+        let bad_start = r#"{"start": "x", "nodes": [{"id": "a", "template": "THE END"}]}"#;
+        assert_eq!(
+            parse_story(bad_start).unwrap_err(),
+            "start node 'x' does not exist"
+        );
+
+        let duplicate = r#"{"start": "a", "nodes": [
+            {"id": "a", "template": "THE END"},
+            {"id": "a", "template": "THE END"}
+        ]}"#;
+        assert_eq!(parse_story(duplicate).unwrap_err(), "duplicate node id 'a'");
+    }
+
+    #[test]
+    fn parse_placeholders_reads_numbered_and_typed_blanks() {
+        let placeholders =
+            parse_placeholders("{name1} met {name2} in {place}. {name1} said {exclamation}!")
+                .unwrap();
+        let keys: Vec<&str> = placeholders.iter().map(|p| p.key.as_str()).collect();
+        assert_eq!(keys, ["name1", "name2", "place", "exclamation"]);
+        assert_eq!(placeholders[1].kind, "name");
+        assert_eq!(placeholders[1].label(), "a name (#2)");
+        assert_eq!(placeholders[3].label(), "an exclamation");
+
+        assert_eq!(
+            parse_placeholders("a {color} hat").unwrap_err(),
+            "unknown placeholder '{color}'"
+        );
+        assert!(parse_placeholders("a {noun hat").is_err());
+    }
+
+    #[test]
+    fn fill_blanks_reuses_numbered_words_across_nodes() {
+        let mut remembered = HashMap::new();
+        let mut asked = Vec::new();
+
+        let mut first = MadLib::new(
+            "first".to_string(),
+            "{name1} found a {noun} and a {noun}.".to_string(),
+        )
+        .unwrap();
+        first.fill_blanks(&mut remembered, |p| {
+            asked.push(p.key.clone());
+            Some(format!("<{}>", p.key))
+        });
+        assert_eq!(first.to_string(), "<name1> found a <noun> and a <noun>.");
+
+        let mut second =
+            MadLib::new("second".to_string(), "{name1} lost the {noun}.".to_string()).unwrap();
+        second.fill_blanks(&mut remembered, |p| {
+            asked.push(p.key.clone());
+            Some("teapot".to_string())
+        });
+        assert_eq!(second.to_string(), "<name1> lost the teapot.");
+        assert_eq!(asked, ["name1", "noun", "noun"]);
+    }
+
+    #[test]
+    fn fill_blanks_stops_when_ask_gives_up() {
+        let mut madlib = MadLib::new("a".to_string(), "{name} saw a {noun}.".to_string()).unwrap();
+        let mut asked = 0;
+        let filled = madlib.fill_blanks(&mut HashMap::new(), |_| {
+            asked += 1;
+            None
+        });
+        assert_eq!(filled, None);
+        assert_eq!(asked, 1);
+    }
+
+    #[test]
+    fn parse_story_rejects_unknown_placeholders() {
+        let story = r#"{"start": "a", "nodes": [{"id": "a", "template": "THE {end}"}]}"#;
+        assert_eq!(
+            parse_story(story).unwrap_err(),
+            "node 'a': unknown placeholder '{end}'"
+        );
+    }
+
+    #[test]
+    fn story_to_dot_labels_nodes_and_branches() {
+        let story = r#"{"start": "a", "nodes": [
+            {"id": "a", "template": "Did you meet a \"talking\" {noun} on the way to {place1}?", "yes": "b", "no": "c"},
+            {"id": "b", "template": "THE END: Yes."},
+            {"id": "c", "template": "THE END: No."}
+        ]}"#;
+        let (root, decision_tree) = parse_story(story).unwrap();
+        assert_eq!(
+            story_to_dot(root, &decision_tree),
+            "digraph story {\n    \
+             node [shape=box];\n    \
+             \"a\" [label=\"a\\nDid you meet a \\\"talking\\\" {noun} on th...\", style=bold];\n    \
+             \"b\" [label=\"b\\nTHE END: Yes.\", peripheries=2];\n    \
+             \"c\" [label=\"c\\nTHE END: No.\", peripheries=2];\n    \
+             \"a\" -> \"b\" [label=\"yes\"];\n    \
+             \"a\" -> \"c\" [label=\"no\"];\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn pending_blanks_skips_remembered_numbered_words() {
+        let madlib = MadLib::new(
+            "a".to_string(),
+            "{name1} gave {number} {noun}s to {name2}.".to_string(),
+        )
+        .unwrap();
+        let remembered = HashMap::from([
+            ("name1".to_string(), "Zorp".to_string()),
+            ("noun".to_string(), "teapot".to_string()),
+        ]);
+        let keys: Vec<&str> = madlib
+            .pending_blanks(&remembered)
+            .iter()
+            .map(|p| p.key.as_str())
+            .collect();
+        assert_eq!(keys, ["number", "noun", "name2"]);
+        assert!(madlib.placeholders[1].accepts("42"));
+        assert!(!madlib.placeholders[1].accepts("lots"));
+        assert!(madlib.placeholders[2].accepts("lots"));
+    }
+
+    #[test]
+    fn flag_value_reads_optional_paths() {
+        let args: Vec<String> = ["--export-dot", "-"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(flag_value(&args, "--export-dot"), Ok(Some("-")));
+        assert_eq!(parse_story_path(&args), Ok(DEFAULT_STORY));
+        assert!(flag_value(&args[..1], "--export-dot").is_err());
+    }
+}
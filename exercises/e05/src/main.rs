@@ -2,73 +2,1588 @@
 //!
 //! This module implements a GUI calculator application for basic arithmetic operations.
 //! Built with egui/eframe, it allows users to perform addition, subtraction, multiplication,
-//! and division on two input values.
+//! and division using an on-screen keypad or the keyboard.
 //!
 //! ## Features
 //!
-//! - **Real-time Calculation**: Results update instantly as values are entered
+//! - **Calculator Keypad**: Number and operator buttons laid out like a desk calculator
+//! - **Keyboard Input**: Digits, operators, Enter/= to evaluate, Esc to clear, Backspace to delete
+//! - **Running Display**: Shows the pending operation above the current entry
+//! - **Chained Operations**: Each result feeds into the next calculation
 //! - **Multiple Operations**: Performs addition, subtraction, multiplication, and division
-//! - **Input Validation**: Handles invalid inputs gracefully
 //! - **Division by Zero Protection**: Special handling for division by zero cases
+//! - **Result Formatting**: Shows results as plain numbers, currency, or percentages with
+//!   selectable precision and thousands separators
+//! - **Scientific Tab**: Applies sqrt, cube root, ln, log10, exp, sin, cos, and tan to a single
+//!   input, with a degree/radian toggle and descriptive errors for values outside a function's
+//!   domain
+//! - **Expression Tab**: Evaluates whole expressions such as `3 * (2 + 4.5) / 7` with operator
+//!   precedence, parentheses, unary minus, and `^` powers, pointing at the offending part of
+//!   the input when it can't be parsed
+//! - **History**: A scrollable side panel lists every computed result; clicking one reuses it
+//!   as an operand, and the session can be exported as plain text or CSV
+//! - **Powers, Roots, and Constants**: The scientific tab raises a value to any power or takes
+//!   its nth root, and both it and the expression tab accept `pi`/`π` and `e` in place of a
+//!   number
+//! - **Units**: Expressions can attach units to operands (`3 ft + 2 m`, `60 mi / 1.5 h`),
+//!   converting between lengths, masses, and times and rejecting sums of incompatible
+//!   quantities; `to <unit>` picks the unit of the result (`3 ft + 2 m to cm`). Length factors
+//!   come from the shared `units` tables
+//! - **Mouse-Free Operation**: Ctrl+1/2/3 switch tabs and move focus to the tab's input, Esc
+//!   clears the focused input, and Ctrl+R reuses the most recent result
 use eframe::egui;
+use num_format::{Locale, ToFormattedString};
+use std::fmt::Display;
+use std::ops::Range;
+use std::path::Path;
+use units::length;
 
+const DEFAULT_EXPORT_PATH: &str = "exercises/e05/history.csv";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Operator {
+    fn from_char(c: char) -> Option<Operator> {
+        match c {
+            '+' => Some(Operator::Add),
+            '-' => Some(Operator::Subtract),
+            '*' | 'x' | '×' => Some(Operator::Multiply),
+            '/' | '÷' => Some(Operator::Divide),
+            _ => None,
+        }
+    }
+
+    fn apply(&self, lhs: f64, rhs: f64) -> Result<f64, String> {
+        match self {
+            Operator::Add => Ok(lhs + rhs),
+            Operator::Subtract => Ok(lhs - rhs),
+            Operator::Multiply => Ok(lhs * rhs),
+            Operator::Divide if rhs == 0.0 => Err("Division by zero is undefined.".to_string()),
+            Operator::Divide => Ok(lhs / rhs),
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operator::Add => write!(f, "+"),
+            Operator::Subtract => write!(f, "-"),
+            Operator::Multiply => write!(f, "×"),
+            Operator::Divide => write!(f, "÷"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum FormatStyle {
+    #[default]
+    Plain,
+    Currency,
+    Percentage,
+}
+
+const FORMAT_STYLES: [FormatStyle; 3] = [
+    FormatStyle::Plain,
+    FormatStyle::Currency,
+    FormatStyle::Percentage,
+];
+
+impl Display for FormatStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatStyle::Plain => write!(f, "Plain"),
+            FormatStyle::Currency => write!(f, "Currency"),
+            FormatStyle::Percentage => write!(f, "Percentage"),
+        }
+    }
+}
+
+const MAX_PRECISION: usize = 10;
+
+/// How results are displayed. A precision of `None` shows as many decimals as needed.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct DisplayFormat {
+    style: FormatStyle,
+    precision: Option<usize>,
+}
+
+impl DisplayFormat {
+    fn format(&self, value: f64) -> String {
+        match self.style {
+            FormatStyle::Plain => group_thousands(&format_number(value, self.precision)),
+            FormatStyle::Currency => {
+                let formatted = group_thousands(&format_number(value.abs(), Some(2)));
+                if format_number(value, Some(2)).starts_with('-') {
+                    format!("-${}", formatted)
+                } else {
+                    format!("${}", formatted)
+                }
+            }
+            FormatStyle::Percentage => {
+                format!(
+                    "{}%",
+                    group_thousands(&format_number(value * 100.0, self.precision))
+                )
+            }
+        }
+    }
+}
+
+/// Formats `value` with a fixed number of decimals, or trims trailing zeros when `precision`
+/// is `None`.
+fn format_number(value: f64, precision: Option<usize>) -> String {
+    let formatted = match precision {
+        Some(decimals) => format!("{:.*}", decimals, value),
+        None => {
+            let formatted = format!("{:.*}", MAX_PRECISION, value);
+            formatted
+                .trim_end_matches('0')
+                .trim_end_matches('.')
+                .to_string()
+        }
+    };
+    // Values that round to zero should not display as negative.
+    if formatted.starts_with('-') && formatted.chars().all(|c| matches!(c, '-' | '0' | '.')) {
+        formatted[1..].to_string()
+    } else {
+        formatted
+    }
+}
+
+/// Inserts thousands separators into the integer part of a formatted number.
+fn group_thousands(number: &str) -> String {
+    let (sign, unsigned) = match number.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", number),
+    };
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+    let Ok(integer) = integer.parse::<u128>() else {
+        return number.to_string();
+    };
+    let mut grouped = format!("{}{}", sign, integer.to_formatted_string(&Locale::en));
+    if let Some(fraction) = fraction {
+        grouped.push('.');
+        grouped.push_str(fraction);
+    }
+    grouped
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum UnaryOp {
+    Sqrt,
+    Cbrt,
+    Ln,
+    Log10,
+    Exp,
+    Sin,
+    Cos,
+    Tan,
+}
+
+const UNARY_OPS: [UnaryOp; 8] = [
+    UnaryOp::Sqrt,
+    UnaryOp::Cbrt,
+    UnaryOp::Ln,
+    UnaryOp::Log10,
+    UnaryOp::Sin,
+    UnaryOp::Cos,
+    UnaryOp::Tan,
+    UnaryOp::Exp,
+];
+
+/// Named constants accepted wherever a number is expected.
+fn constant(name: &str) -> Option<f64> {
+    match name {
+        "pi" | "π" => Some(std::f64::consts::PI),
+        "e" => Some(std::f64::consts::E),
+        _ => None,
+    }
+}
+
+fn parse_operand(input: &str) -> Result<f64, String> {
+    let input = input.trim();
+    constant(input)
+        .map(Ok)
+        .unwrap_or_else(|| input.parse())
+        .map_err(|_| format!("'{}' is not a valid number.", input))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum AngleUnit {
+    #[default]
+    Degrees,
+    Radians,
+}
+
+impl UnaryOp {
+    fn apply(&self, value: f64, angle: AngleUnit) -> Result<f64, String> {
+        let radians = match angle {
+            AngleUnit::Degrees => value.to_radians(),
+            AngleUnit::Radians => value,
+        };
+        match self {
+            UnaryOp::Sqrt if value < 0.0 => {
+                Err("Cannot take the square root of a negative number.".to_string())
+            }
+            UnaryOp::Sqrt => Ok(value.sqrt()),
+            UnaryOp::Cbrt => Ok(value.cbrt()),
+            UnaryOp::Ln | UnaryOp::Log10 if value <= 0.0 => {
+                Err("Logarithms are only defined for positive numbers.".to_string())
+            }
+            UnaryOp::Ln => Ok(value.ln()),
+            UnaryOp::Log10 => Ok(value.log10()),
+            UnaryOp::Exp if !value.exp().is_finite() => {
+                Err("The result is too large to represent.".to_string())
+            }
+            UnaryOp::Exp => Ok(value.exp()),
+            UnaryOp::Sin => Ok(radians.sin()),
+            UnaryOp::Cos => Ok(radians.cos()),
+            // Exact degree inputs are checked directly since cos(90°) isn't exactly zero in
+            // floating point.
+            UnaryOp::Tan
+                if (angle == AngleUnit::Degrees && (value - 90.0).rem_euclid(180.0) == 0.0)
+                    || radians.cos() == 0.0 =>
+            {
+                Err("Tangent is undefined at odd multiples of 90°.".to_string())
+            }
+            UnaryOp::Tan => Ok(radians.tan()),
+        }
+    }
+}
+
+impl Display for UnaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnaryOp::Sqrt => write!(f, "√"),
+            UnaryOp::Cbrt => write!(f, "∛"),
+            UnaryOp::Ln => write!(f, "ln"),
+            UnaryOp::Log10 => write!(f, "log10"),
+            UnaryOp::Exp => write!(f, "exp"),
+            UnaryOp::Sin => write!(f, "sin"),
+            UnaryOp::Cos => write!(f, "cos"),
+            UnaryOp::Tan => write!(f, "tan"),
+        }
+    }
+}
+
+/// Operations of the scientific tab that take a second operand `y`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinaryOp {
+    Power,
+    Root,
+}
+
+impl BinaryOp {
+    fn apply(&self, x: f64, y: f64) -> Result<f64, String> {
+        let result = match self {
+            BinaryOp::Root if y == 0.0 => return Err("The zeroth root is undefined.".to_string()),
+            // Odd integer roots of negative numbers are real even though powf returns NaN.
+            BinaryOp::Root if x < 0.0 && y.fract() == 0.0 && y % 2.0 != 0.0 => -(-x).powf(1.0 / y),
+            BinaryOp::Root => x.powf(1.0 / y),
+            BinaryOp::Power => x.powf(y),
+        };
+        if result.is_nan() {
+            Err("The result is not a real number.".to_string())
+        } else if result.is_infinite() {
+            Err("The result is too large to represent.".to_string())
+        } else {
+            Ok(result)
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            BinaryOp::Power => "xʸ",
+            BinaryOp::Root => "ʸ√x",
+        }
+    }
+
+    fn describe(&self, x: f64, y: f64) -> String {
+        match self {
+            BinaryOp::Power => format!("{}^{}", x, y),
+            BinaryOp::Root => format!("{}√({})", y, x),
+        }
+    }
+}
+
+/// A computed result and the calculation that produced it.
+#[derive(Debug, Clone, PartialEq)]
+struct HistoryEntry {
+    expression: String,
+    result: f64,
+    unit: Option<String>,
+}
+
+impl HistoryEntry {
+    fn formatted_result(&self, format: &DisplayFormat) -> String {
+        match &self.unit {
+            Some(unit) => format!("{} {}", format.format(self.result), unit),
+            None => format.format(self.result),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct History {
+    entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    fn push(&mut self, expression: impl Into<String>, result: f64) {
+        self.entries.push(HistoryEntry {
+            expression: expression.into(),
+            result,
+            unit: None,
+        });
+    }
+
+    fn push_measurement(&mut self, expression: impl Into<String>, measurement: &Measurement) {
+        self.entries.push(HistoryEntry {
+            expression: expression.into(),
+            result: measurement.value,
+            unit: measurement.unit.clone(),
+        });
+    }
+
+    fn to_csv(&self) -> String {
+        // Writing to memory cannot fail and every record has the same three fields.
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer
+            .write_record(["expression", "result", "unit"])
+            .expect("in-memory CSV write");
+        for entry in &self.entries {
+            writer
+                .write_record([
+                    entry.expression.as_str(),
+                    &format_number(entry.result, None),
+                    entry.unit.as_deref().unwrap_or_default(),
+                ])
+                .expect("in-memory CSV write");
+        }
+        let bytes = writer.into_inner().expect("in-memory CSV write");
+        String::from_utf8(bytes).expect("CSV built from strings is UTF-8")
+    }
+
+    fn to_text(&self, format: &DisplayFormat) -> String {
+        self.entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{} = {}\n",
+                    entry.expression,
+                    entry.formatted_result(format)
+                )
+            })
+            .collect()
+    }
+
+    /// Writes the history to `path`, as CSV when the extension is `.csv` and as plain text
+    /// otherwise.
+    fn export(&self, path: &Path, format: &DisplayFormat) -> std::io::Result<()> {
+        let is_csv = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+        let contents = if is_csv {
+            self.to_csv()
+        } else {
+            self.to_text(format)
+        };
+        std::fs::write(path, contents)
+    }
+}
+
+/// Input and last result of the scientific tab.
+#[derive(Default)]
+struct ScientificPanel {
+    input: String,
+    /// Second operand used by powers and roots.
+    y: String,
+    angle: AngleUnit,
+    result: Option<Result<String, String>>,
+}
+
+impl ScientificPanel {
+    /// Applies `op` to the input, returning the calculation on success so it can be added to
+    /// the history.
+    fn evaluate(&mut self, op: UnaryOp, format: &DisplayFormat) -> Option<HistoryEntry> {
+        let entry = parse_operand(&self.input).and_then(|value| {
+            op.apply(value, self.angle).map(|result| HistoryEntry {
+                expression: format!("{}({})", op, value),
+                result,
+                unit: None,
+            })
+        });
+        self.finish(entry, format)
+    }
+
+    fn evaluate_binary(&mut self, op: BinaryOp, format: &DisplayFormat) -> Option<HistoryEntry> {
+        let entry = parse_operand(&self.input).and_then(|x| {
+            let y = parse_operand(&self.y)?;
+            op.apply(x, y).map(|result| HistoryEntry {
+                expression: op.describe(x, y),
+                result,
+                unit: None,
+            })
+        });
+        self.finish(entry, format)
+    }
+
+    fn finish(
+        &mut self,
+        entry: Result<HistoryEntry, String>,
+        format: &DisplayFormat,
+    ) -> Option<HistoryEntry> {
+        self.result = Some(
+            entry
+                .as_ref()
+                .map(|entry| format!("{} = {}", entry.expression, format.format(entry.result)))
+                .map_err(Clone::clone),
+        );
+        entry.ok()
+    }
+}
+
+/// An expression error along with the byte range of the input it refers to.
+#[derive(Debug, Clone, PartialEq)]
+struct ExprError {
+    message: String,
+    span: Range<usize>,
+}
+
+impl ExprError {
+    fn new(message: impl Into<String>, span: Range<usize>) -> Self {
+        ExprError {
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// Renders a caret line pointing at the error beneath `input`.
+    fn underline(&self, input: &str) -> String {
+        let start = input[..self.span.start].chars().count();
+        let width = input[self.span.clone()].chars().count().max(1);
+        format!("{}{}", " ".repeat(start), "^".repeat(width))
+    }
+}
+
+/// Exponents of length, mass, and time, the base dimensions units are built from.
+type Dimension = [i32; 3];
+
+const DIMENSIONLESS: Dimension = [0, 0, 0];
+const LENGTH: Dimension = [1, 0, 0];
+const MASS: Dimension = [0, 1, 0];
+const TIME: Dimension = [0, 0, 1];
+const BASE_UNITS: [&str; 3] = ["m", "kg", "s"];
+
+/// A unit defined by its size in SI base units. The first symbol is used for display.
+#[derive(Debug, PartialEq)]
+struct UnitDef {
+    symbols: &'static [&'static str],
+    factor: f64,
+    dimension: Dimension,
+}
+
+const UNIT_DEFS: [UnitDef; 15] = [
+    UnitDef {
+        symbols: &["m", "meter", "meters"],
+        factor: length::METER,
+        dimension: LENGTH,
+    },
+    UnitDef {
+        symbols: &["cm"],
+        factor: length::CENTIMETER,
+        dimension: LENGTH,
+    },
+    UnitDef {
+        symbols: &["mm"],
+        factor: length::MILLIMETER,
+        dimension: LENGTH,
+    },
+    UnitDef {
+        symbols: &["km"],
+        factor: length::KILOMETER,
+        dimension: LENGTH,
+    },
+    UnitDef {
+        symbols: &["in", "inch", "inches"],
+        factor: length::INCH,
+        dimension: LENGTH,
+    },
+    UnitDef {
+        symbols: &["ft", "foot", "feet"],
+        factor: length::FOOT,
+        dimension: LENGTH,
+    },
+    UnitDef {
+        symbols: &["yd", "yard", "yards"],
+        factor: length::YARD,
+        dimension: LENGTH,
+    },
+    UnitDef {
+        symbols: &["mi", "mile", "miles"],
+        factor: length::MILE,
+        dimension: LENGTH,
+    },
+    UnitDef {
+        symbols: &["g"],
+        factor: 0.001,
+        dimension: MASS,
+    },
+    UnitDef {
+        symbols: &["kg"],
+        factor: 1.0,
+        dimension: MASS,
+    },
+    UnitDef {
+        symbols: &["lb", "lbs"],
+        factor: 0.45359237,
+        dimension: MASS,
+    },
+    UnitDef {
+        symbols: &["oz"],
+        factor: 0.028349523125,
+        dimension: MASS,
+    },
+    UnitDef {
+        symbols: &["s", "sec"],
+        factor: 1.0,
+        dimension: TIME,
+    },
+    UnitDef {
+        symbols: &["min"],
+        factor: 60.0,
+        dimension: TIME,
+    },
+    UnitDef {
+        symbols: &["h", "hr"],
+        factor: 3600.0,
+        dimension: TIME,
+    },
+];
+
+fn find_unit(name: &str) -> Option<&'static UnitDef> {
+    UNIT_DEFS.iter().find(|unit| unit.symbols.contains(&name))
+}
+
+fn scale_dimension(dimension: Dimension, power: i32) -> Dimension {
+    dimension.map(|exponent| exponent * power)
+}
+
+/// Writes a dimension in SI base units, e.g. `m^2` or `m/s`.
+fn format_base_units(dimension: Dimension) -> String {
+    let part = |symbol: &str, exponent: i32| match exponent {
+        1 => symbol.to_string(),
+        _ => format!("{}^{}", symbol, exponent),
+    };
+    let numerator: Vec<String> = BASE_UNITS
+        .iter()
+        .zip(dimension)
+        .filter(|(_, exponent)| *exponent > 0)
+        .map(|(symbol, exponent)| part(symbol, exponent))
+        .collect();
+    let denominator: Vec<String> = BASE_UNITS
+        .iter()
+        .zip(dimension)
+        .filter(|(_, exponent)| *exponent < 0)
+        .map(|(symbol, exponent)| part(symbol, -exponent))
+        .collect();
+    let numerator = if numerator.is_empty() {
+        "1".to_string()
+    } else {
+        numerator.join("·")
+    };
+    if denominator.is_empty() {
+        numerator
+    } else {
+        format!("{}/{}", numerator, denominator.join("·"))
+    }
+}
+
+fn dimension_name(dimension: Dimension) -> String {
+    match dimension {
+        DIMENSIONLESS => "a plain number".to_string(),
+        LENGTH => "a length".to_string(),
+        [2, 0, 0] => "an area".to_string(),
+        [3, 0, 0] => "a volume".to_string(),
+        MASS => "a mass".to_string(),
+        TIME => "a time".to_string(),
+        [1, 0, -1] => "a speed".to_string(),
+        _ => format_base_units(dimension),
+    }
+}
+
+/// A value in SI base units along with its dimension.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Quantity {
+    value: f64,
+    dimension: Dimension,
+}
+
+impl Quantity {
+    fn number(value: f64) -> Self {
+        Quantity {
+            value,
+            dimension: DIMENSIONLESS,
+        }
+    }
+
+    fn combine(self, op: Operator, rhs: Quantity) -> Result<Quantity, String> {
+        let dimension = match op {
+            Operator::Add | Operator::Subtract if self.dimension != rhs.dimension => {
+                return Err(format!(
+                    "incompatible units: {} {} {}",
+                    dimension_name(self.dimension),
+                    op,
+                    dimension_name(rhs.dimension)
+                ));
+            }
+            Operator::Add | Operator::Subtract => self.dimension,
+            Operator::Multiply => std::array::from_fn(|i| self.dimension[i] + rhs.dimension[i]),
+            Operator::Divide => std::array::from_fn(|i| self.dimension[i] - rhs.dimension[i]),
+        };
+        Ok(Quantity {
+            value: op.apply(self.value, rhs.value)?,
+            dimension,
+        })
+    }
+
+    fn powf(self, exponent: Quantity) -> Result<Quantity, String> {
+        if exponent.dimension != DIMENSIONLESS {
+            return Err("exponents must be plain numbers".to_string());
+        }
+        if self.dimension != DIMENSIONLESS && exponent.value.fract() != 0.0 {
+            return Err("units can only be raised to whole powers".to_string());
+        }
+        Ok(Quantity {
+            value: self.value.powf(exponent.value),
+            dimension: scale_dimension(self.dimension, exponent.value as i32),
+        })
+    }
+}
+
+/// A unit a result can be shown in, possibly raised to a power such as `ft^2`.
+#[derive(Debug, Clone, PartialEq)]
+struct DisplayUnit {
+    label: String,
+    factor: f64,
+    dimension: Dimension,
+}
+
+impl DisplayUnit {
+    fn new(unit: &UnitDef) -> Self {
+        DisplayUnit {
+            label: unit.symbols[0].to_string(),
+            factor: unit.factor,
+            dimension: unit.dimension,
+        }
+    }
+
+    fn powi(&self, power: i32) -> Self {
+        DisplayUnit {
+            label: format!("{}^{}", self.label, power),
+            factor: self.factor.powi(power),
+            dimension: scale_dimension(self.dimension, power),
+        }
+    }
+}
+
+/// The result of an expression, converted to the unit it is displayed in.
+#[derive(Debug, Clone, PartialEq)]
+struct Measurement {
+    value: f64,
+    unit: Option<String>,
+}
+
+impl Measurement {
+    fn format(&self, format: &DisplayFormat) -> String {
+        match &self.unit {
+            Some(unit) => format!("{} {}", format.format(self.value), unit),
+            None => format.format(self.value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Number(f64),
+    Unit(&'static UnitDef),
+    Operator(Operator),
+    Power,
+    LParen,
+    RParen,
+    /// `to`, which introduces the unit to show the result in.
+    To,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    span: Range<usize>,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ExprError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let kind = match c {
+            c if c.is_whitespace() => continue,
+            '0'..='9' | '.' => {
+                let mut end = start + 1;
+                while let Some((i, _)) = chars.next_if(|(_, c)| c.is_ascii_digit() || *c == '.') {
+                    end = i + 1;
+                }
+                let number = &input[start..end];
+                let value = number.parse().map_err(|_| {
+                    ExprError::new(format!("'{}' is not a valid number", number), start..end)
+                })?;
+                tokens.push(Token {
+                    kind: TokenKind::Number(value),
+                    span: start..end,
+                });
+                continue;
+            }
+            c if c.is_alphabetic() => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, c)) = chars.next_if(|(_, c)| c.is_alphanumeric()) {
+                    end = i + c.len_utf8();
+                }
+                let name = &input[start..end];
+                let kind = if let Some(value) = constant(name) {
+                    TokenKind::Number(value)
+                } else if let Some(unit) = find_unit(name) {
+                    TokenKind::Unit(unit)
+                } else if name == "to" {
+                    TokenKind::To
+                } else {
+                    return Err(ExprError::new(
+                        format!("unknown name '{}'", name),
+                        start..end,
+                    ));
+                };
+                tokens.push(Token {
+                    kind,
+                    span: start..end,
+                });
+                continue;
+            }
+            '+' | '-' | '*' | '×' | '/' | '÷' => {
+                TokenKind::Operator(Operator::from_char(c).expect("operator character"))
+            }
+            '^' => TokenKind::Power,
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            c => {
+                return Err(ExprError::new(
+                    format!("unexpected character '{}'", c),
+                    start..start + c.len_utf8(),
+                ))
+            }
+        };
+        tokens.push(Token {
+            kind,
+            span: start..start + c.len_utf8(),
+        });
+    }
+    Ok(tokens)
+}
+
+/// Pratt parser that evaluates as it parses.
+struct ExprParser {
+    tokens: Vec<Token>,
+    pos: usize,
+    end: usize,
+    /// Units written in the expression, in order, used to pick the unit of the result.
+    seen_units: Vec<DisplayUnit>,
+}
+
+const PREFIX_BINDING_POWER: u8 = 7;
+
+impl ExprParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn end_span(&self) -> Range<usize> {
+        self.end..self.end
+    }
+
+    /// Left and right binding powers. A unit following a value multiplies it, binding tighter
+    /// than `*` so `6 m / 2 s` divides two quantities; `^` binds tighter than unary minus and
+    /// is right associative.
+    fn infix_binding_power(kind: &TokenKind) -> Option<(u8, u8)> {
+        match kind {
+            TokenKind::Operator(Operator::Add | Operator::Subtract) => Some((1, 2)),
+            TokenKind::Operator(Operator::Multiply | Operator::Divide) => Some((3, 4)),
+            TokenKind::Unit(_) => Some((5, 6)),
+            TokenKind::Power => Some((9, 8)),
+            _ => None,
+        }
+    }
+
+    fn parse_prefix(&mut self) -> Result<(Quantity, Range<usize>), ExprError> {
+        let Some(token) = self.next() else {
+            return Err(ExprError::new("expected a number", self.end_span()));
+        };
+        match token.kind {
+            TokenKind::Number(value) => Ok((Quantity::number(value), token.span)),
+            TokenKind::Unit(unit) => {
+                self.seen_units.push(DisplayUnit::new(unit));
+                Ok((
+                    Quantity {
+                        value: unit.factor,
+                        dimension: unit.dimension,
+                    },
+                    token.span,
+                ))
+            }
+            TokenKind::Operator(op @ (Operator::Add | Operator::Subtract)) => {
+                let (mut quantity, span) = self.parse_expr(PREFIX_BINDING_POWER)?;
+                if op == Operator::Subtract {
+                    quantity.value = -quantity.value;
+                }
+                Ok((quantity, token.span.start..span.end))
+            }
+            TokenKind::LParen => {
+                let (quantity, _) = self.parse_expr(0)?;
+                match self.next() {
+                    Some(Token {
+                        kind: TokenKind::RParen,
+                        span,
+                    }) => Ok((quantity, token.span.start..span.end)),
+                    Some(other) => Err(ExprError::new("expected ')'", other.span)),
+                    None => Err(ExprError::new("unclosed '('", token.span)),
+                }
+            }
+            _ => Err(ExprError::new("expected a number", token.span)),
+        }
+    }
+
+    fn parse_expr(&mut self, min_binding_power: u8) -> Result<(Quantity, Range<usize>), ExprError> {
+        let (mut lhs, mut span) = self.parse_prefix()?;
+        while let Some(token) = self.peek() {
+            let Some((left, right)) = Self::infix_binding_power(&token.kind) else {
+                match token.kind {
+                    TokenKind::RParen | TokenKind::To => break,
+                    _ => return Err(ExprError::new("expected an operator", token.span.clone())),
+                }
+            };
+            if left < min_binding_power {
+                break;
+            }
+            // A unit is an implicit multiplication, so it is left for parse_prefix to consume.
+            let operator = match token.kind {
+                TokenKind::Unit(_) => None,
+                _ => self.next(),
+            };
+            let (rhs, rhs_span) = self.parse_expr(right)?;
+            let operator_start = operator.as_ref().map_or(rhs_span.start, |op| op.span.start);
+            lhs = match operator.map(|op| op.kind) {
+                Some(TokenKind::Operator(op)) => lhs.combine(op, rhs),
+                Some(_) => lhs.powf(rhs),
+                None => lhs.combine(Operator::Multiply, rhs),
+            }
+            .map_err(|e| ExprError::new(e, operator_start..rhs_span.end))?;
+            span = span.start..rhs_span.end;
+        }
+        Ok((lhs, span))
+    }
+
+    /// Parses the unit after `to`, optionally raised to a whole power.
+    fn parse_target(&mut self, to: Range<usize>) -> Result<(DisplayUnit, Range<usize>), ExprError> {
+        let Some(Token {
+            kind: TokenKind::Unit(unit),
+            span,
+        }) = self.next()
+        else {
+            return Err(ExprError::new("expected a unit after 'to'", to));
+        };
+        let unit = DisplayUnit::new(unit);
+        if self.peek().map(|token| &token.kind) != Some(&TokenKind::Power) {
+            return Ok((unit, span));
+        }
+        self.next();
+        match self.next() {
+            Some(Token {
+                kind: TokenKind::Number(power),
+                span: power_span,
+            }) if power.fract() == 0.0 => Ok((unit.powi(power as i32), span.start..power_span.end)),
+            Some(token) => Err(ExprError::new("expected a whole number", token.span)),
+            None => Err(ExprError::new("expected a whole number", self.end_span())),
+        }
+    }
+
+    /// Picks the unit to show a result of `dimension` in: the first unit written with that
+    /// dimension, or a power of one (`ft^2` for an area computed from feet).
+    fn display_unit(&self, dimension: Dimension) -> Option<DisplayUnit> {
+        if dimension == DIMENSIONLESS {
+            return None;
+        }
+        self.seen_units
+            .iter()
+            .find(|unit| unit.dimension == dimension)
+            .cloned()
+            .or_else(|| {
+                self.seen_units.iter().find_map(|unit| {
+                    (2..=3)
+                        .find(|&power| scale_dimension(unit.dimension, power) == dimension)
+                        .map(|power| unit.powi(power))
+                })
+            })
+    }
+}
+
+fn evaluate_expression(input: &str) -> Result<Measurement, ExprError> {
+    let tokens = tokenize(input)?;
+    let mut parser = ExprParser {
+        tokens,
+        pos: 0,
+        end: input.len(),
+        seen_units: Vec::new(),
+    };
+    let (quantity, span) = parser.parse_expr(0)?;
+    let target = match parser.next() {
+        None => None,
+        Some(Token {
+            kind: TokenKind::To,
+            span: to,
+        }) => Some(parser.parse_target(to)?),
+        Some(token) => return Err(ExprError::new("unmatched ')'", token.span)),
+    };
+    if let Some(token) = parser.peek() {
+        return Err(ExprError::new(
+            "unexpected input after the unit",
+            token.span.start..input.len(),
+        ));
+    }
+    if !quantity.value.is_finite() {
+        return Err(ExprError::new(
+            "the result is not a finite number",
+            0..input.len(),
+        ));
+    }
+
+    let unit = match target {
+        Some((unit, unit_span)) if unit.dimension != quantity.dimension => {
+            return Err(ExprError::new(
+                format!(
+                    "cannot convert {} to {}",
+                    dimension_name(quantity.dimension),
+                    unit.label
+                ),
+                span.start..unit_span.end,
+            ));
+        }
+        Some((unit, _)) => Some(unit),
+        None => parser.display_unit(quantity.dimension),
+    };
+    Ok(match unit {
+        Some(unit) => Measurement {
+            value: quantity.value / unit.factor,
+            unit: Some(unit.label),
+        },
+        None if quantity.dimension == DIMENSIONLESS => Measurement {
+            value: quantity.value,
+            unit: None,
+        },
+        None => Measurement {
+            value: quantity.value,
+            unit: Some(format_base_units(quantity.dimension)),
+        },
+    })
+}
+
+/// Input and last result of the expression tab.
 #[derive(Default)]
+struct ExpressionPanel {
+    input: String,
+    result: Option<Result<Measurement, ExprError>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum Tab {
+    #[default]
+    Basic,
+    Scientific,
+    Expression,
+}
+
+/// Calculator state machine driven by key presses from either the keypad or the keyboard.
+struct Calculator {
+    entry: String,
+    accumulator: Option<f64>,
+    pending: Option<Operator>,
+    // Set after an operator or equals so that the next digit starts a fresh entry.
+    start_new_entry: bool,
+    // Set when the entry was recalled from the history: it counts as an operand, but typing
+    // replaces it rather than appending to it.
+    recalled: bool,
+    error: Option<String>,
+    format: DisplayFormat,
+    history: History,
+}
+
+impl Default for Calculator {
+    fn default() -> Self {
+        Self {
+            entry: "0".to_string(),
+            accumulator: None,
+            pending: None,
+            start_new_entry: true,
+            recalled: false,
+            error: None,
+            format: DisplayFormat::default(),
+            history: History::default(),
+        }
+    }
+}
+
+impl Calculator {
+    fn entry_value(&self) -> f64 {
+        self.entry.parse().unwrap_or(0.0)
+    }
+
+    fn input_digit(&mut self, digit: char) {
+        if self.error.is_some() {
+            self.clear();
+        }
+        if self.start_new_entry || self.recalled || self.entry == "0" {
+            self.entry.clear();
+            self.start_new_entry = false;
+            self.recalled = false;
+        }
+        self.entry.push(digit);
+    }
+
+    fn input_decimal(&mut self) {
+        if self.error.is_some() {
+            self.clear();
+        }
+        if self.start_new_entry || self.recalled {
+            self.entry = "0".to_string();
+            self.start_new_entry = false;
+            self.recalled = false;
+        }
+        if !self.entry.contains('.') {
+            self.entry.push('.');
+        }
+    }
+
+    fn input_operator(&mut self, op: Operator) {
+        if self.error.is_some() {
+            return;
+        }
+        // Pressing operators back to back only replaces the pending operator.
+        if !self.start_new_entry || self.accumulator.is_none() {
+            self.evaluate_pending();
+        }
+        if self.error.is_none() {
+            self.pending = Some(op);
+            self.start_new_entry = true;
+            self.recalled = false;
+        }
+    }
+
+    fn equals(&mut self) {
+        if self.error.is_some() || self.pending.is_none() {
+            return;
+        }
+        self.evaluate_pending();
+        self.pending = None;
+        self.accumulator = None;
+        self.start_new_entry = true;
+        self.recalled = false;
+    }
+
+    fn evaluate_pending(&mut self) {
+        let rhs = self.entry_value();
+        let result = match (self.accumulator, self.pending) {
+            (Some(lhs), Some(op)) => op.apply(lhs, rhs).inspect(|&value| {
+                let expression = format!(
+                    "{} {} {}",
+                    format_number(lhs, None),
+                    op,
+                    format_number(rhs, None)
+                );
+                self.history.push(expression, value);
+            }),
+            _ => Ok(rhs),
+        };
+        match result {
+            Ok(value) => {
+                self.accumulator = Some(value);
+                self.entry = format_number(value, None);
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    /// Uses a previous result as the current entry.
+    fn recall(&mut self, value: f64) {
+        if self.error.is_some() {
+            self.clear();
+        }
+        self.entry = format_number(value, None);
+        self.start_new_entry = false;
+        self.recalled = true;
+    }
+
+    fn backspace(&mut self) {
+        if self.start_new_entry || self.recalled || self.error.is_some() {
+            return;
+        }
+        self.entry.pop();
+        if self.entry.is_empty() || self.entry == "-" {
+            self.entry = "0".to_string();
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = Calculator {
+            format: self.format,
+            history: std::mem::take(&mut self.history),
+            ..Calculator::default()
+        };
+    }
+
+    fn running_display(&self) -> String {
+        match (self.accumulator, self.pending) {
+            (Some(lhs), Some(op)) => format!("{} {}", self.format.format(lhs), op),
+            _ => String::new(),
+        }
+    }
+
+    /// Shows the error, the entry being typed verbatim, or the last result in the selected
+    /// format.
+    fn main_display(&self) -> String {
+        if let Some(error) = &self.error {
+            error.clone()
+        } else if self.start_new_entry || self.recalled {
+            self.format.format(self.entry_value())
+        } else {
+            self.entry.clone()
+        }
+    }
+
+    fn press(&mut self, key: char) {
+        match key {
+            '0'..='9' => self.input_digit(key),
+            '.' => self.input_decimal(),
+            '=' => self.equals(),
+            'C' => self.clear(),
+            _ => {
+                if let Some(op) = Operator::from_char(key) {
+                    self.input_operator(op);
+                }
+            }
+        }
+    }
+}
+
 struct SimpleMathApp {
-    value1: String,
-    value2: String,
+    tab: Tab,
+    calculator: Calculator,
+    scientific: ScientificPanel,
+    expression: ExpressionPanel,
+    export_path: String,
+    export_status: Option<Result<String, String>>,
+    /// Set when the tab changes so the new tab's input takes keyboard focus.
+    focus_input: bool,
+}
+
+impl Default for SimpleMathApp {
+    fn default() -> Self {
+        Self {
+            tab: Tab::default(),
+            calculator: Calculator::default(),
+            scientific: ScientificPanel::default(),
+            expression: ExpressionPanel::default(),
+            export_path: DEFAULT_EXPORT_PATH.to_string(),
+            export_status: None,
+            focus_input: false,
+        }
+    }
+}
+
+const KEYPAD: [[char; 4]; 4] = [
+    ['7', '8', '9', '÷'],
+    ['4', '5', '6', '×'],
+    ['1', '2', '3', '-'],
+    ['0', '.', '=', '+'],
+];
+
+const TAB_SHORTCUTS: [(egui::Key, Tab); 3] = [
+    (egui::Key::Num1, Tab::Basic),
+    (egui::Key::Num2, Tab::Scientific),
+    (egui::Key::Num3, Tab::Expression),
+];
+
+/// Esc normally just unfocuses a text field; here it clears the field and keeps focus so the
+/// next value can be typed straight away.
+fn clear_on_escape(ui: &egui::Ui, response: &egui::Response, text: &mut String) {
+    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+        text.clear();
+        response.request_focus();
+    }
+}
+
+impl SimpleMathApp {
+    fn switch_tab(&mut self, tab: Tab) {
+        if self.tab != tab {
+            self.tab = tab;
+            self.focus_input = true;
+        }
+    }
+
+    /// Reuses the most recent history entry, if there is one.
+    fn reuse_last(&mut self) {
+        if let Some(entry) = self.calculator.history.entries.last().cloned() {
+            self.reuse(&entry);
+        }
+    }
+
+    /// Shortcuts that work on every tab, even while a text field has focus.
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        let (tab, reuse) = ctx.input_mut(|input| {
+            let tab = TAB_SHORTCUTS
+                .into_iter()
+                .find(|(key, _)| input.consume_key(egui::Modifiers::COMMAND, *key))
+                .map(|(_, tab)| tab);
+            (
+                tab,
+                input.consume_key(egui::Modifiers::COMMAND, egui::Key::R),
+            )
+        });
+        if let Some(tab) = tab {
+            self.switch_tab(tab);
+        }
+        if reuse {
+            self.reuse_last();
+        }
+    }
+
+    fn handle_keyboard(&mut self, ctx: &egui::Context) {
+        // Keys typed into a focused text field, such as the export path, belong to it.
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        ctx.input(|input| {
+            for event in &input.events {
+                match event {
+                    egui::Event::Text(text) => text.chars().for_each(|c| self.calculator.press(c)),
+                    egui::Event::Key {
+                        key, pressed: true, ..
+                    } => match key {
+                        egui::Key::Enter => self.calculator.equals(),
+                        egui::Key::Escape => self.calculator.clear(),
+                        egui::Key::Backspace => self.calculator.backspace(),
+                        _ => {}
+                    },
+                    _ => {}
+                }
+            }
+        });
+    }
+
+    fn show_scientific(&mut self, ui: &mut egui::Ui) {
+        let panel = &mut self.scientific;
+        let focus_input = std::mem::take(&mut self.focus_input);
+        egui::Grid::new("scientific_inputs").show(ui, |ui| {
+            ui.label("x:");
+            let response = ui.text_edit_singleline(&mut panel.input);
+            clear_on_escape(ui, &response, &mut panel.input);
+            if focus_input {
+                response.request_focus();
+            }
+            if ui.button("π").clicked() {
+                panel.input = "π".to_string();
+            }
+            if ui.button("e").clicked() {
+                panel.input = "e".to_string();
+            }
+            ui.end_row();
+            ui.label("y:");
+            let response = ui.text_edit_singleline(&mut panel.y);
+            clear_on_escape(ui, &response, &mut panel.y);
+            ui.end_row();
+        });
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut panel.angle, AngleUnit::Degrees, "Degrees");
+            ui.radio_value(&mut panel.angle, AngleUnit::Radians, "Radians");
+        });
+
+        let button_size = egui::vec2(80.0, 40.0);
+        egui::Grid::new("scientific_keypad").show(ui, |ui| {
+            for (i, op) in UNARY_OPS.into_iter().enumerate() {
+                let button = egui::Button::new(op.to_string()).min_size(button_size);
+                if ui.add(button).clicked() {
+                    if let Some(entry) = panel.evaluate(op, &self.calculator.format) {
+                        self.calculator.history.push(entry.expression, entry.result);
+                    }
+                }
+                if i % 4 == 3 {
+                    ui.end_row();
+                }
+            }
+            for op in [BinaryOp::Power, BinaryOp::Root] {
+                let button = egui::Button::new(op.label()).min_size(button_size);
+                if ui.add(button).clicked() {
+                    if let Some(entry) = panel.evaluate_binary(op, &self.calculator.format) {
+                        self.calculator.history.push(entry.expression, entry.result);
+                    }
+                }
+            }
+        });
+        ui.separator();
+
+        match &panel.result {
+            Some(Ok(result)) => {
+                ui.heading(result);
+            }
+            Some(Err(e)) => {
+                ui.colored_label(egui::Color32::RED, e);
+            }
+            None => {}
+        }
+    }
+
+    fn show_expression(&mut self, ui: &mut egui::Ui) {
+        let panel = &mut self.expression;
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut panel.input)
+                .font(egui::TextStyle::Monospace)
+                .hint_text("3 * (2 + 4.5) / 7 or 3 ft + 2 m to cm")
+                .desired_width(f32::INFINITY),
+        );
+        if std::mem::take(&mut self.focus_input) {
+            response.request_focus();
+        }
+        clear_on_escape(ui, &response, &mut panel.input);
+        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if ui.button("=").clicked() || submitted {
+            let result = evaluate_expression(&panel.input);
+            if let Ok(measurement) = &result {
+                self.calculator
+                    .history
+                    .push_measurement(panel.input.trim(), measurement);
+            }
+            panel.result = Some(result);
+        }
+        ui.separator();
+
+        match &panel.result {
+            Some(Ok(measurement)) => {
+                ui.heading(measurement.format(&self.calculator.format));
+            }
+            Some(Err(e)) => {
+                ui.monospace(&panel.input);
+                ui.colored_label(
+                    egui::Color32::RED,
+                    egui::RichText::new(e.underline(&panel.input)).monospace(),
+                );
+                ui.colored_label(egui::Color32::RED, &e.message);
+            }
+            None => {}
+        }
+    }
+
+    /// Reuses a previous result as an operand in whichever tab is showing. Only the
+    /// expression tab understands units, so the others take the bare number.
+    fn reuse(&mut self, entry: &HistoryEntry) {
+        let value_text = format_number(entry.result, None);
+        match self.tab {
+            Tab::Basic => self.calculator.recall(entry.result),
+            Tab::Scientific => self.scientific.input = value_text,
+            Tab::Expression => {
+                let input = &mut self.expression.input;
+                if !input.is_empty() && !input.ends_with(' ') {
+                    input.push(' ');
+                }
+                input.push_str(&value_text);
+                if let Some(unit) = &entry.unit {
+                    input.push(' ');
+                    input.push_str(unit);
+                }
+            }
+        }
+    }
+
+    fn show_history(&mut self, ui: &mut egui::Ui) {
+        ui.heading("History");
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.export_path);
+            if ui.button("Export").clicked() {
+                let path = self.export_path.trim();
+                self.export_status = Some(
+                    self.calculator
+                        .history
+                        .export(Path::new(path), &self.calculator.format)
+                        .map(|_| format!("Saved to {}", path))
+                        .map_err(|e| format!("Error writing {}: {}", path, e)),
+                );
+            }
+            if ui.button("Clear").clicked() {
+                self.calculator.history.entries.clear();
+            }
+        });
+        match &self.export_status {
+            Some(Ok(message)) => {
+                ui.label(message);
+            }
+            Some(Err(e)) => {
+                ui.colored_label(egui::Color32::RED, e);
+            }
+            None => {}
+        }
+        ui.separator();
+
+        let mut reused = None;
+        egui::ScrollArea::vertical()
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for entry in &self.calculator.history.entries {
+                    let text = format!(
+                        "{} = {}",
+                        entry.expression,
+                        entry.formatted_result(&self.calculator.format)
+                    );
+                    if ui
+                        .selectable_label(false, text)
+                        .on_hover_text("Click to reuse this result")
+                        .clicked()
+                    {
+                        reused = Some(entry.clone());
+                    }
+                }
+            });
+        if let Some(entry) = reused {
+            self.reuse(&entry);
+        }
+    }
 }
 
 impl eframe::App for SimpleMathApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.handle_shortcuts(ctx);
+        // Typing into the scientific or expression input must not also drive the keypad.
+        if self.tab == Tab::Basic {
+            if std::mem::take(&mut self.focus_input) {
+                ctx.memory_mut(|memory| memory.stop_text_input());
+            }
+            self.handle_keyboard(ctx);
+        }
+
+        egui::SidePanel::right("history")
+            .min_width(200.0)
+            .show(ctx, |ui| self.show_history(ui));
+
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.label("Enter value 1:");
-            ui.text_edit_singleline(&mut self.value1);
-            ui.label("Enter value 2:");
-            ui.text_edit_singleline(&mut self.value2);
+            ui.horizontal(|ui| {
+                for (i, (tab, name)) in [
+                    (Tab::Basic, "Basic"),
+                    (Tab::Scientific, "Scientific"),
+                    (Tab::Expression, "Expression"),
+                ]
+                .into_iter()
+                .enumerate()
+                {
+                    let shortcut = format!("Ctrl+{}", i + 1);
+                    if ui
+                        .selectable_label(self.tab == tab, name)
+                        .on_hover_text(shortcut)
+                        .clicked()
+                    {
+                        self.switch_tab(tab);
+                    }
+                }
+            });
+            ui.separator();
 
-            let value1: f64 = self.value1.parse().unwrap_or(f64::NAN);
-            let value2: f64 = self.value2.parse().unwrap_or(f64::NAN);
+            let format = &mut self.calculator.format;
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("format_style")
+                    .selected_text(format.style.to_string())
+                    .show_ui(ui, |ui| {
+                        for style in FORMAT_STYLES {
+                            ui.selectable_value(&mut format.style, style, style.to_string());
+                        }
+                    });
+                let precision_text = |precision: Option<usize>| match precision {
+                    Some(decimals) => format!("{} decimals", decimals),
+                    None => "Auto".to_string(),
+                };
+                ui.add_enabled_ui(format.style != FormatStyle::Currency, |ui| {
+                    egui::ComboBox::from_id_salt("precision")
+                        .selected_text(precision_text(format.precision))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut format.precision, None, "Auto");
+                            for decimals in 0..=6 {
+                                ui.selectable_value(
+                                    &mut format.precision,
+                                    Some(decimals),
+                                    precision_text(Some(decimals)),
+                                );
+                            }
+                        });
+                });
+            });
+            ui.separator();
 
-            if value1.is_nan() || value2.is_nan() {
-                ui.label("Please enter valid numbers.");
-                return;
+            match self.tab {
+                Tab::Scientific => return self.show_scientific(ui),
+                Tab::Expression => return self.show_expression(ui),
+                Tab::Basic => {}
             }
 
-            ui.label(format!(
-                "{:.2} + {:.2} = {:.2}",
-                value1,
-                value2,
-                value1 + value2
-            ));
-            ui.label(format!(
-                "{:.2} - {:.2} = {:.2}",
-                value1,
-                value2,
-                value1 - value2
-            ));
-            ui.label(format!(
-                "{:.2} * {:.2} = {:.2}",
-                value1,
-                value2,
-                value1 * value2
-            ));
-            if value2 != 0.0 {
-                ui.label(format!(
-                    "{:.2} / {:.2} = {:.2}",
-                    value1,
-                    value2,
-                    value1 / value2
-                ));
-            } else {
-                ui.label("Division by zero is undefined.");
-            }
+            ui.with_layout(egui::Layout::top_down(egui::Align::Max), |ui| {
+                ui.label(self.calculator.running_display());
+                ui.heading(self.calculator.main_display());
+            });
+            ui.separator();
+
+            let button_size = egui::vec2(60.0, 40.0);
+            egui::Grid::new("keypad").show(ui, |ui| {
+                for row in KEYPAD {
+                    for key in row {
+                        let button = egui::Button::new(key.to_string()).min_size(button_size);
+                        if ui.add(button).clicked() {
+                            self.calculator.press(key);
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+
+            ui.horizontal(|ui| {
+                if ui.button("C").clicked() {
+                    self.calculator.clear();
+                }
+                if ui.button("⌫").clicked() {
+                    self.calculator.backspace();
+                }
+            });
         });
     }
 }
 
 fn main() -> eframe::Result {
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 250.0]),
+        viewport: egui::ViewportBuilder::default().with_inner_size([540.0, 370.0]),
         ..Default::default()
     };
     eframe::run_native(
@@ -77,3 +1592,387 @@ fn main() -> eframe::Result {
         Box::new(|_| Ok(Box::<SimpleMathApp>::default())),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press_all(calculator: &mut Calculator, keys: &str) {
+        keys.chars().for_each(|key| calculator.press(key));
+    }
+
+    #[test]
+    fn calculator_evaluates_simple_operations() {
+        let mut calculator = Calculator::default();
+        press_all(&mut calculator, "12+30=");
+        assert_eq!(calculator.main_display(), "42");
+
+        press_all(&mut calculator, "7.5*2=");
+        assert_eq!(calculator.main_display(), "15");
+    }
+
+    #[test]
+    fn calculator_chains_operations() {
+        let mut calculator = Calculator::default();
+        press_all(&mut calculator, "2+3*4");
+        assert_eq!(calculator.running_display(), "5 ×");
+
+        press_all(&mut calculator, "=-1=");
+        assert_eq!(calculator.main_display(), "19");
+    }
+
+    #[test]
+    fn calculator_replaces_pending_operator() {
+        let mut calculator = Calculator::default();
+        press_all(&mut calculator, "8+-/2=");
+        assert_eq!(calculator.main_display(), "4");
+    }
+
+    #[test]
+    fn calculator_reports_division_by_zero() {
+        let mut calculator = Calculator::default();
+        press_all(&mut calculator, "5/0=");
+        assert_eq!(calculator.main_display(), "Division by zero is undefined.");
+
+        press_all(&mut calculator, "3");
+        assert_eq!(calculator.main_display(), "3");
+    }
+
+    #[test]
+    fn calculator_backspace_and_clear() {
+        let mut calculator = Calculator::default();
+        press_all(&mut calculator, "123");
+        calculator.backspace();
+        assert_eq!(calculator.main_display(), "12");
+
+        calculator.clear();
+        assert_eq!(calculator.main_display(), "0");
+        assert_eq!(calculator.running_display(), "");
+    }
+
+    #[test]
+    fn display_format_applies_style_and_precision() {
+        let plain = DisplayFormat::default();
+        assert_eq!(plain.format(1234567.5), "1,234,567.5");
+        assert_eq!(plain.format(-0.0), "0");
+
+        let fixed = DisplayFormat {
+            precision: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(fixed.format(-1234.5), "-1,234.500");
+
+        let currency = DisplayFormat {
+            style: FormatStyle::Currency,
+            precision: Some(0),
+        };
+        assert_eq!(currency.format(1234.567), "$1,234.57");
+        assert_eq!(currency.format(-12.5), "-$12.50");
+        assert_eq!(currency.format(-0.001), "$0.00");
+
+        let percentage = DisplayFormat {
+            style: FormatStyle::Percentage,
+            precision: Some(1),
+        };
+        assert_eq!(percentage.format(0.1234), "12.3%");
+    }
+
+    #[test]
+    fn unary_ops_validate_their_domain() {
+        assert_eq!(UnaryOp::Sqrt.apply(9.0, AngleUnit::Degrees), Ok(3.0));
+        assert!(UnaryOp::Sqrt.apply(-1.0, AngleUnit::Degrees).is_err());
+        assert_eq!(UnaryOp::Log10.apply(1000.0, AngleUnit::Degrees), Ok(3.0));
+        assert!(UnaryOp::Ln.apply(0.0, AngleUnit::Degrees).is_err());
+        assert!(UnaryOp::Log10.apply(-5.0, AngleUnit::Degrees).is_err());
+    }
+
+    #[test]
+    fn trig_ops_respect_angle_unit() {
+        let sin = UnaryOp::Sin.apply(30.0, AngleUnit::Degrees).unwrap();
+        assert!((sin - 0.5).abs() < 1e-12);
+        let cos = UnaryOp::Cos
+            .apply(std::f64::consts::PI, AngleUnit::Radians)
+            .unwrap();
+        assert!((cos + 1.0).abs() < 1e-12);
+        assert!(UnaryOp::Tan.apply(90.0, AngleUnit::Degrees).is_err());
+        assert!(UnaryOp::Tan.apply(-270.0, AngleUnit::Degrees).is_err());
+        let tan = UnaryOp::Tan.apply(45.0, AngleUnit::Degrees).unwrap();
+        assert!((tan - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn scientific_panel_reports_formatted_results_and_errors() {
+        let mut panel = ScientificPanel {
+            input: " 2 ".to_string(),
+            ..Default::default()
+        };
+        panel.evaluate(
+            UnaryOp::Sqrt,
+            &DisplayFormat {
+                precision: Some(3),
+                ..Default::default()
+            },
+        );
+        assert_eq!(panel.result, Some(Ok("√(2) = 1.414".to_string())));
+
+        panel.input = "abc".to_string();
+        panel.evaluate(UnaryOp::Ln, &DisplayFormat::default());
+        assert_eq!(
+            panel.result,
+            Some(Err("'abc' is not a valid number.".to_string()))
+        );
+    }
+
+    #[test]
+    fn calculator_formats_results_but_not_typed_entries() {
+        let mut calculator = Calculator::default();
+        calculator.format.style = FormatStyle::Currency;
+        press_all(&mut calculator, "1234.5");
+        assert_eq!(calculator.main_display(), "1234.5");
+
+        press_all(&mut calculator, "*2=");
+        assert_eq!(calculator.main_display(), "$2,469.00");
+
+        calculator.clear();
+        assert_eq!(calculator.format.style, FormatStyle::Currency);
+    }
+
+    fn expression_value(input: &str) -> Result<f64, ExprError> {
+        evaluate_expression(input).map(|measurement| measurement.value)
+    }
+
+    #[test]
+    fn evaluate_expression_respects_precedence_and_parentheses() {
+        let value = expression_value("3 * (2 + 4.5) / 7").unwrap();
+        assert!((value - 19.5 / 7.0).abs() < 1e-12);
+        assert_eq!(expression_value("2 + 3 * 4"), Ok(14.0));
+        assert_eq!(expression_value("(2 + 3) × 4"), Ok(20.0));
+        assert_eq!(expression_value("10 - 4 - 3"), Ok(3.0));
+        assert_eq!(expression_value("2 ^ 3 ^ 2"), Ok(512.0));
+        assert_eq!(expression_value("-2 ^ 2"), Ok(-4.0));
+        assert_eq!(expression_value("-(1 + 2) * -3"), Ok(9.0));
+    }
+
+    #[test]
+    fn evaluate_expression_reports_error_spans() {
+        let error = |input: &str| evaluate_expression(input).unwrap_err();
+
+        assert_eq!(
+            error("3 * (2 + ) / 7"),
+            ExprError::new("expected a number", 9..10)
+        );
+        assert_eq!(error("3 * (2 + 4"), ExprError::new("unclosed '('", 4..5));
+        assert_eq!(error("2 + 3)"), ExprError::new("unmatched ')'", 5..6));
+        assert_eq!(error("2 3"), ExprError::new("expected an operator", 2..3));
+        assert_eq!(error("2 + abc"), ExprError::new("unknown name 'abc'", 4..7));
+        assert_eq!(
+            error("2 + $"),
+            ExprError::new("unexpected character '$'", 4..5)
+        );
+        assert_eq!(
+            error("1.2.3"),
+            ExprError::new("'1.2.3' is not a valid number", 0..5)
+        );
+        assert_eq!(error("4 +"), ExprError::new("expected a number", 3..3));
+        assert_eq!(
+            error("1 / (2 - 2)"),
+            ExprError::new("Division by zero is undefined.", 2..11)
+        );
+    }
+
+    #[test]
+    fn expr_error_underlines_span() {
+        let input = "3 × (2 + ) / 7";
+        let error = evaluate_expression(input).unwrap_err();
+        assert_eq!(error.underline(input), "         ^");
+    }
+
+    #[test]
+    fn calculator_records_history_and_recalls_results() {
+        let mut calculator = Calculator::default();
+        press_all(&mut calculator, "2+3*4=");
+        assert_eq!(
+            calculator.history.entries,
+            vec![
+                HistoryEntry {
+                    expression: "2 + 3".to_string(),
+                    result: 5.0,
+                    unit: None
+                },
+                HistoryEntry {
+                    expression: "5 × 4".to_string(),
+                    result: 20.0,
+                    unit: None
+                },
+            ]
+        );
+
+        calculator.clear();
+        assert_eq!(calculator.history.entries.len(), 2);
+
+        press_all(&mut calculator, "10-");
+        calculator.recall(5.0);
+        assert_eq!(calculator.main_display(), "5");
+        press_all(&mut calculator, "=");
+        assert_eq!(calculator.main_display(), "5");
+
+        // Typing after a recall replaces the recalled value.
+        calculator.recall(20.0);
+        press_all(&mut calculator, "7");
+        assert_eq!(calculator.main_display(), "7");
+    }
+
+    #[test]
+    fn history_exports_csv_and_text() {
+        let mut history = History::default();
+        history.push("1234 × 2", 2468.0);
+        history.push("a, \"b\"", 0.5);
+        history.push_measurement(
+            "3 ft + 2 m",
+            &Measurement {
+                value: 9.56,
+                unit: Some("ft".to_string()),
+            },
+        );
+
+        assert_eq!(
+            history.to_csv(),
+            "expression,result,unit\n1234 × 2,2468,\n\"a, \"\"b\"\"\",0.5,\n3 ft + 2 m,9.56,ft\n"
+        );
+        assert_eq!(
+            history.to_text(&DisplayFormat::default()),
+            "1234 × 2 = 2,468\na, \"b\" = 0.5\n3 ft + 2 m = 9.56 ft\n"
+        );
+
+        let path = std::env::temp_dir().join("e05_history_export.CSV");
+        history.export(&path, &DisplayFormat::default()).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), history.to_csv());
+        std::fs::remove_file(&path).unwrap();
+
+        let mut history = History::default();
+        history.push("1 +\r2", 3.0);
+        assert_eq!(history.to_csv(), "expression,result,unit\n\"1 +\r2\",3,\n");
+    }
+
+    #[test]
+    fn binary_ops_compute_powers_and_roots() {
+        assert_eq!(BinaryOp::Power.apply(2.0, 10.0), Ok(1024.0));
+        assert_eq!(BinaryOp::Root.apply(81.0, 4.0), Ok(3.0));
+        assert_eq!(BinaryOp::Root.apply(-27.0, 3.0), Ok(-3.0));
+        assert!(BinaryOp::Root.apply(-16.0, 4.0).is_err());
+        assert!(BinaryOp::Root.apply(8.0, 0.0).is_err());
+        assert!(BinaryOp::Power.apply(-8.0, 0.5).is_err());
+        assert!(BinaryOp::Power.apply(10.0, 400.0).is_err());
+    }
+
+    #[test]
+    fn constants_are_accepted_as_operands() {
+        let mut panel = ScientificPanel {
+            input: "π".to_string(),
+            angle: AngleUnit::Radians,
+            ..Default::default()
+        };
+        let entry = panel.evaluate(UnaryOp::Cos, &DisplayFormat::default());
+        assert_eq!(entry.map(|entry| entry.result), Some(-1.0));
+
+        panel.input = " e ".to_string();
+        panel.y = "2".to_string();
+        let entry = panel
+            .evaluate_binary(BinaryOp::Power, &DisplayFormat::default())
+            .unwrap();
+        assert!((entry.result - std::f64::consts::E.powi(2)).abs() < 1e-12);
+
+        let value = expression_value("2 * pi * e").unwrap();
+        assert!((value - 2.0 * std::f64::consts::PI * std::f64::consts::E).abs() < 1e-12);
+        assert_eq!(expression_value("π / π"), Ok(1.0));
+    }
+
+    fn measurement(input: &str) -> (f64, String) {
+        let measurement = evaluate_expression(input).unwrap();
+        (measurement.value, measurement.unit.unwrap_or_default())
+    }
+
+    #[test]
+    fn evaluate_expression_converts_units() {
+        let (feet, unit) = measurement("3 ft + 2 m");
+        assert!((feet - (3.0 + 2.0 / 0.3048)).abs() < 1e-9);
+        assert_eq!(unit, "ft");
+
+        let (centimeters, unit) = measurement("3 ft + 2 m to cm");
+        assert!((centimeters - 291.44).abs() < 1e-9);
+        assert_eq!(unit, "cm");
+
+        let (speed, unit) = measurement("60 mi / 1.5 h");
+        assert!((speed - 40.0 * 1609.344 / 3600.0).abs() < 1e-9);
+        assert_eq!(unit, "m/s");
+
+        let (area, unit) = measurement("12 ft * 10 ft");
+        assert!((area - 120.0).abs() < 1e-9);
+        assert_eq!(unit, "ft^2");
+
+        let (area, unit) = measurement("(2 m)^2 to ft^2");
+        assert!((area - 4.0 / 0.09290304).abs() < 1e-9);
+        assert_eq!(unit, "ft^2");
+
+        assert_eq!(measurement("2 km / 500 m"), (4.0, String::new()));
+        assert_eq!(measurement("-3 ft^2 to ft^2"), (-3.0, "ft^2".to_string()));
+    }
+
+    #[test]
+    fn evaluate_expression_rejects_incompatible_units() {
+        let error = |input: &str| evaluate_expression(input).unwrap_err();
+
+        assert_eq!(
+            error("3 ft + 2 kg"),
+            ExprError::new("incompatible units: a length + a mass", 5..11)
+        );
+        assert_eq!(
+            error("1 + 2 s"),
+            ExprError::new("incompatible units: a plain number + a time", 2..7)
+        );
+        assert_eq!(
+            error("3 ft to kg"),
+            ExprError::new("cannot convert a length to kg", 0..10)
+        );
+        assert_eq!(
+            error("3 ft to"),
+            ExprError::new("expected a unit after 'to'", 5..7)
+        );
+        assert_eq!(
+            error("2 ^ (3 m)"),
+            ExprError::new("exponents must be plain numbers", 2..9)
+        );
+        assert_eq!(
+            error("(2 m)^0.5"),
+            ExprError::new("units can only be raised to whole powers", 5..9)
+        );
+    }
+
+    #[test]
+    fn switching_tabs_requests_focus_and_reuse_last_uses_latest_result() {
+        let mut app = SimpleMathApp::default();
+        app.switch_tab(Tab::Basic);
+        assert!(!app.focus_input);
+        app.switch_tab(Tab::Expression);
+        assert!(app.focus_input);
+
+        app.reuse_last();
+        assert_eq!(app.expression.input, "");
+
+        app.calculator.history.push("1 + 1", 2.0);
+        app.calculator.history.push_measurement(
+            "3 ft + 2 m",
+            &Measurement {
+                value: 9.5,
+                unit: Some("ft".to_string()),
+            },
+        );
+        app.expression.input = "2 *".to_string();
+        app.reuse_last();
+        assert_eq!(app.expression.input, "2 * 9.5 ft");
+
+        app.switch_tab(Tab::Basic);
+        app.reuse_last();
+        assert_eq!(app.calculator.main_display(), "9.5");
+    }
+}
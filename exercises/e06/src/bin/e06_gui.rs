@@ -0,0 +1,180 @@
+//! # Retirement Calculator GUI
+//!
+//! This module implements a graphical front-end for the retirement calculator that
+//! recomputes the retirement estimate and savings projection live as the inputs change.
+//!
+//! ## Features
+//!
+//! - **Age Sliders**: Sliders for the current age and the planned retirement age
+//! - **Live Recalculation**: Updates the years left, retirement year, and nest egg on every change
+//! - **Savings Projection**: Shows the year-by-year contributions, growth, and balance table
+//! - **Eligibility Milestones**: Lists the calendar year of each milestone age from
+//!   `inputs/milestones.toml`
+//! - **Shared Logic**: Uses the same projection and milestone implementation as the CLI
+use chrono::Datelike;
+use e06::{
+    format_dollars, get_milestone_year, load_milestones, project_savings, Milestone, SavingsPlan,
+    MILESTONES_FILE,
+};
+use eframe::egui;
+use std::path::Path;
+
+struct RetirementCalculator {
+    current_age: u32,
+    retirement_age: u32,
+    plan: SavingsPlan,
+    milestones: Result<Vec<Milestone>, String>,
+}
+
+impl Default for RetirementCalculator {
+    fn default() -> Self {
+        Self {
+            current_age: 30,
+            retirement_age: 65,
+            plan: SavingsPlan {
+                current_savings: 10_000.0,
+                monthly_contribution: 500.0,
+                annual_return: 6.0,
+            },
+            milestones: load_milestones(Path::new(MILESTONES_FILE)).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+impl RetirementCalculator {
+    fn show_inputs(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("inputs").num_columns(2).show(ui, |ui| {
+            ui.label("Current age:");
+            ui.add(egui::Slider::new(&mut self.current_age, 1..=100));
+            ui.end_row();
+
+            ui.label("Retirement age:");
+            ui.add(egui::Slider::new(&mut self.retirement_age, 1..=100));
+            ui.end_row();
+
+            ui.label("Current savings:");
+            ui.add(
+                egui::DragValue::new(&mut self.plan.current_savings)
+                    .range(0.0..=f64::MAX)
+                    .speed(100.0)
+                    .prefix("$"),
+            );
+            ui.end_row();
+
+            ui.label("Monthly contribution:");
+            ui.add(
+                egui::DragValue::new(&mut self.plan.monthly_contribution)
+                    .range(0.0..=f64::MAX)
+                    .speed(10.0)
+                    .prefix("$"),
+            );
+            ui.end_row();
+
+            ui.label("Annual return:");
+            ui.add(
+                egui::DragValue::new(&mut self.plan.annual_return)
+                    .range(0.0..=100.0)
+                    .speed(0.1)
+                    .suffix("%"),
+            );
+            ui.end_row();
+        });
+    }
+
+    fn show_projection(&self, ui: &mut egui::Ui, current_year: u32) {
+        if self.retirement_age <= self.current_age {
+            ui.label("You should already be retired by now!");
+            return;
+        }
+
+        let years_left = self.retirement_age - self.current_age;
+        ui.label(format!(
+            "You have {years_left} years left until you can retire."
+        ));
+        ui.label(format!(
+            "You will be able to retire in the year {}.",
+            current_year + years_left
+        ));
+
+        let projection = project_savings(&self.plan, self.current_age, current_year, years_left);
+        if let Some(last) = projection.last() {
+            ui.strong(format!(
+                "Estimated nest egg at retirement in {}: {}",
+                last.year,
+                format_dollars(last.balance)
+            ));
+        }
+
+        egui::Grid::new("projection").striped(true).show(ui, |ui| {
+            ui.strong("Year");
+            ui.strong("Age");
+            ui.strong("Contributions");
+            ui.strong("Growth");
+            ui.strong("Balance");
+            ui.end_row();
+            for row in &projection {
+                ui.label(row.year.to_string());
+                ui.label(row.age.to_string());
+                ui.label(format_dollars(row.contributions));
+                ui.label(format_dollars(row.growth));
+                ui.label(format_dollars(row.balance));
+                ui.end_row();
+            }
+        });
+    }
+
+    fn show_milestones(&self, ui: &mut egui::Ui) {
+        match &self.milestones {
+            Ok(milestones) => {
+                ui.strong("Milestones");
+                for milestone in milestones {
+                    let year = get_milestone_year(self.current_age, milestone.age);
+                    if milestone.age <= self.current_age {
+                        ui.label(format!(
+                            "{} (age {}): reached in {}",
+                            milestone.name, milestone.age, year
+                        ));
+                    } else {
+                        ui.label(format!(
+                            "{} (age {}): {}",
+                            milestone.name, milestone.age, year
+                        ));
+                    }
+                }
+            }
+            Err(e) => {
+                ui.colored_label(
+                    egui::Color32::RED,
+                    format!("Error loading milestones: {}", e),
+                );
+            }
+        }
+    }
+}
+
+impl eframe::App for RetirementCalculator {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let current_year = chrono::Local::now().date_naive().year() as u32;
+        egui::CentralPanel::default().show(ctx, |ui| {
+            self.show_inputs(ui);
+            ui.separator();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                self.show_projection(ui, current_year);
+                ui.separator();
+                self.show_milestones(ui);
+            });
+        });
+    }
+}
+
+fn main() -> eframe::Result {
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([560.0, 600.0]),
+        ..Default::default()
+    };
+    eframe::run_native(
+        "Retirement Calculator",
+        options,
+        Box::new(|_| Ok(Box::<RetirementCalculator>::default())),
+    )
+}
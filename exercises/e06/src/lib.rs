@@ -0,0 +1,550 @@
+//! # Retirement Calculator Library
+//!
+//! Retirement date, statutory age, milestone, and savings projection logic shared by the
+//! command-line and GUI front-ends of the retirement calculator.
+use chrono::{Datelike, Months, NaiveDate};
+use num_format::{Locale, ToFormattedString};
+use serde::Deserialize;
+use std::path::Path;
+
+pub const MILESTONES_FILE: &str = "exercises/e06/inputs/milestones.toml";
+pub const RETIREMENT_AGES_FILE: &str = "exercises/e06/inputs/retirement_ages.toml";
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Milestone {
+    pub name: String,
+    pub age: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct MilestoneConfig {
+    #[serde(rename = "milestone", default)]
+    milestones: Vec<Milestone>,
+}
+
+pub fn load_milestones(file_path: &Path) -> Result<Vec<Milestone>, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(file_path)?;
+    let mut config: MilestoneConfig = toml::from_str(&content)?;
+    config.milestones.sort_by_key(|m| m.age);
+    Ok(config.milestones)
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Cohort {
+    /// First birth year the cohort applies to; `None` covers everyone born earlier.
+    pub born_from: Option<i32>,
+    pub years: u32,
+    #[serde(default)]
+    pub months: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Country {
+    pub name: String,
+    pub code: String,
+    #[serde(rename = "cohort")]
+    pub cohorts: Vec<Cohort>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RetirementAgeConfig {
+    #[serde(rename = "country", default)]
+    countries: Vec<Country>,
+}
+
+pub fn load_countries(file_path: &Path) -> Result<Vec<Country>, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(file_path)?;
+    let mut config: RetirementAgeConfig = toml::from_str(&content)?;
+    for country in &mut config.countries {
+        country.cohorts.sort_by_key(|cohort| cohort.born_from);
+    }
+    Ok(config.countries)
+}
+
+impl Country {
+    /// Returns the cohort for someone born in `birth_year`.
+    pub fn cohort(&self, birth_year: i32) -> Option<&Cohort> {
+        self.cohorts
+            .iter()
+            .rev()
+            .find(|cohort| cohort.born_from.is_none_or(|year| year <= birth_year))
+    }
+
+    /// Returns the statutory retirement age and the date it is reached.
+    pub fn statutory_retirement(&self, birthdate: NaiveDate) -> Option<(Age, NaiveDate)> {
+        let cohort = self.cohort(birthdate.year())?;
+        let date = months_after(birthdate, cohort.years * 12 + cohort.months)?;
+        Some((age_on(birthdate, date)?, date))
+    }
+}
+
+pub fn find_country<'a>(countries: &'a [Country], query: &str) -> Option<&'a Country> {
+    let query = query.trim();
+    countries.iter().find(|country| {
+        country.code.eq_ignore_ascii_case(query) || country.name.eq_ignore_ascii_case(query)
+    })
+}
+
+/// Returns how long before the statutory date the desired retirement falls, if it does.
+pub fn early_retirement_gap(desired: NaiveDate, statutory: NaiveDate) -> Option<Age> {
+    if desired < statutory {
+        age_on(desired, statutory)
+    } else {
+        None
+    }
+}
+
+/// An exact age as whole years, months, and days.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Age {
+    pub years: u32,
+    pub months: u32,
+    pub days: u32,
+}
+
+impl std::fmt::Display for Age {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unit = |n: u32, name: &str| format!("{n} {name}{}", if n == 1 { "" } else { "s" });
+        write!(
+            f,
+            "{}, {} and {}",
+            unit(self.years, "year"),
+            unit(self.months, "month"),
+            unit(self.days, "day")
+        )
+    }
+}
+
+/// Returns the date `months` months after `birthdate`. Chrono clamps to the end of the month,
+/// so a February 29 birthday falls on February 28 in common years.
+pub fn months_after(birthdate: NaiveDate, months: u32) -> Option<NaiveDate> {
+    birthdate.checked_add_months(Months::new(months))
+}
+
+/// Returns the exact age on `date` of someone born on `birthdate`, or `None` if `date` is
+/// before the birthdate.
+pub fn age_on(birthdate: NaiveDate, date: NaiveDate) -> Option<Age> {
+    if date < birthdate {
+        return None;
+    }
+    // Count from the birthdate each time rather than stepping, so clamping at the end of a
+    // short month doesn't drift later anniversaries.
+    let mut total_months = ((date.year() - birthdate.year()) * 12 + date.month() as i32
+        - birthdate.month() as i32) as u32;
+    while total_months > 0 && months_after(birthdate, total_months)? > date {
+        total_months -= 1;
+    }
+    let last_month = months_after(birthdate, total_months)?;
+    Some(Age {
+        years: total_months / 12,
+        months: total_months % 12,
+        days: (date - last_month).num_days() as u32,
+    })
+}
+
+/// Returns the date on which someone born on `birthdate` reaches `retirement_age` years. The
+/// fractional part of the age is a share of the days between the surrounding birthdays, so it
+/// accounts for a February 29 falling in that year.
+pub fn retirement_date(birthdate: NaiveDate, retirement_age: f64) -> Option<NaiveDate> {
+    if !retirement_age.is_finite() || retirement_age < 0.0 {
+        return None;
+    }
+    let whole_years = retirement_age.trunc() as u32;
+    let birthday = months_after(birthdate, whole_years.checked_mul(12)?)?;
+    let next_birthday = months_after(birthdate, (whole_years + 1).checked_mul(12)?)?;
+    let year_length = (next_birthday - birthday).num_days() as f64;
+    let extra_days = (retirement_age.fract() * year_length).round() as u64;
+    birthday.checked_add_days(chrono::Days::new(extra_days))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SavingsPlan {
+    pub current_savings: f64,
+    pub monthly_contribution: f64,
+    /// Expected annual return as a percentage, e.g. `6.0` for 6%.
+    pub annual_return: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct YearProjection {
+    pub year: u32,
+    pub age: u32,
+    pub contributions: f64,
+    pub growth: f64,
+    pub balance: f64,
+}
+
+/// Projects the plan one year at a time from `start_year` until `years` have passed,
+/// compounding the return monthly with contributions made at the end of each month.
+pub fn project_savings(
+    plan: &SavingsPlan,
+    curr_age: u32,
+    start_year: u32,
+    years: u32,
+) -> Vec<YearProjection> {
+    let monthly_rate = plan.annual_return / 100.0 / 12.0;
+    let mut balance = plan.current_savings;
+    (1..=years)
+        .map(|n| {
+            let start_balance = balance;
+            for _ in 0..12 {
+                balance = balance * (1.0 + monthly_rate) + plan.monthly_contribution;
+            }
+            let contributions = plan.monthly_contribution * 12.0;
+            YearProjection {
+                year: start_year + n,
+                age: curr_age + n,
+                contributions,
+                growth: balance - start_balance - contributions,
+                balance,
+            }
+        })
+        .collect()
+}
+
+/// Formats an amount as dollars with thousands separators, e.g. `$1,234.50`.
+pub fn format_dollars(amount: f64) -> String {
+    let cents = (amount.abs() * 100.0).round() as u64;
+    let sign = if amount < 0.0 && cents > 0 { "-" } else { "" };
+    format!(
+        "{sign}${}.{:02}",
+        (cents / 100).to_formatted_string(&Locale::en),
+        cents % 100
+    )
+}
+
+/// Reads the comma-separated retirement ages given to `--compare`, e.g. `60,65,70`.
+pub fn parse_compare_arg(args: &[String]) -> Result<Option<Vec<f64>>, String> {
+    let Some(i) = args.iter().position(|arg| arg == "--compare") else {
+        return Ok(None);
+    };
+    let value = args
+        .get(i + 1)
+        .ok_or("--compare requires a list of retirement ages, e.g. 60,65,70")?;
+    value
+        .split(',')
+        .map(|age| match age.trim().parse::<f64>() {
+            Ok(age) if age.is_finite() && age > 0.0 => Ok(age),
+            _ => Err(format!("Invalid retirement age '{}'", age.trim())),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+/// The outcome of retiring at one age, as compared side by side in `--compare` mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scenario {
+    pub retirement_age: f64,
+    pub date: NaiveDate,
+    /// Time left until the retirement date, or `None` if it has already passed.
+    pub remaining: Option<Age>,
+    pub nest_egg: f64,
+}
+
+/// Evaluates each retirement age for someone born on `birthdate`, projecting `plan` up to the
+/// retirement year. Ages whose date can't be represented are skipped.
+pub fn compare_scenarios(
+    birthdate: NaiveDate,
+    today: NaiveDate,
+    plan: &SavingsPlan,
+    retirement_ages: &[f64],
+) -> Vec<Scenario> {
+    let curr_age = age_on(birthdate, today).map_or(0, |age| age.years);
+    retirement_ages
+        .iter()
+        .filter_map(|&retirement_age| {
+            let date = retirement_date(birthdate, retirement_age)?;
+            let remaining = (date > today).then(|| age_on(today, date)).flatten();
+            let years_left = (date.year() - today.year()).max(0) as u32;
+            let nest_egg = if remaining.is_some() {
+                project_savings(plan, curr_age, today.year() as u32, years_left)
+                    .last()
+                    .map_or(plan.current_savings, |row| row.balance)
+            } else {
+                plan.current_savings
+            };
+            Some(Scenario {
+                retirement_age,
+                date,
+                remaining,
+                nest_egg,
+            })
+        })
+        .collect()
+}
+
+/// Returns the calendar year in which someone of `curr_age` turns `milestone_age`, which may
+/// be in the past.
+pub fn get_milestone_year(curr_age: u32, milestone_age: u32) -> u32 {
+    let current_year = chrono::Utc::now().date_naive().year() as u32;
+    (current_year + milestone_age).saturating_sub(curr_age)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_milestone_year_handles_past_and_future_milestones() {
+        let current_year = chrono::Utc::now().year() as u32;
+        assert_eq!(get_milestone_year(40, 67), current_year + 27);
+        assert_eq!(get_milestone_year(70, 62), current_year - 8);
+        assert_eq!(get_milestone_year(62, 62), current_year);
+    }
+
+    #[test]
+    fn load_milestones_sorts_by_age() {
+        let milestones = load_milestones(Path::new("inputs/milestones.toml")).unwrap();
+        assert_eq!(
+            milestones.first(),
+            Some(&Milestone {
+                name: "Early Social Security benefits".to_string(),
+                age: 62,
+            })
+        );
+        assert!(milestones.windows(2).all(|w| w[0].age <= w[1].age));
+    }
+
+    #[test]
+    fn project_savings_without_return_adds_contributions() {
+        let plan = SavingsPlan {
+            current_savings: 1000.0,
+            monthly_contribution: 100.0,
+            annual_return: 0.0,
+        };
+        let projection = project_savings(&plan, 60, 2030, 3);
+        assert_eq!(projection.len(), 3);
+        assert_eq!(
+            projection[0],
+            YearProjection {
+                year: 2031,
+                age: 61,
+                contributions: 1200.0,
+                growth: 0.0,
+                balance: 2200.0,
+            }
+        );
+        assert_eq!(projection[2].balance, 4600.0);
+        assert!(project_savings(&plan, 60, 2030, 0).is_empty());
+    }
+
+    #[test]
+    fn project_savings_compounds_monthly() {
+        let plan = SavingsPlan {
+            current_savings: 10_000.0,
+            monthly_contribution: 0.0,
+            annual_return: 12.0,
+        };
+        let projection = project_savings(&plan, 40, 2030, 2);
+        let expected = 10_000.0 * 1.01f64.powi(12);
+        assert!((projection[0].balance - expected).abs() < 1e-6);
+        assert!((projection[0].growth - (expected - 10_000.0)).abs() < 1e-6);
+        assert!((projection[1].balance - 10_000.0 * 1.01f64.powi(24)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn format_dollars_groups_thousands() {
+        assert_eq!(format_dollars(0.0), "$0.00");
+        assert_eq!(format_dollars(999.999), "$1,000.00");
+        assert_eq!(format_dollars(1234567.891), "$1,234,567.89");
+        assert_eq!(format_dollars(-0.001), "$0.00");
+        assert_eq!(format_dollars(-1500.5), "-$1,500.50");
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn retirement_date_adds_whole_and_fractional_years() {
+        let birthdate = date(1960, 3, 15);
+        assert_eq!(retirement_date(birthdate, 65.0), Some(date(2025, 3, 15)));
+        // March 2027 to March 2028 spans February 29, so the year is 366 days long.
+        assert_eq!(retirement_date(birthdate, 67.25), Some(date(2027, 6, 15)));
+        assert_eq!(retirement_date(birthdate, 66.25), Some(date(2026, 6, 14)));
+        assert_eq!(retirement_date(birthdate, -1.0), None);
+        assert_eq!(retirement_date(birthdate, f64::NAN), None);
+    }
+
+    #[test]
+    fn retirement_date_handles_leap_day_birthdays() {
+        let birthdate = date(1960, 2, 29);
+        assert_eq!(retirement_date(birthdate, 64.0), Some(date(2024, 2, 29)));
+        assert_eq!(retirement_date(birthdate, 65.0), Some(date(2025, 2, 28)));
+    }
+
+    #[test]
+    fn age_on_counts_years_months_and_days() {
+        let birthdate = date(1960, 3, 15);
+        assert_eq!(
+            age_on(birthdate, date(2027, 6, 15)),
+            Some(Age {
+                years: 67,
+                months: 3,
+                days: 0,
+            })
+        );
+        assert_eq!(
+            age_on(birthdate, date(2025, 3, 15)),
+            Some(Age {
+                years: 65,
+                months: 0,
+                days: 0,
+            })
+        );
+        assert_eq!(
+            age_on(birthdate, birthdate),
+            Some(Age {
+                years: 0,
+                months: 0,
+                days: 0,
+            })
+        );
+        assert_eq!(age_on(birthdate, date(1959, 12, 31)), None);
+    }
+
+    #[test]
+    fn age_on_handles_month_ends_and_leap_days() {
+        assert_eq!(
+            age_on(date(1960, 1, 31), date(2025, 2, 28)),
+            Some(Age {
+                years: 65,
+                months: 1,
+                days: 0,
+            })
+        );
+        assert_eq!(
+            age_on(date(1960, 2, 29), date(2025, 3, 1)),
+            Some(Age {
+                years: 65,
+                months: 0,
+                days: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn age_display_pluralizes_units() {
+        let age = Age {
+            years: 66,
+            months: 1,
+            days: 0,
+        };
+        assert_eq!(age.to_string(), "66 years, 1 month and 0 days");
+    }
+
+    #[test]
+    fn statutory_retirement_uses_birth_year_cohorts() {
+        let countries = load_countries(Path::new("inputs/retirement_ages.toml")).unwrap();
+        let us = find_country(&countries, "us").unwrap();
+        assert_eq!(
+            us.statutory_retirement(date(1957, 5, 10)),
+            Some((
+                Age {
+                    years: 66,
+                    months: 6,
+                    days: 0,
+                },
+                date(2023, 11, 10)
+            ))
+        );
+        assert_eq!(
+            us.statutory_retirement(date(1930, 1, 1)).map(|(_, d)| d),
+            Some(date(1995, 1, 1))
+        );
+        assert_eq!(
+            us.statutory_retirement(date(1990, 8, 31)).map(|(_, d)| d),
+            Some(date(2057, 8, 31))
+        );
+        let germany = find_country(&countries, "Germany").unwrap();
+        assert_eq!(
+            germany
+                .statutory_retirement(date(1961, 1, 31))
+                .map(|(_, d)| d),
+            Some(date(2027, 7, 31))
+        );
+        assert!(find_country(&countries, "Atlantis").is_none());
+    }
+
+    #[test]
+    fn load_countries_has_a_cohort_for_every_birth_year() {
+        let countries = load_countries(Path::new("inputs/retirement_ages.toml")).unwrap();
+        assert!(!countries.is_empty());
+        for country in &countries {
+            assert!(
+                country.cohort(1900).is_some(),
+                "{} has no base cohort",
+                country.name
+            );
+            assert!(country
+                .cohorts
+                .windows(2)
+                .all(|w| w[0].born_from < w[1].born_from));
+        }
+    }
+
+    #[test]
+    fn early_retirement_gap_flags_only_early_dates() {
+        assert_eq!(
+            early_retirement_gap(date(2025, 3, 15), date(2027, 5, 20)),
+            Some(Age {
+                years: 2,
+                months: 2,
+                days: 5,
+            })
+        );
+        assert_eq!(
+            early_retirement_gap(date(2027, 5, 20), date(2027, 5, 20)),
+            None
+        );
+        assert_eq!(
+            early_retirement_gap(date(2028, 1, 1), date(2027, 5, 20)),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_compare_arg_reads_age_lists() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            parse_compare_arg(&args(&["--compare", "60, 65,66.5"])),
+            Ok(Some(vec![60.0, 65.0, 66.5]))
+        );
+        assert_eq!(parse_compare_arg(&[]), Ok(None));
+        assert!(parse_compare_arg(&args(&["--compare"])).is_err());
+        assert_eq!(
+            parse_compare_arg(&args(&["--compare", "60,abc"])),
+            Err("Invalid retirement age 'abc'".to_string())
+        );
+    }
+
+    #[test]
+    fn compare_scenarios_projects_each_age() {
+        let plan = SavingsPlan {
+            current_savings: 1000.0,
+            monthly_contribution: 100.0,
+            annual_return: 0.0,
+        };
+        let today = date(2030, 6, 1);
+        let scenarios = compare_scenarios(date(1970, 1, 15), today, &plan, &[55.0, 65.0, 70.5]);
+        assert_eq!(scenarios.len(), 3);
+
+        assert_eq!(scenarios[0].date, date(2025, 1, 15));
+        assert_eq!(scenarios[0].remaining, None);
+        assert_eq!(scenarios[0].nest_egg, 1000.0);
+
+        assert_eq!(scenarios[1].date, date(2035, 1, 15));
+        assert_eq!(
+            scenarios[1].remaining,
+            Some(Age {
+                years: 4,
+                months: 7,
+                days: 14,
+            })
+        );
+        assert_eq!(scenarios[1].nest_egg, 1000.0 + 5.0 * 1200.0);
+
+        assert_eq!(scenarios[2].date, date(2040, 7, 16));
+        assert_eq!(scenarios[2].nest_egg, 1000.0 + 10.0 * 1200.0);
+    }
+}
@@ -10,10 +10,132 @@
 //! - **Early Retirement Detection**: Identifies when users should already be retired
 //! - **User Interaction**: Provides clear prompts and feedback during input
 //! - **Error Handling**: Gracefully handles invalid inputs with appropriate messages
-use chrono::Datelike;
+//! - **Eligibility Milestones**: Prints the calendar year of each milestone age (early and full
+//!   benefits, required distributions, ...) loaded from `inputs/milestones.toml`
+//! - **Savings Projection**: Projects current savings plus monthly contributions at an expected
+//!   annual return, compounded monthly, as a year-by-year table ending with the estimated nest
+//!   egg in the retirement year
+//! - **Exact Retirement Date**: Takes a birthdate and a possibly fractional retirement age (e.g.
+//!   `66.5`) and reports the retirement date, the days remaining, and the exact age on that date
+//! - **Statutory Retirement Age**: Looks up the statutory retirement age for the chosen country
+//!   and birth-year cohort in `inputs/retirement_ages.toml`, reports the statutory date, and
+//!   flags the gap when the desired retirement comes first
+//! - **Scenario Comparison**: `--compare 60,65,70` evaluates several retirement ages at once and
+//!   prints the time remaining, retirement year and date, and projected savings side by side
+//!
+//! A GUI front-end sharing the same calculation logic is available via `cargo run --bin e06_gui`.
+use chrono::{Datelike, NaiveDate};
+use e06::{
+    age_on, compare_scenarios, early_retirement_gap, find_country, format_dollars,
+    get_milestone_year, load_countries, load_milestones, parse_compare_arg, project_savings,
+    retirement_date, Country, Milestone, SavingsPlan, Scenario, YearProjection, MILESTONES_FILE,
+    RETIREMENT_AGES_FILE,
+};
 use std::io::Write;
+use std::path::Path;
+
+/// Prompts for a country by code or name; an empty answer skips the statutory comparison.
+fn prompt_for_country(countries: &[Country]) -> Option<&Country> {
+    let codes: Vec<&str> = countries
+        .iter()
+        .map(|country| country.code.as_str())
+        .collect();
+    loop {
+        print!(
+            "Which country's statutory retirement age applies ({}, or blank to skip)? ",
+            codes.join(", ")
+        );
+        let mut input = String::new();
+        if let Err(e) = std::io::stdout().flush() {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        if let Err(e) = std::io::stdin().read_line(&mut input) {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        if input.trim().is_empty() {
+            return None;
+        }
+        match find_country(countries, &input) {
+            Some(country) => return Some(country),
+            None => println!(
+                "Unknown country. Please enter one of: {}.",
+                codes.join(", ")
+            ),
+        }
+    }
+}
+
+fn print_statutory_retirement(country: &Country, birthdate: NaiveDate, desired: NaiveDate) {
+    let Some((age, date)) = country.statutory_retirement(birthdate) else {
+        println!(
+            "No statutory retirement age is known for your cohort in {}.",
+            country.name
+        );
+        return;
+    };
+    println!(
+        "The statutory retirement age in {} for your cohort is {age}, reached on {}.",
+        country.name,
+        date.format("%B %-d, %Y")
+    );
+    match early_retirement_gap(desired, date) {
+        Some(gap) => println!(
+            "Warning: you plan to retire {gap} ({} days) before the statutory date; you will \
+             need to cover that gap before state benefits begin.",
+            (date - desired).num_days()
+        ),
+        None => println!("Your planned retirement is on or after the statutory date."),
+    }
+}
+
+fn prompt_for_birthdate(today: NaiveDate) -> NaiveDate {
+    loop {
+        print!("What is your birthdate (YYYY-MM-DD)? ");
+        let mut input = String::new();
+        if let Err(e) = std::io::stdout().flush() {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        if let Err(e) = std::io::stdin().read_line(&mut input) {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        match NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d") {
+            Ok(birthdate) if birthdate <= today => return birthdate,
+            Ok(_) => println!("Your birthdate cannot be in the future."),
+            Err(_) => println!("Invalid date. Please use the format YYYY-MM-DD."),
+        }
+    }
+}
 
-fn prompt_for_age(question: &str) -> u32 {
+fn prompt_for_retirement_age() -> f64 {
+    loop {
+        print!("At what age do you plan to retire? ");
+        let mut input = String::new();
+        if let Err(e) = std::io::stdout().flush() {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        if let Err(e) = std::io::stdin().read_line(&mut input) {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        match input.trim().parse::<f64>() {
+            Ok(age) if age.is_finite() && age > 0.0 && age < 150.0 => return age,
+            _ => println!("Invalid age. Please enter a number such as 65 or 66.5."),
+        }
+    }
+}
+
+fn prompt_for_amount(question: &str) -> f64 {
     loop {
         print!("{question} ");
         let mut input = String::new();
@@ -27,58 +149,142 @@ fn prompt_for_age(question: &str) -> u32 {
             continue;
         }
 
-        if let Ok(age) = input.trim().parse::<u32>() {
-            if age == 0 {
-                println!("Age cannot be zero. Please enter a valid age.");
-                continue;
-            }
-            return age;
-        } else {
-            println!("Invalid age. Please enter a valid number.");
+        match input
+            .trim()
+            .trim_start_matches('$')
+            .replace(',', "")
+            .parse::<f64>()
+        {
+            Ok(amount) if amount.is_finite() && amount >= 0.0 => return amount,
+            _ => println!("Invalid amount. Please enter a number that is zero or more."),
         }
     }
 }
 
-fn get_retirement_year(years_to_retirement: u32) -> u32 {
-    let current_year = chrono::Utc::now().date_naive().year() as u32;
-    current_year + years_to_retirement
+fn print_projection(projection: &[YearProjection]) {
+    println!(
+        "{:<6} {:>4} {:>16} {:>16} {:>18}",
+        "Year", "Age", "Contributions", "Growth", "Balance"
+    );
+    for row in projection {
+        println!(
+            "{:<6} {:>4} {:>16} {:>16} {:>18}",
+            row.year,
+            row.age,
+            format_dollars(row.contributions),
+            format_dollars(row.growth),
+            format_dollars(row.balance)
+        );
+    }
+    if let Some(last) = projection.last() {
+        println!(
+            "Estimated nest egg at retirement in {}: {}",
+            last.year,
+            format_dollars(last.balance)
+        );
+    }
 }
 
-fn main() {
-    let curr_age = prompt_for_age("What is your current age?");
-    let retirement_age = prompt_for_age("At what age do you plan to retire?");
-    if retirement_age <= curr_age {
-        println!("You should already be retired by now!");
-    } else {
-        let years_left = retirement_age - curr_age;
-        println!("You have {years_left} years left until you can retire.");
-        let retirement_year = get_retirement_year(years_left);
-        println!("You will be able to retire in the year {retirement_year}.");
+fn prompt_for_savings_plan() -> SavingsPlan {
+    SavingsPlan {
+        current_savings: prompt_for_amount("How much have you saved so far?"),
+        monthly_contribution: prompt_for_amount("How much will you contribute each month?"),
+        annual_return: prompt_for_amount("What annual return do you expect (percent)?"),
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use chrono::Datelike;
+fn print_milestones(curr_age: u32, milestones: &[Milestone]) {
+    println!("Milestones:");
+    for milestone in milestones {
+        let year = get_milestone_year(curr_age, milestone.age);
+        if milestone.age <= curr_age {
+            println!(
+                "  {} (age {}): reached in {}",
+                milestone.name, milestone.age, year
+            );
+        } else {
+            println!("  {} (age {}): {}", milestone.name, milestone.age, year);
+        }
+    }
+}
+
+fn print_scenarios(scenarios: &[Scenario]) {
+    let row = |label: &str, cell: &dyn Fn(&Scenario) -> String| {
+        let cells: Vec<String> = scenarios
+            .iter()
+            .map(|scenario| format!("{:>18}", cell(scenario)))
+            .collect();
+        println!("{:<18}{}", label, cells.join(""));
+    };
+    row("Retirement age", &|s| format!("{}", s.retirement_age));
+    row("Years remaining", &|s| match s.remaining {
+        Some(remaining) => format!("{}y {}m", remaining.years, remaining.months),
+        None => "retired".to_string(),
+    });
+    row("Retirement year", &|s| s.date.year().to_string());
+    row("Retirement date", &|s| {
+        s.date.format("%Y-%m-%d").to_string()
+    });
+    row("Projected savings", &|s| format_dollars(s.nest_egg));
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let compare_ages = match parse_compare_arg(&args) {
+        Ok(ages) => ages,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
 
-    #[test]
-    fn get_retirement_year_handles_zero_years() {
-        let current_year = chrono::Utc::now().year() as u32;
-        assert_eq!(get_retirement_year(0), current_year);
+    let today = chrono::Local::now().date_naive();
+    let birthdate = prompt_for_birthdate(today);
+    if let Some(ages) = compare_ages {
+        let plan = prompt_for_savings_plan();
+        print_scenarios(&compare_scenarios(birthdate, today, &plan, &ages));
+        return;
     }
+    let curr_age = age_on(birthdate, today).map_or(0, |age| age.years);
+    let retirement_age = prompt_for_retirement_age();
+    let desired_date = retirement_date(birthdate, retirement_age);
+    match desired_date {
+        None => println!("That retirement age is out of range."),
+        Some(date) if date <= today => println!("You should already be retired by now!"),
+        Some(date) => {
+            let days_left = (date - today).num_days();
+            println!("You have {days_left} days left until you can retire.");
+            println!(
+                "You will be able to retire on {}.",
+                date.format("%B %-d, %Y")
+            );
+            if let Some(age) = age_on(birthdate, date) {
+                println!("You will be {age} old on that date.");
+            }
 
-    #[test]
-    fn get_retirement_year_calculates_future_year_correctly() {
-        let current_year = chrono::Utc::now().year() as u32;
-        assert_eq!(get_retirement_year(10), current_year + 10);
-        assert_eq!(get_retirement_year(25), current_year + 25);
-        assert_eq!(get_retirement_year(40), current_year + 40);
+            let plan = prompt_for_savings_plan();
+            let years_left = (date.year() - today.year()) as u32;
+            print_projection(&project_savings(
+                &plan,
+                curr_age,
+                today.year() as u32,
+                years_left,
+            ));
+        }
+    }
+
+    match load_countries(Path::new(RETIREMENT_AGES_FILE)) {
+        Ok(countries) => {
+            if let (Some(country), Some(desired)) = (prompt_for_country(&countries), desired_date) {
+                print_statutory_retirement(country, birthdate, desired);
+            }
+        }
+        Err(e) => eprintln!("Error loading statutory retirement ages: {}", e),
     }
 
-    #[test]
-    fn get_retirement_year_handles_large_values() {
-        let current_year = chrono::Utc::now().year() as u32;
-        assert_eq!(get_retirement_year(100), current_year + 100);
+    match load_milestones(Path::new(MILESTONES_FILE)) {
+        Ok(milestones) if !milestones.is_empty() => print_milestones(curr_age, &milestones),
+        Ok(_) => {}
+        Err(e) => eprintln!("Error loading milestones: {}", e),
     }
 }
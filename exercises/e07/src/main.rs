@@ -5,30 +5,190 @@
 //!
 //! ## Features
 //!
-//! - **Unit Selection**: Allows users to choose between feet and meters for input
+//! - **Unit Selection**: Allows users to enter dimensions in centimeters, feet, yards, or meters
 //! - **Real-time Calculation**: Results update instantly as dimensions are entered
-//! - **Dual Unit Display**: Shows area in both square feet and square meters simultaneously
+//! - **Multi-Unit Display**: Shows area in square centimeters, square feet, square yards,
+//!   square meters, acres, and hectares simultaneously
+//! - **Conversion Engine**: An `AreaUnit` enum converts every unit through square meters using
+//!   the shared `units` tables, and room areas and the project total are shown in the output
+//!   unit picked from a ComboBox
+//! - **Room Presets**: Saves the rooms and dimension unit under a name in
+//!   `exercises/e07/presets.json` and loads them back for repeat estimates
 //! - **Input Validation**: Gracefully handles invalid dimension inputs
 //! - **Conversion Logic**: Accurately converts between imperial and metric measurement systems
+//! - **Cost Estimation**: Estimates flooring material cost from an embedded price table
+//! - **Multi-Room Projects**: Holds a list of named rooms that can be added and removed, showing
+//!   each room's area plus a project total in every unit
 use eframe::egui::{self, ComboBox};
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use units::area;
 
-const FT_TO_METER: f64 = 0.09290304; // 1 square foot to square meters
+const PRESETS_FILE: &str = "exercises/e07/presets.json";
 
+/// An area unit, converted through square meters as the canonical unit.
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum AreaUnit {
-    Meters,
+    SquareCentimeters,
+    SquareFeet,
+    SquareYards,
+    SquareMeters,
+    Acres,
+    Hectares,
+}
+
+const AREA_UNITS: [AreaUnit; 6] = [
+    AreaUnit::SquareCentimeters,
+    AreaUnit::SquareFeet,
+    AreaUnit::SquareYards,
+    AreaUnit::SquareMeters,
+    AreaUnit::Acres,
+    AreaUnit::Hectares,
+];
+
+impl AreaUnit {
+    /// How many square meters one of this unit covers.
+    fn square_meters(&self) -> f64 {
+        match self {
+            AreaUnit::SquareCentimeters => area::SQUARE_CENTIMETER,
+            AreaUnit::SquareFeet => area::SQUARE_FOOT,
+            AreaUnit::SquareYards => area::SQUARE_YARD,
+            AreaUnit::SquareMeters => area::SQUARE_METER,
+            AreaUnit::Acres => area::ACRE,
+            AreaUnit::Hectares => area::HECTARE,
+        }
+    }
+
+    /// Decimal places shown; large units need more to be meaningful for a single room.
+    fn precision(&self) -> usize {
+        match self {
+            AreaUnit::Acres | AreaUnit::Hectares => 5,
+            _ => 2,
+        }
+    }
+
+    /// Converts an area in square meters into this unit.
+    fn area_in_unit(&self, area_meters: f64) -> f64 {
+        area_meters / self.square_meters()
+    }
+
+    /// Converts an area in this unit into square meters.
+    fn area_in_meters(&self, area: f64) -> f64 {
+        area * self.square_meters()
+    }
+
+    fn format(&self, area_meters: f64) -> String {
+        format!(
+            "{:.*} {}",
+            self.precision(),
+            self.area_in_unit(area_meters),
+            self
+        )
+    }
+}
+
+impl Display for AreaUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AreaUnit::SquareCentimeters => write!(f, "square centimeters"),
+            AreaUnit::SquareFeet => write!(f, "square feet"),
+            AreaUnit::SquareYards => write!(f, "square yards"),
+            AreaUnit::SquareMeters => write!(f, "square meters"),
+            AreaUnit::Acres => write!(f, "acres"),
+            AreaUnit::Hectares => write!(f, "hectares"),
+        }
+    }
+}
+
+/// The unit room dimensions are entered in.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum LengthUnit {
+    Centimeters,
     Feet,
+    Yards,
+    Meters,
 }
 
-struct AreaCalculator {
-    selected_unit: AreaUnit,
+const LENGTH_UNITS: [LengthUnit; 4] = [
+    LengthUnit::Centimeters,
+    LengthUnit::Feet,
+    LengthUnit::Yards,
+    LengthUnit::Meters,
+];
+
+impl LengthUnit {
+    /// The area unit of a length times a width in this unit.
+    fn area_unit(&self) -> AreaUnit {
+        match self {
+            LengthUnit::Centimeters => AreaUnit::SquareCentimeters,
+            LengthUnit::Feet => AreaUnit::SquareFeet,
+            LengthUnit::Yards => AreaUnit::SquareYards,
+            LengthUnit::Meters => AreaUnit::SquareMeters,
+        }
+    }
+}
+
+impl Display for LengthUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LengthUnit::Centimeters => write!(f, "centimeters"),
+            LengthUnit::Feet => write!(f, "feet"),
+            LengthUnit::Yards => write!(f, "yards"),
+            LengthUnit::Meters => write!(f, "meters"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Material {
+    name: &'static str,
+    price_per_sq_ft: f64,
+}
+
+impl Material {
+    fn price_per_sq_meter(&self) -> f64 {
+        AreaUnit::SquareFeet.area_in_unit(self.price_per_sq_ft)
+    }
+
+    fn estimate_cost(&self, area_feet: f64) -> f64 {
+        area_feet * self.price_per_sq_ft
+    }
+}
+
+const MATERIALS: [Material; 3] = [
+    Material {
+        name: "Hardwood",
+        price_per_sq_ft: 8.00,
+    },
+    Material {
+        name: "Tile",
+        price_per_sq_ft: 5.50,
+    },
+    Material {
+        name: "Carpet",
+        price_per_sq_ft: 3.25,
+    },
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Room {
+    name: String,
     length: String,
     width: String,
 }
 
-impl AreaCalculator {
-    fn calculate_area(&self) -> Option<(f64, f64)> {
+impl Room {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            length: String::new(),
+            width: String::new(),
+        }
+    }
+
+    /// Returns the area in square meters and square feet, with the dimensions read in `unit`.
+    fn calculate_area(&self, unit: LengthUnit) -> Option<(f64, f64)> {
         let length: f64 = self.length.parse().unwrap_or(f64::NAN);
         let width: f64 = self.width.parse().unwrap_or(f64::NAN);
 
@@ -36,60 +196,286 @@ impl AreaCalculator {
             return None;
         }
 
-        let area = length * width;
-        match self.selected_unit {
-            AreaUnit::Meters => Some((area, area / FT_TO_METER)),
-            AreaUnit::Feet => Some((area * FT_TO_METER, area)),
-        }
+        let area_meters = unit.area_unit().area_in_meters(length * width);
+        Some((area_meters, AreaUnit::SquareFeet.area_in_unit(area_meters)))
     }
 }
 
-impl Default for AreaCalculator {
-    fn default() -> Self {
-        Self {
-            selected_unit: AreaUnit::Meters,
-            length: String::new(),
-            width: String::new(),
-        }
+/// A named set of rooms and the unit their dimensions are in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Preset {
+    name: String,
+    unit: LengthUnit,
+    rooms: Vec<Room>,
+}
+
+fn load_presets(file_path: &Path) -> Result<Vec<Preset>, Box<dyn std::error::Error>> {
+    if !file_path.exists() {
+        return Ok(Vec::new());
     }
+    let file = std::fs::File::open(file_path)?;
+    Ok(serde_json::from_reader(file)?)
 }
 
-impl Display for AreaUnit {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            AreaUnit::Meters => write!(f, "meters"),
-            AreaUnit::Feet => write!(f, "feet"),
+fn save_presets(file_path: &Path, presets: &[Preset]) -> Result<(), Box<dyn std::error::Error>> {
+    persist::write_json(file_path, presets)?;
+    Ok(())
+}
+
+/// The combined area of every room with valid dimensions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ProjectTotal {
+    area_meters: f64,
+    area_feet: f64,
+    /// Rooms left out of the total because a dimension isn't a number.
+    invalid_rooms: usize,
+}
+
+struct AreaCalculator {
+    selected_unit: LengthUnit,
+    output_unit: AreaUnit,
+    selected_material: Material,
+    rooms: Vec<Room>,
+    // Numbers new rooms so their default names stay unique after removals.
+    rooms_added: usize,
+    presets: Vec<Preset>,
+    presets_path: PathBuf,
+    preset_name: String,
+    preset_status: String,
+}
+
+impl AreaCalculator {
+    fn add_room(&mut self) {
+        self.rooms_added += 1;
+        self.rooms
+            .push(Room::new(format!("Room {}", self.rooms_added)));
+    }
+
+    fn remove_room(&mut self, index: usize) {
+        if index < self.rooms.len() {
+            self.rooms.remove(index);
+        }
+    }
+
+    fn with_presets(presets_path: PathBuf) -> Self {
+        let mut calculator = Self {
+            presets_path,
+            ..Default::default()
+        };
+        match load_presets(&calculator.presets_path) {
+            Ok(presets) => calculator.presets = presets,
+            Err(e) => calculator.preset_status = format!("Error loading presets: {}", e),
+        }
+        calculator
+    }
+
+    /// Saves the current rooms and unit under `preset_name`, replacing a preset of that name.
+    fn save_preset(&mut self) {
+        let name = self.preset_name.trim().to_string();
+        if name.is_empty() {
+            self.preset_status = "Enter a name for the preset.".to_string();
+            return;
+        }
+        let preset = Preset {
+            name: name.clone(),
+            unit: self.selected_unit,
+            rooms: self.rooms.clone(),
+        };
+        match self.presets.iter_mut().find(|p| p.name == name) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+        self.preset_status = match save_presets(&self.presets_path, &self.presets) {
+            Ok(()) => format!("Saved preset '{}'.", name),
+            Err(e) => format!("Error saving presets: {}", e),
+        };
+    }
+
+    fn load_preset(&mut self, index: usize) {
+        let Some(preset) = self.presets.get(index) else {
+            return;
+        };
+        self.selected_unit = preset.unit;
+        self.rooms = preset.rooms.clone();
+        self.preset_name = preset.name.clone();
+        self.preset_status = format!("Loaded preset '{}'.", preset.name);
+        // Continue numbering after the highest default name so new rooms never repeat one.
+        self.rooms_added = self
+            .rooms
+            .iter()
+            .filter_map(|room| room.name.strip_prefix("Room ")?.parse::<usize>().ok())
+            .max()
+            .unwrap_or(0);
+        if self.rooms.is_empty() {
+            self.add_room();
+        }
+    }
+
+    fn show_presets(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Presets");
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.preset_name).desired_width(120.0));
+            if ui.button("Save").clicked() {
+                self.save_preset();
+            }
+        });
+        ui.label(&self.preset_status);
+        ui.separator();
+
+        let mut loaded = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (index, preset) in self.presets.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.button("Load").clicked() {
+                        loaded = Some(index);
+                    }
+                    ui.label(format!(
+                        "{} ({} room(s), {})",
+                        preset.name,
+                        preset.rooms.len(),
+                        preset.unit
+                    ));
+                });
+            }
+        });
+        if let Some(index) = loaded {
+            self.load_preset(index);
+        }
+    }
+
+    fn project_total(&self) -> ProjectTotal {
+        let mut total = ProjectTotal {
+            area_meters: 0.0,
+            area_feet: 0.0,
+            invalid_rooms: 0,
+        };
+        for room in &self.rooms {
+            match room.calculate_area(self.selected_unit) {
+                Some((area_meters, area_feet)) => {
+                    total.area_meters += area_meters;
+                    total.area_feet += area_feet;
+                }
+                None => total.invalid_rooms += 1,
+            }
+        }
+        total
+    }
+
+    fn show_rooms(&mut self, ui: &mut egui::Ui) {
+        let mut removed = None;
+        egui::Grid::new("rooms").striped(true).show(ui, |ui| {
+            ui.strong("Room");
+            ui.strong(format!("Length ({})", self.selected_unit));
+            ui.strong(format!("Width ({})", self.selected_unit));
+            ui.strong("Area");
+            ui.end_row();
+
+            let can_remove = self.rooms.len() > 1;
+            for (index, room) in self.rooms.iter_mut().enumerate() {
+                ui.add(egui::TextEdit::singleline(&mut room.name).desired_width(100.0));
+                ui.add(egui::TextEdit::singleline(&mut room.length).desired_width(60.0));
+                ui.add(egui::TextEdit::singleline(&mut room.width).desired_width(60.0));
+                match room.calculate_area(self.selected_unit) {
+                    Some((area_meters, _)) => ui.label(self.output_unit.format(area_meters)),
+                    None => ui.label("Enter valid numbers"),
+                };
+                if ui
+                    .add_enabled(can_remove, egui::Button::new("Remove"))
+                    .clicked()
+                {
+                    removed = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(index) = removed {
+            self.remove_room(index);
+        }
+        if ui.button("Add room").clicked() {
+            self.add_room();
         }
     }
 }
 
+impl Default for AreaCalculator {
+    fn default() -> Self {
+        let mut calculator = Self {
+            selected_unit: LengthUnit::Meters,
+            output_unit: AreaUnit::SquareMeters,
+            selected_material: MATERIALS[0],
+            rooms: Vec::new(),
+            rooms_added: 0,
+            presets: Vec::new(),
+            presets_path: PathBuf::from(PRESETS_FILE),
+            preset_name: String::new(),
+            preset_status: String::new(),
+        };
+        calculator.add_room();
+        calculator
+    }
+}
+
 impl eframe::App for AreaCalculator {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::SidePanel::right("presets").show(ctx, |ui| self.show_presets(ui));
         egui::CentralPanel::default().show(ctx, |ui| {
-            ComboBox::from_label("Choose an option")
+            ComboBox::from_label("Dimensions in")
                 .selected_text(self.selected_unit.to_string())
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.selected_unit, AreaUnit::Meters, "meters");
-                    ui.selectable_value(&mut self.selected_unit, AreaUnit::Feet, "feet");
+                    for unit in LENGTH_UNITS {
+                        ui.selectable_value(&mut self.selected_unit, unit, unit.to_string());
+                    }
                 });
 
-            ui.label(format!(
-                "What is the length of the room in {}?",
-                self.selected_unit
-            ));
-            ui.text_edit_singleline(&mut self.length);
-
-            ui.label(format!(
-                "What is the width of the room in {}?",
-                self.selected_unit
-            ));
-            ui.text_edit_singleline(&mut self.width);
-
-            let area = self.calculate_area();
-            if let Some((area_meters, area_feet)) = area {
-                ui.label("The area is:");
-                ui.label(format!("{:.2} square feet", area_feet));
-                ui.label(format!("{:.2} square meters", area_meters));
+            ComboBox::from_label("Show area in")
+                .selected_text(self.output_unit.to_string())
+                .show_ui(ui, |ui| {
+                    for unit in AREA_UNITS {
+                        ui.selectable_value(&mut self.output_unit, unit, unit.to_string());
+                    }
+                });
+
+            ComboBox::from_label("Flooring material")
+                .selected_text(self.selected_material.name)
+                .show_ui(ui, |ui| {
+                    for material in MATERIALS {
+                        ui.selectable_value(&mut self.selected_material, material, material.name);
+                    }
+                });
+
+            ui.separator();
+            self.show_rooms(ui);
+            ui.separator();
+
+            let total = self.project_total();
+            if total.invalid_rooms < self.rooms.len() {
+                let (area_meters, area_feet) = (total.area_meters, total.area_feet);
+                ui.strong(format!(
+                    "The project total is {}",
+                    self.output_unit.format(area_meters)
+                ));
+                ui.label("In other units:");
+                for unit in AREA_UNITS.iter().filter(|unit| **unit != self.output_unit) {
+                    ui.label(unit.format(area_meters));
+                }
+                if total.invalid_rooms > 0 {
+                    ui.label(format!(
+                        "({} room(s) with invalid dimensions are not included.)",
+                        total.invalid_rooms
+                    ));
+                }
+
+                let material = self.selected_material;
+                ui.label(format!(
+                    "Estimated {} cost: ${:.2}",
+                    material.name.to_lowercase(),
+                    material.estimate_cost(area_feet)
+                ));
+                ui.label(format!(
+                    "(${:.2} per square foot, ${:.2} per square meter)",
+                    material.price_per_sq_ft,
+                    material.price_per_sq_meter()
+                ));
             } else {
                 ui.label("Please enter valid numbers for length and width.");
             }
@@ -99,13 +485,17 @@ impl eframe::App for AreaCalculator {
 
 fn main() -> eframe::Result {
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 250.0]),
+        viewport: egui::ViewportBuilder::default().with_inner_size([780.0, 480.0]),
         ..Default::default()
     };
     eframe::run_native(
         "Simple Math",
         options,
-        Box::new(|_| Ok(Box::<AreaCalculator>::default())),
+        Box::new(|_| {
+            Ok(Box::new(AreaCalculator::with_presets(PathBuf::from(
+                PRESETS_FILE,
+            ))))
+        }),
     )
 }
 
@@ -115,13 +505,13 @@ mod tests {
 
     #[test]
     fn calculate_area_converts_meters_to_feet_correctly() {
-        let calculator = AreaCalculator {
-            selected_unit: AreaUnit::Meters,
+        let room = Room {
             length: String::from("5"),
             width: String::from("4"),
+            ..Room::new(String::from("Kitchen"))
         };
 
-        if let Some((area_meters, area_feet)) = calculator.calculate_area() {
+        if let Some((area_meters, area_feet)) = room.calculate_area(LengthUnit::Meters) {
             assert_eq!(area_meters, 20.0);
             assert!((area_feet - 215.28).abs() < 0.01); // Approximately 20.0 / 0.09290304
         } else {
@@ -131,13 +521,13 @@ mod tests {
 
     #[test]
     fn calculate_area_converts_feet_to_meters_correctly() {
-        let calculator = AreaCalculator {
-            selected_unit: AreaUnit::Feet,
+        let room = Room {
             length: String::from("10"),
             width: String::from("10"),
+            ..Room::new(String::from("Kitchen"))
         };
 
-        if let Some((area_meters, area_feet)) = calculator.calculate_area() {
+        if let Some((area_meters, area_feet)) = room.calculate_area(LengthUnit::Feet) {
             assert_eq!(area_feet, 100.0);
             assert!((area_meters - 9.29).abs() < 0.01); // Approximately 100.0 * 0.09290304
         } else {
@@ -147,36 +537,210 @@ mod tests {
 
     #[test]
     fn calculate_area_handles_invalid_inputs() {
-        let calculator_invalid_length = AreaCalculator {
-            selected_unit: AreaUnit::Meters,
+        let room_invalid_length = Room {
             length: String::from("invalid"),
             width: String::from("5"),
+            ..Room::new(String::from("Kitchen"))
         };
 
-        assert!(calculator_invalid_length.calculate_area().is_none());
+        assert!(room_invalid_length
+            .calculate_area(LengthUnit::Meters)
+            .is_none());
 
-        let calculator_invalid_width = AreaCalculator {
-            selected_unit: AreaUnit::Feet,
+        let room_invalid_width = Room {
             length: String::from("10"),
             width: String::from("abc"),
+            ..Room::new(String::from("Kitchen"))
         };
 
-        assert!(calculator_invalid_width.calculate_area().is_none());
+        assert!(room_invalid_width
+            .calculate_area(LengthUnit::Feet)
+            .is_none());
     }
 
     #[test]
     fn calculate_area_handles_zero_dimensions() {
-        let calculator = AreaCalculator {
-            selected_unit: AreaUnit::Meters,
+        let room = Room {
             length: String::from("0"),
             width: String::from("0"),
+            ..Room::new(String::from("Kitchen"))
         };
 
-        if let Some((area_meters, area_feet)) = calculator.calculate_area() {
+        if let Some((area_meters, area_feet)) = room.calculate_area(LengthUnit::Meters) {
             assert_eq!(area_meters, 0.0);
             assert_eq!(area_feet, 0.0);
         } else {
             panic!("calculate_area returned None when it should have returned Some");
         }
     }
+
+    #[test]
+    fn material_estimates_cost_in_both_unit_systems() {
+        let tile = MATERIALS[1];
+        assert_eq!(tile.estimate_cost(100.0), 550.0);
+        assert!((tile.price_per_sq_meter() - 59.20).abs() < 0.01);
+        // The same area expressed in square meters yields the same cost.
+        let area_meters = AreaUnit::SquareFeet.area_in_meters(100.0);
+        assert!((tile.price_per_sq_meter() * area_meters - 550.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn units_convert_through_square_meters() {
+        let acre = AreaUnit::Acres;
+        let hectare = AreaUnit::Hectares;
+        let yard = AreaUnit::SquareYards;
+        let feet = AreaUnit::SquareFeet;
+        assert!((feet.area_in_unit(acre.area_in_meters(1.0)) - 43_560.0).abs() < 1e-6);
+        assert!((yard.area_in_unit(feet.area_in_meters(9.0)) - 1.0).abs() < 1e-12);
+        assert!((acre.area_in_unit(hectare.area_in_meters(1.0)) - 2.4710538).abs() < 1e-6);
+        assert_eq!(AreaUnit::SquareMeters.area_in_unit(12.5), 12.5);
+        assert!(
+            (AreaUnit::SquareCentimeters.area_in_unit(feet.area_in_meters(1.0)) - 929.0304).abs()
+                < 1e-9
+        );
+    }
+
+    #[test]
+    fn units_format_with_their_precision() {
+        let area_meters = 20.0;
+        let formatted: Vec<String> = AREA_UNITS
+            .iter()
+            .map(|unit| unit.format(area_meters))
+            .collect();
+        assert_eq!(
+            formatted,
+            vec![
+                "200000.00 square centimeters",
+                "215.28 square feet",
+                "23.92 square yards",
+                "20.00 square meters",
+                "0.00494 acres",
+                "0.00200 hectares"
+            ]
+        );
+    }
+
+    #[test]
+    fn rooms_are_added_and_removed_with_unique_names() {
+        let mut calculator = AreaCalculator::default();
+        assert_eq!(calculator.rooms.len(), 1);
+        calculator.add_room();
+        calculator.add_room();
+        calculator.remove_room(1);
+        calculator.remove_room(7);
+        calculator.add_room();
+        let names: Vec<&str> = calculator.rooms.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["Room 1", "Room 3", "Room 4"]);
+    }
+
+    #[test]
+    fn project_total_sums_valid_rooms_in_both_units() {
+        let mut calculator = AreaCalculator {
+            selected_unit: LengthUnit::Feet,
+            ..Default::default()
+        };
+        calculator.rooms = vec![
+            Room {
+                length: String::from("10"),
+                width: String::from("12"),
+                ..Room::new(String::from("Bedroom"))
+            },
+            Room {
+                length: String::from("8"),
+                width: String::from("10"),
+                ..Room::new(String::from("Office"))
+            },
+            Room::new(String::from("Hallway")),
+        ];
+
+        let total = calculator.project_total();
+        assert_eq!(total.area_feet, 200.0);
+        assert!((total.area_meters - 18.580608).abs() < 1e-9);
+        assert_eq!(total.invalid_rooms, 1);
+    }
+
+    #[test]
+    fn length_units_square_into_matching_area_units() {
+        let room = Room {
+            length: String::from("300"),
+            width: String::from("400"),
+            ..Room::new(String::from("Kitchen"))
+        };
+        let (area_meters, _) = room.calculate_area(LengthUnit::Centimeters).unwrap();
+        assert!((area_meters - 12.0).abs() < 1e-9);
+
+        let room = Room {
+            length: String::from("3"),
+            width: String::from("4"),
+            ..Room::new(String::from("Kitchen"))
+        };
+        let (area_meters, area_feet) = room.calculate_area(LengthUnit::Yards).unwrap();
+        assert!((area_feet - 108.0).abs() < 1e-9);
+        assert!((AreaUnit::SquareYards.area_in_unit(area_meters) - 12.0).abs() < 1e-9);
+
+        let meters_per_unit = [0.01, 0.3048, 0.9144, 1.0];
+        for (unit, meters) in LENGTH_UNITS.into_iter().zip(meters_per_unit) {
+            assert!((unit.area_unit().square_meters() - meters * meters).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn presets_save_and_load_rooms_and_unit() {
+        let path = std::env::temp_dir().join(format!("e07_presets_{}.json", std::process::id()));
+        let mut calculator = AreaCalculator::with_presets(path.clone());
+        assert!(calculator.presets.is_empty());
+        calculator.selected_unit = LengthUnit::Feet;
+        calculator.rooms[0].length = String::from("12");
+        calculator.rooms[0].width = String::from("10");
+        calculator.add_room();
+        calculator.preset_name = String::from(" Upstairs ");
+        calculator.save_preset();
+
+        let mut reloaded = AreaCalculator::with_presets(path.clone());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reloaded.presets.len(), 1);
+        assert_eq!(reloaded.presets[0].name, "Upstairs");
+        reloaded.load_preset(0);
+        assert_eq!(reloaded.selected_unit, LengthUnit::Feet);
+        assert_eq!(reloaded.rooms, calculator.rooms);
+        reloaded.add_room();
+        assert_eq!(reloaded.rooms[2].name, "Room 3");
+    }
+
+    #[test]
+    fn load_preset_numbers_new_rooms_after_the_highest_default_name() {
+        let mut calculator = AreaCalculator::default();
+        calculator.add_room();
+        calculator.add_room();
+        calculator.remove_room(1);
+        calculator.rooms.push(Room::new(String::from("Garage")));
+        calculator.presets = vec![Preset {
+            name: String::from("Gappy"),
+            unit: LengthUnit::Feet,
+            rooms: calculator.rooms.clone(),
+        }];
+
+        calculator.load_preset(0);
+        calculator.add_room();
+        let names: Vec<&str> = calculator.rooms.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["Room 1", "Room 3", "Garage", "Room 4"]);
+    }
+
+    #[test]
+    fn save_preset_replaces_a_preset_with_the_same_name() {
+        let path = std::env::temp_dir().join(format!("e07_replace_{}.json", std::process::id()));
+        let mut calculator = AreaCalculator::with_presets(path.clone());
+        calculator.preset_name = String::from("Kitchen");
+        calculator.save_preset();
+        calculator.selected_unit = LengthUnit::Yards;
+        calculator.save_preset();
+        calculator.preset_name = String::new();
+        calculator.save_preset();
+
+        let presets = load_presets(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(presets.len(), 1);
+        assert_eq!(presets[0].unit, LengthUnit::Yards);
+        assert_eq!(calculator.preset_status, "Enter a name for the preset.");
+    }
 }
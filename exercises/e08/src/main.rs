@@ -10,8 +10,64 @@
 //! - **Optimal Pizza Calculation**: Calculates the minimum number of whole pizzas needed
 //! - **Rounding Logic**: Always rounds up to ensure everyone gets enough slices
 //! - **Input Validation**: Ensures valid numeric inputs through robust error handling
+//! - **Event Planning Mode**: Estimates drinks and dessert servings as well, producing a
+//!   consolidated shopping list with an estimated total cost
+//! - **Audience Presets**: Splits attendees into kids, adults, and hungry teenagers, each with
+//!   a preset slice demand, and sums the demand before counting pizzas
 use std::io::Write;
 
+const PIZZA_PRICE: f64 = 12.99;
+const DRINK_BOTTLE_LITERS: f64 = 2.0;
+const DRINK_BOTTLE_PRICE: f64 = 2.49;
+const DESSERT_SERVING_PRICE: f64 = 1.75;
+const SLICES_PER_PIZZA: u32 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Audience {
+    Kids,
+    Adults,
+    HungryTeenagers,
+}
+
+impl Audience {
+    fn name(&self) -> &'static str {
+        match self {
+            Audience::Kids => "kids",
+            Audience::Adults => "adults",
+            Audience::HungryTeenagers => "hungry teenagers",
+        }
+    }
+
+    fn slices_per_person(&self) -> u32 {
+        match self {
+            Audience::Kids => 2,
+            Audience::Adults => 3,
+            Audience::HungryTeenagers => 4,
+        }
+    }
+}
+
+/// Total slices wanted by a party split into audience categories.
+fn slice_demand(attendance: &[(Audience, u32)]) -> u32 {
+    attendance
+        .iter()
+        .map(|(audience, count)| audience.slices_per_person() * count)
+        .sum()
+}
+
+#[derive(Debug, PartialEq)]
+struct ShoppingItem {
+    name: &'static str,
+    quantity: u32,
+    unit_price: f64,
+}
+
+impl ShoppingItem {
+    fn cost(&self) -> f64 {
+        self.quantity as f64 * self.unit_price
+    }
+}
+
 fn prompt_for_uint(prompt: &str) -> u32 {
     loop {
         print!("{prompt} ");
@@ -38,17 +94,162 @@ fn calculate_num_pizzas(num_people: u32, slices_per_person: u32) -> u32 {
     if slices_per_person == 0 {
         return 0; // Avoid division by zero
     }
-    (num_people * slices_per_person + 7) / 8 // Round up to nearest whole pizza
+    pizzas_for_slices(num_people * slices_per_person)
+}
+
+fn pizzas_for_slices(total_slices: u32) -> u32 {
+    total_slices.div_ceil(SLICES_PER_PIZZA) // Round up to nearest whole pizza
+}
+
+fn calculate_num_bottles(num_people: u32, liters_per_person: f64) -> u32 {
+    (num_people as f64 * liters_per_person / DRINK_BOTTLE_LITERS).ceil() as u32
+}
+
+fn plan_event(
+    num_people: u32,
+    total_slices: u32,
+    liters_per_person: f64,
+    desserts_per_person: u32,
+) -> Vec<ShoppingItem> {
+    vec![
+        ShoppingItem {
+            name: "pizzas",
+            quantity: pizzas_for_slices(total_slices),
+            unit_price: PIZZA_PRICE,
+        },
+        ShoppingItem {
+            name: "2L drink bottles",
+            quantity: calculate_num_bottles(num_people, liters_per_person),
+            unit_price: DRINK_BOTTLE_PRICE,
+        },
+        ShoppingItem {
+            name: "dessert servings",
+            quantity: num_people * desserts_per_person,
+            unit_price: DESSERT_SERVING_PRICE,
+        },
+    ]
+}
+
+fn prompt_for_float(prompt: &str) -> f64 {
+    loop {
+        print!("{prompt} ");
+        let mut input = String::new();
+        if let Err(e) = std::io::stdout().flush() {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        if let Err(e) = std::io::stdin().read_line(&mut input) {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        match input.trim().parse::<f64>() {
+            Ok(value) if value >= 0.0 => return value,
+            _ => println!("Invalid input. Please enter a valid non-negative number."),
+        }
+    }
+}
+
+fn prompt_for_yes_no(prompt: &str) -> bool {
+    loop {
+        print!("{prompt} (y/n) ");
+        let mut input = String::new();
+        if let Err(e) = std::io::stdout().flush() {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        if let Err(e) = std::io::stdin().read_line(&mut input) {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        match input.trim().to_lowercase().as_str() {
+            "y" | "yes" => return true,
+            "n" | "no" => return false,
+            _ => println!("Invalid input. Please enter 'y' or 'n'."),
+        }
+    }
+}
+
+/// Asks how many attendees fall into each category, counting whoever is left as adults.
+fn prompt_for_attendance(num_people: u32) -> Vec<(Audience, u32)> {
+    loop {
+        let kids = prompt_for_uint("How many of them are kids?");
+        let teenagers = prompt_for_uint("How many of them are hungry teenagers?");
+        match kids
+            .checked_add(teenagers)
+            .and_then(|n| num_people.checked_sub(n))
+        {
+            Some(adults) => {
+                return vec![
+                    (Audience::Kids, kids),
+                    (Audience::Adults, adults),
+                    (Audience::HungryTeenagers, teenagers),
+                ]
+            }
+            None => println!(
+                "Kids and teenagers add up to more than {} people. Please try again.",
+                num_people
+            ),
+        }
+    }
 }
 
 fn main() {
+    let event_mode = prompt_for_yes_no("Plan the full event (drinks and dessert too)?");
+    let use_presets = prompt_for_yes_no("Use audience presets (kids, adults, hungry teenagers)?");
     let num_people = prompt_for_uint("How many people are in your party?");
-    let num_pizzas = prompt_for_uint("How many slices per person?");
-    let total_pizzas = calculate_num_pizzas(num_people, num_pizzas);
-    println!(
-        "You will need {} pizzas to feed {} people with {} slices each.",
-        total_pizzas, num_people, num_pizzas
+
+    let (total_slices, total_pizzas) = if use_presets {
+        let attendance = prompt_for_attendance(num_people);
+        for (audience, count) in &attendance {
+            println!(
+                "  {:>4} {:<16} x {} slices",
+                count,
+                audience.name(),
+                audience.slices_per_person()
+            );
+        }
+        let demand = slice_demand(&attendance);
+        (demand, pizzas_for_slices(demand))
+    } else {
+        let slices_per_person = prompt_for_uint("How many slices per person?");
+        (
+            num_people * slices_per_person,
+            calculate_num_pizzas(num_people, slices_per_person),
+        )
+    };
+
+    if !event_mode {
+        println!(
+            "You will need {} pizzas to feed {} people with {} slices in total.",
+            total_pizzas, num_people, total_slices
+        );
+        return;
+    }
+
+    let liters_per_person = prompt_for_float("How many liters of drinks per person?");
+    let desserts_per_person = prompt_for_uint("How many dessert servings per person?");
+    let shopping_list = plan_event(
+        num_people,
+        total_slices,
+        liters_per_person,
+        desserts_per_person,
     );
+
+    println!("Shopping list for {} people:", num_people);
+    for item in &shopping_list {
+        println!(
+            "  {:>4} {:<18} ${:>8.2}",
+            item.quantity,
+            item.name,
+            item.cost()
+        );
+    }
+    let total: f64 = shopping_list.iter().map(ShoppingItem::cost).sum();
+    println!("Estimated total cost: ${:.2}", total);
 }
 
 #[cfg(test)]
@@ -93,4 +294,34 @@ mod tests {
         assert_eq!(calculate_num_pizzas(1, 9), 2); // 9 slices needed, 2 pizzas
         assert_eq!(calculate_num_pizzas(3, 3), 2); // 9 slices needed, 2 pizzas
     }
+
+    #[test]
+    fn calculate_num_bottles_rounds_up_to_whole_bottles() {
+        assert_eq!(calculate_num_bottles(4, 1.0), 2); // 4 liters, 2 bottles
+        assert_eq!(calculate_num_bottles(5, 1.0), 3); // 5 liters, 3 bottles
+        assert_eq!(calculate_num_bottles(0, 1.5), 0);
+    }
+
+    #[test]
+    fn plan_event_builds_consolidated_shopping_list() {
+        let list = plan_event(10, 30, 0.5, 2);
+        let quantities: Vec<u32> = list.iter().map(|item| item.quantity).collect();
+        assert_eq!(quantities, vec![4, 3, 20]); // 30 slices, 5 liters, 20 desserts
+
+        let total: f64 = list.iter().map(ShoppingItem::cost).sum();
+        let expected = 4.0 * PIZZA_PRICE + 3.0 * DRINK_BOTTLE_PRICE + 20.0 * DESSERT_SERVING_PRICE;
+        assert!((total - expected).abs() < 0.001);
+    }
+
+    #[test]
+    fn slice_demand_sums_audience_presets() {
+        let attendance = [
+            (Audience::Kids, 5),
+            (Audience::Adults, 4),
+            (Audience::HungryTeenagers, 3),
+        ];
+        assert_eq!(slice_demand(&attendance), 34); // 10 + 12 + 12
+        assert_eq!(pizzas_for_slices(slice_demand(&attendance)), 5);
+        assert_eq!(slice_demand(&[]), 0);
+    }
 }
@@ -0,0 +1,164 @@
+//! # Paint Calculator GUI
+//!
+//! This module implements a graphical front-end for the paint calculator that
+//! recomputes the required paint live as room dimensions are entered.
+//!
+//! ## Features
+//!
+//! - **Room Shape Selector**: Switches between rectangular, circular, and L-shaped rooms
+//! - **Live Recalculation**: Updates the area and gallon estimate on every keystroke
+//! - **Shared Logic**: Uses the same `RoomType`/`Area` implementation as the CLI
+//! - **Input Validation**: Prompts for valid numbers until every dimension parses
+//! - **Product Comparison**: Shows the cans and cost of each bundled paint, cheapest first
+use e09::{
+    bundled_products, calculate_gallons_needed, compare_products, Area, PaintProduct, RoomType,
+};
+use eframe::egui::{self, ComboBox};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RoomShape {
+    Rectangular,
+    Circular,
+    LShaped,
+}
+
+impl Display for RoomShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RoomShape::Rectangular => write!(f, "Rectangular"),
+            RoomShape::Circular => write!(f, "Circular"),
+            RoomShape::LShaped => write!(f, "L-Shaped"),
+        }
+    }
+}
+
+struct PaintCalculator {
+    shape: RoomShape,
+    length: String,
+    width: String,
+    diameter: String,
+    alcove_length: String,
+    alcove_width: String,
+    products: Result<Vec<PaintProduct>, String>,
+}
+
+impl Default for PaintCalculator {
+    fn default() -> Self {
+        Self {
+            shape: RoomShape::Rectangular,
+            length: String::new(),
+            width: String::new(),
+            diameter: String::new(),
+            alcove_length: String::new(),
+            alcove_width: String::new(),
+            products: bundled_products(),
+        }
+    }
+}
+
+impl PaintCalculator {
+    fn room_type(&self) -> Option<RoomType> {
+        let parse = |field: &str| field.trim().parse::<f64>().ok();
+        match self.shape {
+            RoomShape::Rectangular => Some(RoomType::Rectangular {
+                length: parse(&self.length)?,
+                width: parse(&self.width)?,
+            }),
+            RoomShape::Circular => Some(RoomType::Circular {
+                diameter: parse(&self.diameter)?,
+            }),
+            RoomShape::LShaped => Some(RoomType::LShaped {
+                length: parse(&self.length)?,
+                width: parse(&self.width)?,
+                alcove_length: parse(&self.alcove_length)?,
+                alcove_width: parse(&self.alcove_width)?,
+            }),
+        }
+    }
+}
+
+fn dimension_field(ui: &mut egui::Ui, label: &str, value: &mut String) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        ui.text_edit_singleline(value);
+    });
+}
+
+impl eframe::App for PaintCalculator {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ComboBox::from_label("Room shape")
+                .selected_text(self.shape.to_string())
+                .show_ui(ui, |ui| {
+                    for shape in [
+                        RoomShape::Rectangular,
+                        RoomShape::Circular,
+                        RoomShape::LShaped,
+                    ] {
+                        ui.selectable_value(&mut self.shape, shape, shape.to_string());
+                    }
+                });
+
+            match self.shape {
+                RoomShape::Rectangular => {
+                    dimension_field(ui, "Length (ft):", &mut self.length);
+                    dimension_field(ui, "Width (ft):", &mut self.width);
+                }
+                RoomShape::Circular => {
+                    dimension_field(ui, "Diameter (ft):", &mut self.diameter);
+                }
+                RoomShape::LShaped => {
+                    dimension_field(ui, "Main length (ft):", &mut self.length);
+                    dimension_field(ui, "Main width (ft):", &mut self.width);
+                    dimension_field(ui, "Alcove length (ft):", &mut self.alcove_length);
+                    dimension_field(ui, "Alcove width (ft):", &mut self.alcove_width);
+                }
+            }
+
+            ui.separator();
+            if let Some(room_type) = self.room_type() {
+                ui.label(format!("Area: {:.2} square feet", room_type.area()));
+                ui.label(format!(
+                    "Paint needed: {} gallons",
+                    calculate_gallons_needed(&room_type)
+                ));
+
+                ui.separator();
+                match &self.products {
+                    Ok(products) => {
+                        egui::Grid::new("products").striped(true).show(ui, |ui| {
+                            ui.strong("Product");
+                            ui.strong("Cans");
+                            ui.strong("Cost");
+                            ui.end_row();
+                            for estimate in compare_products(room_type.area(), products) {
+                                ui.label(estimate.name);
+                                ui.label(estimate.cans.to_string());
+                                ui.label(format!("${:.2}", estimate.cost));
+                                ui.end_row();
+                            }
+                        });
+                    }
+                    Err(e) => {
+                        ui.colored_label(egui::Color32::RED, e);
+                    }
+                }
+            } else {
+                ui.label("Please enter valid numbers for every dimension.");
+            }
+        });
+    }
+}
+
+fn main() -> eframe::Result {
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 420.0]),
+        ..Default::default()
+    };
+    eframe::run_native(
+        "Paint Calculator",
+        options,
+        Box::new(|_| Ok(Box::<PaintCalculator>::default())),
+    )
+}
@@ -0,0 +1,244 @@
+//! # Paint Calculator Library
+//!
+//! Room shape and paint estimation logic shared by the command-line and GUI front-ends of
+//! the paint calculator, plus a bundled database of paint products for comparing costs.
+use serde::Deserialize;
+
+pub trait Area {
+    fn area(&self) -> f64;
+}
+
+pub enum RoomType {
+    Rectangular {
+        length: f64,
+        width: f64,
+    },
+    Circular {
+        diameter: f64,
+    },
+    LShaped {
+        length: f64,
+        width: f64,
+        alcove_length: f64,
+        alcove_width: f64,
+    },
+}
+
+impl Area for RoomType {
+    fn area(&self) -> f64 {
+        match self {
+            RoomType::Rectangular { length, width } => length * width,
+            RoomType::Circular { diameter } => {
+                let radius = diameter / 2.0;
+                std::f64::consts::PI * radius * radius
+            }
+            RoomType::LShaped {
+                length,
+                width,
+                alcove_length,
+                alcove_width,
+            } => {
+                let main_area = length * width;
+                let alcove_area = alcove_length * alcove_width;
+                main_area + alcove_area
+            }
+        }
+    }
+}
+
+pub fn calculate_gallons_needed(room_type: &RoomType) -> u32 {
+    const SQUARE_FT_PER_GALLON: f64 = 350.0; // Average coverage of paint in square feet per gallon
+    let area = room_type.area();
+    let gallons_needed = area / SQUARE_FT_PER_GALLON;
+    if gallons_needed < 1.0 {
+        1 // At least one gallon is needed
+    } else {
+        gallons_needed.ceil() as u32 // Round up to the nearest whole gallon
+    }
+}
+
+/// A paint product sold in one-gallon cans.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PaintProduct {
+    pub name: String,
+    /// Square feet covered by one gallon.
+    pub coverage_per_gallon: f64,
+    pub price_per_gallon: f64,
+}
+
+const BUNDLED_PRODUCTS: &str = include_str!("../inputs/paints.json");
+
+pub fn parse_products(json: &str) -> Result<Vec<PaintProduct>, String> {
+    let products: Vec<PaintProduct> =
+        serde_json::from_str(json).map_err(|e| format!("invalid paint database: {}", e))?;
+    if let Some(product) = products
+        .iter()
+        .find(|p| p.coverage_per_gallon <= 0.0 || p.price_per_gallon < 0.0)
+    {
+        return Err(format!(
+            "paint '{}' must have a positive coverage and a non-negative price",
+            product.name
+        ));
+    }
+    Ok(products)
+}
+
+/// Loads the paint database shipped with the calculator.
+pub fn bundled_products() -> Result<Vec<PaintProduct>, String> {
+    parse_products(BUNDLED_PRODUCTS)
+}
+
+/// The cans and total cost one product needs to cover an area.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductEstimate {
+    pub name: String,
+    pub cans: u32,
+    pub cost: f64,
+}
+
+/// Estimates every product for `area` square feet, cheapest first.
+pub fn compare_products(area: f64, products: &[PaintProduct]) -> Vec<ProductEstimate> {
+    let mut estimates: Vec<ProductEstimate> = products
+        .iter()
+        .map(|product| {
+            let cans = ((area / product.coverage_per_gallon).ceil() as u32).max(1);
+            ProductEstimate {
+                name: product.name.clone(),
+                cans,
+                cost: f64::from(cans) * product.price_per_gallon,
+            }
+        })
+        .collect();
+    estimates.sort_by(|a, b| a.cost.total_cmp(&b.cost).then_with(|| a.name.cmp(&b.name)));
+    estimates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calculate_gallons_needed_handles_exact_division() {
+        // Test cases where area is exactly divisible by SQUARE_FT_PER_GALLON
+        let room = RoomType::Rectangular {
+            length: 35.0,
+            width: 10.0,
+        }; // 350 sq ft
+        assert_eq!(calculate_gallons_needed(&room), 1); // Exactly 1 gallon
+
+        let room = RoomType::Rectangular {
+            length: 70.0,
+            width: 10.0,
+        }; // 700 sq ft
+        assert_eq!(calculate_gallons_needed(&room), 2); // Exactly 2 gallons
+    }
+
+    #[test]
+    fn calculate_gallons_needed_rounds_up_correctly() {
+        // Test cases where we need to round up
+        let room = RoomType::Rectangular {
+            length: 20.0,
+            width: 20.0,
+        }; // 400 sq ft
+        assert_eq!(calculate_gallons_needed(&room), 2); // Slightly more than 1 gallon (1.14)
+
+        let room = RoomType::Circular { diameter: 10.0 }; // ~78.54 sq ft
+        assert_eq!(calculate_gallons_needed(&room), 1); // Less than 1 gallon but rounds up
+
+        let room = RoomType::LShaped {
+            length: 30.0,
+            width: 10.0,
+            alcove_length: 10.0,
+            alcove_width: 6.0,
+        }; // 360 sq ft
+        assert_eq!(calculate_gallons_needed(&room), 2); // Slightly more than 1 gallon (1.03)
+    }
+
+    #[test]
+    fn calculate_gallons_needed_handles_small_areas() {
+        // Test with small areas (less than one gallon)
+        let room = RoomType::Rectangular {
+            length: 10.0,
+            width: 10.0,
+        }; // 100 sq ft
+        assert_eq!(calculate_gallons_needed(&room), 1); // Less than 1 gallon but minimum is 1
+
+        let room = RoomType::Circular { diameter: 5.0 }; // ~19.63 sq ft
+        assert_eq!(calculate_gallons_needed(&room), 1); // Much less than 1 gallon but minimum is 1
+    }
+
+    #[test]
+    fn calculate_gallons_needed_handles_large_areas() {
+        // Test with large areas
+        let room = RoomType::Rectangular {
+            length: 100.0,
+            width: 100.0,
+        }; // 10,000 sq ft
+        assert_eq!(calculate_gallons_needed(&room), 29); // 28.57 gallons rounded up
+
+        let room = RoomType::LShaped {
+            length: 50.0,
+            width: 30.0,
+            alcove_length: 20.0,
+            alcove_width: 15.0,
+        }; // 1800 sq ft
+        assert_eq!(calculate_gallons_needed(&room), 6); // 5.14 gallons rounded up
+    }
+
+    #[test]
+    fn calculate_gallons_needed_handles_different_room_types() {
+        // Test with different room types
+        let rectangular = RoomType::Rectangular {
+            length: 35.0,
+            width: 10.0,
+        }; // 350 sq ft
+        assert_eq!(calculate_gallons_needed(&rectangular), 1);
+
+        let circular = RoomType::Circular { diameter: 21.0 }; // ~346.36 sq ft
+        assert_eq!(calculate_gallons_needed(&circular), 1);
+
+        let l_shaped = RoomType::LShaped {
+            length: 20.0,
+            width: 15.0,
+            alcove_length: 10.0,
+            alcove_width: 5.0,
+        }; // 350 sq ft
+        assert_eq!(calculate_gallons_needed(&l_shaped), 1);
+    }
+
+    #[test]
+    fn compare_products_sorts_by_total_cost() {
+        let products = parse_products(
+            r#"[
+                {"name": "Cheap", "coverage_per_gallon": 200.0, "price_per_gallon": 20.0},
+                {"name": "Thick", "coverage_per_gallon": 500.0, "price_per_gallon": 35.0},
+                {"name": "Premium", "coverage_per_gallon": 400.0, "price_per_gallon": 60.0}
+            ]"#,
+        )
+        .unwrap();
+        let estimates = compare_products(900.0, &products);
+        let summary: Vec<(&str, u32, f64)> = estimates
+            .iter()
+            .map(|e| (e.name.as_str(), e.cans, e.cost))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Thick", 2, 70.0),
+                ("Cheap", 5, 100.0),
+                ("Premium", 3, 180.0)
+            ]
+        );
+        assert_eq!(compare_products(10.0, &products)[0].cans, 1);
+    }
+
+    #[test]
+    fn parse_products_validates_database() {
+        assert!(bundled_products().unwrap().len() >= 5);
+        assert!(parse_products("not json").is_err());
+        assert!(parse_products(
+            r#"[{"name": "Bad", "coverage_per_gallon": 0.0, "price_per_gallon": 10.0}]"#
+        )
+        .is_err());
+    }
+}
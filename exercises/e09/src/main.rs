@@ -10,50 +10,13 @@
 //! - **Paint Estimation**: Determines required gallons based on standard coverage rates
 //! - **User Interaction**: Provides clear prompts and guides users through input process
 //! - **Rounding Logic**: Ensures users purchase sufficient paint by rounding up to whole gallons
+//! - **Product Comparison**: Lists the cans and cost each paint in the bundled database needs
+//!   for the room, cheapest first
+//!
+//! A GUI front-end sharing the same calculation logic is available via `cargo run --bin e09_gui`.
+use e09::{bundled_products, calculate_gallons_needed, compare_products, Area, RoomType};
 use std::io::Write;
 
-trait Area {
-    fn area(&self) -> f64;
-}
-
-enum RoomType {
-    Rectangular {
-        length: f64,
-        width: f64,
-    },
-    Circular {
-        diameter: f64,
-    },
-    LShaped {
-        length: f64,
-        width: f64,
-        alcove_length: f64,
-        alcove_width: f64,
-    },
-}
-
-impl Area for RoomType {
-    fn area(&self) -> f64 {
-        match self {
-            RoomType::Rectangular { length, width } => length * width,
-            RoomType::Circular { diameter } => {
-                let radius = diameter / 2.0;
-                std::f64::consts::PI * radius * radius
-            }
-            RoomType::LShaped {
-                length,
-                width,
-                alcove_length,
-                alcove_width,
-            } => {
-                let main_area = length * width;
-                let alcove_area = alcove_length * alcove_width;
-                main_area + alcove_area
-            }
-        }
-    }
-}
-
 fn prompt_for_float(prompt: &str) -> f64 {
     loop {
         print!("{prompt} ");
@@ -116,17 +79,6 @@ fn prompt_for_room_type() -> RoomType {
     }
 }
 
-fn calculate_gallons_needed(room_type: &RoomType) -> u32 {
-    const SQUARE_FT_PER_GALLON: f64 = 350.0; // Average coverage of paint in square feet per gallon
-    let area = room_type.area();
-    let gallons_needed = area / SQUARE_FT_PER_GALLON;
-    if gallons_needed < 1.0 {
-        1 // At least one gallon is needed
-    } else {
-        gallons_needed.ceil() as u32 // Round up to the nearest whole gallon
-    }
-}
-
 fn main() {
     let room_type = prompt_for_room_type();
     let area = room_type.area();
@@ -135,98 +87,19 @@ fn main() {
         calculate_gallons_needed(&room_type),
         area
     );
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[test]
-    fn calculate_gallons_needed_handles_exact_division() {
-        // Test cases where area is exactly divisible by SQUARE_FT_PER_GALLON
-        let room = RoomType::Rectangular {
-            length: 35.0,
-            width: 10.0,
-        }; // 350 sq ft
-        assert_eq!(calculate_gallons_needed(&room), 1); // Exactly 1 gallon
-
-        let room = RoomType::Rectangular {
-            length: 70.0,
-            width: 10.0,
-        }; // 700 sq ft
-        assert_eq!(calculate_gallons_needed(&room), 2); // Exactly 2 gallons
-    }
-
-    #[test]
-    fn calculate_gallons_needed_rounds_up_correctly() {
-        // Test cases where we need to round up
-        let room = RoomType::Rectangular {
-            length: 20.0,
-            width: 20.0,
-        }; // 400 sq ft
-        assert_eq!(calculate_gallons_needed(&room), 2); // Slightly more than 1 gallon (1.14)
-
-        let room = RoomType::Circular { diameter: 10.0 }; // ~78.54 sq ft
-        assert_eq!(calculate_gallons_needed(&room), 1); // Less than 1 gallon but rounds up
-
-        let room = RoomType::LShaped {
-            length: 30.0,
-            width: 10.0,
-            alcove_length: 10.0,
-            alcove_width: 6.0,
-        }; // 360 sq ft
-        assert_eq!(calculate_gallons_needed(&room), 2); // Slightly more than 1 gallon (1.03)
-    }
-
-    #[test]
-    fn calculate_gallons_needed_handles_small_areas() {
-        // Test with small areas (less than one gallon)
-        let room = RoomType::Rectangular {
-            length: 10.0,
-            width: 10.0,
-        }; // 100 sq ft
-        assert_eq!(calculate_gallons_needed(&room), 1); // Less than 1 gallon but minimum is 1
-
-        let room = RoomType::Circular { diameter: 5.0 }; // ~19.63 sq ft
-        assert_eq!(calculate_gallons_needed(&room), 1); // Much less than 1 gallon but minimum is 1
-    }
-
-    #[test]
-    fn calculate_gallons_needed_handles_large_areas() {
-        // Test with large areas
-        let room = RoomType::Rectangular {
-            length: 100.0,
-            width: 100.0,
-        }; // 10,000 sq ft
-        assert_eq!(calculate_gallons_needed(&room), 29); // 28.57 gallons rounded up
-
-        let room = RoomType::LShaped {
-            length: 50.0,
-            width: 30.0,
-            alcove_length: 20.0,
-            alcove_width: 15.0,
-        }; // 1800 sq ft
-        assert_eq!(calculate_gallons_needed(&room), 6); // 5.14 gallons rounded up
-    }
-
-    #[test]
-    fn calculate_gallons_needed_handles_different_room_types() {
-        // Test with different room types
-        let rectangular = RoomType::Rectangular {
-            length: 35.0,
-            width: 10.0,
-        }; // 350 sq ft
-        assert_eq!(calculate_gallons_needed(&rectangular), 1);
-
-        let circular = RoomType::Circular { diameter: 21.0 }; // ~346.36 sq ft
-        assert_eq!(calculate_gallons_needed(&circular), 1);
-
-        let l_shaped = RoomType::LShaped {
-            length: 20.0,
-            width: 15.0,
-            alcove_length: 10.0,
-            alcove_width: 5.0,
-        }; // 350 sq ft
-        assert_eq!(calculate_gallons_needed(&l_shaped), 1);
+    match bundled_products() {
+        Ok(products) => {
+            println!("\n{:<30} {:>5} {:>10}", "Product", "Cans", "Cost");
+            for estimate in compare_products(area, &products) {
+                println!(
+                    "{:<30} {:>5} {:>10}",
+                    estimate.name,
+                    estimate.cans,
+                    format!("${:.2}", estimate.cost)
+                );
+            }
+        }
+        Err(e) => eprintln!("Error loading paint products: {}", e),
     }
 }
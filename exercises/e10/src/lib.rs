@@ -0,0 +1,153 @@
+//! # Purchase Receipt Library
+//!
+//! Receipt and sales tax calculations shared by the self-checkout application and other
+//! exercises that need to total a purchase.
+use std::fmt::Display;
+
+pub struct PurchaseItem {
+    quanity: u32,
+    price_per_item: f64,
+}
+
+impl PurchaseItem {
+    pub fn new(quantity: u32, price_per_item: f64) -> Self {
+        PurchaseItem {
+            quanity: quantity,
+            price_per_item,
+        }
+    }
+}
+
+pub struct PurchaseReceipt {
+    items: Vec<PurchaseItem>,
+}
+
+impl PurchaseReceipt {
+    pub fn new(items: Vec<PurchaseItem>) -> Self {
+        PurchaseReceipt { items }
+    }
+
+    pub fn total_cost(&self) -> f64 {
+        if self.items.is_empty() {
+            return 0.0;
+        }
+
+        self.items
+            .iter()
+            .map(|item| item.quanity as f64 * item.price_per_item)
+            .sum()
+    }
+
+    pub fn tax(&self, tax_rate: f64) -> f64 {
+        self.total_cost() * tax_rate
+    }
+
+    pub fn total_with_tax(&self, tax_rate: f64) -> f64 {
+        self.total_cost() + self.tax(tax_rate)
+    }
+}
+
+impl Display for PurchaseReceipt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const TAX_RATE: f64 = 0.055;
+        write!(f, "Subtotal: ${:.2}", self.total_cost())?;
+        write!(f, "\nTax: ${:.2}", self.tax(TAX_RATE))?;
+        write!(f, "\nTotal: ${:.2}", self.total_with_tax(TAX_RATE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_receipt() -> PurchaseReceipt {
+        PurchaseReceipt {
+            items: vec![
+                PurchaseItem {
+                    quanity: 2,
+                    price_per_item: 10.0,
+                },
+                PurchaseItem {
+                    quanity: 1,
+                    price_per_item: 15.0,
+                },
+                PurchaseItem {
+                    quanity: 3,
+                    price_per_item: 5.0,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn total_cost_calculates_sum_correctly() {
+        let receipt = create_test_receipt();
+        // (2 * 10.0) + (1 * 15.0) + (3 * 5.0) = 20.0 + 15.0 + 15.0 = 50.0
+        assert_eq!(receipt.total_cost(), 50.0);
+    }
+
+    #[test]
+    fn total_cost_handles_empty_receipt() {
+        let receipt = PurchaseReceipt { items: vec![] };
+        assert_eq!(receipt.total_cost(), 0.0);
+    }
+
+    #[test]
+    fn tax_calculates_correct_amount() {
+        let receipt = create_test_receipt();
+        // 50.0 * 0.05 = 2.5
+        assert_eq!(receipt.tax(0.05), 2.5);
+        // 50.0 * 0.1 = 5.0
+        assert_eq!(receipt.tax(0.1), 5.0);
+        // 50.0 * 0.0 = 0.0
+        assert_eq!(receipt.tax(0.0), 0.0);
+    }
+
+    #[test]
+    fn tax_handles_empty_receipt() {
+        let receipt = PurchaseReceipt { items: vec![] };
+        assert_eq!(receipt.tax(0.05), 0.0);
+    }
+
+    #[test]
+    fn total_with_tax_calculates_correct_amount() {
+        let receipt = create_test_receipt();
+        // 50.0 + (50.0 * 0.05) = 50.0 + 2.5 = 52.5
+        assert_eq!(receipt.total_with_tax(0.05), 52.5);
+        // 50.0 + (50.0 * 0.1) = 50.0 + 5.0 = 55.0
+        assert_eq!(receipt.total_with_tax(0.1), 55.0);
+        // 50.0 + (50.0 * 0.0) = 50.0 + 0.0 = 50.0
+        assert_eq!(receipt.total_with_tax(0.0), 50.0);
+    }
+
+    #[test]
+    fn total_with_tax_handles_empty_receipt() {
+        let receipt = PurchaseReceipt { items: vec![] };
+        assert_eq!(receipt.total_with_tax(0.05), 0.0);
+    }
+
+    #[test]
+    fn display_formats_receipt_correctly() {
+        let receipt = create_test_receipt();
+        let display_string = format!("{}", receipt);
+
+        // The tax rate in the Display implementation is 0.055 (5.5%)
+        // Subtotal: $50.00
+        // Tax: $2.75 (50.0 * 0.055)
+        // Total: $52.75 (50.0 + 2.75)
+
+        assert!(display_string.contains("Subtotal: $50.00"));
+        assert!(display_string.contains("Tax: $2.75"));
+        assert!(display_string.contains("Total: $52.75"));
+    }
+
+    #[test]
+    fn display_handles_empty_receipt() {
+        let receipt = PurchaseReceipt { items: vec![] };
+        let display_string = format!("{}", receipt);
+
+        assert!(display_string.contains("Subtotal: $0.00"));
+        assert!(display_string.contains("Tax: $0.00"));
+        assert!(display_string.contains("Total: $0.00"));
+    }
+}
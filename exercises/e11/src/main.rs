@@ -10,6 +10,11 @@
 //! - **Tabular Display**: Shows available currency options in a formatted table
 //! - **User Interaction**: Provides clear prompts for country selection and amount input
 //! - **Error Handling**: Gracefully handles API connection issues and invalid inputs
+//! - **Currency Formatting**: Prints amounts with the currency's symbol, decimal places,
+//!   and locale-aware thousands separators (e.g. "¥1,234" or "€1.234,56")
+//! - **Tolerant Amount Parsing**: Accepts amounts with currency symbols and thousands
+//!   separators such as "$1,234.56" or "1.234,56", rejecting ambiguous or malformed input
+use num_format::{Locale, ToFormattedString};
 use reqwest::blocking::get;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -19,6 +24,127 @@ type CountryCode = String;
 type ExchangeRate = f64;
 type USDExchangeRates = HashMap<CountryCode, ExchangeRate>;
 
+/// Display metadata for a currency.
+struct CurrencyFormat {
+    code: &'static str,
+    symbol: &'static str,
+    decimals: u32,
+    symbol_after: bool,
+    locale: Locale,
+}
+
+const CURRENCY_FORMATS: [CurrencyFormat; 10] = [
+    CurrencyFormat {
+        code: "USD",
+        symbol: "$",
+        decimals: 2,
+        symbol_after: false,
+        locale: Locale::en,
+    },
+    CurrencyFormat {
+        code: "EUR",
+        symbol: "€",
+        decimals: 2,
+        symbol_after: false,
+        locale: Locale::de,
+    },
+    CurrencyFormat {
+        code: "GBP",
+        symbol: "£",
+        decimals: 2,
+        symbol_after: false,
+        locale: Locale::en,
+    },
+    CurrencyFormat {
+        code: "JPY",
+        symbol: "¥",
+        decimals: 0,
+        symbol_after: false,
+        locale: Locale::ja,
+    },
+    CurrencyFormat {
+        code: "CNY",
+        symbol: "¥",
+        decimals: 2,
+        symbol_after: false,
+        locale: Locale::zh,
+    },
+    CurrencyFormat {
+        code: "KRW",
+        symbol: "₩",
+        decimals: 0,
+        symbol_after: false,
+        locale: Locale::ko,
+    },
+    CurrencyFormat {
+        code: "INR",
+        symbol: "₹",
+        decimals: 2,
+        symbol_after: false,
+        locale: Locale::en_IN,
+    },
+    CurrencyFormat {
+        code: "CHF",
+        symbol: "CHF ",
+        decimals: 2,
+        symbol_after: false,
+        locale: Locale::de_CH,
+    },
+    CurrencyFormat {
+        code: "SEK",
+        symbol: " kr",
+        decimals: 2,
+        symbol_after: true,
+        locale: Locale::sv,
+    },
+    CurrencyFormat {
+        code: "PLN",
+        symbol: " zł",
+        decimals: 2,
+        symbol_after: true,
+        locale: Locale::pl,
+    },
+];
+
+fn find_currency_format(code: &str) -> Option<&'static CurrencyFormat> {
+    CURRENCY_FORMATS.iter().find(|format| format.code == code)
+}
+
+/// Formats a number with `decimals` fractional digits using the separators of `locale`.
+fn format_number(amount: f64, decimals: u32, locale: &Locale) -> String {
+    let scale = 10u64.pow(decimals);
+    let units = (amount.abs() * scale as f64).round() as u64;
+    let mut formatted = (units / scale).to_formatted_string(locale);
+    if decimals > 0 {
+        formatted.push_str(locale.decimal());
+        formatted.push_str(&format!(
+            "{:0width$}",
+            units % scale,
+            width = decimals as usize
+        ));
+    }
+    if amount < 0.0 && units > 0 {
+        formatted.insert_str(0, locale.minus_sign());
+    }
+    formatted
+}
+
+/// Formats an amount in the given currency, falling back to the bare code for currencies
+/// without metadata.
+fn format_amount(amount: f64, code: &str) -> String {
+    match find_currency_format(code) {
+        Some(format) => {
+            let number = format_number(amount, format.decimals, &format.locale);
+            if format.symbol_after {
+                format!("{}{}", number, format.symbol)
+            } else {
+                format!("{}{}", format.symbol, number)
+            }
+        }
+        None => format!("{} {}", format_number(amount, 2, &Locale::en), code),
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 struct ExchangeRateResponse {
@@ -86,7 +212,92 @@ fn prompt_for_country_code(countries: Vec<CountryCode>) -> CountryCode {
     }
 }
 
-fn prompt_for_currency(prompt: &str) -> f64 {
+// Characters that only ever group digits, never separate the fraction.
+const GROUPING_CHARS: [char; 4] = [' ', '\u{a0}', '\u{202f}', '\''];
+
+/// Checks that the groups of an integer part split on thousands separators are well formed,
+/// allowing the two-digit groups used by the Indian numbering system.
+fn valid_grouping(groups: &[&str]) -> bool {
+    let Some((first, rest)) = groups.split_first() else {
+        return false;
+    };
+    let Some((last, middle)) = rest.split_last() else {
+        return !first.is_empty();
+    };
+    (1..=3).contains(&first.len())
+        && last.len() == 3
+        && middle.iter().all(|g| g.len() == 2 || g.len() == 3)
+}
+
+/// Parses an amount such as "$1,234.56", "1.234,56 €", or "1 234,56". Currency symbols and
+/// codes around the number are ignored. A single separator followed by exactly three digits
+/// ("1,234") is read using `locale`, and is rejected when the locale doesn't settle it.
+fn parse_amount(input: &str, locale: &Locale) -> Result<f64, String> {
+    let trimmed = input.trim();
+    let is_decoration = |c: char| !c.is_ascii_digit() && !matches!(c, '-' | '.' | ',');
+    let number = trimmed.trim_matches(is_decoration);
+    let (negative, number) = match number.strip_prefix('-') {
+        Some(rest) => (true, rest.trim_start_matches(is_decoration)),
+        None => (false, number),
+    };
+    if number.is_empty() {
+        return Err("please enter an amount".to_string());
+    }
+    if let Some(c) = number
+        .chars()
+        .find(|c| !c.is_ascii_digit() && !matches!(c, '.' | ',') && !GROUPING_CHARS.contains(c))
+    {
+        return Err(format!("unexpected character '{}' in amount", c));
+    }
+
+    let last_dot = number.rfind('.');
+    let last_comma = number.rfind(',');
+    let decimal = match (last_dot, last_comma) {
+        (Some(dot), Some(comma)) => Some(if dot > comma { '.' } else { ',' }),
+        (Some(_), None) | (None, Some(_)) => {
+            let separator = if last_dot.is_some() { '.' } else { ',' };
+            let count = number.matches(separator).count();
+            let digits_after = number.len() - number.rfind(separator).unwrap() - 1;
+            if count > 1 {
+                None
+            } else if digits_after != 3 || locale.decimal() == separator.to_string() {
+                Some(separator)
+            } else if locale.separator() == separator.to_string() {
+                None
+            } else {
+                return Err(format!(
+                    "'{}' is ambiguous; write it as {} or {}",
+                    number,
+                    number.replace(separator, ""),
+                    number.replace(separator, ".")
+                ));
+            }
+        }
+        (None, None) => None,
+    };
+
+    let (integer, fraction) = match decimal {
+        Some(decimal) => number.rsplit_once(decimal).unwrap(),
+        None => (number, ""),
+    };
+    if !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("'{}' has misplaced separators", number));
+    }
+    let groups: Vec<&str> = integer
+        .split(|c| matches!(c, '.' | ',') || GROUPING_CHARS.contains(&c))
+        .collect();
+    if groups.len() > 1 && !valid_grouping(&groups) {
+        return Err(format!("'{}' has misplaced thousands separators", number));
+    }
+
+    let digits = format!("{}.{}", groups.concat(), fraction);
+    let value: f64 = digits
+        .parse()
+        .map_err(|_| format!("'{}' is not a valid amount", number))?;
+    Ok(if negative { -value } else { value })
+}
+
+fn prompt_for_currency(prompt: &str, locale: &Locale) -> f64 {
     loop {
         print!("{prompt} ");
         let mut input = String::new();
@@ -100,10 +311,9 @@ fn prompt_for_currency(prompt: &str) -> f64 {
             continue;
         }
 
-        if let Ok(value) = input.trim().parse::<f64>() {
-            return value;
-        } else {
-            println!("Invalid input. Please enter a valid number.");
+        match parse_amount(&input, locale) {
+            Ok(value) => return value,
+            Err(e) => println!("Invalid input: {}.", e),
         }
     }
 }
@@ -124,13 +334,69 @@ fn main() {
         keys
     };
     let country_code = prompt_for_country_code(country_codes);
-    let usd_amount = prompt_for_currency("Enter the amount in USD to convert:");
+    let usd_locale = find_currency_format("USD").map_or(Locale::en, |format| format.locale);
+    let usd_amount = prompt_for_currency("Enter the amount in USD to convert:", &usd_locale);
 
     println!(
-        "{:.2} USD at an exchange rate of {} will give you {:.2} {}.",
-        usd_amount,
+        "{} at an exchange rate of {} will give you {}.",
+        format_amount(usd_amount, "USD"),
         exchange_rates[&country_code],
-        usd_amount * exchange_rates[&country_code],
-        country_code
+        format_amount(usd_amount * exchange_rates[&country_code], &country_code)
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_amount_uses_currency_metadata() {
+        assert_eq!(format_amount(1234.4, "JPY"), "¥1,234");
+        assert_eq!(format_amount(1234.56, "EUR"), "€1.234,56");
+        assert_eq!(format_amount(1234.5, "USD"), "$1,234.50");
+        assert_eq!(format_amount(1234.5, "PLN"), "1\u{a0}234,50 zł");
+    }
+
+    #[test]
+    fn format_amount_falls_back_to_code() {
+        assert_eq!(format_amount(1234567.891, "XYZ"), "1,234,567.89 XYZ");
+    }
+
+    #[test]
+    fn format_number_handles_negative_and_rounding() {
+        assert_eq!(format_number(-0.004, 2, &Locale::en), "0.00");
+        assert_eq!(format_number(-1999.999, 2, &Locale::en), "-2,000.00");
+    }
+
+    #[test]
+    fn parse_amount_accepts_symbols_and_separators() {
+        assert_eq!(parse_amount("$1,234.56", &Locale::en), Ok(1234.56));
+        assert_eq!(parse_amount("1.234,56 €", &Locale::en), Ok(1234.56));
+        assert_eq!(parse_amount("USD 1 234 567", &Locale::en), Ok(1234567.0));
+        assert_eq!(parse_amount("CHF 1'250.5", &Locale::en), Ok(1250.5));
+        assert_eq!(parse_amount("₹1,23,456.78", &Locale::en), Ok(123456.78));
+        assert_eq!(parse_amount("-$12.5", &Locale::en), Ok(-12.5));
+        assert_eq!(parse_amount("42", &Locale::en), Ok(42.0));
+    }
+
+    #[test]
+    fn parse_amount_resolves_single_separator_by_locale() {
+        assert_eq!(parse_amount("1,234", &Locale::en), Ok(1234.0));
+        assert_eq!(parse_amount("1,234", &Locale::de), Ok(1.234));
+        assert_eq!(parse_amount("1.234", &Locale::de), Ok(1234.0));
+        assert_eq!(parse_amount("1,5", &Locale::en), Ok(1.5));
+        assert!(parse_amount("1,234", &Locale::de_CH)
+            .unwrap_err()
+            .contains("ambiguous"));
+    }
+
+    #[test]
+    fn parse_amount_rejects_malformed_input() {
+        assert!(parse_amount("", &Locale::en).is_err());
+        assert!(parse_amount("$", &Locale::en).is_err());
+        assert!(parse_amount("12a4", &Locale::en).is_err());
+        assert!(parse_amount("1,23,4", &Locale::en).is_err());
+        assert!(parse_amount("1.234.56,7.8", &Locale::en).is_err());
+        assert!(parse_amount("12,34.567", &Locale::en).is_err());
+    }
+}
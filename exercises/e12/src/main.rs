@@ -10,7 +10,96 @@
 //! - **User Interaction**: Prompts for principal amount, interest rate, and time period
 //! - **Formatted Output**: Displays results with proper currency formatting and decimal precision
 //! - **Input Validation**: Ensures valid numeric inputs through robust error handling
+//! - **Compounding Frequency**: `--compounding simple|annual|monthly` switches the breakdown from
+//!   simple interest to interest compounded annually or monthly
+//! - **Schedule Export**: `--csv <path>` writes the year-by-year table to a CSV file
+//! - **Tax on Interest**: `--tax-rate <percent>` shows gross vs. net interest each year and the
+//!   cumulative net value, taxed yearly or only at withdrawal (`--tax-treatment annual|withdrawal`)
+use e13::future_value;
 use std::io::Write;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum Compounding {
+    #[default]
+    Simple,
+    Annual,
+    Monthly,
+}
+
+impl Compounding {
+    fn description(&self) -> &'static str {
+        match self {
+            Compounding::Simple => "simple interest",
+            Compounding::Annual => "compounded annually",
+            Compounding::Monthly => "compounded monthly",
+        }
+    }
+}
+
+/// When interest is taxed. Annual taxation also shrinks the balance that keeps compounding.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum TaxTreatment {
+    #[default]
+    Annual,
+    AtWithdrawal,
+}
+
+impl TaxTreatment {
+    fn description(&self) -> &'static str {
+        match self {
+            TaxTreatment::Annual => "taxed annually",
+            TaxTreatment::AtWithdrawal => "taxed at withdrawal",
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    compounding: Compounding,
+    csv_file: Option<PathBuf>,
+    tax_rate: Option<f64>,
+    tax_treatment: TaxTreatment,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--compounding" => {
+                let value = args.next().ok_or("--compounding requires a value")?;
+                options.compounding = match value.as_str() {
+                    "simple" => Compounding::Simple,
+                    "annual" => Compounding::Annual,
+                    "monthly" => Compounding::Monthly,
+                    _ => return Err(format!("unknown compounding '{}'", value)),
+                };
+            }
+            "--csv" => {
+                let path = args.next().ok_or("--csv requires a file path")?;
+                options.csv_file = Some(PathBuf::from(path));
+            }
+            "--tax-rate" => {
+                let value = args.next().ok_or("--tax-rate requires a percentage")?;
+                match value.parse::<f64>() {
+                    Ok(rate) if (0.0..=100.0).contains(&rate) => options.tax_rate = Some(rate),
+                    _ => return Err(format!("invalid tax rate '{}'", value)),
+                }
+            }
+            "--tax-treatment" => {
+                let value = args.next().ok_or("--tax-treatment requires a value")?;
+                options.tax_treatment = match value.as_str() {
+                    "annual" => TaxTreatment::Annual,
+                    "withdrawal" => TaxTreatment::AtWithdrawal,
+                    _ => return Err(format!("unknown tax treatment '{}'", value)),
+                };
+            }
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+    Ok(options)
+}
 
 fn prompt_for_float(prompt: &str) -> f64 {
     loop {
@@ -38,23 +127,182 @@ fn compute_simple_interest(principal: f64, rate: f64, years: f64) -> f64 {
     principal * (rate / 100.0) * years
 }
 
+fn compute_interest(principal: f64, rate: f64, years: f64, compounding: Compounding) -> f64 {
+    match compounding {
+        Compounding::Simple => compute_simple_interest(principal, rate, years),
+        Compounding::Annual => future_value(principal, rate, years, 1.0) - principal,
+        Compounding::Monthly => future_value(principal, rate, years, 12.0) - principal,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct ScheduleRow {
+    year: u32,
+    interest: f64,
+    total_interest: f64,
+    balance: f64,
+}
+
+fn build_schedule(
+    principal: f64,
+    rate: f64,
+    years: f64,
+    compounding: Compounding,
+) -> Vec<ScheduleRow> {
+    let mut schedule = Vec::new();
+    let mut previous_interest = 0.0;
+    for year in 1..=years as u32 {
+        let total_interest = compute_interest(principal, rate, year as f64, compounding);
+        schedule.push(ScheduleRow {
+            year,
+            interest: total_interest - previous_interest,
+            total_interest,
+            balance: principal + total_interest,
+        });
+        previous_interest = total_interest;
+    }
+    schedule
+}
+
+#[derive(Debug, PartialEq)]
+struct NetRow {
+    year: u32,
+    gross_interest: f64,
+    tax: f64,
+    net_interest: f64,
+    /// What the investment is worth after tax if withdrawn at the end of the year.
+    net_value: f64,
+}
+
+fn build_net_schedule(
+    principal: f64,
+    rate: f64,
+    years: f64,
+    compounding: Compounding,
+    tax_rate: f64,
+    treatment: TaxTreatment,
+) -> Vec<NetRow> {
+    let tax_fraction = tax_rate / 100.0;
+    let mut schedule = Vec::new();
+    match treatment {
+        TaxTreatment::Annual => {
+            let mut balance = principal;
+            for year in 1..=years as u32 {
+                // Simple interest is always earned on the principal; compounding interest is
+                // earned on whatever remains after the previous years' taxes.
+                let gross_interest = match compounding {
+                    Compounding::Simple => compute_simple_interest(principal, rate, 1.0),
+                    _ => compute_interest(balance, rate, 1.0, compounding),
+                };
+                let tax = gross_interest * tax_fraction;
+                balance += gross_interest - tax;
+                schedule.push(NetRow {
+                    year,
+                    gross_interest,
+                    tax,
+                    net_interest: gross_interest - tax,
+                    net_value: balance,
+                });
+            }
+        }
+        TaxTreatment::AtWithdrawal => {
+            let mut previous_tax = 0.0;
+            for row in build_schedule(principal, rate, years, compounding) {
+                let total_tax = row.total_interest * tax_fraction;
+                let tax = total_tax - previous_tax;
+                schedule.push(NetRow {
+                    year: row.year,
+                    gross_interest: row.interest,
+                    tax,
+                    net_interest: row.interest - tax,
+                    net_value: row.balance - total_tax,
+                });
+                previous_tax = total_tax;
+            }
+        }
+    }
+    schedule
+}
+
+fn write_schedule_csv(
+    writer: &mut impl Write,
+    schedule: &[ScheduleRow],
+) -> Result<(), Box<dyn std::error::Error>> {
+    writeln!(writer, "year,interest,total_interest,balance")?;
+    for row in schedule {
+        writeln!(
+            writer,
+            "{},{:.2},{:.2},{:.2}",
+            row.year, row.interest, row.total_interest, row.balance
+        )?;
+    }
+    Ok(())
+}
+
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!(
+                "Usage: e12 [--compounding simple|annual|monthly] [--csv <path>] \
+                 [--tax-rate <percent>] [--tax-treatment annual|withdrawal]"
+            );
+            std::process::exit(1);
+        }
+    };
+
     let principal = prompt_for_float("Enter the principal amount:");
     let rate = prompt_for_float("Enter the annual interest rate (as a percentage):");
     let years = prompt_for_float("Enter the number of years:");
     println!(
-        "After {} years at at {:.2}%, the investment will be worth: ${:.2}.",
+        "After {} years at {:.2}% ({}), the investment will be worth: ${:.2}.",
         years,
         rate,
-        principal + compute_simple_interest(principal, rate, years)
+        options.compounding.description(),
+        principal + compute_interest(principal, rate, years, options.compounding)
     );
 
-    let yearly_simple_interest: Vec<f64> = (1..=years as u32)
-        .map(|year| compute_simple_interest(principal, rate, year as f64))
-        .collect();
+    let schedule = build_schedule(principal, rate, years, options.compounding);
     println!("Here's the breakdown of interest earned each year:");
-    for (year, interest) in yearly_simple_interest.iter().enumerate() {
-        println!("Year {}: ${:.2}", year + 1, interest);
+    for row in &schedule {
+        println!(
+            "Year {}: ${:.2} (total interest ${:.2}, balance ${:.2})",
+            row.year, row.interest, row.total_interest, row.balance
+        );
+    }
+
+    if let Some(tax_rate) = options.tax_rate {
+        let net_schedule = build_net_schedule(
+            principal,
+            rate,
+            years,
+            options.compounding,
+            tax_rate,
+            options.tax_treatment,
+        );
+        println!(
+            "With a {:.2}% tax on interest ({}):",
+            tax_rate,
+            options.tax_treatment.description()
+        );
+        for row in &net_schedule {
+            println!(
+                "Year {}: gross ${:.2}, tax ${:.2}, net ${:.2} (net value ${:.2})",
+                row.year, row.gross_interest, row.tax, row.net_interest, row.net_value
+            );
+        }
+    }
+
+    if let Some(path) = options.csv_file {
+        let result = std::fs::File::create(&path)
+            .map_err(|e| e.into())
+            .and_then(|mut file| write_schedule_csv(&mut file, &schedule));
+        match result {
+            Ok(()) => println!("Schedule written to {}.", path.display()),
+            Err(e) => eprintln!("Error: failed to write {}: {}", path.display(), e),
+        }
     }
 }
 
@@ -83,4 +331,105 @@ mod tests {
         assert_eq!(compute_simple_interest(100000.0, 2.5, 10.0), 25000.0); // $100,000 at 2.5% for 10 years = $25,000
         assert_eq!(compute_simple_interest(50000.0, 7.5, 5.0), 18750.0); // $50,000 at 7.5% for 5 years = $18,750
     }
+
+    #[test]
+    fn compute_interest_compounds_by_frequency() {
+        assert_eq!(
+            compute_interest(1000.0, 5.0, 2.0, Compounding::Simple),
+            100.0
+        );
+        // Expected: 1000 * 1.05^2 - 1000 = 102.50
+        assert!((compute_interest(1000.0, 5.0, 2.0, Compounding::Annual) - 102.5).abs() < 1e-9);
+        // Expected: 1000 * (1 + 0.05/12)^24 - 1000 ≈ 104.94
+        assert!((compute_interest(1000.0, 5.0, 2.0, Compounding::Monthly) - 104.94).abs() < 0.01);
+    }
+
+    #[test]
+    fn build_schedule_tracks_yearly_interest() {
+        let schedule = build_schedule(1000.0, 10.0, 2.0, Compounding::Annual);
+        assert_eq!(schedule.len(), 2);
+        assert!((schedule[0].interest - 100.0).abs() < 1e-9);
+        assert!((schedule[1].interest - 110.0).abs() < 1e-9);
+        assert!((schedule[1].balance - 1210.0).abs() < 1e-9);
+
+        let mut csv = Vec::new();
+        write_schedule_csv(&mut csv, &schedule).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "year,interest,total_interest,balance\n1,100.00,100.00,1100.00\n2,110.00,210.00,1210.00\n"
+        );
+    }
+
+    #[test]
+    fn parse_args_reads_compounding_and_csv() {
+        let args: Vec<String> = ["--compounding", "monthly", "--csv", "schedule.csv"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            parse_args(&args),
+            Ok(Options {
+                compounding: Compounding::Monthly,
+                csv_file: Some(PathBuf::from("schedule.csv")),
+                ..Default::default()
+            })
+        );
+        assert!(parse_args(&["--compounding".to_string(), "daily".to_string()]).is_err());
+    }
+
+    #[test]
+    fn parse_args_reads_tax_options() {
+        let args: Vec<String> = ["--tax-rate", "25", "--tax-treatment", "withdrawal"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let options = parse_args(&args).unwrap();
+        assert_eq!(options.tax_rate, Some(25.0));
+        assert_eq!(options.tax_treatment, TaxTreatment::AtWithdrawal);
+        assert!(parse_args(&["--tax-rate".to_string(), "120".to_string()]).is_err());
+    }
+
+    #[test]
+    fn build_net_schedule_taxes_interest_annually() {
+        let schedule = build_net_schedule(
+            1000.0,
+            10.0,
+            2.0,
+            Compounding::Annual,
+            20.0,
+            TaxTreatment::Annual,
+        );
+        assert!((schedule[0].tax - 20.0).abs() < 1e-9);
+        assert!((schedule[0].net_value - 1080.0).abs() < 1e-9);
+        // The second year compounds on the after-tax balance: 1080 * 10% = 108.
+        assert!((schedule[1].gross_interest - 108.0).abs() < 1e-9);
+        assert!((schedule[1].net_value - 1166.4).abs() < 1e-9);
+
+        let simple = build_net_schedule(
+            1000.0,
+            10.0,
+            2.0,
+            Compounding::Simple,
+            20.0,
+            TaxTreatment::Annual,
+        );
+        assert!((simple[1].net_value - 1160.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn build_net_schedule_defers_tax_until_withdrawal() {
+        let schedule = build_net_schedule(
+            1000.0,
+            10.0,
+            2.0,
+            Compounding::Annual,
+            20.0,
+            TaxTreatment::AtWithdrawal,
+        );
+        assert!((schedule[1].gross_interest - 110.0).abs() < 1e-9);
+        // 210 of total interest, taxed once: 1000 + 210 * 0.8.
+        assert!((schedule[1].net_value - 1168.0).abs() < 1e-9);
+        let total_tax: f64 = schedule.iter().map(|row| row.tax).sum();
+        assert!((total_tax - 42.0).abs() < 1e-9);
+    }
 }
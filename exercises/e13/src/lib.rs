@@ -0,0 +1,65 @@
+//! # Finance Library
+//!
+//! Compound interest formulas shared by the exercises that deal with investment growth.
+//! Rates are given in percent, e.g. `5.0` for 5%.
+
+/// Returns the future value of `principal` compounded `compound_frequency` times per year,
+/// using the standard formula P(1 + r/n)^(nt).
+pub fn future_value(principal: f64, rate: f64, years: f64, compound_frequency: f64) -> f64 {
+    principal * (1.0 + rate / (100.0 * compound_frequency)).powf(compound_frequency * years)
+}
+
+/// Returns the effective annual rate (APY), in percent, implied by a nominal `rate`
+/// compounded `compound_frequency` times per year: (1 + r/n)^n - 1.
+pub fn effective_annual_rate(rate: f64, compound_frequency: f64) -> Option<f64> {
+    if compound_frequency <= 0.0 {
+        return None;
+    }
+    Some(((1.0 + rate / (100.0 * compound_frequency)).powf(compound_frequency) - 1.0) * 100.0)
+}
+
+/// Estimates the number of years needed to double an investment using the rule of 72.
+pub fn rule_of_72(rate: f64) -> Option<f64> {
+    if rate <= 0.0 {
+        return None;
+    }
+    Some(72.0 / rate)
+}
+
+/// Returns the exact number of years needed to double an investment: ln 2 / (n ln(1 + r/n)).
+pub fn doubling_time(rate: f64, compound_frequency: f64) -> Option<f64> {
+    if rate <= 0.0 || compound_frequency <= 0.0 {
+        return None;
+    }
+    Some(2f64.ln() / (compound_frequency * (1.0 + rate / (100.0 * compound_frequency)).ln()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn effective_annual_rate_accounts_for_compounding() {
+        // Annual compounding leaves the rate unchanged
+        assert!((effective_annual_rate(5.0, 1.0).unwrap() - 5.0).abs() < 1e-9);
+        // Expected: (1 + 0.05/12)^12 - 1 ≈ 5.1162%
+        assert!((effective_annual_rate(5.0, 12.0).unwrap() - 5.1162).abs() < 0.0001);
+        assert_eq!(effective_annual_rate(5.0, 0.0), None);
+    }
+
+    #[test]
+    fn rule_of_72_estimates_doubling_time() {
+        assert_eq!(rule_of_72(8.0), Some(9.0));
+        assert_eq!(rule_of_72(0.0), None);
+        assert_eq!(rule_of_72(-1.0), None);
+    }
+
+    #[test]
+    fn doubling_time_matches_future_value() {
+        let years = doubling_time(8.0, 4.0).unwrap();
+        assert!((future_value(1000.0, 8.0, years, 4.0) - 2000.0).abs() < 1e-6);
+        // The rule of 72 is a close approximation at moderate rates
+        assert!((years - rule_of_72(8.0).unwrap()).abs() < 0.5);
+        assert_eq!(doubling_time(0.0, 12.0), None);
+    }
+}
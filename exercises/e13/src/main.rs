@@ -10,6 +10,9 @@
 //! - **Compound Interest Formula**: Uses the standard formula P(1 + r/n)^(nt)
 //! - **Input Validation**: Gracefully handles invalid numeric inputs
 //! - **Edge Case Handling**: Properly manages zero values for all parameters
+//! - **Effective Annual Rate**: Displays the APY implied by the rate and compounding frequency
+//! - **Doubling Time**: Estimates how long the investment takes to double using the rule of 72
+use e13::{doubling_time, effective_annual_rate, future_value, rule_of_72};
 use eframe::egui::{self};
 
 #[derive(Debug, Default)]
@@ -31,10 +34,14 @@ impl Investment {
             return None;
         }
 
-        Some(
-            principal
-                * (1.0 + rate / (100.0 * compound_frequency)).powf(compound_frequency * years),
-        )
+        Some(future_value(principal, rate, years, compound_frequency))
+    }
+
+    fn rate_and_frequency(&self) -> Option<(f64, f64)> {
+        Some((
+            self.rate.parse().ok()?,
+            self.compound_frequency.parse().ok()?,
+        ))
     }
 }
 
@@ -55,6 +62,20 @@ impl eframe::App for Investment {
                     "${} invested at {}% for {} years compounded {} times per year is ${:.2}.",
                     self.principal, self.rate, self.years, self.compound_frequency, result
                 ));
+
+                if let Some((rate, compound_frequency)) = self.rate_and_frequency() {
+                    if let Some(apy) = effective_annual_rate(rate, compound_frequency) {
+                        ui.label(format!("Effective annual rate (APY): {:.3}%", apy));
+                    }
+                    if let (Some(estimate), Some(exact)) =
+                        (rule_of_72(rate), doubling_time(rate, compound_frequency))
+                    {
+                        ui.label(format!(
+                            "Doubling time: about {:.1} years by the rule of 72 ({:.1} years exactly).",
+                            estimate, exact
+                        ));
+                    }
+                }
             } else {
                 ui.label("Please enter valid numbers for all fields.");
             }
@@ -64,7 +85,7 @@ impl eframe::App for Investment {
 
 fn main() -> eframe::Result {
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 250.0]),
+        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 300.0]),
         ..Default::default()
     };
     eframe::run_native(
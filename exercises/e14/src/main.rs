@@ -7,6 +7,8 @@
 //! - **State Recognition**: Identifies all 50 US states by full name or abbreviation
 //! - **Tax Calculation**: Applies appropriate sales tax based on the state (Wisconsin: 5.5%)
 //! - **User Interaction**: Prompts for order amount and state with input validation
+//! - **Rounding Modes**: Rounds tax half-up or with banker's rounding, per line or on the total
+//! - **Receipt Output**: Prints a receipt block with amount, tax rate, tax, and total
 use once_cell::sync::Lazy;
 use std::collections::HashSet;
 use std::io::Write;
@@ -237,9 +239,9 @@ static STATE_NAMES: Lazy<HashSet<State>> = Lazy::new(|| {
     set
 });
 
-fn prompt_for_float(prompt: &str) -> f64 {
+fn prompt_for_state() -> State {
     loop {
-        print!("{prompt} ");
+        print!("What is the state? ");
         let mut input = String::new();
         if let Err(e) = std::io::stdout().flush() {
             eprintln!("Error: {}", e);
@@ -251,21 +253,176 @@ fn prompt_for_float(prompt: &str) -> f64 {
             continue;
         }
 
-        if let Ok(value) = input.trim().parse::<f64>() {
-            if value < 0.0 {
-                println!("Please enter a non-negative number.");
-                continue;
+        let input = input.trim().to_lowercase();
+        for state in STATE_NAMES.iter() {
+            if state.name.to_lowercase() == input || state.abbreviation.to_lowercase() == input {
+                return state.clone();
             }
-            return value;
-        } else {
-            println!("Invalid input. Please enter a valid number.");
         }
+        println!("Invalid state name or abbreviation. Please try again.");
     }
 }
 
-fn prompt_for_state() -> State {
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RoundingMode {
+    HalfUp,
+    Bankers,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RoundingLevel {
+    Line,
+    Total,
+}
+
+#[derive(Debug, PartialEq)]
+struct ReceiptOptions {
+    mode: RoundingMode,
+    level: RoundingLevel,
+    tax_id: Option<String>,
+}
+
+impl Default for ReceiptOptions {
+    fn default() -> Self {
+        Self {
+            mode: RoundingMode::HalfUp,
+            level: RoundingLevel::Total,
+            tax_id: None,
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<ReceiptOptions, String> {
+    let mut options = ReceiptOptions::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || {
+            iter.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--rounding" => {
+                options.mode = match value()?.as_str() {
+                    "half-up" => RoundingMode::HalfUp,
+                    "bankers" => RoundingMode::Bankers,
+                    other => return Err(format!("Unknown rounding mode: {}", other)),
+                }
+            }
+            "--round-at" => {
+                options.level = match value()?.as_str() {
+                    "line" => RoundingLevel::Line,
+                    "total" => RoundingLevel::Total,
+                    other => return Err(format!("Unknown rounding level: {}", other)),
+                }
+            }
+            "--tax-id" => options.tax_id = Some(value()?.clone()),
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+    Ok(options)
+}
+
+/// Returns the state's sales tax rate in basis points (1/100th of a percent).
+fn tax_rate_basis_points(state: &State) -> i64 {
+    match state.abbreviation.as_str() {
+        "WI" => 550, // Wisconsin
+        _ => 0,      // Default tax rate for other states
+    }
+}
+
+/// Divides a non-negative `numerator` by `denominator`, rounding ties according to `mode`.
+fn round_div(numerator: i64, denominator: i64, mode: RoundingMode) -> i64 {
+    let quotient = numerator / denominator;
+    let twice_remainder = 2 * (numerator % denominator);
+    let round_up = match mode {
+        RoundingMode::HalfUp => twice_remainder >= denominator,
+        RoundingMode::Bankers => {
+            twice_remainder > denominator || (twice_remainder == denominator && quotient % 2 == 1)
+        }
+    };
+    if round_up { quotient + 1 } else { quotient }
+}
+
+#[derive(Debug, PartialEq)]
+struct Receipt {
+    subtotal_cents: i64,
+    tax_cents: i64,
+    rate_basis_points: i64,
+}
+
+impl Receipt {
+    fn total_cents(&self) -> i64 {
+        self.subtotal_cents + self.tax_cents
+    }
+}
+
+fn calculate_receipt(
+    line_cents: &[i64],
+    rate_basis_points: i64,
+    options: &ReceiptOptions,
+) -> Receipt {
+    let subtotal_cents = line_cents.iter().sum();
+    let tax_cents = match options.level {
+        RoundingLevel::Line => line_cents
+            .iter()
+            .map(|cents| round_div(cents * rate_basis_points, 10_000, options.mode))
+            .sum(),
+        RoundingLevel::Total => round_div(subtotal_cents * rate_basis_points, 10_000, options.mode),
+    };
+    Receipt {
+        subtotal_cents,
+        tax_cents,
+        rate_basis_points,
+    }
+}
+
+fn format_cents(cents: i64) -> String {
+    format!("${}.{:02}", cents / 100, cents % 100)
+}
+
+fn print_receipt(receipt: &Receipt, state: &State, options: &ReceiptOptions) {
+    let mode = match options.mode {
+        RoundingMode::HalfUp => "half-up",
+        RoundingMode::Bankers => "banker's",
+    };
+    let level = match options.level {
+        RoundingLevel::Line => "per line",
+        RoundingLevel::Total => "on total",
+    };
+    let rate = format!(
+        "{}.{:02}%",
+        receipt.rate_basis_points / 100,
+        receipt.rate_basis_points % 100
+    );
+
+    println!("{}", "-".repeat(32));
+    if let Some(tax_id) = &options.tax_id {
+        println!("{:<10}{:>22}", "Tax ID:", tax_id);
+    }
+    println!("{:<10}{:>22}", "State:", state.name);
+    println!(
+        "{:<10}{:>22}",
+        "Amount:",
+        format_cents(receipt.subtotal_cents)
+    );
+    println!("{:<10}{:>22}", "Tax rate:", rate);
+    println!("{:<10}{:>22}", "Tax:", format_cents(receipt.tax_cents));
+    println!(
+        "{:<10}{:>22}",
+        "Total:",
+        format_cents(receipt.total_cents())
+    );
+    println!("{:<10}{:>22}", "Rounding:", format!("{} {}", mode, level));
+    println!("{}", "-".repeat(32));
+}
+
+fn prompt_for_line_items() -> Vec<i64> {
+    let mut line_cents = Vec::new();
     loop {
-        print!("What is the state? ");
+        print!(
+            "Enter item {} amount (blank to finish): ",
+            line_cents.len() + 1
+        );
         let mut input = String::new();
         if let Err(e) = std::io::stdout().flush() {
             eprintln!("Error: {}", e);
@@ -277,75 +434,154 @@ fn prompt_for_state() -> State {
             continue;
         }
 
-        let input = input.trim().to_lowercase();
-        for state in STATE_NAMES.iter() {
-            if state.name.to_lowercase() == input || state.abbreviation.to_lowercase() == input {
-                return state.clone();
+        let input = input.trim();
+        if input.is_empty() {
+            if line_cents.is_empty() {
+                println!("Please enter at least one item.");
+                continue;
             }
+            return line_cents;
+        }
+        match input.parse::<f64>() {
+            Ok(value) if value >= 0.0 => line_cents.push((value * 100.0).round() as i64),
+            Ok(_) => println!("Please enter a non-negative number."),
+            Err(_) => println!("Invalid input. Please enter a valid number."),
         }
-        println!("Invalid state name or abbreviation. Please try again.");
     }
 }
 
-fn calculate_total(order_amount: f64, state: &State) -> f64 {
-    let tax_rate = match state.abbreviation.as_str() {
-        "WI" => 0.055, // Wisconsin
-        _ => 0.0,      // Default tax rate for other states
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!(
+                "Usage: e14 [--rounding half-up|bankers] [--round-at line|total] [--tax-id ID]"
+            );
+            std::process::exit(1);
+        }
     };
-    let tax_amount = order_amount * tax_rate;
-    order_amount + tax_amount
-}
 
-fn main() {
-    let order_amount = prompt_for_float("What is the order amount?");
+    let line_cents = prompt_for_line_items();
     let state = prompt_for_state();
-    println!(
-        "The total is ${:.2}.",
-        calculate_total(order_amount, &state)
-    );
+    let receipt = calculate_receipt(&line_cents, tax_rate_basis_points(&state), &options);
+    println!("The total is {}.", format_cents(receipt.total_cents()));
+    print_receipt(&receipt, &state, &options);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn total_cents(line_cents: &[i64], state: &State) -> i64 {
+        let rate = tax_rate_basis_points(state);
+        calculate_receipt(line_cents, rate, &ReceiptOptions::default()).total_cents()
+    }
+
     #[test]
-    fn calculate_total_applies_wisconsin_tax() {
+    fn calculate_receipt_total_applies_wisconsin_tax() {
         let wi_state = State {
             name: String::from("Wisconsin"),
             abbreviation: String::from("WI"),
         };
-        assert_eq!(calculate_total(100.0, &wi_state), 105.5); // $100 with 5.5% tax
-        assert_eq!(calculate_total(50.0, &wi_state), 52.75); // $50 with 5.5% tax
+        assert_eq!(total_cents(&[10_000], &wi_state), 10_550); // $100 with 5.5% tax
+        assert_eq!(total_cents(&[5_000], &wi_state), 5_275); // $50 with 5.5% tax
     }
 
     #[test]
-    fn calculate_total_no_tax_for_other_states() {
+    fn calculate_receipt_total_no_tax_for_other_states() {
         let ca_state = State {
             name: String::from("California"),
             abbreviation: String::from("CA"),
         };
-        assert_eq!(calculate_total(100.0, &ca_state), 100.0); // No tax for California
+        assert_eq!(total_cents(&[10_000], &ca_state), 10_000); // No tax for California
 
         let tx_state = State {
             name: String::from("Texas"),
             abbreviation: String::from("TX"),
         };
-        assert_eq!(calculate_total(75.0, &tx_state), 75.0); // No tax for Texas
+        assert_eq!(total_cents(&[7_500], &tx_state), 7_500); // No tax for Texas
     }
 
     #[test]
-    fn calculate_total_handles_zero_values() {
+    fn calculate_receipt_total_handles_zero_values() {
         let wi_state = State {
             name: String::from("Wisconsin"),
             abbreviation: String::from("WI"),
         };
-        assert_eq!(calculate_total(0.0, &wi_state), 0.0); // $0 order should result in $0 total
+        assert_eq!(total_cents(&[0], &wi_state), 0); // $0 order should result in $0 total
 
         let ny_state = State {
             name: String::from("New York"),
             abbreviation: String::from("NY"),
         };
-        assert_eq!(calculate_total(0.0, &ny_state), 0.0); // $0 order should result in $0 total
+        assert_eq!(total_cents(&[0], &ny_state), 0); // $0 order should result in $0 total
+    }
+
+    #[test]
+    fn round_div_rounds_ties_by_mode() {
+        assert_eq!(round_div(25, 10, RoundingMode::HalfUp), 3);
+        assert_eq!(round_div(25, 10, RoundingMode::Bankers), 2);
+        assert_eq!(round_div(35, 10, RoundingMode::Bankers), 4);
+        assert_eq!(round_div(26, 10, RoundingMode::Bankers), 3);
+        assert_eq!(round_div(24, 10, RoundingMode::HalfUp), 2);
+    }
+
+    #[test]
+    fn calculate_receipt_rounds_per_line_or_on_total() {
+        // Each $0.10 line carries $0.0055 tax, which rounds to a cent per line.
+        let lines = [10, 10, 10, 10];
+        let line_level = ReceiptOptions {
+            level: RoundingLevel::Line,
+            ..Default::default()
+        };
+        assert_eq!(calculate_receipt(&lines, 550, &line_level).tax_cents, 4);
+
+        let total_level = ReceiptOptions::default();
+        let receipt = calculate_receipt(&lines, 550, &total_level);
+        assert_eq!(receipt.tax_cents, 2);
+        assert_eq!(receipt.total_cents(), 42);
+    }
+
+    #[test]
+    fn calculate_receipt_applies_bankers_rounding() {
+        // $1.00 at 5.5% is exactly 5.5 cents.
+        let bankers = ReceiptOptions {
+            mode: RoundingMode::Bankers,
+            ..Default::default()
+        };
+        assert_eq!(calculate_receipt(&[100], 550, &bankers).tax_cents, 6);
+        // $3.00 at 5.5% is exactly 16.5 cents.
+        assert_eq!(calculate_receipt(&[300], 550, &bankers).tax_cents, 16);
+        assert_eq!(
+            calculate_receipt(&[300], 550, &ReceiptOptions::default()).tax_cents,
+            17
+        );
+    }
+
+    #[test]
+    fn parse_args_reads_receipt_options() {
+        let args: Vec<String> = [
+            "--rounding",
+            "bankers",
+            "--round-at",
+            "line",
+            "--tax-id",
+            "WI-123",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert_eq!(
+            parse_args(&args).unwrap(),
+            ReceiptOptions {
+                mode: RoundingMode::Bankers,
+                level: RoundingLevel::Line,
+                tax_id: Some("WI-123".to_string()),
+            }
+        );
+        assert!(parse_args(&["--rounding".to_string()]).is_err());
+        assert!(parse_args(&["--round-at".to_string(), "item".to_string()]).is_err());
     }
 }
@@ -7,32 +7,70 @@
 //!
 //! - **Secure Password Storage**: Uses bcrypt hashing to protect user passwords
 //! - **Password Masking**: Hides password input during entry
+//! - **Role-Based Access**: Each user has a role, and admin-only commands are gated on the
+//!   authenticated user's role
 //!
 //! The system verifies user credentials against a pre-defined set of bcrypt-hashed
-//! passwords stored in memory.
-use bcrypt::verify;
+//! passwords stored in memory. Once logged in, users can enter commands; admins may also
+//! list users and reset passwords.
+use bcrypt::{DEFAULT_COST, hash, verify};
 use once_cell::sync::Lazy;
 use rpassword::read_password;
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
 
-static USERS: Lazy<HashMap<String, String>> = Lazy::new(|| {
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Role {
+    Admin,
+    User,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Role::Admin => write!(f, "admin"),
+            Role::User => write!(f, "user"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UserRecord {
+    password_hash: String,
+    role: Role,
+}
+
+static USERS: Lazy<Mutex<HashMap<String, UserRecord>>> = Lazy::new(|| {
     let mut m = HashMap::new();
     // password: hello
     m.insert(
         "alice".to_string(),
-        "$2b$12$2jP33spRZpG0cuc/ZqtHs.zkIFnk5nvlkYJXm71Aoa1GXGcOl39z2".to_string(),
+        UserRecord {
+            password_hash: "$2b$12$2jP33spRZpG0cuc/ZqtHs.zkIFnk5nvlkYJXm71Aoa1GXGcOl39z2"
+                .to_string(),
+            role: Role::Admin,
+        },
     );
     // password: world
     m.insert(
         "bob".to_string(),
-        "$2b$12$oeKean9q91hYXzHNBNMah.PKgS3.HMau4sse2UgzaS1bgvY5aYJwK".to_string(),
+        UserRecord {
+            password_hash: "$2b$12$oeKean9q91hYXzHNBNMah.PKgS3.HMau4sse2UgzaS1bgvY5aYJwK"
+                .to_string(),
+            role: Role::User,
+        },
     );
     // password: qwerty
     m.insert(
         "tom".to_string(),
-        "$2b$12$MKPGObt5PmpFPlj5tEjKfeiQvRW5Jo0pmcNdWGg5iTBoKpkXvSfxm".to_string(),
+        UserRecord {
+            password_hash: "$2b$12$MKPGObt5PmpFPlj5tEjKfeiQvRW5Jo0pmcNdWGg5iTBoKpkXvSfxm"
+                .to_string(),
+            role: Role::User,
+        },
     );
-    m
+    Mutex::new(m)
 });
 
 fn get_username() -> String {
@@ -49,20 +87,105 @@ fn get_password() -> String {
     read_password().expect("Failed to read password")
 }
 
+#[derive(Debug, PartialEq)]
+enum Command {
+    List,
+    Reset(String),
+    Quit,
+}
+
+fn parse_command(input: &str) -> Result<Command, String> {
+    let mut parts = input.split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some("quit"), _) => Ok(Command::Quit),
+        (Some("list"), _) => Ok(Command::List),
+        (Some("reset"), Some(username)) => Ok(Command::Reset(username.to_string())),
+        (Some("reset"), None) => Err("Usage: reset <user>".to_string()),
+        _ => Err(format!("Unknown command: {}", input)),
+    }
+}
+
+/// Returns whether a user with `role` may run `command`.
+fn authorize(role: Role, command: &Command) -> bool {
+    match command {
+        Command::Quit => true,
+        Command::List | Command::Reset(_) => role == Role::Admin,
+    }
+}
+
+/// Returns `None` once stdin is closed or can no longer be read.
+fn get_command() -> Option<String> {
+    println!("Enter a command (list, reset <user>, quit):");
+    let mut command = String::new();
+    match std::io::stdin().read_line(&mut command) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(command.trim().to_string()),
+    }
+}
+
+fn list_users(users: &HashMap<String, UserRecord>) {
+    let mut names: Vec<&String> = users.keys().collect();
+    names.sort();
+    for name in names {
+        println!("{} ({})", name, users[name].role);
+    }
+}
+
+fn reset_password(users: &mut HashMap<String, UserRecord>, username: &str) {
+    let Some(record) = users.get_mut(username) else {
+        println!("User not found: {}", username);
+        return;
+    };
+
+    println!("Enter the new password for {}:", username);
+    let password = read_password().expect("Failed to read password");
+    match hash(&password, DEFAULT_COST) {
+        Ok(password_hash) => {
+            record.password_hash = password_hash;
+            println!("Password reset for user: {}", username);
+        }
+        Err(e) => {
+            println!("Error hashing password: {}", e);
+        }
+    }
+}
+
+fn run_session(users: &mut HashMap<String, UserRecord>, role: Role) {
+    while let Some(input) = get_command() {
+        let command = match parse_command(&input) {
+            Ok(command) => command,
+            Err(e) => {
+                println!("{}", e);
+                continue;
+            }
+        };
+        if !authorize(role, &command) {
+            println!("Permission denied: admin role required.");
+            continue;
+        }
+        match command {
+            Command::Quit => break,
+            Command::List => list_users(users),
+            Command::Reset(username) => reset_password(users, &username),
+        }
+    }
+}
+
 fn main() {
     println!("Welcome to the secure login system!");
 
+    let mut users = USERS.lock().expect("User store lock poisoned");
     let username = get_username();
-    if !USERS.contains_key(&username) {
+    let Some(record) = users.get(&username).cloned() else {
         println!("User not found: {}", username);
         return;
-    }
+    };
     let password = get_password();
 
-    let hashed_password = USERS.get(&username).unwrap();
-    match verify(&password, hashed_password) {
+    match verify(&password, &record.password_hash) {
         Ok(true) => {
-            println!("Login successful for user: {}", username);
+            println!("Login successful for user: {} ({})", username, record.role);
+            run_session(&mut users, record.role);
         }
         Ok(false) => {
             println!("Invalid password for user: {}", username);
@@ -72,3 +195,34 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_command_recognizes_each_command() {
+        assert_eq!(parse_command("quit"), Ok(Command::Quit));
+        assert_eq!(parse_command("list"), Ok(Command::List));
+        assert_eq!(
+            parse_command("reset bob"),
+            Ok(Command::Reset("bob".to_string()))
+        );
+        assert!(parse_command("reset").is_err());
+        assert!(parse_command("delete bob").is_err());
+    }
+
+    #[test]
+    fn authorize_allows_admin_every_command() {
+        assert!(authorize(Role::Admin, &Command::Quit));
+        assert!(authorize(Role::Admin, &Command::List));
+        assert!(authorize(Role::Admin, &Command::Reset("bob".to_string())));
+    }
+
+    #[test]
+    fn authorize_limits_user_to_quit() {
+        assert!(authorize(Role::User, &Command::Quit));
+        assert!(!authorize(Role::User, &Command::List));
+        assert!(!authorize(Role::User, &Command::Reset("bob".to_string())));
+    }
+}
@@ -10,22 +10,135 @@
 //! - **Input Validation**: Ensures valid numeric inputs through robust error handling
 //! - **Legal Limit Check**: Determines if the calculated BAC is above or below the legal limit
 //! - **Time Consideration**: Factors in hours since last drink to account for alcohol metabolism
+//! - **Metric Units**: Accepts weight in kilograms and alcohol in grams or standard drinks
+//! - **Country Presets**: Selects the legal BAC limit (0.08, 0.05, 0.02, or zero tolerance)
+//!   from the user's country
+//! - **Drinking Sessions**: `--session` logs timestamped drinks over an evening, recomputes the
+//!   current BAC on demand, and persists the session so it survives restarts
+//! - **Metabolism Chart**: `--gui` plots the logged session's projected BAC over the next 12
+//!   hours, marking the legal limit and the estimated sober time
 //!
 //! The formula used is: BAC = (A × 5.14 / W × r) - (0.015 × H) where:
 //! - A = Total alcohol consumed in ounces
 //! - W = Body weight in pounds
 //! - r = Alcohol distribution ratio (0.73 for men, 0.66 for women)
 //! - H = Hours since last drink
+use chrono::{DateTime, Local, Utc};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
 use std::io::Write;
+use std::path::Path;
 
+const SESSION_FILE: &str = "exercises/e17/session.json";
+const METABOLISM_PER_HOUR: f64 = 0.015;
+const POUNDS_PER_KILOGRAM: f64 = 2.204_62;
+const GRAMS_PER_OUNCE: f64 = 23.34; // Grams of ethanol in one fluid ounce
+const GRAMS_PER_STANDARD_DRINK: f64 = 14.0;
+const PROJECTION_HOURS: f64 = 12.0;
+const PROJECTION_STEP_MINUTES: i64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 enum Gender {
     Male,
     Female,
 }
 
-fn prompt_for_weight() -> f64 {
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WeightUnit {
+    Pounds,
+    Kilograms,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AlcoholUnit {
+    Ounces,
+    Grams,
+    StandardDrinks,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum Country {
+    UnitedStates,
+    Australia,
+    Poland,
+    Hungary,
+}
+
+impl Country {
+    fn name(&self) -> &'static str {
+        match self {
+            Country::UnitedStates => "the United States",
+            Country::Australia => "Australia",
+            Country::Poland => "Poland",
+            Country::Hungary => "Hungary",
+        }
+    }
+
+    fn legal_limit(&self) -> f64 {
+        match self {
+            Country::UnitedStates => 0.08,
+            Country::Australia => 0.05,
+            Country::Poland => 0.02,
+            Country::Hungary => 0.0,
+        }
+    }
+}
+
+fn to_pounds(weight: f64, unit: WeightUnit) -> f64 {
+    match unit {
+        WeightUnit::Pounds => weight,
+        WeightUnit::Kilograms => weight * POUNDS_PER_KILOGRAM,
+    }
+}
+
+fn to_ounces(alcohol: f64, unit: AlcoholUnit) -> f64 {
+    match unit {
+        AlcoholUnit::Ounces => alcohol,
+        AlcoholUnit::Grams => alcohol / GRAMS_PER_OUNCE,
+        AlcoholUnit::StandardDrinks => alcohol * GRAMS_PER_STANDARD_DRINK / GRAMS_PER_OUNCE,
+    }
+}
+
+fn is_over_limit(bac: f64, limit: f64) -> bool {
+    // A zero tolerance limit is exceeded by any detectable amount of alcohol.
+    if limit == 0.0 {
+        bac > 0.0
+    } else {
+        bac >= limit
+    }
+}
+
+fn prompt_for_choice<T: Copy>(prompt: &str, choices: &[(&str, T)]) -> T {
+    loop {
+        print!("{prompt} ");
+        let mut input = String::new();
+        if let Err(e) = std::io::stdout().flush() {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+        if let Err(e) = std::io::stdin().read_line(&mut input) {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        let input = input.trim().to_uppercase();
+        match choices.iter().find(|(key, _)| *key == input) {
+            Some((_, choice)) => return *choice,
+            None => {
+                let keys: Vec<&str> = choices.iter().map(|(key, _)| *key).collect();
+                println!("Invalid input. Please enter one of: {}.", keys.join(", "));
+            }
+        }
+    }
+}
+
+fn prompt_for_weight(unit: WeightUnit) -> f64 {
+    let unit_name = match unit {
+        WeightUnit::Pounds => "pounds",
+        WeightUnit::Kilograms => "kilograms",
+    };
     loop {
-        print!("Enter the weight in pounds: ");
+        print!("Enter the weight in {}: ", unit_name);
         let mut input = String::new();
         if let Err(e) = std::io::stdout().flush() {
             eprintln!("Error: {}", e);
@@ -96,22 +209,443 @@ fn calculate_bac(weight_lb: f64, gender: Gender, hours: f64, total_alcohol_oz: f
         Gender::Male => 0.73,
         Gender::Female => 0.66,
     };
-    (total_alcohol_oz * 5.14 / weight_lb * r) - (0.015 * hours)
+    (total_alcohol_oz * 5.14 / weight_lb * r) - (METABOLISM_PER_HOUR * hours)
 }
 
-fn main() {
-    let weight_lb = prompt_for_weight();
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Drink {
+    time: DateTime<Utc>,
+    alcohol_oz: f64,
+}
+
+/// A drinking session persisted between runs of the program.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Session {
+    country: Country,
+    weight_lb: f64,
+    gender: Gender,
+    drinks: Vec<Drink>,
+}
+
+impl Session {
+    /// Projects the BAC at `at` by adding each drink as it was logged and metabolizing alcohol
+    /// at a constant rate in between, never dropping below zero.
+    fn bac_at(&self, at: DateTime<Utc>) -> f64 {
+        let mut bac: f64 = 0.0;
+        let mut last_time: Option<DateTime<Utc>> = None;
+        for drink in self.drinks.iter().filter(|d| d.time <= at) {
+            if let Some(last_time) = last_time {
+                bac = (bac - METABOLISM_PER_HOUR * hours_between(last_time, drink.time)).max(0.0);
+            }
+            bac += calculate_bac(self.weight_lb, self.gender, 0.0, drink.alcohol_oz);
+            last_time = Some(drink.time);
+        }
+        match last_time {
+            Some(last_time) => (bac - METABOLISM_PER_HOUR * hours_between(last_time, at)).max(0.0),
+            None => 0.0,
+        }
+    }
+}
+
+/// The BAC projected forward from a point in time, assuming no further drinks.
+#[derive(Debug, Clone, PartialEq)]
+struct Projection {
+    start: DateTime<Utc>,
+    /// `(hours after start, BAC)` samples.
+    points: Vec<(f64, f64)>,
+    within_limit_hours: f64,
+    sober_hours: f64,
+}
+
+impl Projection {
+    fn time_after(&self, hours: f64) -> DateTime<Utc> {
+        self.start + chrono::Duration::seconds((hours * 3600.0) as i64)
+    }
+}
+
+impl Session {
+    fn project(&self, start: DateTime<Utc>, hours: f64) -> Projection {
+        let samples = (hours * 60.0) as i64 / PROJECTION_STEP_MINUTES;
+        let points = (0..=samples)
+            .map(|i| {
+                let at = start + chrono::Duration::minutes(i * PROJECTION_STEP_MINUTES);
+                (hours_between(start, at), self.bac_at(at))
+            })
+            .collect();
+        let bac = self.bac_at(start);
+        Projection {
+            start,
+            points,
+            within_limit_hours: hours_until_within_limit(bac, self.country.legal_limit()),
+            sober_hours: hours_until_within_limit(bac, 0.0),
+        }
+    }
+}
+
+fn hours_between(start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+    (end - start).num_seconds() as f64 / 3600.0
+}
+
+/// Hours until `bac` metabolizes down to `limit` (or to zero for a zero tolerance limit).
+fn hours_until_within_limit(bac: f64, limit: f64) -> f64 {
+    if !is_over_limit(bac, limit) {
+        return 0.0;
+    }
+    (bac - limit) / METABOLISM_PER_HOUR
+}
+
+fn load_session(file_path: &Path) -> Result<Option<Session>, Box<dyn std::error::Error>> {
+    if !file_path.exists() {
+        return Ok(None);
+    }
+    let file = std::fs::File::open(file_path)?;
+    Ok(Some(serde_json::from_reader(file)?))
+}
+
+fn save_session(file_path: &Path, session: &Session) -> Result<(), Box<dyn std::error::Error>> {
+    persist::write_json(file_path, session)?;
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+enum SessionCommand {
+    Drink(f64),
+    Status,
+    End,
+    Quit,
+}
+
+fn parse_session_command(input: &str) -> Option<SessionCommand> {
+    let mut parts = input.split_whitespace();
+    let command = match parts.next()?.to_lowercase().as_str() {
+        "drink" => match parts.next() {
+            None => SessionCommand::Drink(1.0),
+            Some(amount) => SessionCommand::Drink(amount.parse().ok().filter(|&a| a > 0.0)?),
+        },
+        "status" => SessionCommand::Status,
+        "end" => SessionCommand::End,
+        "quit" => SessionCommand::Quit,
+        _ => return None,
+    };
+    parts.next().is_none().then_some(command)
+}
+
+fn prompt_for_profile() -> (Country, f64, Gender) {
+    let country = prompt_for_country();
+    let weight_unit = prompt_for_choice(
+        "Enter the weight unit (LB or KG):",
+        &[("LB", WeightUnit::Pounds), ("KG", WeightUnit::Kilograms)],
+    );
+    let weight_lb = to_pounds(prompt_for_weight(weight_unit), weight_unit);
     let gender = prompt_for_gender();
+    (country, weight_lb, gender)
+}
+
+fn prompt_for_country() -> Country {
+    prompt_for_choice(
+        "Select your country (US, AU, PL, HU):",
+        &[
+            ("US", Country::UnitedStates),
+            ("AU", Country::Australia),
+            ("PL", Country::Poland),
+            ("HU", Country::Hungary),
+        ],
+    )
+}
+
+fn limit_description(country: Country) -> String {
+    let limit = country.legal_limit();
+    if limit == 0.0 {
+        format!("the zero tolerance limit in {}", country.name())
+    } else {
+        format!("the legal limit of {:.2} in {}", limit, country.name())
+    }
+}
+
+fn print_session_status(session: &Session) {
+    let projection = session.project(Utc::now(), 0.0);
+    let bac = projection.points[0].1;
+    println!(
+        "{} drink(s) logged. Current projected BAC: {:.3}.",
+        session.drinks.len(),
+        bac
+    );
+    if is_over_limit(bac, session.country.legal_limit()) {
+        let hours = projection.within_limit_hours;
+        println!(
+            "You are over {}. Expect to be within it in {:.1} hour(s), around {}.",
+            limit_description(session.country),
+            hours,
+            projection
+                .time_after(hours)
+                .with_timezone(&Local)
+                .format("%H:%M")
+        );
+    } else {
+        println!("You are within {}.", limit_description(session.country));
+    }
+    if bac > 0.0 {
+        println!(
+            "Estimated sober around {}.",
+            projection
+                .time_after(projection.sober_hours)
+                .with_timezone(&Local)
+                .format("%H:%M")
+        );
+    }
+}
+
+fn run_session() {
+    let session_path = Path::new(SESSION_FILE);
+    let mut session = match load_session(session_path) {
+        Ok(Some(session)) => {
+            if let Some(first) = session.drinks.first() {
+                println!(
+                    "Resuming the session started at {}.",
+                    first.time.with_timezone(&Local).format("%H:%M")
+                );
+            }
+            session
+        }
+        Ok(None) => {
+            let (country, weight_lb, gender) = prompt_for_profile();
+            Session {
+                country,
+                weight_lb,
+                gender,
+                drinks: Vec::new(),
+            }
+        }
+        Err(e) => {
+            eprintln!("Error loading session: {}", e);
+            return;
+        }
+    };
+
+    println!("Commands: drink [standard drinks], status, end (clear session), quit");
+    loop {
+        print!("> ");
+        let mut input = String::new();
+        if let Err(e) = std::io::stdout().flush() {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+        match std::io::stdin().read_line(&mut input) {
+            Ok(0) => return,
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                continue;
+            }
+        }
+
+        match parse_session_command(&input) {
+            Some(SessionCommand::Drink(standard_drinks)) => {
+                session.drinks.push(Drink {
+                    time: Utc::now(),
+                    alcohol_oz: to_ounces(standard_drinks, AlcoholUnit::StandardDrinks),
+                });
+                if let Err(e) = save_session(session_path, &session) {
+                    eprintln!("Error saving session: {}", e);
+                }
+                print_session_status(&session);
+            }
+            Some(SessionCommand::Status) => print_session_status(&session),
+            Some(SessionCommand::End) => {
+                if let Err(e) = std::fs::remove_file(session_path)
+                    && e.kind() != std::io::ErrorKind::NotFound
+                {
+                    eprintln!("Error removing session: {}", e);
+                }
+                println!("Session ended.");
+                return;
+            }
+            Some(SessionCommand::Quit) => return,
+            None => println!("Unknown command. Use drink [amount], status, end, or quit."),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct MetabolismChart {
+    session: Option<Session>,
+    error: Option<String>,
+}
+
+impl MetabolismChart {
+    fn reload(&mut self) {
+        match load_session(Path::new(SESSION_FILE)) {
+            Ok(session) => {
+                self.session = session;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(format!("Error loading session: {}", e)),
+        }
+    }
+}
+
+/// Draws the projected BAC with the legal limit as a horizontal line and the sober time as a
+/// vertical one.
+fn plot_projection(ui: &mut egui::Ui, projection: &Projection, limit: f64) {
+    let size = egui::vec2(ui.available_width(), 200.0);
+    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let max_bac = projection
+        .points
+        .iter()
+        .map(|(_, bac)| *bac)
+        .fold(limit.max(0.01), f64::max)
+        * 1.1;
+    let to_screen = |hours: f64, bac: f64| {
+        egui::pos2(
+            rect.left() + (hours / PROJECTION_HOURS) as f32 * rect.width(),
+            rect.bottom() - (bac / max_bac) as f32 * rect.height(),
+        )
+    };
+
+    let painter = ui.painter_at(rect);
+    painter.rect_stroke(
+        rect,
+        0.0,
+        ui.visuals().widgets.noninteractive.bg_stroke,
+        egui::StrokeKind::Inside,
+    );
+    let text_color = ui.visuals().text_color();
+    let font = egui::FontId::proportional(12.0);
+    if limit > 0.0 {
+        let y = to_screen(0.0, limit).y;
+        painter.hline(
+            rect.x_range(),
+            y,
+            egui::Stroke::new(1.0, egui::Color32::RED),
+        );
+        painter.text(
+            egui::pos2(rect.right() - 4.0, y - 2.0),
+            egui::Align2::RIGHT_BOTTOM,
+            format!("Legal limit {:.2}", limit),
+            font.clone(),
+            egui::Color32::RED,
+        );
+    }
+    if projection.sober_hours > 0.0 && projection.sober_hours <= PROJECTION_HOURS {
+        let x = to_screen(projection.sober_hours, 0.0).x;
+        let sober_at = projection.time_after(projection.sober_hours);
+        painter.vline(
+            x,
+            rect.y_range(),
+            egui::Stroke::new(1.0, egui::Color32::from_rgb(50, 205, 50)),
+        );
+        painter.text(
+            egui::pos2(x + 4.0, rect.top() + 4.0),
+            egui::Align2::LEFT_TOP,
+            format!("Sober ~{}", sober_at.with_timezone(&Local).format("%H:%M")),
+            font.clone(),
+            egui::Color32::from_rgb(50, 205, 50),
+        );
+    }
+    let points = projection
+        .points
+        .iter()
+        .map(|&(hours, bac)| to_screen(hours, bac))
+        .collect();
+    painter.add(egui::Shape::line(
+        points,
+        egui::Stroke::new(2.0, ui.visuals().selection.bg_fill),
+    ));
+    for (hours, align) in [
+        (0.0, egui::Align2::LEFT_BOTTOM),
+        (PROJECTION_HOURS, egui::Align2::RIGHT_BOTTOM),
+    ] {
+        painter.text(
+            to_screen(hours, 0.0),
+            align,
+            projection
+                .time_after(hours)
+                .with_timezone(&Local)
+                .format("%H:%M")
+                .to_string(),
+            font.clone(),
+            text_color,
+        );
+    }
+}
+
+impl eframe::App for MetabolismChart {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if ui.button("Reload session").clicked() {
+                self.reload();
+            }
+            if let Some(error) = &self.error {
+                ui.colored_label(egui::Color32::RED, error);
+            }
+            let Some(session) = &self.session else {
+                ui.label("No drinks logged. Start a session with --session.");
+                return;
+            };
+
+            let projection = session.project(Utc::now(), PROJECTION_HOURS);
+            let limit = session.country.legal_limit();
+            ui.label(format!(
+                "{} drink(s) logged. Current projected BAC: {:.3}.",
+                session.drinks.len(),
+                projection.points[0].1
+            ));
+            plot_projection(ui, &projection, limit);
+        });
+        // Keep the chart anchored to the current time.
+        ctx.request_repaint_after(std::time::Duration::from_secs(60));
+    }
+}
+
+fn run_gui() -> eframe::Result {
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([500.0, 280.0]),
+        ..Default::default()
+    };
+    let mut chart = MetabolismChart::default();
+    chart.reload();
+    eframe::run_native(
+        "BAC Metabolism Chart",
+        options,
+        Box::new(|_| Ok(Box::new(chart))),
+    )
+}
+
+fn main() {
+    if std::env::args().skip(1).any(|arg| arg == "--session") {
+        run_session();
+        return;
+    }
+    if std::env::args().skip(1).any(|arg| arg == "--gui") {
+        if let Err(e) = run_gui() {
+            eprintln!("Error: {}", e);
+        }
+        return;
+    }
+
+    let (country, weight_lb, gender) = prompt_for_profile();
     let hours_since_last_drink =
         prompt_for_float("How many hours have passed since your last drink?");
-    let total_alcohol_oz = prompt_for_float("How many ounces of alcohol have you consumed?");
+    let alcohol_unit = prompt_for_choice(
+        "Enter the alcohol unit (OZ, G, or SD for standard drinks):",
+        &[
+            ("OZ", AlcoholUnit::Ounces),
+            ("G", AlcoholUnit::Grams),
+            ("SD", AlcoholUnit::StandardDrinks),
+        ],
+    );
+    let total_alcohol_oz = to_ounces(
+        prompt_for_float("How much alcohol have you consumed?"),
+        alcohol_unit,
+    );
 
-    const BAC_LIMIT: f64 = 0.08;
+    let limit = country.legal_limit();
     let bac = calculate_bac(weight_lb, gender, hours_since_last_drink, total_alcohol_oz);
-    if bac >= BAC_LIMIT {
-        println!("You are over the legal limit with a BAC of {:.2}.", bac);
+    let limit_desc = limit_description(country);
+    if is_over_limit(bac, limit) {
+        println!("You are over {} with a BAC of {:.2}.", limit_desc, bac);
     } else {
-        println!("You are within the legal limit with a BAC of {:.2}.", bac);
+        println!("You are within {} with a BAC of {:.2}.", limit_desc, bac);
     }
 }
 
@@ -176,4 +710,116 @@ mod tests {
 
         assert!((actual - expected).abs() < 0.001);
     }
+
+    #[test]
+    fn to_pounds_converts_kilograms() {
+        assert!((to_pounds(100.0, WeightUnit::Kilograms) - 220.462).abs() < 0.001);
+        assert_eq!(to_pounds(150.0, WeightUnit::Pounds), 150.0);
+    }
+
+    #[test]
+    fn to_ounces_converts_grams_and_standard_drinks() {
+        assert!((to_ounces(23.34, AlcoholUnit::Grams) - 1.0).abs() < 0.001);
+        assert!((to_ounces(5.0, AlcoholUnit::StandardDrinks) - 2.999).abs() < 0.001);
+        assert_eq!(to_ounces(2.0, AlcoholUnit::Ounces), 2.0);
+    }
+
+    #[test]
+    fn is_over_limit_uses_country_limit() {
+        assert!(!is_over_limit(0.06, Country::UnitedStates.legal_limit()));
+        assert!(is_over_limit(0.06, Country::Australia.legal_limit()));
+        assert!(is_over_limit(0.02, Country::Poland.legal_limit()));
+    }
+
+    #[test]
+    fn is_over_limit_handles_zero_tolerance() {
+        assert!(is_over_limit(0.001, Country::Hungary.legal_limit()));
+        assert!(!is_over_limit(0.0, Country::Hungary.legal_limit()));
+        assert!(!is_over_limit(-0.02, Country::Hungary.legal_limit()));
+    }
+
+    fn session(drinks: &[(i64, f64)]) -> (Session, DateTime<Utc>) {
+        let start = DateTime::parse_from_rfc3339("2025-01-01T20:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let session = Session {
+            country: Country::UnitedStates,
+            weight_lb: 160.0,
+            gender: Gender::Male,
+            drinks: drinks
+                .iter()
+                .map(|&(minutes, alcohol_oz)| Drink {
+                    time: start + chrono::Duration::minutes(minutes),
+                    alcohol_oz,
+                })
+                .collect(),
+        };
+        (session, start)
+    }
+
+    #[test]
+    fn session_bac_matches_single_drink_formula() {
+        let (session, start) = session(&[(0, 5.0)]);
+        let at = start + chrono::Duration::hours(2);
+        let expected = calculate_bac(160.0, Gender::Male, 2.0, 5.0);
+        assert!((session.bac_at(at) - expected).abs() < 1e-9);
+        assert_eq!(session.bac_at(start - chrono::Duration::hours(1)), 0.0);
+    }
+
+    #[test]
+    fn session_bac_does_not_metabolize_below_zero_between_drinks() {
+        // The first drink is fully metabolized long before the second one.
+        let (session, start) = session(&[(0, 0.5), (600, 0.5)]);
+        let single = calculate_bac(160.0, Gender::Male, 0.0, 0.5);
+        let at = start + chrono::Duration::minutes(600);
+        assert!((session.bac_at(at) - single).abs() < 1e-9);
+        assert_eq!(session.bac_at(at + chrono::Duration::hours(24)), 0.0);
+    }
+
+    #[test]
+    fn hours_until_within_limit_uses_metabolism_rate() {
+        assert_eq!(hours_until_within_limit(0.05, 0.08), 0.0);
+        assert!((hours_until_within_limit(0.03, 0.0) - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parse_session_command_reads_commands() {
+        assert_eq!(
+            parse_session_command("drink"),
+            Some(SessionCommand::Drink(1.0))
+        );
+        assert_eq!(
+            parse_session_command("DRINK 1.5\n"),
+            Some(SessionCommand::Drink(1.5))
+        );
+        assert_eq!(
+            parse_session_command("status"),
+            Some(SessionCommand::Status)
+        );
+        assert_eq!(parse_session_command("drink -1"), None);
+        assert_eq!(parse_session_command("status now"), None);
+        assert_eq!(parse_session_command(""), None);
+    }
+
+    #[test]
+    fn project_samples_bac_over_the_window() {
+        let (session, start) = session(&[(0, 5.0)]);
+        let projection = session.project(start, PROJECTION_HOURS);
+        assert_eq!(projection.points.len(), 73);
+        assert_eq!(projection.points[0], (0.0, session.bac_at(start)));
+        assert_eq!(projection.points[72].0, 12.0);
+        assert_eq!(projection.points[72].1, 0.0);
+    }
+
+    #[test]
+    fn project_estimates_limit_and_sober_times() {
+        // 5 oz for a 160 lb man is a BAC of about 0.117.
+        let (session, start) = session(&[(0, 5.0)]);
+        let bac = session.bac_at(start);
+        let projection = session.project(start, PROJECTION_HOURS);
+        assert!((projection.within_limit_hours - (bac - 0.08) / 0.015).abs() < 1e-9);
+        assert!((projection.sober_hours - bac / 0.015).abs() < 1e-9);
+        let sober_at = projection.time_after(projection.sober_hours);
+        assert!(session.bac_at(sober_at) < 1e-3);
+    }
 }
@@ -10,13 +10,52 @@
 //! - **Multiple Temperature Scales**: Support for Celsius, Fahrenheit and Kelvin
 //! - **Input Flexibility**: Users can input temperature in any supported scale
 //! - **Scientific Accuracy**: Uses standard temperature conversion formulas
+//! - **Session History**: Records each committed conversion, with per-row copy to clipboard
+//! - **Unit Pinning**: Pins one scale as the source so only that field is editable
 use eframe::egui::{self};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Scale {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+impl Display for Scale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Scale::Celsius => write!(f, "Celsius"),
+            Scale::Fahrenheit => write!(f, "Fahrenheit"),
+            Scale::Kelvin => write!(f, "Kelvin"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Conversion {
+    celsius: f64,
+    fahrenheit: f64,
+    kelvin: f64,
+}
+
+impl Display for Conversion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.2} °C = {:.2} °F = {:.2} K",
+            self.celsius, self.fahrenheit, self.kelvin
+        )
+    }
+}
 
 #[derive(Debug, Default)]
 struct TemperatureCalculator {
     celsius: f64,
     fahrenheit: f64,
     kelvin: f64,
+    pinned: Option<Scale>,
+    history: Vec<Conversion>,
 }
 
 impl TemperatureCalculator {
@@ -31,42 +70,93 @@ impl TemperatureCalculator {
     fn celsius_to_kelvin(&self, celsius: f64) -> f64 {
         celsius + 273.15
     }
+
+    fn value_mut(&mut self, scale: Scale) -> &mut f64 {
+        match scale {
+            Scale::Celsius => &mut self.celsius,
+            Scale::Fahrenheit => &mut self.fahrenheit,
+            Scale::Kelvin => &mut self.kelvin,
+        }
+    }
+
+    /// Recomputes the other scales from the value of `source`.
+    fn update_from(&mut self, source: Scale) {
+        match source {
+            Scale::Celsius => {}
+            Scale::Fahrenheit => self.celsius = self.fahrenheit_to_celsius(self.fahrenheit),
+            Scale::Kelvin => self.celsius = self.kelvin - 273.15,
+        }
+        if source != Scale::Fahrenheit {
+            self.fahrenheit = self.celsius_to_fahrenheit(self.celsius);
+        }
+        if source != Scale::Kelvin {
+            self.kelvin = self.celsius_to_kelvin(self.celsius);
+        }
+    }
+
+    fn is_editable(&self, scale: Scale) -> bool {
+        self.pinned.is_none_or(|pinned| pinned == scale)
+    }
+
+    /// Appends the current conversion to the history unless it repeats the latest entry.
+    fn record_conversion(&mut self) {
+        let conversion = Conversion {
+            celsius: self.celsius,
+            fahrenheit: self.fahrenheit,
+            kelvin: self.kelvin,
+        };
+        if self.history.last() != Some(&conversion) {
+            self.history.push(conversion);
+        }
+    }
 }
 
 impl eframe::App for TemperatureCalculator {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.label("Temperature in Celsius:");
-            if ui
-                .add(egui::DragValue::new(&mut self.celsius).speed(0.1))
-                .changed()
-            {
-                self.fahrenheit = self.celsius_to_fahrenheit(self.celsius);
-                self.kelvin = self.celsius_to_kelvin(self.celsius);
-            }
-            ui.label("Temperature in Fahrenheit:");
-            if ui
-                .add(egui::DragValue::new(&mut self.fahrenheit).speed(0.1))
-                .changed()
-            {
-                self.celsius = self.fahrenheit_to_celsius(self.fahrenheit);
-                self.kelvin = self.celsius_to_kelvin(self.celsius);
-            }
-            ui.label("Temperature in Kelvin:");
-            if ui
-                .add(egui::DragValue::new(&mut self.kelvin).speed(0.1))
-                .changed()
-            {
-                self.celsius = self.kelvin - 273.15;
-                self.fahrenheit = self.celsius_to_fahrenheit(self.celsius);
+            egui::ComboBox::from_label("Pinned source scale")
+                .selected_text(self.pinned.map_or("None".to_string(), |s| s.to_string()))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.pinned, None, "None");
+                    for scale in [Scale::Celsius, Scale::Fahrenheit, Scale::Kelvin] {
+                        ui.selectable_value(&mut self.pinned, Some(scale), scale.to_string());
+                    }
+                });
+
+            for scale in [Scale::Celsius, Scale::Fahrenheit, Scale::Kelvin] {
+                ui.label(format!("Temperature in {}:", scale));
+                let editable = self.is_editable(scale);
+                let response = ui.add_enabled(
+                    editable,
+                    egui::DragValue::new(self.value_mut(scale)).speed(0.1),
+                );
+                if response.changed() {
+                    self.update_from(scale);
+                }
+                if response.drag_stopped() || response.lost_focus() {
+                    self.record_conversion();
+                }
             }
+
+            ui.separator();
+            ui.label("History:");
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for conversion in self.history.iter().rev() {
+                    ui.horizontal(|ui| {
+                        if ui.small_button("Copy").clicked() {
+                            ctx.copy_text(conversion.to_string());
+                        }
+                        ui.label(conversion.to_string());
+                    });
+                }
+            });
         });
     }
 }
 
 fn main() -> eframe::Result {
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 150.0]),
+        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 400.0]),
         ..Default::default()
     };
     eframe::run_native(
@@ -126,4 +216,43 @@ mod tests {
         assert_eq!(celsius, calculator.fahrenheit_to_celsius(fahrenheit));
         assert_eq!(kelvin - 273.15, celsius);
     }
+
+    #[test]
+    fn update_from_recomputes_other_scales() {
+        let mut calculator = TemperatureCalculator {
+            kelvin: 373.15,
+            ..Default::default()
+        };
+        calculator.update_from(Scale::Kelvin);
+        assert!((calculator.celsius - 100.0).abs() < 0.0001);
+        assert!((calculator.fahrenheit - 212.0).abs() < 0.0001);
+        assert_eq!(calculator.kelvin, 373.15);
+    }
+
+    #[test]
+    fn is_editable_respects_pinned_scale() {
+        let mut calculator = TemperatureCalculator::default();
+        assert!(calculator.is_editable(Scale::Kelvin));
+
+        calculator.pinned = Some(Scale::Fahrenheit);
+        assert!(calculator.is_editable(Scale::Fahrenheit));
+        assert!(!calculator.is_editable(Scale::Celsius));
+        assert!(!calculator.is_editable(Scale::Kelvin));
+    }
+
+    #[test]
+    fn record_conversion_skips_duplicate_entries() {
+        let mut calculator = TemperatureCalculator {
+            celsius: 25.0,
+            ..Default::default()
+        };
+        calculator.update_from(Scale::Celsius);
+        calculator.record_conversion();
+        calculator.record_conversion();
+        assert_eq!(calculator.history.len(), 1);
+        assert_eq!(
+            calculator.history[0].to_string(),
+            "25.00 °C = 77.00 °F = 298.15 K"
+        );
+    }
 }
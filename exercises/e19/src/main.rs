@@ -11,15 +11,171 @@
 //! - **Visual Feedback**: Color-coded results to indicate different BMI categories
 //! - **Standard Formula**: Uses the standard BMI formula with US measurements (lbs/inches)
 //! - **Zero-Value Protection**: Prevents division by zero when height is not provided
+//! - **Input Validation**: Shows an inline error for zero or negative weight and height
+//! - **Persistent Preferences**: Remembers the preferred unit system (imperial or metric) and
+//!   the last entered values between runs, saving once an edit is finished and on exit, and
+//!   showing any save failure in the window
+//! - **Body Fat Estimate**: Estimates body fat percentage from neck, waist, and (for women) hip
+//!   circumference using the US Navy method, with a category label, since BMI alone
+//!   misclassifies muscular users
 use eframe::egui::{self};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const PREFERENCES_FILE: &str = "exercises/e19/preferences.json";
+const LB_PER_KG: f64 = 2.204_622_62;
+const CM_PER_IN: f64 = 2.54;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum UnitSystem {
+    #[default]
+    Imperial,
+    Metric,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum Sex {
+    #[default]
+    Male,
+    Female,
+}
+
+/// Circumferences used by the body fat estimate, in inches.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct Circumferences {
+    neck_in: f64,
+    waist_in: f64,
+    hip_in: f64,
+}
+
+/// Settings restored when the app reopens.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct Preferences {
+    units: UnitSystem,
+    weight_lbs: f64,
+    height_in: f64,
+    // Older preference files predate the body fat estimate.
+    #[serde(default)]
+    sex: Sex,
+    #[serde(default)]
+    circumferences: Circumferences,
+}
+
+/// Estimates body fat percentage with the US Navy circumference method. The hip measurement is
+/// only used for women.
+fn navy_body_fat(sex: Sex, height_in: f64, c: &Circumferences) -> Result<f64, &'static str> {
+    if height_in <= 0.0 {
+        return Err("Height must be greater than zero.");
+    }
+    if c.neck_in <= 0.0 || c.waist_in <= 0.0 || (sex == Sex::Female && c.hip_in <= 0.0) {
+        return Err("Enter all circumference measurements to estimate body fat.");
+    }
+    let body_fat = match sex {
+        Sex::Male => {
+            if c.waist_in <= c.neck_in {
+                return Err("Waist must be larger than neck.");
+            }
+            86.010 * (c.waist_in - c.neck_in).log10() - 70.041 * height_in.log10() + 36.76
+        }
+        Sex::Female => {
+            if c.waist_in + c.hip_in <= c.neck_in {
+                return Err("Waist plus hip must be larger than neck.");
+            }
+            163.205 * (c.waist_in + c.hip_in - c.neck_in).log10()
+                - 97.684 * height_in.log10()
+                - 78.387
+        }
+    };
+    Ok(body_fat.max(0.0))
+}
+
+/// American Council on Exercise body fat categories.
+fn body_fat_category(sex: Sex, body_fat: f64) -> &'static str {
+    let limits = match sex {
+        Sex::Male => [6.0, 14.0, 18.0, 25.0],
+        Sex::Female => [14.0, 21.0, 25.0, 32.0],
+    };
+    match body_fat {
+        b if b < limits[0] => "essential fat",
+        b if b < limits[1] => "athletic",
+        b if b < limits[2] => "fit",
+        b if b < limits[3] => "average",
+        _ => "obese",
+    }
+}
+
+fn load_preferences(file_path: &Path) -> Result<Preferences, Box<dyn std::error::Error>> {
+    let file = std::fs::File::open(file_path)?;
+    Ok(serde_json::from_reader(file)?)
+}
+
+fn save_preferences(
+    file_path: &Path,
+    preferences: &Preferences,
+) -> Result<(), Box<dyn std::error::Error>> {
+    persist::write_json(file_path, preferences)?;
+    Ok(())
+}
 
 #[derive(Debug, Default)]
 struct BMICalculator {
     weight_lbs: f64,
     height_in: f64,
+    units: UnitSystem,
+    sex: Sex,
+    circumferences: Circumferences,
+    saved: Preferences,
+    save_error: Option<String>,
 }
 
 impl BMICalculator {
+    fn from_preferences(preferences: Preferences) -> Self {
+        Self {
+            weight_lbs: preferences.weight_lbs,
+            height_in: preferences.height_in,
+            units: preferences.units,
+            sex: preferences.sex,
+            circumferences: preferences.circumferences,
+            saved: preferences,
+            save_error: None,
+        }
+    }
+
+    /// Writes the preferences to disk if they differ from the last saved copy.
+    fn save_if_changed(&mut self, file_path: &Path) {
+        let preferences = self.preferences();
+        if preferences == self.saved {
+            return;
+        }
+        match save_preferences(file_path, &preferences) {
+            Ok(()) => {
+                self.saved = preferences;
+                self.save_error = None;
+            }
+            Err(e) => self.save_error = Some(format!("Error saving preferences: {}", e)),
+        }
+    }
+
+    fn preferences(&self) -> Preferences {
+        Preferences {
+            units: self.units,
+            weight_lbs: self.weight_lbs,
+            height_in: self.height_in,
+            sex: self.sex,
+            circumferences: self.circumferences,
+        }
+    }
+
+    fn validate(&self) -> Result<(), &'static str> {
+        if self.weight_lbs <= 0.0 {
+            Err("Weight must be greater than zero.")
+        } else if self.height_in <= 0.0 {
+            Err("Height must be greater than zero.")
+        } else {
+            Ok(())
+        }
+    }
+
     fn calculate_bmi(&self) -> f64 {
         if self.height_in == 0.0 {
             return 0.0; // Avoid division by zero
@@ -31,10 +187,77 @@ impl BMICalculator {
 impl eframe::App for BMICalculator {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.label("Weight (lb):");
-            ui.add(egui::DragValue::new(&mut self.weight_lbs).speed(0.5));
-            ui.label("Height (in):");
-            ui.add(egui::DragValue::new(&mut self.height_in).speed(0.5));
+            // Preferences are saved once an edit is finished rather than on every frame of a drag.
+            let mut edit_finished = false;
+            let finished =
+                |response: &egui::Response| response.drag_stopped() || response.lost_focus();
+            ui.horizontal(|ui| {
+                edit_finished |= ui
+                    .radio_value(&mut self.units, UnitSystem::Imperial, "Imperial")
+                    .changed();
+                edit_finished |= ui
+                    .radio_value(&mut self.units, UnitSystem::Metric, "Metric")
+                    .changed();
+            });
+
+            // Values are stored in pounds and inches and converted for display.
+            let (weight_label, height_label, weight_factor, height_factor) = match self.units {
+                UnitSystem::Imperial => ("Weight (lb):", "Height (in):", 1.0, 1.0),
+                UnitSystem::Metric => ("Weight (kg):", "Height (cm):", 1.0 / LB_PER_KG, CM_PER_IN),
+            };
+            let mut weight = self.weight_lbs * weight_factor;
+            let mut height = self.height_in * height_factor;
+            ui.label(weight_label);
+            let response = ui.add(egui::DragValue::new(&mut weight).speed(0.5));
+            if response.changed() {
+                self.weight_lbs = weight / weight_factor;
+            }
+            edit_finished |= finished(&response);
+            ui.label(height_label);
+            let response = ui.add(egui::DragValue::new(&mut height).speed(0.5));
+            if response.changed() {
+                self.height_in = height / height_factor;
+            }
+            edit_finished |= finished(&response);
+
+            ui.horizontal(|ui| {
+                edit_finished |= ui.radio_value(&mut self.sex, Sex::Male, "Male").changed();
+                edit_finished |= ui
+                    .radio_value(&mut self.sex, Sex::Female, "Female")
+                    .changed();
+            });
+            let length_unit = match self.units {
+                UnitSystem::Imperial => "in",
+                UnitSystem::Metric => "cm",
+            };
+            let c = &mut self.circumferences;
+            let mut fields = vec![("Neck", &mut c.neck_in), ("Waist", &mut c.waist_in)];
+            if self.sex == Sex::Female {
+                fields.push(("Hip", &mut c.hip_in));
+            }
+            ui.horizontal(|ui| {
+                for (label, value_in) in fields {
+                    ui.label(format!("{} ({}):", label, length_unit));
+                    let mut value = *value_in * height_factor;
+                    let response = ui.add(egui::DragValue::new(&mut value).speed(0.25));
+                    if response.changed() {
+                        *value_in = value / height_factor;
+                    }
+                    edit_finished |= finished(&response);
+                }
+            });
+
+            if edit_finished {
+                self.save_if_changed(Path::new(PREFERENCES_FILE));
+            }
+            if let Some(e) = &self.save_error {
+                ui.colored_label(egui::Color32::RED, e);
+            }
+
+            if let Err(e) = self.validate() {
+                ui.colored_label(egui::Color32::RED, e);
+                return;
+            }
 
             const HEALTHY_BMI: (f64, f64) = (18.0, 25.0);
             let bmi = self.calculate_bmi();
@@ -58,19 +281,41 @@ impl eframe::App for BMICalculator {
                     );
                 }
             }
+
+            match navy_body_fat(self.sex, self.height_in, &self.circumferences) {
+                Ok(body_fat) => {
+                    ui.label(format!(
+                        "Estimated body fat is {:.1}% ({}).",
+                        body_fat,
+                        body_fat_category(self.sex, body_fat)
+                    ));
+                }
+                Err(e) => {
+                    ui.weak(e);
+                }
+            }
         });
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.save_if_changed(Path::new(PREFERENCES_FILE));
+        if let Some(e) = &self.save_error {
+            eprintln!("{}", e);
+        }
+    }
 }
 
 fn main() -> eframe::Result {
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 150.0]),
+        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 240.0]),
         ..Default::default()
     };
+    // A missing preferences file just means this is the first run.
+    let preferences = load_preferences(Path::new(PREFERENCES_FILE)).unwrap_or_default();
     eframe::run_native(
         "BMI Calculator",
         options,
-        Box::new(|_| Ok(Box::<BMICalculator>::default())),
+        Box::new(move |_| Ok(Box::new(BMICalculator::from_preferences(preferences)))),
     )
 }
 
@@ -84,6 +329,7 @@ mod tests {
         let calculator = BMICalculator {
             weight_lbs: 150.0,
             height_in: 70.0,
+            ..Default::default()
         };
         // BMI = (150 / (70 * 70)) * 703 = 21.52
         assert!((calculator.calculate_bmi() - 21.52).abs() < 0.01);
@@ -92,6 +338,7 @@ mod tests {
         let calculator = BMICalculator {
             weight_lbs: 180.0,
             height_in: 68.0,
+            ..Default::default()
         };
         // BMI = (180 / (68 * 68)) * 703 = 27.36
         assert!((calculator.calculate_bmi() - 27.36).abs() < 0.01);
@@ -103,6 +350,7 @@ mod tests {
         let calculator = BMICalculator {
             weight_lbs: 110.0,
             height_in: 72.0,
+            ..Default::default()
         };
         // BMI = (110 / (72 * 72)) * 703 = 14.92
         let bmi = calculator.calculate_bmi();
@@ -116,6 +364,7 @@ mod tests {
         let calculator = BMICalculator {
             weight_lbs: 150.0,
             height_in: 70.0,
+            ..Default::default()
         };
         // BMI = (150 / (70 * 70)) * 703 = 21.52
         let bmi = calculator.calculate_bmi();
@@ -129,6 +378,7 @@ mod tests {
         let calculator = BMICalculator {
             weight_lbs: 200.0,
             height_in: 68.0,
+            ..Default::default()
         };
         // BMI = (200 / (68 * 68)) * 703 = 30.40
         let bmi = calculator.calculate_bmi();
@@ -141,8 +391,135 @@ mod tests {
         let calculator = BMICalculator {
             weight_lbs: 150.0,
             height_in: 0.0,
+            ..Default::default()
         };
         // Should return 0.0 to avoid division by zero
         assert_eq!(calculator.calculate_bmi(), 0.0);
     }
+
+    #[test]
+    fn validate_rejects_non_positive_measurements() {
+        let calculator = BMICalculator {
+            weight_lbs: 150.0,
+            height_in: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            calculator.validate(),
+            Err("Height must be greater than zero.")
+        );
+
+        let calculator = BMICalculator {
+            weight_lbs: -5.0,
+            height_in: 70.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            calculator.validate(),
+            Err("Weight must be greater than zero.")
+        );
+
+        let calculator = BMICalculator {
+            weight_lbs: 150.0,
+            height_in: 70.0,
+            ..Default::default()
+        };
+        assert_eq!(calculator.validate(), Ok(()));
+    }
+
+    #[test]
+    fn preferences_round_trip_through_calculator() {
+        let preferences = Preferences {
+            units: UnitSystem::Metric,
+            weight_lbs: 150.0,
+            height_in: 70.0,
+            sex: Sex::Female,
+            circumferences: Circumferences {
+                neck_in: 13.0,
+                waist_in: 30.0,
+                hip_in: 38.0,
+            },
+        };
+        let calculator = BMICalculator::from_preferences(preferences);
+        assert_eq!(calculator.preferences(), preferences);
+
+        let json = serde_json::to_string(&preferences).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Preferences>(&json).unwrap(),
+            preferences
+        );
+    }
+
+    #[test]
+    fn navy_body_fat_estimates_by_sex() {
+        // 86.010*log10(34-15) - 70.041*log10(70) + 36.76 = 17.5
+        let c = Circumferences {
+            neck_in: 15.0,
+            waist_in: 34.0,
+            hip_in: 0.0,
+        };
+        let body_fat = navy_body_fat(Sex::Male, 70.0, &c).unwrap();
+        assert!((body_fat - 17.5).abs() < 0.1);
+        assert_eq!(body_fat_category(Sex::Male, body_fat), "fit");
+
+        // 163.205*log10(30+38-13) - 97.684*log10(65) - 78.387 = 28.6
+        let c = Circumferences {
+            neck_in: 13.0,
+            waist_in: 30.0,
+            hip_in: 38.0,
+        };
+        let body_fat = navy_body_fat(Sex::Female, 65.0, &c).unwrap();
+        assert!((body_fat - 28.6).abs() < 0.1);
+        assert_eq!(body_fat_category(Sex::Female, body_fat), "average");
+    }
+
+    #[test]
+    fn navy_body_fat_rejects_incomplete_measurements() {
+        let c = Circumferences {
+            neck_in: 15.0,
+            waist_in: 34.0,
+            hip_in: 0.0,
+        };
+        assert!(navy_body_fat(Sex::Female, 65.0, &c).is_err());
+        let c = Circumferences {
+            neck_in: 16.0,
+            waist_in: 15.0,
+            hip_in: 0.0,
+        };
+        assert_eq!(
+            navy_body_fat(Sex::Male, 70.0, &c),
+            Err("Waist must be larger than neck.")
+        );
+    }
+
+    #[test]
+    fn preferences_load_without_body_fat_fields() {
+        let json = r#"{"units": "Imperial", "weight_lbs": 150.0, "height_in": 70.0}"#;
+        let preferences: Preferences = serde_json::from_str(json).unwrap();
+        assert_eq!(preferences.sex, Sex::Male);
+        assert_eq!(preferences.circumferences, Circumferences::default());
+    }
+
+    #[test]
+    fn save_if_changed_keeps_failures_for_display() {
+        let mut calculator = BMICalculator {
+            weight_lbs: 150.0,
+            height_in: 70.0,
+            ..Default::default()
+        };
+        let missing_dir = std::env::temp_dir().join(format!("e19_missing_{}", std::process::id()));
+        calculator.save_if_changed(&missing_dir.join("preferences.json"));
+        assert!(calculator.save_error.is_some());
+        assert_ne!(calculator.saved, calculator.preferences());
+
+        let file_path = std::env::temp_dir().join(format!("e19_prefs_{}.json", std::process::id()));
+        calculator.save_if_changed(&file_path);
+        assert!(calculator.save_error.is_none());
+        assert_eq!(calculator.saved, calculator.preferences());
+        assert_eq!(
+            load_preferences(&file_path).unwrap(),
+            calculator.preferences()
+        );
+        std::fs::remove_file(&file_path).unwrap();
+    }
 }
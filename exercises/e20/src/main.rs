@@ -10,17 +10,24 @@
 //! - **Case-Insensitive Matching**: Recognizes state names and abbreviations regardless of case
 //! - **Complete US Coverage**: Includes all 50 US states with their official abbreviations
 //! - **Precise Calculation**: Applies appropriate tax rates based on geographic location
+//! - **Item Categories**: Groceries, clothing, and prescriptions can be exempt or taxed at a
+//!   reduced rate per state, with the rules kept alongside the rates in `inputs/tax_rates.toml`
 //!
 //! ## Tax Structure
 //!
 //! - **Wisconsin**: Base state rate of 0% with county-specific rates:
 //!   - Eau Claire County: 0.5%
 //!   - Dunn County: 0.4%
+//!   - Groceries and prescriptions are exempt
 //! - **Illinois**: Flat state rate of 8% with no county-specific adjustments
+//!   - Groceries and prescriptions are taxed at a reduced 1%
 //! - **Other States**: No tax applied
 use once_cell::sync::Lazy;
+use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 use std::io::Write;
+use std::str::FromStr;
 
 type CountyName = String;
 
@@ -30,37 +37,106 @@ struct StateName {
     abbreviation: String,
 }
 
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Category {
+    General,
+    Groceries,
+    Clothing,
+    Prescriptions,
+}
+
+const CATEGORIES: [Category; 4] = [
+    Category::General,
+    Category::Groceries,
+    Category::Clothing,
+    Category::Prescriptions,
+];
+
+impl Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Category::General => write!(f, "general"),
+            Category::Groceries => write!(f, "groceries"),
+            Category::Clothing => write!(f, "clothing"),
+            Category::Prescriptions => write!(f, "prescriptions"),
+        }
+    }
+}
+
+impl FromStr for Category {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CATEGORIES
+            .into_iter()
+            .find(|category| category.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("Unknown category '{}'", s.trim()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
 struct StateTax {
+    #[serde(rename = "rate")]
     tax_rate: f64,
+    #[serde(default)]
     counties: HashMap<CountyName, f64>,
+    /// Rates that replace the combined state and county rate for a category.
+    #[serde(default)]
+    categories: HashMap<Category, f64>,
+}
+
+impl StateTax {
+    fn rate(&self, county: Option<&str>, category: Category) -> f64 {
+        if let Some(rate) = self.categories.get(&category) {
+            return *rate;
+        }
+        let county_tax = county
+            .and_then(|county| self.counties.get(county))
+            .copied()
+            .unwrap_or(0.0);
+        self.tax_rate + county_tax
+    }
+
+    /// Whether the county changes the rate for `category`.
+    fn needs_county(&self, category: Category) -> bool {
+        !self.counties.is_empty() && !self.categories.contains_key(&category)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StateTaxEntry {
+    name: String,
+    abbreviation: String,
+    #[serde(flatten)]
+    tax: StateTax,
+}
+
+#[derive(Debug, Deserialize)]
+struct TaxTable {
+    #[serde(rename = "state", default)]
+    states: Vec<StateTaxEntry>,
+}
+
+fn parse_tax_table(content: &str) -> Result<HashMap<StateName, StateTax>, toml::de::Error> {
+    let table: TaxTable = toml::from_str(content)?;
+    Ok(table
+        .states
+        .into_iter()
+        .map(|entry| {
+            (
+                StateName {
+                    full_name: entry.name,
+                    abbreviation: entry.abbreviation,
+                },
+                entry.tax,
+            )
+        })
+        .collect())
 }
 
 static TAXABLE_STATES: Lazy<HashMap<StateName, StateTax>> = Lazy::new(|| {
-    let mut m = HashMap::new();
-    m.insert(
-        StateName {
-            full_name: "Wisconsin".to_string(),
-            abbreviation: "WI".to_string(),
-        },
-        StateTax {
-            tax_rate: 0.0,
-            counties: HashMap::from([
-                ("Eau Claire".to_string(), 0.005),
-                ("Dunn".to_string(), 0.004),
-            ]),
-        },
-    );
-    m.insert(
-        StateName {
-            full_name: "Illinois".to_string(),
-            abbreviation: "IL".to_string(),
-        },
-        StateTax {
-            tax_rate: 0.08,
-            counties: HashMap::new(),
-        },
-    );
-    m
+    parse_tax_table(include_str!("../inputs/tax_rates.toml")).expect("tax_rates.toml is valid")
 });
 
 static VALID_STATE_NAMES: Lazy<HashSet<StateName>> = Lazy::new(|| {
@@ -327,6 +403,30 @@ fn prompt_for_state_name() -> StateName {
     }
 }
 
+fn prompt_for_category() -> Category {
+    loop {
+        print!("Enter the item category (general, groceries, clothing, prescriptions): ");
+        let mut input = String::new();
+        if let Err(e) = std::io::stdout().flush() {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        if let Err(e) = std::io::stdin().read_line(&mut input) {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        if input.trim().is_empty() {
+            return Category::General;
+        }
+        match input.parse() {
+            Ok(category) => return category,
+            Err(e) => println!("{}. Please try again.", e),
+        }
+    }
+}
+
 fn prompt_for_amount() -> f64 {
     loop {
         print!("Enter the amount: ");
@@ -354,37 +454,24 @@ fn prompt_for_amount() -> f64 {
     }
 }
 
-fn calculate_tax(amount: f64, state_name: &StateName) -> f64 {
-    let wisconsin = StateName {
-        full_name: "Wisconsin".to_string(),
-        abbreviation: "WI".to_string(),
+fn calculate_tax(amount: f64, state_name: &StateName, category: Category) -> f64 {
+    let Some(state_tax) = TAXABLE_STATES.get(state_name) else {
+        return 0.0;
     };
-    let illinois = StateName {
-        full_name: "Illinois".to_string(),
-        abbreviation: "IL".to_string(),
-    };
-
-    match state_name {
-        name if name == &wisconsin => {
-            let state_tax = TAXABLE_STATES.get(&wisconsin).unwrap();
-            let county_name = prompt_for_county_name();
-            let county_tax = state_tax.counties.get(&county_name).cloned().unwrap_or(0.0);
-            amount * (state_tax.tax_rate + county_tax)
-        }
-        name if name == &illinois => {
-            let state_tax = TAXABLE_STATES.get(&illinois).unwrap();
-            amount * state_tax.tax_rate
-        }
-        _ => 0.0,
-    }
+    // The county only matters when the category doesn't have a rate of its own.
+    let county_name = state_tax
+        .needs_county(category)
+        .then(prompt_for_county_name);
+    amount * state_tax.rate(county_name.as_deref(), category)
 }
 
 fn main() {
     let amount = prompt_for_amount();
+    let category = prompt_for_category();
     let state_name = prompt_for_state_name();
 
     if TAXABLE_STATES.contains_key(&state_name) {
-        let tax = calculate_tax(amount, &state_name);
+        let tax = calculate_tax(amount, &state_name, category);
         let total = amount + tax;
 
         println!("The tax is ${:.2}.", tax);
@@ -393,3 +480,86 @@ fn main() {
         println!("The total amount is ${:.2}.", amount);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(full_name: &str, abbreviation: &str) -> &'static StateTax {
+        &TAXABLE_STATES[&StateName {
+            full_name: full_name.to_string(),
+            abbreviation: abbreviation.to_string(),
+        }]
+    }
+
+    fn assert_rate(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-12,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn general_items_use_state_and_county_rates() {
+        let wisconsin = state("Wisconsin", "WI");
+        assert_rate(wisconsin.rate(Some("Eau Claire"), Category::General), 0.005);
+        assert_rate(wisconsin.rate(Some("Dunn"), Category::General), 0.004);
+        assert_rate(wisconsin.rate(None, Category::General), 0.0);
+        assert_rate(state("Illinois", "IL").rate(None, Category::General), 0.08);
+    }
+
+    #[test]
+    fn groceries_are_exempt_or_reduced() {
+        let wisconsin = state("Wisconsin", "WI");
+        assert_rate(wisconsin.rate(Some("Eau Claire"), Category::Groceries), 0.0);
+        assert!(!wisconsin.needs_county(Category::Groceries));
+        assert_rate(
+            state("Illinois", "IL").rate(None, Category::Groceries),
+            0.01,
+        );
+    }
+
+    #[test]
+    fn clothing_is_taxed_like_general_items() {
+        let wisconsin = state("Wisconsin", "WI");
+        assert_rate(wisconsin.rate(Some("Dunn"), Category::Clothing), 0.004);
+        assert!(wisconsin.needs_county(Category::Clothing));
+        assert_rate(state("Illinois", "IL").rate(None, Category::Clothing), 0.08);
+    }
+
+    #[test]
+    fn prescriptions_are_exempt_or_reduced() {
+        assert_rate(
+            state("Wisconsin", "WI").rate(Some("Dunn"), Category::Prescriptions),
+            0.0,
+        );
+        assert_rate(
+            state("Illinois", "IL").rate(None, Category::Prescriptions),
+            0.01,
+        );
+    }
+
+    #[test]
+    fn category_parses_case_insensitively() {
+        assert_eq!(" Groceries ".parse(), Ok(Category::Groceries));
+        assert_eq!("PRESCRIPTIONS".parse(), Ok(Category::Prescriptions));
+        assert_eq!(
+            "toys".parse::<Category>(),
+            Err("Unknown category 'toys'".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_tax_table_rejects_unknown_categories() {
+        let table = r#"
+            [[state]]
+            name = "Iowa"
+            abbreviation = "IA"
+            rate = 0.06
+
+            [state.categories]
+            candy = 0.0
+        "#;
+        assert!(parse_tax_table(table).is_err());
+    }
+}
@@ -10,13 +10,56 @@
 //! - **Multilingual Support**: Provides month names in the user's selected language
 //! - **Complete Coverage**: Handles all twelve months with proper translations
 //! - **Localized Messages**: Displays prompts and error messages in the selected language
+//! - **Reverse Translation**: Looks up a month number from its name in any supported language,
+//!   tolerating minor typos and missing accents
 use std::io::Write;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum Language {
     English,
     Spanish,
 }
 
+const LANGUAGES: [Language; 2] = [Language::English, Language::Spanish];
+
+// Maximum number of single character edits tolerated when matching a month name.
+const MAX_TYPO_DISTANCE: usize = 2;
+
+impl Language {
+    fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Spanish",
+        }
+    }
+}
+
+enum Mode {
+    NumberToName,
+    NameToNumber,
+}
+
+fn prompt_for_mode() -> Mode {
+    loop {
+        print!("Translate a month (N)umber to a name or a month (M)onth name to a number? ");
+        let mut input = String::new();
+        if let Err(e) = std::io::stdout().flush() {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+        if let Err(e) = std::io::stdin().read_line(&mut input) {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        match input.trim().to_uppercase().as_str() {
+            "N" => return Mode::NumberToName,
+            "M" => return Mode::NameToNumber,
+            _ => println!("Invalid input. Please enter 'N' or 'M'."),
+        }
+    }
+}
+
 fn prompt_for_language() -> Language {
     loop {
         print!("Enter your preferred language (E for English or S for Spanish): ");
@@ -102,7 +145,104 @@ fn get_month_name(month_num: u8, language: Language) -> String {
     }
 }
 
+fn fold_accents(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'ö' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ñ' => 'n',
+            _ => c,
+        })
+        .collect()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+/// Finds the month whose name in any supported language best matches `name`, returning the
+/// month number and the language the name was matched in.
+fn find_month(name: &str) -> Option<(u8, Language)> {
+    let folded = fold_accents(name);
+    let mut best: Option<(usize, u8, Language)> = None;
+    for language in LANGUAGES {
+        for month_num in 1..=12 {
+            let candidate = fold_accents(&get_month_name(month_num, language));
+            let distance = edit_distance(&folded, &candidate);
+            if best.is_none_or(|(best_distance, _, _)| distance < best_distance) {
+                best = Some((distance, month_num, language));
+            }
+        }
+    }
+
+    best.filter(|(distance, _, _)| *distance <= MAX_TYPO_DISTANCE && *distance < folded.len() / 2)
+        .map(|(_, month_num, language)| (month_num, language))
+}
+
+fn prompt_for_month_name() -> String {
+    loop {
+        print!("Please enter the name of the month: ");
+        let mut input = String::new();
+        if let Err(e) = std::io::stdout().flush() {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        if let Err(e) = std::io::stdin().read_line(&mut input) {
+            eprintln!("Error: {}", e);
+            continue;
+        }
+
+        if !input.trim().is_empty() {
+            return input.trim().to_string();
+        }
+        println!("Invalid input. Please enter a month name.");
+    }
+}
+
+fn run_name_to_number() {
+    let name = prompt_for_month_name();
+    match find_month(&name) {
+        Some((month_num, matched_language)) => {
+            println!(
+                "{} is month number {} ({}).",
+                name,
+                month_num,
+                matched_language.name()
+            );
+            for language in LANGUAGES.into_iter().filter(|l| *l != matched_language) {
+                println!(
+                    "In {}: {}.",
+                    language.name(),
+                    get_month_name(month_num, language)
+                );
+            }
+        }
+        None => println!("No month matches '{}'.", name),
+    }
+}
+
 fn main() {
+    if let Mode::NameToNumber = prompt_for_mode() {
+        run_name_to_number();
+        return;
+    }
+
     let language = prompt_for_language();
     let (prompt_msg, error_msg) = match language {
         Language::English => (
@@ -173,4 +313,38 @@ mod tests {
         assert_eq!(get_month_name(13, Language::Spanish), "Mes inválido");
         assert_eq!(get_month_name(255, Language::Spanish), "Mes inválido");
     }
+
+    #[test]
+    fn find_month_matches_exact_names_in_any_language() {
+        assert_eq!(find_month("March"), Some((3, Language::English)));
+        assert_eq!(find_month("diciembre"), Some((12, Language::Spanish)));
+        assert_eq!(find_month("  JULIO "), Some((7, Language::Spanish)));
+    }
+
+    #[test]
+    fn find_month_tolerates_typos() {
+        assert_eq!(find_month("Febuary"), Some((2, Language::English)));
+        assert_eq!(find_month("Septmber"), Some((9, Language::English)));
+        assert_eq!(find_month("Novienbre"), Some((11, Language::Spanish)));
+    }
+
+    #[test]
+    fn find_month_ignores_accents() {
+        assert_eq!(find_month("Éneró"), Some((1, Language::Spanish)));
+        assert_eq!(fold_accents("Mes Inválido"), "mes invalido");
+    }
+
+    #[test]
+    fn find_month_rejects_unrelated_input() {
+        assert_eq!(find_month("Banana"), None);
+        assert_eq!(find_month("Mxy"), None);
+        assert_eq!(find_month(""), None);
+    }
+
+    #[test]
+    fn edit_distance_counts_single_character_edits() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("may", "mayo"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
 }
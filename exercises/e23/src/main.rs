@@ -5,99 +5,498 @@
 //!
 //! ## Features
 //!
-//! - **Binary Decision Tree**: Navigates through yes/no questions to identify issues
+//! - **Decision Tree**: Navigates through yes/no and multiple choice questions to identify issues
 //! - **Interactive Prompting**: Guides users through the troubleshooting process
 //! - **Comprehensive Coverage**: Addresses multiple potential car problems including:
 //!   - Battery and electrical system issues
 //!   - Starting and ignition problems
 //!   - Fuel delivery complications
 //! - **Solution-Oriented**: Provides specific actions to resolve identified problems
+//! - **Confidence Scoring**: Each recommendation carries a likelihood that it fixes the problem
 //! - **Graph-based Structure**: Uses petgraph for efficient decision tree representation
+//! - **Weighted Scoring Mode**: Asks every question and lets each answer, including "not sure",
+//!   add weighted evidence toward possible causes, then ranks the most likely problems
+//! - **Tree Linting**: Checks the decision tree for cycles, unreachable nodes, and questions
+//!   missing an answer edge before the session starts
 use petgraph::{
-    graph::{DefaultIx, NodeIndex},
-    visit::EdgeRef,
     Graph,
+    algo::tarjan_scc,
+    graph::{DefaultIx, NodeIndex},
+    visit::{Dfs, EdgeRef},
 };
+use std::fmt::Display;
 use std::io::Write;
 
-type TroubleshootDecisionTree = Graph<String, bool>;
+#[derive(Debug, Clone)]
+enum TroubleshootNode {
+    Question(String),
+    Solution { action: String, confidence: f64 },
+}
+
+type TroubleshootDecisionTree = Graph<TroubleshootNode, String>;
 type DecisionTreeNode = NodeIndex<DefaultIx>;
 
+fn question(text: &str) -> TroubleshootNode {
+    TroubleshootNode::Question(text.to_string())
+}
+
+fn solution(action: &str, confidence: f64) -> TroubleshootNode {
+    TroubleshootNode::Solution {
+        action: action.to_string(),
+        confidence,
+    }
+}
+
 fn create_troubleshoot_tree() -> (DecisionTreeNode, TroubleshootDecisionTree) {
     let mut decision_tree: TroubleshootDecisionTree = Graph::new();
-    let base = decision_tree.add_node("Is the car silent when you turn the key?".to_string());
-    let l1_a = decision_tree.add_node("Are the battery terminals corroded?".to_string());
-    let l1_b = decision_tree.add_node("Does the car make a clicking noise?".to_string());
-    let l2_a = decision_tree.add_node("Clean terminals and try starting again.".to_string());
-    let l2_b = decision_tree.add_node("Replaces cables and try again.".to_string());
-    let l2_c = decision_tree.add_node("Replace the battery.".to_string());
-    let l2_d = decision_tree.add_node("Does the car crank up but fail to start?".to_string());
-    let l3_a = decision_tree.add_node("Check spark plug connections.".to_string());
-    let l3_b = decision_tree.add_node("Does the engine start and then die?".to_string());
-    let l4_a = decision_tree.add_node("Does your car have fuel injection?".to_string());
-    let l4_b =
-        decision_tree.add_node("No further questions. Please consult a mechanic.".to_string());
-    let l5_a =
-        decision_tree.add_node("Check to ensure the choke is opening and closing.".to_string());
-    let l5_b = decision_tree.add_node("Get it in for service.".to_string());
-
-    decision_tree.extend_with_edges([
-        (base, l1_a, true),
-        (base, l1_b, false),
-        (l1_a, l2_a, true),
-        (l1_a, l2_b, false),
-        (l1_b, l2_c, true),
-        (l1_b, l2_d, false),
-        (l2_d, l3_a, true),
-        (l2_d, l3_b, false),
-        (l3_b, l4_a, true),
-        (l3_b, l4_b, false),
-        (l4_a, l5_a, false),
-        (l4_a, l5_b, true),
-    ]);
+    let base = decision_tree.add_node(question("Is the car silent when you turn the key?"));
+    let l1_a = decision_tree.add_node(question("Are the battery terminals corroded?"));
+    let l1_b = decision_tree.add_node(question("Does the car make a clicking noise?"));
+    let l2_a = decision_tree.add_node(solution("Clean terminals and try starting again.", 0.8));
+    let l2_b = decision_tree.add_node(solution("Replaces cables and try again.", 0.6));
+    let l2_c = decision_tree.add_node(solution("Replace the battery.", 0.7));
+    let l2_d = decision_tree.add_node(question("Does the car crank up but fail to start?"));
+    let l3_a = decision_tree.add_node(solution("Check spark plug connections.", 0.6));
+    let l3_b = decision_tree.add_node(question("Does the engine start and then die?"));
+    let l4_a = decision_tree.add_node(question("What kind of fuel system does your car have?"));
+    let l4_b = decision_tree.add_node(solution(
+        "No further questions. Please consult a mechanic.",
+        0.3,
+    ));
+    let l5_a = decision_tree.add_node(solution(
+        "Check to ensure the choke is opening and closing.",
+        0.7,
+    ));
+    let l5_b = decision_tree.add_node(solution("Get it in for service.", 0.5));
+    let l5_c = decision_tree.add_node(solution(
+        "Check the owner's manual for the fuel system, then get it in for service.",
+        0.3,
+    ));
+
+    for (from, to, answer) in [
+        (base, l1_a, "yes"),
+        (base, l1_b, "no"),
+        (l1_a, l2_a, "yes"),
+        (l1_a, l2_b, "no"),
+        (l1_b, l2_c, "yes"),
+        (l1_b, l2_d, "no"),
+        (l2_d, l3_a, "yes"),
+        (l2_d, l3_b, "no"),
+        (l3_b, l4_a, "yes"),
+        (l3_b, l4_b, "no"),
+        (l4_a, l5_a, "carburetor"),
+        (l4_a, l5_b, "fuel injection"),
+        (l4_a, l5_c, "not sure"),
+    ] {
+        decision_tree.add_edge(from, to, answer.to_string());
+    }
     (base, decision_tree)
 }
 
-fn prompt_for_answer(prompt: &str) -> bool {
+/// Returns the outgoing answers of `node` in the order they were added to the tree.
+fn answers(
+    decision_tree: &TroubleshootDecisionTree,
+    node: DecisionTreeNode,
+) -> Vec<(String, DecisionTreeNode)> {
+    let mut edges: Vec<_> = decision_tree.edges(node).collect();
+    edges.sort_by_key(|edge| edge.id());
+    edges
+        .into_iter()
+        .map(|edge| (edge.weight().clone(), edge.target()))
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+enum LintIssue {
+    /// Nodes that lead back to themselves, in index order.
+    Cycle(Vec<DecisionTreeNode>),
+    Unreachable(DecisionTreeNode),
+    /// A question with no answers at all.
+    DeadEnd(DecisionTreeNode),
+    /// A yes/no question with only one of the two answers.
+    MissingAnswer {
+        node: DecisionTreeNode,
+        answer: &'static str,
+    },
+}
+
+impl Display for LintIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintIssue::Cycle(nodes) => {
+                let ids: Vec<String> = nodes.iter().map(|n| n.index().to_string()).collect();
+                write!(f, "cycle through nodes {}", ids.join(", "))
+            }
+            LintIssue::Unreachable(node) => {
+                write!(f, "node {} is unreachable from the root", node.index())
+            }
+            LintIssue::DeadEnd(node) => write!(f, "question {} has no answers", node.index()),
+            LintIssue::MissingAnswer { node, answer } => {
+                write!(f, "question {} has no '{}' answer", node.index(), answer)
+            }
+        }
+    }
+}
+
+/// Validates the tree so that traversal from `root` always ends at a solution.
+fn lint_tree(decision_tree: &TroubleshootDecisionTree, root: DecisionTreeNode) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+
+    for mut component in tarjan_scc(decision_tree) {
+        let self_loop = component.len() == 1
+            && decision_tree
+                .find_edge(component[0], component[0])
+                .is_some();
+        if component.len() > 1 || self_loop {
+            component.sort();
+            issues.push(LintIssue::Cycle(component));
+        }
+    }
+
+    let mut reachable = vec![false; decision_tree.node_count()];
+    let mut dfs = Dfs::new(decision_tree, root);
+    while let Some(node) = dfs.next(decision_tree) {
+        reachable[node.index()] = true;
+    }
+
+    for node in decision_tree.node_indices() {
+        if !reachable[node.index()] {
+            issues.push(LintIssue::Unreachable(node));
+        }
+        if !matches!(decision_tree[node], TroubleshootNode::Question(_)) {
+            continue;
+        }
+        let labels: Vec<String> = answers(decision_tree, node)
+            .into_iter()
+            .map(|(label, _)| label)
+            .collect();
+        if labels.is_empty() {
+            issues.push(LintIssue::DeadEnd(node));
+            continue;
+        }
+        let has = |answer: &str| labels.iter().any(|label| label == answer);
+        for (answer, other) in [("yes", "no"), ("no", "yes")] {
+            if has(other) && !has(answer) {
+                issues.push(LintIssue::MissingAnswer { node, answer });
+            }
+        }
+    }
+    issues
+}
+
+/// Accepts an answer by its full text, its 1-based position, or an unambiguous prefix,
+/// ignoring case and surrounding whitespace. Answers are expected in lowercase.
+fn match_answer(input: &str, answers: &[String]) -> Option<usize> {
+    let input = input.trim().to_lowercase();
+    if let Some(i) = answers.iter().position(|answer| *answer == input) {
+        return Some(i);
+    }
+    if let Ok(n) = input.parse::<usize>()
+        && (1..=answers.len()).contains(&n)
+    {
+        return Some(n - 1);
+    }
+    let matches: Vec<usize> = (0..answers.len())
+        .filter(|&i| !input.is_empty() && answers[i].starts_with(&input))
+        .collect();
+    match matches[..] {
+        [i] => Some(i),
+        _ => None,
+    }
+}
+
+fn prompt_for_answer(prompt: &str, answers: &[String]) -> usize {
     loop {
-        print!("{prompt} (yes/no): ");
+        print!("{prompt} ({}): ", answers.join("/"));
         let mut input = String::new();
         if let Err(e) = std::io::stdout().flush() {
             eprintln!("Error: {}", e);
             continue;
         }
-        if let Err(e) = std::io::stdin().read_line(&mut input) {
-            eprintln!("Error: {}", e);
-            continue;
+        match std::io::stdin().read_line(&mut input) {
+            // There is no answer to wait for once input ends.
+            Ok(0) => {
+                println!();
+                std::process::exit(0);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                continue;
+            }
         }
 
-        let input = input.trim().to_lowercase();
-        match input.as_str() {
-            "yes" | "y" => return true,
-            "no" | "n" => return false,
-            _ => println!("Invalid input. Please enter 'yes' or 'no'."),
+        if let Some(i) = match_answer(&input, answers) {
+            return i;
+        }
+        println!(
+            "Invalid input. Please enter one of: {}.",
+            answers.join(", ")
+        );
+    }
+}
+
+struct Cause {
+    name: &'static str,
+    action: &'static str,
+}
+
+const CAUSES: [Cause; 6] = [
+    Cause {
+        name: "Dead battery",
+        action: "Replace the battery.",
+    },
+    Cause {
+        name: "Corroded battery terminals",
+        action: "Clean terminals and try starting again.",
+    },
+    Cause {
+        name: "Faulty battery cables",
+        action: "Replace cables and try again.",
+    },
+    Cause {
+        name: "Ignition problem",
+        action: "Check spark plug connections.",
+    },
+    Cause {
+        name: "Stuck choke",
+        action: "Check to ensure the choke is opening and closing.",
+    },
+    Cause {
+        name: "Fuel injection fault",
+        action: "Get it in for service.",
+    },
+];
+
+/// A yes/no question whose answers add weight to the causes they point toward, indexed into
+/// `CAUSES`.
+struct SymptomQuestion {
+    text: &'static str,
+    yes: &'static [(usize, f64)],
+    no: &'static [(usize, f64)],
+}
+
+const SYMPTOM_QUESTIONS: [SymptomQuestion; 6] = [
+    SymptomQuestion {
+        text: "Is the car silent when you turn the key?",
+        yes: &[(0, 1.0), (1, 1.0), (2, 1.0)],
+        no: &[(3, 1.0), (4, 1.0), (5, 1.0)],
+    },
+    SymptomQuestion {
+        text: "Are the battery terminals corroded?",
+        yes: &[(1, 3.0)],
+        no: &[(0, 1.0), (2, 1.0)],
+    },
+    SymptomQuestion {
+        text: "Does the car make a clicking noise?",
+        yes: &[(0, 2.0)],
+        no: &[(2, 0.5), (3, 0.5)],
+    },
+    SymptomQuestion {
+        text: "Does the car crank up but fail to start?",
+        yes: &[(3, 2.0)],
+        no: &[(4, 1.0), (5, 1.0)],
+    },
+    SymptomQuestion {
+        text: "Does the engine start and then die?",
+        yes: &[(4, 2.0), (5, 2.0)],
+        no: &[(3, 1.0)],
+    },
+    SymptomQuestion {
+        text: "Does your car have a carburetor?",
+        yes: &[(4, 2.0)],
+        no: &[(5, 2.0)],
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Answer {
+    Yes,
+    No,
+    NotSure,
+}
+
+const ANSWERS: [(&str, Answer); 3] = [
+    ("yes", Answer::Yes),
+    ("no", Answer::No),
+    ("not sure", Answer::NotSure),
+];
+
+/// Sums the weights contributed by each answer and returns `(cause index, share of the total
+/// score)` pairs, most likely first. An unsure answer adds half the weight of both options.
+fn score_causes(questions: &[SymptomQuestion], answers: &[Answer]) -> Vec<(usize, f64)> {
+    let mut scores = vec![0.0; CAUSES.len()];
+    for (question, answer) in questions.iter().zip(answers) {
+        let contributions = match answer {
+            Answer::Yes => vec![(question.yes, 1.0)],
+            Answer::No => vec![(question.no, 1.0)],
+            Answer::NotSure => vec![(question.yes, 0.5), (question.no, 0.5)],
+        };
+        for (weights, factor) in contributions {
+            for &(cause, weight) in weights {
+                scores[cause] += weight * factor;
+            }
         }
     }
+
+    let total: f64 = scores.iter().sum();
+    let mut ranked: Vec<(usize, f64)> = scores
+        .into_iter()
+        .enumerate()
+        .filter(|&(_, score)| score > 0.0)
+        .map(|(cause, score)| (cause, score / total))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked
+}
+
+fn run_weighted_diagnosis() {
+    let labels: Vec<String> = ANSWERS.iter().map(|(label, _)| label.to_string()).collect();
+    let answers: Vec<Answer> = SYMPTOM_QUESTIONS
+        .iter()
+        .map(|question| ANSWERS[prompt_for_answer(question.text, &labels)].1)
+        .collect();
+
+    println!("Most likely problems:");
+    for (rank, (cause, share)) in score_causes(&SYMPTOM_QUESTIONS, &answers)
+        .into_iter()
+        .take(3)
+        .enumerate()
+    {
+        println!(
+            "{}. {} ({:.0}%): {}",
+            rank + 1,
+            CAUSES[cause].name,
+            share * 100.0,
+            CAUSES[cause].action
+        );
+    }
 }
 
 fn main() {
-    let is_question = |node: &str| node.contains('?');
+    let modes = vec!["step-by-step".to_string(), "weighted".to_string()];
+    if prompt_for_answer("Which diagnostic mode would you like to use?", &modes) == 1 {
+        run_weighted_diagnosis();
+        return;
+    }
+
     let (mut root, decision_tree) = create_troubleshoot_tree();
+    let issues = lint_tree(&decision_tree, root);
+    if !issues.is_empty() {
+        eprintln!("The decision tree is invalid:");
+        for issue in issues {
+            eprintln!("  - {}", issue);
+        }
+        std::process::exit(1);
+    }
 
     loop {
-        let current = decision_tree[root].clone();
-        if is_question(&current) {
-            let answer = prompt_for_answer(current.as_str());
-            let next_node = decision_tree
-                .edges(root)
-                .find(|edge| edge.weight() == &answer)
-                .map(|edge| edge.target())
-                .expect("No matching edge found");
-
-            root = next_node;
-        } else {
-            println!("{}", current);
-            break;
+        match &decision_tree[root] {
+            TroubleshootNode::Question(text) => {
+                let answers = answers(&decision_tree, root);
+                let labels: Vec<String> = answers.iter().map(|(label, _)| label.clone()).collect();
+                let choice = prompt_for_answer(text, &labels);
+                root = answers[choice].1;
+            }
+            TroubleshootNode::Solution { action, confidence } => {
+                println!("{} (confidence: {:.0}%)", action, confidence * 100.0);
+                break;
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_causes_ranks_strongest_evidence_first() {
+        let answers = [
+            Answer::Yes,
+            Answer::Yes,
+            Answer::No,
+            Answer::NotSure,
+            Answer::NotSure,
+            Answer::NotSure,
+        ];
+        let ranked = score_causes(&SYMPTOM_QUESTIONS, &answers);
+        assert_eq!(ranked[0].0, 1); // Corroded battery terminals
+        let total: f64 = ranked.iter().map(|(_, share)| share).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn lint_tree_accepts_builtin_tree() {
+        let (root, decision_tree) = create_troubleshoot_tree();
+        assert!(lint_tree(&decision_tree, root).is_empty());
+    }
+
+    #[test]
+    fn lint_tree_reports_problems_by_node() {
+        let mut tree: TroubleshootDecisionTree = Graph::new();
+        let root = tree.add_node(question("Root?"));
+        let loop_a = tree.add_node(question("A?"));
+        let loop_b = tree.add_node(question("B?"));
+        let dead_end = tree.add_node(question("Dead end?"));
+        let orphan = tree.add_node(solution("Orphan.", 0.5));
+        tree.add_edge(root, loop_a, "yes".to_string());
+        tree.add_edge(root, dead_end, "no".to_string());
+        tree.add_edge(loop_a, loop_b, "yes".to_string());
+        tree.add_edge(loop_b, loop_a, "no".to_string());
+
+        let issues = lint_tree(&tree, root);
+        assert!(issues.contains(&LintIssue::Cycle(vec![loop_a, loop_b])));
+        assert!(issues.contains(&LintIssue::Unreachable(orphan)));
+        assert!(issues.contains(&LintIssue::DeadEnd(dead_end)));
+        assert!(issues.contains(&LintIssue::MissingAnswer {
+            node: loop_a,
+            answer: "no"
+        }));
+        assert!(issues.contains(&LintIssue::MissingAnswer {
+            node: loop_b,
+            answer: "yes"
+        }));
+        assert_eq!(issues.len(), 5);
+        assert_eq!(issues[0].to_string(), "cycle through nodes 1, 2");
+    }
+
+    #[test]
+    fn score_causes_splits_unsure_answers() {
+        let question = [SymptomQuestion {
+            text: "?",
+            yes: &[(0, 2.0)],
+            no: &[(1, 2.0)],
+        }];
+        assert_eq!(
+            score_causes(&question, &[Answer::NotSure]),
+            vec![(0, 0.5), (1, 0.5)]
+        );
+        assert_eq!(score_causes(&question, &[Answer::No]), vec![(1, 1.0)]);
+    }
+
+    #[test]
+    fn match_answer_accepts_text_number_and_prefix() {
+        let answers: Vec<String> = ["yes", "no", "not sure"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(match_answer("yes", &answers), Some(0));
+        assert_eq!(match_answer("  NO\n", &answers), Some(1));
+        assert_eq!(match_answer("3", &answers), Some(2));
+        assert_eq!(match_answer("y", &answers), Some(0));
+        assert_eq!(match_answer("Not", &answers), Some(2));
+    }
+
+    #[test]
+    fn match_answer_rejects_ambiguous_and_unknown_input() {
+        let answers: Vec<String> = ["no", "not sure"].iter().map(|s| s.to_string()).collect();
+        // "no" is an exact answer even though it is also a prefix of "not sure".
+        assert_eq!(match_answer("no", &answers), Some(0));
+        assert_eq!(match_answer("n", &answers), None);
+        assert_eq!(match_answer("", &answers), None);
+        assert_eq!(match_answer("0", &answers), None);
+        assert_eq!(match_answer("3", &answers), None);
+        assert_eq!(match_answer("maybe", &answers), None);
+    }
+}
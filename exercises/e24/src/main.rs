@@ -11,7 +11,72 @@
 //! - **Special Character Support**: Considers spaces and special characters in comparisons
 //! - **Input Validation**: Ensures non-empty string inputs with proper error messages
 //! - **Comprehensive Testing**: Includes test cases for various anagram scenarios
-use std::io::Write;
+//! - **Configurable Normalization**: Optionally ignores case (`--ignore-case`) and
+//!   non-alphanumeric characters (`--ignore-punctuation`)
+//! - **Batch Mode**: Checks every pair in a CSV/TSV file (`--batch <file>`) and writes the
+//!   results, optionally to a file (`--output <file>`), followed by a summary count. Files
+//!   ending in `.tsv` or whose first line contains a tab are read as TSV, quoted fields may
+//!   contain the delimiter, and a leading `first,second` header row is skipped
+//! - **Anagram Groups**: Reads a whole word list (`--groups <file>`) and writes each group of
+//!   mutual anagrams on one line, largest groups first
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Normalization {
+    ignore_case: bool,
+    ignore_punctuation: bool,
+}
+
+impl Normalization {
+    fn apply(&self, s: &str) -> String {
+        s.chars()
+            .filter(|c| !self.ignore_punctuation || c.is_alphanumeric())
+            .flat_map(|c| {
+                if self.ignore_case {
+                    c.to_lowercase().collect::<Vec<char>>()
+                } else {
+                    vec![c]
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    normalization: Normalization,
+    batch_file: Option<PathBuf>,
+    groups_file: Option<PathBuf>,
+    output_file: Option<PathBuf>,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ignore-case" => options.normalization.ignore_case = true,
+            "--ignore-punctuation" => options.normalization.ignore_punctuation = true,
+            "--batch" => {
+                let path = args.next().ok_or("--batch requires a file path")?;
+                options.batch_file = Some(PathBuf::from(path));
+            }
+            "--groups" => {
+                let path = args.next().ok_or("--groups requires a file path")?;
+                options.groups_file = Some(PathBuf::from(path));
+            }
+            "--output" => {
+                let path = args.next().ok_or("--output requires a file path")?;
+                options.output_file = Some(PathBuf::from(path));
+            }
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+    Ok(options)
+}
 
 fn prompt_for_string(prompt: &str) -> String {
     loop {
@@ -42,11 +107,189 @@ fn is_anagram(s1: &str, s2: &str) -> bool {
     chars2.sort_unstable();
     chars1 == chars2
 }
+
+fn is_anagram_with(s1: &str, s2: &str, normalization: &Normalization) -> bool {
+    is_anagram(&normalization.apply(s1), &normalization.apply(s2))
+}
+
+/// Picks tabs for `.tsv` files or tab-separated content and commas otherwise.
+fn delimiter_for(path: &Path, content: &str) -> u8 {
+    let is_tsv = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("tsv"));
+    if is_tsv
+        || content
+            .lines()
+            .next()
+            .is_some_and(|line| line.contains('\t'))
+    {
+        b'\t'
+    } else {
+        b','
+    }
+}
+
+/// Checks every pair in `input`, writes one result row per pair to `output`, and returns the
+/// number of anagram and non-anagram pairs. Rows that do not have exactly two fields are
+/// rejected.
+fn check_pairs(
+    input: impl Read,
+    delimiter: u8,
+    output: &mut impl Write,
+    normalization: &Normalization,
+) -> Result<(usize, usize), std::io::Error> {
+    let (mut anagrams, mut non_anagrams) = (0, 0);
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(input);
+    let mut writer = csv::Writer::from_writer(output);
+    writer.write_record(["first", "second", "anagram"])?;
+    for (i, record) in reader.records().enumerate() {
+        let record = record?;
+        if record.len() != 2 {
+            let line = record.position().map_or(i as u64 + 1, |p| p.line());
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("line {}: expected 2 fields, found {}", line, record.len()),
+            ));
+        }
+        let (first, second) = (&record[0], &record[1]);
+        if i == 0 && first.eq_ignore_ascii_case("first") && second.eq_ignore_ascii_case("second") {
+            continue;
+        }
+
+        let result = is_anagram_with(first, second, normalization);
+        if result {
+            anagrams += 1;
+        } else {
+            non_anagrams += 1;
+        }
+        writer.write_record([first, second, &result.to_string()])?;
+    }
+    writer.flush()?;
+    Ok((anagrams, non_anagrams))
+}
+
+fn run_batch(
+    input_file: &Path,
+    output_file: Option<&Path>,
+    normalization: &Normalization,
+) -> Result<(), std::io::Error> {
+    let content = std::fs::read_to_string(input_file)?;
+    let delimiter = delimiter_for(input_file, &content);
+    let input = content.as_bytes();
+    let (anagrams, non_anagrams) = match output_file {
+        Some(path) => check_pairs(input, delimiter, &mut File::create(path)?, normalization)?,
+        None => check_pairs(input, delimiter, &mut std::io::stdout(), normalization)?,
+    };
+    println!(
+        "Checked {} pairs: {} anagrams, {} not anagrams.",
+        anagrams + non_anagrams,
+        anagrams,
+        non_anagrams
+    );
+    Ok(())
+}
+
+/// The sorted characters of the normalized word, shared by every anagram of it.
+fn signature(word: &str, normalization: &Normalization) -> String {
+    let mut chars: Vec<char> = normalization.apply(word).chars().collect();
+    chars.sort_unstable();
+    chars.into_iter().collect()
+}
+
+/// Buckets the words of `input` (one per line) by signature and returns the groups with at
+/// least two distinct words, largest first and ties ordered by their first word.
+fn anagram_groups(
+    input: impl BufRead,
+    normalization: &Normalization,
+) -> Result<Vec<Vec<String>>, std::io::Error> {
+    let mut buckets: HashMap<String, Vec<String>> = HashMap::new();
+    for line in input.lines() {
+        let line = line?;
+        let word = line.trim();
+        if word.is_empty() {
+            continue;
+        }
+        buckets
+            .entry(signature(word, normalization))
+            .or_default()
+            .push(word.to_string());
+    }
+
+    let mut groups: Vec<Vec<String>> = buckets
+        .into_values()
+        .map(|mut group| {
+            group.sort();
+            group.dedup();
+            group
+        })
+        .filter(|group| group.len() > 1)
+        .collect();
+    groups.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a[0].cmp(&b[0])));
+    Ok(groups)
+}
+
+fn write_groups(groups: &[Vec<String>], output: &mut impl Write) -> Result<(), std::io::Error> {
+    for group in groups {
+        writeln!(output, "{}", group.join(" "))?;
+    }
+    Ok(())
+}
+
+fn run_groups(
+    input_file: &Path,
+    output_file: Option<&Path>,
+    normalization: &Normalization,
+) -> Result<(), std::io::Error> {
+    let groups = anagram_groups(BufReader::new(File::open(input_file)?), normalization)?;
+    match output_file {
+        Some(path) => write_groups(&groups, &mut File::create(path)?)?,
+        None => write_groups(&groups, &mut std::io::stdout())?,
+    }
+    println!("Found {} anagram groups.", groups.len());
+    Ok(())
+}
+
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    if let Some(groups_file) = &options.groups_file {
+        if let Err(e) = run_groups(
+            groups_file,
+            options.output_file.as_deref(),
+            &options.normalization,
+        ) {
+            eprintln!("Error processing word list: {}", e);
+        }
+        return;
+    }
+
+    if let Some(batch_file) = &options.batch_file {
+        if let Err(e) = run_batch(
+            batch_file,
+            options.output_file.as_deref(),
+            &options.normalization,
+        ) {
+            eprintln!("Error processing batch file: {}", e);
+        }
+        return;
+    }
+
     println!("Enter two strings to check if they are anagrams.");
     let str1 = prompt_for_string("Enter the first string:");
     let str2 = prompt_for_string("Enter the second string:");
-    if is_anagram(&str1, &str2) {
+    if is_anagram_with(&str1, &str2, &options.normalization) {
         println!("The strings '{}' and '{}' are anagrams.", str1, str2);
     } else {
         println!("The strings '{}' and '{}' are not anagrams.", str1, str2);
@@ -103,4 +346,140 @@ mod tests {
         assert!(is_anagram("a", "a"));
         assert!(!is_anagram("a", "b"));
     }
+
+    #[test]
+    fn is_anagram_with_applies_normalization() {
+        let relaxed = Normalization {
+            ignore_case: true,
+            ignore_punctuation: true,
+        };
+        assert!(is_anagram_with("Dormitory", "Dirty room", &relaxed));
+        assert!(is_anagram_with(
+            "Tom Marvolo Riddle",
+            "I am Lord Voldemort",
+            &relaxed
+        ));
+        assert!(!is_anagram_with(
+            "Dormitory",
+            "Dirty room",
+            &Normalization::default()
+        ));
+    }
+
+    #[test]
+    fn check_pairs_writes_results_and_counts() {
+        let input = "first,second\nlisten,silent\nhello, world\n\nrust,trust\n";
+        let mut output = Vec::new();
+        let counts = check_pairs(
+            input.as_bytes(),
+            b',',
+            &mut output,
+            &Normalization::default(),
+        )
+        .unwrap();
+
+        assert_eq!(counts, (1, 2));
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "first,second,anagram\nlisten,silent,true\nhello,world,false\nrust,trust,false\n"
+        );
+    }
+
+    #[test]
+    fn check_pairs_quotes_fields_containing_commas_and_quotes() {
+        let input = "a,b\tb,a\n\"say \"\"hi\"\"\"\thi say\n";
+        let mut output = Vec::new();
+        check_pairs(
+            input.as_bytes(),
+            b'\t',
+            &mut output,
+            &Normalization::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "first,second,anagram\n\"a,b\",\"b,a\",true\n\"say \"\"hi\"\"\",hi say,false\n"
+        );
+    }
+
+    #[test]
+    fn check_pairs_reads_quoted_fields_and_rejects_malformed_rows() {
+        let relaxed = Normalization {
+            ignore_case: true,
+            ignore_punctuation: true,
+        };
+        let input = "\"Dormitory, old\",\"Dirty room, old\"\n";
+        let mut output = Vec::new();
+        assert_eq!(
+            check_pairs(input.as_bytes(), b',', &mut output, &relaxed).unwrap(),
+            (1, 0)
+        );
+        assert!(
+            String::from_utf8(output)
+                .unwrap()
+                .ends_with("\"Dormitory, old\",\"Dirty room, old\",true\n")
+        );
+
+        for input in ["listen,silent,enlist\n", "listen\n"] {
+            let error = check_pairs(input.as_bytes(), b',', &mut Vec::new(), &relaxed);
+            assert_eq!(error.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn delimiter_for_detects_tsv() {
+        assert_eq!(delimiter_for(Path::new("pairs.tsv"), "a,b"), b'\t');
+        assert_eq!(delimiter_for(Path::new("pairs.txt"), "a\tb\nc,d"), b'\t');
+        assert_eq!(delimiter_for(Path::new("pairs.csv"), "a,b\nc\td"), b',');
+    }
+
+    #[test]
+    fn anagram_groups_orders_by_size() {
+        let input = "listen\nenlist\nrust\nsilent\nruts\nhello\nlisten\n\nTinsel\n";
+        let groups = anagram_groups(input.as_bytes(), &Normalization::default()).unwrap();
+        assert_eq!(
+            groups,
+            vec![vec!["enlist", "listen", "silent"], vec!["rust", "ruts"]]
+        );
+
+        let ignore_case = Normalization {
+            ignore_case: true,
+            ..Default::default()
+        };
+        let groups = anagram_groups(input.as_bytes(), &ignore_case).unwrap();
+        assert_eq!(groups[0], vec!["Tinsel", "enlist", "listen", "silent"]);
+    }
+
+    #[test]
+    fn write_groups_prints_one_line_per_group() {
+        let groups = vec![
+            vec!["a".to_string(), "b".to_string()],
+            vec!["c".to_string()],
+        ];
+        let mut output = Vec::new();
+        write_groups(&groups, &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "a b\nc\n");
+    }
+
+    #[test]
+    fn parse_args_reads_batch_options() {
+        let args: Vec<String> = [
+            "--batch",
+            "pairs.csv",
+            "--ignore-case",
+            "--output",
+            "out.csv",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let options = parse_args(&args).unwrap();
+
+        assert_eq!(options.batch_file, Some(PathBuf::from("pairs.csv")));
+        assert_eq!(options.output_file, Some(PathBuf::from("out.csv")));
+        assert!(options.normalization.ignore_case);
+        assert!(!options.normalization.ignore_punctuation);
+        assert!(parse_args(&["--batch".to_string()]).is_err());
+    }
 }
@@ -0,0 +1,29 @@
+/* C interface to the e25 password strength library. Link against the `e25` cdylib. */
+#ifndef E25_H
+#define E25_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Category codes: 0 = Very Weak, 1 = Weak, 2 = Strong, 3 = Very Strong. */
+#define E25_INVALID_CATEGORY (-1)
+
+typedef struct E25Score {
+    double entropy_bits;
+    int category;
+} E25Score;
+
+/* Scores a NUL-terminated UTF-8 password. A null or non-UTF-8 password yields
+ * E25_INVALID_CATEGORY. */
+E25Score e25_score_password(const char *password);
+
+/* Returns the static display name of a category, or NULL if the category is unknown.
+ * The returned string must not be freed. */
+const char *e25_category_name(int category);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* E25_H */
@@ -0,0 +1,518 @@
+//! # Password Strength Library
+//!
+//! Password strength classification and entropy estimation shared by the strength validator
+//! GUI and the password generator, the random password generator itself, and generation of
+//! stronger variants of weak passwords built with that generator.
+//!
+//! The [`ffi`] module exposes the same scoring engine through a C-compatible interface; the
+//! crate is also built as a `cdylib` and `include/e25.h` declares the exported functions.
+use rand::Rng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(i32)]
+pub enum PasswordStrength {
+    VeryWeak = 0,
+    Weak = 1,
+    Strong = 2,
+    VeryStrong = 3,
+}
+
+impl TryFrom<i32> for PasswordStrength {
+    type Error = i32;
+
+    fn try_from(code: i32) -> Result<Self, Self::Error> {
+        match code {
+            0 => Ok(PasswordStrength::VeryWeak),
+            1 => Ok(PasswordStrength::Weak),
+            2 => Ok(PasswordStrength::Strong),
+            3 => Ok(PasswordStrength::VeryStrong),
+            _ => Err(code),
+        }
+    }
+}
+
+impl Display for PasswordStrength {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PasswordStrength::VeryWeak => write!(f, "Very Weak"),
+            PasswordStrength::Weak => write!(f, "Weak"),
+            PasswordStrength::Strong => write!(f, "Strong"),
+            PasswordStrength::VeryStrong => write!(f, "Very Strong"),
+        }
+    }
+}
+
+pub fn is_very_weak(password: &str) -> bool {
+    if password.is_empty() {
+        return true;
+    }
+    password.len() < 8 && password.chars().all(|c| c.is_numeric())
+}
+
+pub fn is_weak(password: &str) -> bool {
+    if password.is_empty() {
+        return false;
+    }
+    password.len() < 8 && password.chars().all(|c| c.is_alphabetic())
+}
+
+pub fn is_strong(password: &str) -> bool {
+    if password.is_empty() {
+        return false;
+    }
+    password.len() >= 8
+        && password.chars().any(|c| c.is_numeric())
+        && password.chars().any(|c| c.is_alphabetic())
+        && password.chars().all(|c| c.is_alphanumeric())
+}
+
+pub fn is_very_strong(password: &str) -> bool {
+    if password.is_empty() {
+        return false;
+    }
+    password.len() >= 8
+        && password.chars().any(|c| c.is_numeric())
+        && password.chars().any(|c| c.is_alphabetic())
+        && password.chars().any(|c| !c.is_alphanumeric())
+}
+
+pub fn password_strength(password: &str) -> PasswordStrength {
+    if is_very_weak(password) {
+        PasswordStrength::VeryWeak
+    } else if is_weak(password) {
+        PasswordStrength::Weak
+    } else if is_strong(password) {
+        PasswordStrength::Strong
+    } else if is_very_strong(password) {
+        PasswordStrength::VeryStrong
+    } else {
+        PasswordStrength::Weak // Default case for invalid passwords
+    }
+}
+
+/// Estimates the entropy of `password` in bits as `length * log2(pool size)`, where the pool
+/// is the union of the character classes the password draws from.
+pub fn entropy_bits(password: &str) -> f64 {
+    let mut pool = 0u32;
+    if password.chars().any(|c| c.is_ascii_lowercase()) {
+        pool += 26;
+    }
+    if password.chars().any(|c| c.is_ascii_uppercase()) {
+        pool += 26;
+    }
+    if password.chars().any(|c| c.is_ascii_digit()) {
+        pool += 10;
+    }
+    if password.chars().any(|c| !c.is_ascii_alphanumeric()) {
+        pool += 33;
+    }
+    if pool == 0 {
+        return 0.0;
+    }
+    password.chars().count() as f64 * f64::from(pool).log2()
+}
+
+/// The strength category and entropy estimate of a password.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PasswordScore {
+    pub strength: PasswordStrength,
+    pub entropy_bits: f64,
+}
+
+pub fn score_password(password: &str) -> PasswordScore {
+    PasswordScore {
+        strength: password_strength(password),
+        entropy_bits: entropy_bits(password),
+    }
+}
+
+/// C-compatible wrappers around [`score_password`]. Passwords are NUL-terminated UTF-8 strings
+/// and categories are the [`PasswordStrength`] discriminants (0 = very weak ... 3 = very strong).
+pub mod ffi {
+    use super::{PasswordStrength, score_password};
+    use std::ffi::{CStr, c_char};
+
+    /// Returned as the category when a password is null or not valid UTF-8.
+    pub const E25_INVALID_CATEGORY: i32 = -1;
+
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct E25Score {
+        pub entropy_bits: f64,
+        pub category: i32,
+    }
+
+    /// Scores `password`.
+    ///
+    /// # Safety
+    ///
+    /// `password` must be null or point to a NUL-terminated string that stays valid for the
+    /// duration of the call.
+    #[unsafe(no_mangle)]
+    pub unsafe extern "C" fn e25_score_password(password: *const c_char) -> E25Score {
+        if password.is_null() {
+            return E25Score {
+                entropy_bits: 0.0,
+                category: E25_INVALID_CATEGORY,
+            };
+        }
+        // SAFETY: the caller guarantees a valid NUL-terminated string.
+        match unsafe { CStr::from_ptr(password) }.to_str() {
+            Ok(password) => {
+                let score = score_password(password);
+                E25Score {
+                    entropy_bits: score.entropy_bits,
+                    category: score.strength as i32,
+                }
+            }
+            Err(_) => E25Score {
+                entropy_bits: 0.0,
+                category: E25_INVALID_CATEGORY,
+            },
+        }
+    }
+
+    /// Returns the static, NUL-terminated display name of `category`, or null for an unknown
+    /// category. The caller must not free the returned string.
+    #[unsafe(no_mangle)]
+    pub extern "C" fn e25_category_name(category: i32) -> *const c_char {
+        let name: &CStr = match PasswordStrength::try_from(category) {
+            Ok(PasswordStrength::VeryWeak) => c"Very Weak",
+            Ok(PasswordStrength::Weak) => c"Weak",
+            Ok(PasswordStrength::Strong) => c"Strong",
+            Ok(PasswordStrength::VeryStrong) => c"Very Strong",
+            Err(_) => return std::ptr::null(),
+        };
+        name.as_ptr()
+    }
+}
+
+const ALPHABET: [char; 52] = {
+    let mut alphabet = ['a'; 52];
+    let mut i = 0;
+    while i < 26 {
+        alphabet[2 * i] = (b'a' + i as u8) as char;
+        alphabet[2 * i + 1] = (b'A' + i as u8) as char;
+        i += 1;
+    }
+    alphabet
+};
+const DIGITS: [char; 10] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+const SPECIAL_CHARS: [char; 29] = [
+    '!', '@', '#', '$', '%', '^', '&', '*', '(', ')', '-', '_', '=', '+', '{', '}', '[', ']', ':',
+    ';', '"', '\'', '<', '>', ',', '.', '?', '/', '\\',
+];
+
+/// A character class that a generated password draws from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PasswordComponent {
+    AlphaChar,
+    Digit,
+    SpecialChar,
+}
+
+pub const COMPONENT_CLASSES: [PasswordComponent; 3] = [
+    PasswordComponent::AlphaChar,
+    PasswordComponent::Digit,
+    PasswordComponent::SpecialChar,
+];
+
+impl PasswordComponent {
+    /// The characters this class draws from.
+    pub fn chars(&self) -> &'static [char] {
+        match self {
+            PasswordComponent::AlphaChar => &ALPHABET,
+            PasswordComponent::Digit => &DIGITS,
+            PasswordComponent::SpecialChar => &SPECIAL_CHARS,
+        }
+    }
+
+    /// The class `c` belongs to, if any.
+    pub fn of(c: char) -> Option<PasswordComponent> {
+        COMPONENT_CLASSES
+            .into_iter()
+            .find(|component| component.chars().contains(&c))
+    }
+
+    pub fn random_char(&self, rng: &mut impl Rng) -> char {
+        *self.chars().choose(rng).unwrap()
+    }
+}
+
+/// Generates a password with one random character per component, in shuffled order.
+pub fn generate_password(components: &[PasswordComponent], rng: &mut impl Rng) -> String {
+    let mut components = components.to_vec();
+    components.shuffle(rng);
+    components
+        .iter()
+        .map(|component| component.random_char(rng))
+        .collect()
+}
+
+const MIN_LENGTH: usize = 8;
+const STRENGTHENED_LENGTH: usize = 12;
+const PASSPHRASE_WORDS: usize = 4;
+const WORD_LIST: [&str; 32] = [
+    "anchor", "basil", "cactus", "dragon", "ember", "falcon", "glacier", "harbor", "iris",
+    "jasper", "kettle", "lantern", "meadow", "nickel", "orchid", "pepper", "quartz", "raven",
+    "saddle", "timber", "umbra", "velvet", "walnut", "yonder", "zephyr", "bramble", "cobalt",
+    "dune", "fjord", "gravel", "hollow", "juniper",
+];
+
+/// How an improved variant was derived from the original password.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HintKind {
+    /// The original with the missing character classes appended.
+    AddMissing,
+    /// The original extended with extra digits and symbols to a longer length.
+    Lengthen,
+    /// An unrelated passphrase of random words.
+    Passphrase,
+}
+
+/// A concrete stronger alternative to a password along with its projected score.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hint {
+    pub kind: HintKind,
+    pub password: String,
+    pub strength: PasswordStrength,
+    pub entropy: f64,
+}
+
+/// Appends a letter, digit, or symbol for each class `password` lacks, padding with letters up
+/// to the minimum length.
+fn add_missing(password: &str, rng: &mut impl Rng) -> String {
+    let mut missing = Vec::new();
+    if !password.chars().any(|c| c.is_alphabetic()) {
+        missing.push(PasswordComponent::AlphaChar);
+    }
+    if !password.chars().any(|c| c.is_numeric()) {
+        missing.push(PasswordComponent::Digit);
+    }
+    if password.chars().all(|c| c.is_alphanumeric()) {
+        missing.push(PasswordComponent::SpecialChar);
+    }
+    let padding = MIN_LENGTH.saturating_sub(password.chars().count() + missing.len());
+    missing.extend(std::iter::repeat_n(PasswordComponent::AlphaChar, padding));
+    format!("{}{}", password, generate_password(&missing, rng))
+}
+
+fn lengthen(password: &str, rng: &mut impl Rng) -> String {
+    let variant = add_missing(password, rng);
+    let extra = STRENGTHENED_LENGTH
+        .max(password.chars().count() + 4)
+        .saturating_sub(variant.chars().count());
+    let components: Vec<PasswordComponent> = [
+        PasswordComponent::Digit,
+        PasswordComponent::SpecialChar,
+        PasswordComponent::AlphaChar,
+    ]
+    .into_iter()
+    .cycle()
+    .take(extra)
+    .collect();
+    format!("{}{}", variant, generate_password(&components, rng))
+}
+
+fn passphrase(rng: &mut impl Rng) -> String {
+    let words: Vec<&str> = WORD_LIST
+        .choose_multiple(rng, PASSPHRASE_WORDS)
+        .copied()
+        .collect();
+    format!(
+        "{}-{}",
+        words.join("-"),
+        PasswordComponent::Digit.random_char(rng)
+    )
+}
+
+/// Generates two or three stronger variants of `password`, keeping only those that score
+/// better than the original. Returns nothing for passwords that are already strong.
+pub fn improvement_hints(password: &str, rng: &mut impl Rng) -> Vec<Hint> {
+    let strength = password_strength(password);
+    if strength >= PasswordStrength::Strong {
+        return Vec::new();
+    }
+
+    let candidates = [
+        (HintKind::AddMissing, add_missing(password, rng)),
+        (HintKind::Lengthen, lengthen(password, rng)),
+        (HintKind::Passphrase, passphrase(rng)),
+    ];
+    let mut hints: Vec<Hint> = Vec::new();
+    for (kind, variant) in candidates {
+        if hints.iter().any(|h| h.password == variant) {
+            continue;
+        }
+        let hint = Hint {
+            kind,
+            strength: password_strength(&variant),
+            entropy: entropy_bits(&variant),
+            password: variant,
+        };
+        if hint.strength > strength {
+            hints.push(hint);
+        }
+    }
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn is_very_weak_identifies_passwords_correctly() {
+        assert!(is_very_weak("123456"));
+        assert!(!is_very_weak("12345678")); // Long enough but only numbers
+        assert!(!is_very_weak("123abc")); // Contains letters
+        assert!(is_very_weak("")); // Empty string
+    }
+
+    #[test]
+    fn is_weak_identifies_passwords_correctly() {
+        assert!(is_weak("abcdef"));
+        assert!(!is_weak("PASSWORD"));
+        assert!(!is_weak("abcdefgh")); // Long enough but only letters
+        assert!(!is_weak("abc123")); // Contains numbers
+    }
+
+    #[test]
+    fn is_strong_identifies_passwords_correctly() {
+        assert!(is_strong("abcd1234"));
+        assert!(is_strong("Pass1234"));
+        assert!(!is_strong("pass123")); // Not long enough
+        assert!(!is_strong("password")); // No numbers
+        assert!(!is_strong("12345678")); // No letters
+        assert!(!is_strong("Pass123!")); // Contains special character
+    }
+
+    #[test]
+    fn is_very_strong_identifies_passwords_correctly() {
+        assert!(is_very_strong("abcd123!"));
+        assert!(is_very_strong("P@ssw0rd"));
+        assert!(!is_very_strong("pass123")); // Not long enough
+        assert!(!is_very_strong("password!")); // No numbers
+        assert!(!is_very_strong("Pass1234")); // No special characters
+    }
+
+    #[test]
+    fn password_strength_returns_correct_strength() {
+        // Very weak passwords (numbers only, less than 8 chars)
+        assert_eq!(password_strength("123456"), PasswordStrength::VeryWeak);
+        // Weak passwords (letters only, less than 8 chars)
+        assert_eq!(password_strength("abcdef"), PasswordStrength::Weak);
+        // Strong passwords (letters and numbers, at least 8 chars, alphanumeric only)
+        assert_eq!(password_strength("abcd1234"), PasswordStrength::Strong);
+        // Very strong passwords (letters, numbers, and special chars, at least 8 chars)
+        assert_eq!(password_strength("P@ssw0rd"), PasswordStrength::VeryStrong);
+        // Default case: too short with mixed types
+        assert_eq!(password_strength("a1!"), PasswordStrength::Weak);
+    }
+
+    #[test]
+    fn entropy_bits_grows_with_pool_and_length() {
+        assert_eq!(entropy_bits(""), 0.0);
+        assert!((entropy_bits("123456") - 6.0 * 10f64.log2()).abs() < 1e-9);
+        assert!((entropy_bits("abcdefgh") - 8.0 * 26f64.log2()).abs() < 1e-9);
+        assert!(entropy_bits("Abcdefg1") > entropy_bits("abcdefg1"));
+        assert!(entropy_bits("P@ssw0rd!") > entropy_bits("P@ssw0rd"));
+    }
+
+    #[test]
+    fn improvement_hints_produce_stronger_variants() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let hints = improvement_hints("abc", &mut rng);
+        let kinds: Vec<HintKind> = hints.iter().map(|h| h.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                HintKind::AddMissing,
+                HintKind::Lengthen,
+                HintKind::Passphrase
+            ]
+        );
+        assert!(hints[0].password.starts_with("abc"));
+        assert_eq!(hints[0].strength, PasswordStrength::VeryStrong);
+        assert!(hints[1].password.chars().count() >= STRENGTHENED_LENGTH);
+        assert!(hints[1].entropy > hints[0].entropy);
+        assert_eq!(hints[2].password.split('-').count(), PASSPHRASE_WORDS + 1);
+    }
+
+    #[test]
+    fn generate_password_draws_one_character_per_component() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let components = [
+            PasswordComponent::AlphaChar,
+            PasswordComponent::AlphaChar,
+            PasswordComponent::Digit,
+            PasswordComponent::SpecialChar,
+        ];
+        let password = generate_password(&components, &mut rng);
+        let classes: Vec<Option<PasswordComponent>> =
+            password.chars().map(PasswordComponent::of).collect();
+        assert_eq!(password.chars().count(), 4);
+        for class in COMPONENT_CLASSES {
+            assert_eq!(
+                classes.iter().filter(|c| **c == Some(class)).count(),
+                components.iter().filter(|c| **c == class).count()
+            );
+        }
+    }
+
+    #[test]
+    fn improvement_hints_skip_strong_passwords() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert!(improvement_hints("P@ssw0rd", &mut rng).is_empty());
+        assert!(improvement_hints("abcd1234", &mut rng).is_empty());
+    }
+
+    #[test]
+    fn score_password_combines_strength_and_entropy() {
+        let score = score_password("abc123!@#");
+        assert_eq!(score.strength, PasswordStrength::VeryStrong);
+        assert_eq!(score.entropy_bits, entropy_bits("abc123!@#"));
+        for strength in [
+            PasswordStrength::VeryWeak,
+            PasswordStrength::Weak,
+            PasswordStrength::Strong,
+            PasswordStrength::VeryStrong,
+        ] {
+            assert_eq!(PasswordStrength::try_from(strength as i32), Ok(strength));
+        }
+        assert_eq!(PasswordStrength::try_from(4), Err(4));
+    }
+
+    #[test]
+    fn ffi_score_round_trips_through_c_strings() {
+        use std::ffi::{CStr, CString};
+
+        for password in ["123", "abc", "abc123xyz", "abc123!@#", "pässwörd!1"] {
+            let c_password = CString::new(password).unwrap();
+            let score = unsafe { ffi::e25_score_password(c_password.as_ptr()) };
+            let expected = score_password(password);
+            assert_eq!(score.category, expected.strength as i32);
+            assert_eq!(score.entropy_bits, expected.entropy_bits);
+
+            let name = unsafe { CStr::from_ptr(ffi::e25_category_name(score.category)) };
+            assert_eq!(name.to_str().unwrap(), expected.strength.to_string());
+        }
+    }
+
+    #[test]
+    fn ffi_rejects_null_and_invalid_input() {
+        let null_score = unsafe { ffi::e25_score_password(std::ptr::null()) };
+        assert_eq!(null_score.category, ffi::E25_INVALID_CATEGORY);
+
+        let invalid_utf8 = [0xffu8, 0xfe, 0];
+        let score = unsafe { ffi::e25_score_password(invalid_utf8.as_ptr().cast()) };
+        assert_eq!(score.category, ffi::E25_INVALID_CATEGORY);
+
+        assert!(ffi::e25_category_name(ffi::E25_INVALID_CATEGORY).is_null());
+        assert!(ffi::e25_category_name(4).is_null());
+    }
+}
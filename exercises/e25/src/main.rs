@@ -11,89 +11,221 @@
 //! - **Visual Indicators**: Color-coded feedback based on password strength
 //! - **Security Rules**: Enforces modern password security best practices
 //! - **Validation Logic**: Clear criteria for each password strength level
+//! - **Entropy Estimate**: Shows the estimated entropy in bits alongside the strength level
+//! - **Suggestions**: Lists what to add to make a password stronger
+//! - **Localization**: Language selector for English and Spanish labels, strength names, and
+//!   suggestions
+//! - **Improved Variants**: For weak passwords, shows two or three concrete stronger variants
+//!   (adding missing characters, lengthening, or a passphrase) with their projected scores
+use e25::{Hint, HintKind, PasswordStrength, entropy_bits, improvement_hints, password_strength};
 use eframe::egui::{self};
 
-enum PasswordStrength {
-    VeryWeak,
-    Weak,
-    Strong,
-    VeryStrong,
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum Language {
+    #[default]
+    English,
+    Spanish,
 }
 
-#[derive(Debug, Default)]
-struct PasswordValidator {
-    password: String,
-}
+const LANGUAGES: [Language; 2] = [Language::English, Language::Spanish];
 
-impl PasswordValidator {
-    fn is_very_weak(&self) -> bool {
-        if self.password.is_empty() {
-            return true;
+impl Language {
+    fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
         }
-        self.password.len() < 8 && self.password.chars().all(|c| c.is_numeric())
     }
 
-    fn is_weak(&self) -> bool {
-        if self.password.is_empty() {
-            return false;
+    fn strings(&self) -> &'static UiStrings {
+        match self {
+            Language::English => &ENGLISH,
+            Language::Spanish => &SPANISH,
         }
-        self.password.len() < 8 && self.password.chars().all(|c| c.is_alphabetic())
     }
+}
+
+/// Language pack holding every user-facing string in the GUI.
+struct UiStrings {
+    language: &'static str,
+    enter_password: &'static str,
+    very_weak: &'static str,
+    weak: &'static str,
+    strong: &'static str,
+    very_strong: &'static str,
+    entropy: &'static str,
+    suggestions: &'static str,
+    use_more_characters: &'static str,
+    add_digit: &'static str,
+    add_letter: &'static str,
+    add_special: &'static str,
+    try_instead: &'static str,
+    add_missing: &'static str,
+    lengthen: &'static str,
+    passphrase: &'static str,
+}
 
-    fn is_strong(&self) -> bool {
-        if self.password.is_empty() {
-            return false;
+const ENGLISH: UiStrings = UiStrings {
+    language: "Language:",
+    enter_password: "Enter Password:",
+    very_weak: "Very Weak Password",
+    weak: "Weak Password",
+    strong: "Strong Password",
+    very_strong: "Very Strong Password",
+    entropy: "Estimated entropy: {} bits",
+    suggestions: "Suggestions:",
+    use_more_characters: "Use at least 8 characters",
+    add_digit: "Add a number",
+    add_letter: "Add a letter",
+    add_special: "Add a special character",
+    try_instead: "Try one of these instead:",
+    add_missing: "Add missing characters",
+    lengthen: "Make it longer",
+    passphrase: "Use a passphrase",
+};
+
+const SPANISH: UiStrings = UiStrings {
+    language: "Idioma:",
+    enter_password: "Introduzca la contraseña:",
+    very_weak: "Contraseña muy débil",
+    weak: "Contraseña débil",
+    strong: "Contraseña fuerte",
+    very_strong: "Contraseña muy fuerte",
+    entropy: "Entropía estimada: {} bits",
+    suggestions: "Sugerencias:",
+    use_more_characters: "Use al menos 8 caracteres",
+    add_digit: "Añada un número",
+    add_letter: "Añada una letra",
+    add_special: "Añada un carácter especial",
+    try_instead: "Pruebe una de estas:",
+    add_missing: "Añada los caracteres que faltan",
+    lengthen: "Hágala más larga",
+    passphrase: "Use una frase de contraseña",
+};
+
+impl UiStrings {
+    fn strength(&self, strength: PasswordStrength) -> &'static str {
+        match strength {
+            PasswordStrength::VeryWeak => self.very_weak,
+            PasswordStrength::Weak => self.weak,
+            PasswordStrength::Strong => self.strong,
+            PasswordStrength::VeryStrong => self.very_strong,
         }
-        self.password.len() >= 8
-            && self.password.chars().any(|c| c.is_numeric())
-            && self.password.chars().any(|c| c.is_alphabetic())
-            && self.password.chars().all(|c| c.is_alphanumeric())
     }
 
-    fn is_very_strong(&self) -> bool {
-        if self.password.is_empty() {
-            return false;
+    fn suggestion(&self, suggestion: Suggestion) -> &'static str {
+        match suggestion {
+            Suggestion::UseMoreCharacters => self.use_more_characters,
+            Suggestion::AddDigit => self.add_digit,
+            Suggestion::AddLetter => self.add_letter,
+            Suggestion::AddSpecial => self.add_special,
         }
-        self.password.len() >= 8
-            && self.password.chars().any(|c| c.is_numeric())
-            && self.password.chars().any(|c| c.is_alphabetic())
-            && self.password.chars().any(|c| !c.is_alphanumeric())
     }
 
-    fn get_password_strength(&self) -> PasswordStrength {
-        if self.is_very_weak() {
-            PasswordStrength::VeryWeak
-        } else if self.is_weak() {
-            PasswordStrength::Weak
-        } else if self.is_strong() {
-            PasswordStrength::Strong
-        } else if self.is_very_strong() {
-            PasswordStrength::VeryStrong
-        } else {
-            PasswordStrength::Weak // Default case for invalid passwords
+    fn hint(&self, kind: HintKind) -> &'static str {
+        match kind {
+            HintKind::AddMissing => self.add_missing,
+            HintKind::Lengthen => self.lengthen,
+            HintKind::Passphrase => self.passphrase,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Suggestion {
+    UseMoreCharacters,
+    AddDigit,
+    AddLetter,
+    AddSpecial,
+}
+
+/// Lists the criteria a password is missing to be classified as very strong.
+fn suggestions(password: &str) -> Vec<Suggestion> {
+    let mut suggestions = Vec::new();
+    if password.len() < 8 {
+        suggestions.push(Suggestion::UseMoreCharacters);
+    }
+    if !password.chars().any(|c| c.is_numeric()) {
+        suggestions.push(Suggestion::AddDigit);
+    }
+    if !password.chars().any(|c| c.is_alphabetic()) {
+        suggestions.push(Suggestion::AddLetter);
+    }
+    if password.chars().all(|c| c.is_alphanumeric()) {
+        suggestions.push(Suggestion::AddSpecial);
+    }
+    suggestions
+}
+
+#[derive(Debug, Default)]
+struct PasswordValidator {
+    password: String,
+    language: Language,
+    // Variants are regenerated only when the password changes so they don't flicker.
+    hints: Vec<Hint>,
+    hints_for: String,
+}
+
+impl PasswordValidator {
+    fn refresh_hints(&mut self) {
+        if self.hints_for != self.password {
+            self.hints = improvement_hints(&self.password, &mut rand::rng());
+            self.hints_for = self.password.clone();
         }
     }
 }
 
 impl eframe::App for PasswordValidator {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let strings = self.language.strings();
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.label("Enter Password:");
+            ui.horizontal(|ui| {
+                ui.label(strings.language);
+                egui::ComboBox::from_id_salt("language")
+                    .selected_text(self.language.name())
+                    .show_ui(ui, |ui| {
+                        for language in LANGUAGES {
+                            ui.selectable_value(&mut self.language, language, language.name());
+                        }
+                    });
+            });
+
+            ui.label(strings.enter_password);
             ui.text_edit_singleline(&mut self.password);
 
             if !self.password.is_empty() {
-                match self.get_password_strength() {
-                    PasswordStrength::VeryWeak => {
-                        ui.colored_label(egui::Color32::RED, "Very Weak Password");
+                let strength = password_strength(&self.password);
+                let color = match strength {
+                    PasswordStrength::VeryWeak => egui::Color32::RED,
+                    PasswordStrength::Weak => egui::Color32::YELLOW,
+                    PasswordStrength::Strong => egui::Color32::DARK_GREEN,
+                    PasswordStrength::VeryStrong => egui::Color32::GREEN,
+                };
+                ui.colored_label(color, strings.strength(strength));
+                let entropy = format!("{:.1}", entropy_bits(&self.password));
+                ui.label(strings.entropy.replace("{}", &entropy));
+
+                let suggestions = suggestions(&self.password);
+                if !suggestions.is_empty() {
+                    ui.label(strings.suggestions);
+                    for suggestion in suggestions {
+                        ui.label(format!("• {}", strings.suggestion(suggestion)));
                     }
-                    PasswordStrength::Weak => {
-                        ui.colored_label(egui::Color32::YELLOW, "Weak Password");
-                    }
-                    PasswordStrength::Strong => {
-                        ui.colored_label(egui::Color32::DARK_GREEN, "Strong Password");
-                    }
-                    PasswordStrength::VeryStrong => {
-                        ui.colored_label(egui::Color32::GREEN, "Very Strong Password");
+                }
+
+                self.refresh_hints();
+                if !self.hints.is_empty() {
+                    ui.label(strings.try_instead);
+                    for hint in &self.hints {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("• {}:", strings.hint(hint.kind)));
+                            ui.monospace(&hint.password);
+                        });
+                        ui.label(format!(
+                            "    {} ({:.1} bits)",
+                            strings.strength(hint.strength),
+                            hint.entropy
+                        ));
                     }
                 }
             }
@@ -103,7 +235,7 @@ impl eframe::App for PasswordValidator {
 
 fn main() -> eframe::Result {
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 100.0]),
+        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 360.0]),
         ..Default::default()
     };
     eframe::run_native(
@@ -118,157 +250,35 @@ mod tests {
     use super::*;
 
     #[test]
-    fn is_very_weak_identifies_passwords_correctly() {
-        let validator = PasswordValidator {
-            password: "123456".to_string(),
-        };
-        assert!(validator.is_very_weak());
-
-        let validator = PasswordValidator {
-            password: "12345678".to_string(),
-        };
-        assert!(!validator.is_very_weak()); // Long enough but only numbers
-
-        let validator = PasswordValidator {
-            password: "123abc".to_string(),
-        };
-        assert!(!validator.is_very_weak()); // Contains letters
-
-        let validator = PasswordValidator {
-            password: "".to_string(),
-        };
-        assert!(validator.is_very_weak()); // Empty string
+    fn suggestions_list_missing_criteria() {
+        assert_eq!(
+            suggestions("abc"),
+            vec![
+                Suggestion::UseMoreCharacters,
+                Suggestion::AddDigit,
+                Suggestion::AddSpecial
+            ]
+        );
+        assert_eq!(
+            suggestions("12345678"),
+            vec![Suggestion::AddLetter, Suggestion::AddSpecial]
+        );
+        assert!(suggestions("P@ssw0rd").is_empty());
     }
 
     #[test]
-    fn is_weak_identifies_passwords_correctly() {
-        let validator = PasswordValidator {
-            password: "abcdef".to_string(),
-        };
-        assert!(validator.is_weak());
-
-        let validator = PasswordValidator {
-            password: "PASSWORD".to_string(),
-        };
-        assert!(!validator.is_weak());
-
-        let validator = PasswordValidator {
-            password: "abcdefgh".to_string(),
-        };
-        assert!(!validator.is_weak()); // Long enough but only letters
-
-        let validator = PasswordValidator {
-            password: "abc123".to_string(),
-        };
-        assert!(!validator.is_weak()); // Contains numbers
-    }
-
-    #[test]
-    fn is_strong_identifies_passwords_correctly() {
-        let validator = PasswordValidator {
-            password: "abcd1234".to_string(),
-        };
-        assert!(validator.is_strong());
-
-        let validator = PasswordValidator {
-            password: "Pass1234".to_string(),
-        };
-        assert!(validator.is_strong());
-
-        let validator = PasswordValidator {
-            password: "pass123".to_string(),
-        };
-        assert!(!validator.is_strong()); // Not long enough
-
-        let validator = PasswordValidator {
-            password: "password".to_string(),
-        };
-        assert!(!validator.is_strong()); // No numbers
-
-        let validator = PasswordValidator {
-            password: "12345678".to_string(),
-        };
-        assert!(!validator.is_strong()); // No letters
-
-        let validator = PasswordValidator {
-            password: "Pass123!".to_string(),
-        };
-        assert!(!validator.is_strong()); // Contains special character
-    }
-
-    #[test]
-    fn is_very_strong_identifies_passwords_correctly() {
-        let validator = PasswordValidator {
-            password: "abcd123!".to_string(),
-        };
-        assert!(validator.is_very_strong());
-
-        let validator = PasswordValidator {
-            password: "P@ssw0rd".to_string(),
-        };
-        assert!(validator.is_very_strong());
-
-        let validator = PasswordValidator {
-            password: "pass123".to_string(),
-        };
-        assert!(!validator.is_very_strong()); // Not long enough
-
-        let validator = PasswordValidator {
-            password: "password!".to_string(),
-        };
-        assert!(!validator.is_very_strong()); // No numbers
-
-        let validator = PasswordValidator {
-            password: "Pass1234".to_string(),
-        };
-        assert!(!validator.is_very_strong()); // No special characters
-    }
-
-    #[test]
-    fn get_password_strength_returns_correct_strength() {
-        // Very weak passwords (numbers only, less than 8 chars)
-        let validator = PasswordValidator {
-            password: "123456".to_string(),
-        };
-        assert!(matches!(
-            validator.get_password_strength(),
-            PasswordStrength::VeryWeak
-        ));
-
-        // Weak passwords (letters only, less than 8 chars)
-        let validator = PasswordValidator {
-            password: "abcdef".to_string(),
-        };
-        assert!(matches!(
-            validator.get_password_strength(),
-            PasswordStrength::Weak
-        ));
-
-        // Strong passwords (letters and numbers, at least 8 chars, alphanumeric only)
-        let validator = PasswordValidator {
-            password: "abcd1234".to_string(),
-        };
-        assert!(matches!(
-            validator.get_password_strength(),
-            PasswordStrength::Strong
-        ));
-
-        // Very strong passwords (letters, numbers, and special chars, at least 8 chars)
-        let validator = PasswordValidator {
-            password: "P@ssw0rd".to_string(),
-        };
-        assert!(matches!(
-            validator.get_password_strength(),
-            PasswordStrength::VeryStrong
-        ));
-
-        // Default case
-        let validator = PasswordValidator {
-            password: "a1!".to_string(),
-        }; // Too short with mixed types
-        assert!(matches!(
-            validator.get_password_strength(),
-            PasswordStrength::Weak
-        ));
+    fn language_packs_localize_strength_names() {
+        assert_eq!(
+            Language::English.strings().strength(PasswordStrength::Weak),
+            "Weak Password"
+        );
+        assert_eq!(
+            Language::Spanish.strings().strength(PasswordStrength::Weak),
+            "Contraseña débil"
+        );
+        assert_eq!(
+            Language::Spanish.strings().suggestion(Suggestion::AddDigit),
+            "Añada un número"
+        );
     }
 }
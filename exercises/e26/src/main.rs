@@ -6,29 +6,102 @@
 //! ## Features
 //!
 //! - **Interactive Interface**: Real-time calculation as values are adjusted
-//! - **Financial Formula**: Implements standard credit card payment duration formula
+//! - **Payment Schedule**: Steps through the balance month by month, so the payoff time and the
+//!   transfer comparison below always agree
 //! - **Key Parameters**: Takes into account balance, APR, and monthly payment amount
-//! - **Daily Rate Calculation**: Correctly converts annual percentage rate to daily rate
+//! - **Daily Rate Calculation**: Converts the annual percentage rate to a daily rate compounded
+//!   over each 30-day month, as the standard credit card payment duration formula does
 //! - **Visual Feedback**: Displays number of months until the balance is paid off
+//! - **Balance Transfer Comparison**: Models a promotional transfer offer (fee, promo APR, and
+//!   promo length) month by month and compares its payoff time and total cost with staying on
+//!   the current card
 use eframe::egui::{self};
 
+// Simulations give up after 50 years, which only happens when payments barely cover interest.
+const MAX_MONTHS: u32 = 600;
+
 #[derive(Debug, Default)]
 struct PaymentCalculator {
-    daily_rate: f64,
     apr: f64,
     balance: f64,
     monthly_payment: f64,
+    transfer: TransferOffer,
+}
+
+/// A promotional balance transfer. After the promo period the balance accrues interest at
+/// the current card's APR.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct TransferOffer {
+    fee_percent: f64,
+    promo_apr: f64,
+    promo_months: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Payoff {
+    months: u32,
+    total_paid: f64,
+    total_interest: f64,
+}
+
+/// The interest charged over a 30-day month: the APR is converted to a daily rate that compounds
+/// daily, as in the standard credit card payoff formula.
+fn monthly_rate(apr: f64) -> f64 {
+    (1.0 + apr / 100.0 / 365.0).powf(30.0) - 1.0
+}
+
+/// Pays `payment` each month against `balance`, charging interest at `apr_for_month(month)`
+/// (1-based) first. Returns `None` when the balance is never paid off. Both the payoff time and
+/// the balance transfer comparison come from this schedule.
+fn simulate_payoff(
+    balance: f64,
+    payment: f64,
+    apr_for_month: impl Fn(u32) -> f64,
+) -> Option<Payoff> {
+    let mut remaining = balance;
+    let mut payoff = Payoff {
+        months: 0,
+        total_paid: 0.0,
+        total_interest: 0.0,
+    };
+    while remaining > 0.0 {
+        if payoff.months == MAX_MONTHS || payment <= 0.0 {
+            return None;
+        }
+        payoff.months += 1;
+        let interest = remaining * monthly_rate(apr_for_month(payoff.months));
+        remaining += interest;
+        let paid = payment.min(remaining);
+        remaining -= paid;
+        payoff.total_interest += interest;
+        payoff.total_paid += paid;
+    }
+    Some(payoff)
+}
+
+fn stay_on_card(balance: f64, apr: f64, payment: f64) -> Option<Payoff> {
+    simulate_payoff(balance, payment, |_| apr)
 }
 
-impl PaymentCalculator {
-    fn calculate_months_until_paid_off(&self) -> u32 {
-        let term1 = -(1.0 / 30.0);
-        let numerator = (1.0
-            + (self.balance / self.monthly_payment) * (1.0 - (1.0 + self.daily_rate).powf(30.0)))
-        .log10();
-        let denominator = (1.0 + self.daily_rate).log10();
+/// The transfer fee is added to the transferred balance, so it counts toward the total paid.
+fn transfer_balance(balance: f64, apr: f64, payment: f64, offer: &TransferOffer) -> Option<Payoff> {
+    let transferred = balance * (1.0 + offer.fee_percent / 100.0);
+    simulate_payoff(transferred, payment, |month| {
+        if month <= offer.promo_months {
+            offer.promo_apr
+        } else {
+            apr
+        }
+    })
+}
 
-        (term1 * (numerator / denominator)).ceil() as u32
+fn payoff_text(payoff: Option<Payoff>) -> String {
+    match payoff {
+        Some(payoff) => format!(
+            "{} months, ${:.2} total (${:.2} interest)",
+            payoff.months, payoff.total_paid, payoff.total_interest
+        ),
+        None => "never paid off at this payment".to_string(),
     }
 }
 
@@ -38,26 +111,67 @@ impl eframe::App for PaymentCalculator {
             ui.label("What is your balance:");
             ui.add(egui::DragValue::new(&mut self.balance).speed(0.01));
             ui.label("What is the APR on the card:");
-            if ui
-                .add(egui::DragValue::new(&mut self.apr).speed(0.1))
-                .changed()
-            {
-                self.daily_rate = self.apr / 100.0 / 365.0;
-            }
+            ui.add(egui::DragValue::new(&mut self.apr).speed(0.1));
             ui.label("What is the monthly payment you can make:");
             ui.add(egui::DragValue::new(&mut self.monthly_payment).speed(0.1));
 
-            ui.label(format!(
-                "Months until paid off: {}",
-                self.calculate_months_until_paid_off()
-            ));
+            let stay = stay_on_card(self.balance, self.apr, self.monthly_payment);
+            match stay {
+                Some(stay) => ui.label(format!("Months until paid off: {}", stay.months)),
+                None => ui.label("Months until paid off: never at this payment"),
+            };
+
+            ui.separator();
+            ui.heading("Balance transfer offer");
+            let offer = &mut self.transfer;
+            egui::Grid::new("transfer_offer").show(ui, |ui| {
+                ui.label("Transfer fee (%):");
+                ui.add(
+                    egui::DragValue::new(&mut offer.fee_percent)
+                        .speed(0.1)
+                        .range(0.0..=100.0),
+                );
+                ui.end_row();
+                ui.label("Promo APR (%):");
+                ui.add(
+                    egui::DragValue::new(&mut offer.promo_apr)
+                        .speed(0.1)
+                        .range(0.0..=100.0),
+                );
+                ui.end_row();
+                ui.label("Promo length (months):");
+                ui.add(egui::DragValue::new(&mut offer.promo_months).range(0..=60));
+                ui.end_row();
+            });
+
+            let transfer = transfer_balance(self.balance, self.apr, self.monthly_payment, offer);
+            ui.label(format!("Stay on current card: {}", payoff_text(stay)));
+            ui.label(format!("Transfer balance: {}", payoff_text(transfer)));
+            match (stay, transfer) {
+                (Some(stay), Some(transfer)) if transfer.total_paid < stay.total_paid => {
+                    ui.label(format!(
+                        "Transferring saves ${:.2}.",
+                        stay.total_paid - transfer.total_paid
+                    ));
+                }
+                (Some(stay), Some(transfer)) => {
+                    ui.label(format!(
+                        "Staying saves ${:.2}.",
+                        transfer.total_paid - stay.total_paid
+                    ));
+                }
+                (None, Some(_)) => {
+                    ui.label("Only the transfer pays off the balance.");
+                }
+                _ => {}
+            }
         });
     }
 }
 
 fn main() -> eframe::Result {
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 150.0]),
+        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 330.0]),
         ..Default::default()
     };
     eframe::run_native(
@@ -66,3 +180,50 @@ fn main() -> eframe::Result {
         Box::new(|_| Ok(Box::<PaymentCalculator>::default())),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simulate_payoff_charges_daily_compounded_interest() {
+        let payoff = stay_on_card(1000.0, 12.0, 500.0).unwrap();
+        let rate = monthly_rate(12.0);
+        let first = 1000.0 * rate;
+        let second = (1000.0 + first - 500.0) * rate;
+        let third = (1000.0 + first + second - 1000.0) * rate;
+        assert_eq!(payoff.months, 3);
+        assert!((payoff.total_interest - (first + second + third)).abs() < 1e-9);
+        assert!((payoff.total_paid - (1000.0 + payoff.total_interest)).abs() < 1e-9);
+        assert_eq!(stay_on_card(1000.0, 24.0, 15.0), None);
+        assert_eq!(stay_on_card(0.0, 24.0, 0.0).unwrap().months, 0);
+    }
+
+    #[test]
+    fn stay_on_card_matches_the_payoff_formula() {
+        // The book's example: $5,000 at 12% APR paying $100 a month takes 70 months.
+        assert_eq!(stay_on_card(5000.0, 12.0, 100.0).unwrap().months, 70);
+    }
+
+    #[test]
+    fn transfer_balance_applies_fee_and_promo_rate() {
+        let offer = TransferOffer {
+            fee_percent: 3.0,
+            promo_apr: 0.0,
+            promo_months: 12,
+        };
+        let transfer = transfer_balance(1200.0, 24.0, 103.0, &offer).unwrap();
+        assert_eq!(transfer.months, 12);
+        assert_eq!(transfer.total_interest, 0.0);
+        assert!((transfer.total_paid - 1236.0).abs() < 1e-9);
+
+        // A short promo reverts to the card's APR once it ends.
+        let short = TransferOffer {
+            promo_months: 1,
+            ..offer
+        };
+        let short_transfer = transfer_balance(1200.0, 24.0, 103.0, &short).unwrap();
+        assert!(short_transfer.total_interest > 0.0);
+        assert!(short_transfer.total_paid > transfer.total_paid);
+    }
+}
@@ -11,8 +11,82 @@
 //! - **Focus-based Validation**: Performs validation when users move between fields
 //! - **Regular Expression Patterns**: Uses regex patterns for precise format validation
 //! - **Field-specific Rules**: Implements different validation rules for each input type
+//! - **CSV Import Validation**: Runs every row of an employee CSV through the same rules,
+//!   lists invalid rows in a scrollable error panel, and exports only the valid rows
+//! - **Keyboard Navigation**: Tab and Shift+Tab cycle through the fields in a fixed order,
+//!   Enter submits the form when it is valid, and a failed submit moves focus to the first
+//!   invalid field
+//! - **Drafts**: Unfinished form contents are saved to `exercises/e27/draft.json` when the
+//!   window closes and restored on the next launch, with a button to discard the draft
 use eframe::egui::{self};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const FIRST_NAME_ERROR: &str = "Invalid first name. Must be at least 2 letters.";
+const LAST_NAME_ERROR: &str = "Invalid last name. Must be at least 2 letters.";
+const EMPLOYEE_ID_ERROR: &str = "Invalid employee ID. Must be in format AA-1234.";
+const ZIPCODE_ERROR: &str = "Invalid zipcode. Must be exactly 5 digits.";
+
+const CSV_HEADERS: [&str; 4] = ["first_name", "last_name", "employee_id", "zipcode"];
+
+const DRAFT_FILE: &str = "exercises/e27/draft.json";
+
+/// Form fields in tab order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Field {
+    FirstName,
+    LastName,
+    EmployeeId,
+    Zipcode,
+}
+
+const FIELDS: [Field; 4] = [
+    Field::FirstName,
+    Field::LastName,
+    Field::EmployeeId,
+    Field::Zipcode,
+];
+
+impl Field {
+    fn index(&self) -> usize {
+        FIELDS.iter().position(|field| field == self).unwrap()
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Field::FirstName => "Enter the first name:",
+            Field::LastName => "Enter the last name:",
+            Field::EmployeeId => "Enter the employee ID (format: AA-1234):",
+            Field::Zipcode => "Enter the zipcode (5 digits):",
+        }
+    }
+
+    fn error(&self) -> &'static str {
+        match self {
+            Field::FirstName => FIRST_NAME_ERROR,
+            Field::LastName => LAST_NAME_ERROR,
+            Field::EmployeeId => EMPLOYEE_ID_ERROR,
+            Field::Zipcode => ZIPCODE_ERROR,
+        }
+    }
+
+    fn id(&self) -> egui::Id {
+        egui::Id::new(("employee_field", *self))
+    }
+
+    /// Returns the field focused by Tab (or Shift+Tab when `backwards`), wrapping around.
+    fn next(&self, backwards: bool) -> Field {
+        let offset = if backwards { FIELDS.len() - 1 } else { 1 };
+        FIELDS[(self.index() + offset) % FIELDS.len()]
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum SubmitOutcome {
+    Submitted,
+    Invalid(Field),
+}
 
 #[derive(Debug, Default)]
 struct EmployeeInfo {
@@ -24,9 +98,61 @@ struct EmployeeInfo {
     last_name_error: bool,
     employee_id_error: bool,
     zipcode_error: bool,
+    status: String,
+}
+
+/// The field contents of an unfinished form.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Draft {
+    first_name: String,
+    last_name: String,
+    employee_id: String,
+    zipcode: String,
+}
+
+fn load_draft(file_path: &Path) -> Result<Option<Draft>, Box<dyn std::error::Error>> {
+    if !file_path.exists() {
+        return Ok(None);
+    }
+    let file = std::fs::File::open(file_path)?;
+    Ok(Some(serde_json::from_reader(file)?))
+}
+
+fn save_draft(file_path: &Path, draft: &Draft) -> Result<(), Box<dyn std::error::Error>> {
+    persist::write_json(file_path, draft)?;
+    Ok(())
+}
+
+fn discard_draft(file_path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(file_path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
 }
 
 impl EmployeeInfo {
+    /// Returns the form contents as a draft, or `None` when every field is empty.
+    fn draft(&self) -> Option<Draft> {
+        let draft = Draft {
+            first_name: self.first_name.clone(),
+            last_name: self.last_name.clone(),
+            employee_id: self.employee_id.clone(),
+            zipcode: self.zipcode.clone(),
+        };
+        (draft != Draft::default()).then_some(draft)
+    }
+
+    fn from_draft(draft: Draft) -> Self {
+        EmployeeInfo {
+            first_name: draft.first_name,
+            last_name: draft.last_name,
+            employee_id: draft.employee_id,
+            zipcode: draft.zipcode,
+            status: "Restored your unfinished draft.".to_string(),
+            ..Default::default()
+        }
+    }
+
     fn is_valid_first_name(&self) -> bool {
         let re = Regex::new(r"^[A-Za-z]{2,}$").unwrap();
         re.is_match(&self.first_name)
@@ -46,53 +172,379 @@ impl EmployeeInfo {
         let re = Regex::new(r"^\d{5}$").unwrap();
         re.is_match(&self.zipcode)
     }
-}
 
-impl eframe::App for EmployeeInfo {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.label("Enter the first name:");
-            let first_name_response = ui.add(egui::TextEdit::singleline(&mut self.first_name));
-            if first_name_response.changed() {
-                self.first_name_error = false;
-            } else if first_name_response.lost_focus() && !self.first_name.is_empty() {
-                self.first_name_error = !self.is_valid_first_name();
+    fn is_valid(&self, field: Field) -> bool {
+        match field {
+            Field::FirstName => self.is_valid_first_name(),
+            Field::LastName => self.is_valid_last_name(),
+            Field::EmployeeId => self.is_valid_employee_id(),
+            Field::Zipcode => self.is_valid_zipcode(),
+        }
+    }
+
+    fn value_mut(&mut self, field: Field) -> &mut String {
+        match field {
+            Field::FirstName => &mut self.first_name,
+            Field::LastName => &mut self.last_name,
+            Field::EmployeeId => &mut self.employee_id,
+            Field::Zipcode => &mut self.zipcode,
+        }
+    }
+
+    fn error_flag_mut(&mut self, field: Field) -> &mut bool {
+        match field {
+            Field::FirstName => &mut self.first_name_error,
+            Field::LastName => &mut self.last_name_error,
+            Field::EmployeeId => &mut self.employee_id_error,
+            Field::Zipcode => &mut self.zipcode_error,
+        }
+    }
+
+    /// Returns the field name and message for every rule the entry violates.
+    fn validation_errors(&self) -> Vec<(&'static str, &'static str)> {
+        FIELDS
+            .into_iter()
+            .filter(|field| !self.is_valid(*field))
+            .map(|field| (CSV_HEADERS[field.index()], field.error()))
+            .collect()
+    }
+
+    /// Validates every field, flagging each invalid one. On success the form is cleared.
+    fn submit(&mut self) -> SubmitOutcome {
+        for field in FIELDS {
+            *self.error_flag_mut(field) = !self.is_valid(field);
+        }
+        match FIELDS.into_iter().find(|field| !self.is_valid(*field)) {
+            Some(field) => {
+                self.status = "Please correct the highlighted fields.".to_string();
+                SubmitOutcome::Invalid(field)
             }
-            if self.first_name_error {
-                ui.label("Invalid first name. Must be at least 2 letters.");
+            None => {
+                let status = format!(
+                    "Submitted {} {} ({}).",
+                    self.first_name, self.last_name, self.employee_id
+                );
+                *self = EmployeeInfo {
+                    status,
+                    ..Default::default()
+                };
+                SubmitOutcome::Submitted
             }
+        }
+    }
 
-            ui.label("Enter the last name:");
-            let last_name_response = ui.add(egui::TextEdit::singleline(&mut self.last_name));
-            if last_name_response.changed() {
-                self.last_name_error = false;
-            } else if last_name_response.lost_focus() && !self.last_name.is_empty() {
-                self.last_name_error = !self.is_valid_last_name();
+    fn show(&mut self, ui: &mut egui::Ui) {
+        let mut submit = false;
+        let mut focus = None;
+        for field in FIELDS {
+            ui.label(field.label());
+            // Focus is locked so that Tab reaches this handler instead of egui's default
+            // traversal, which follows layout order rather than the form's tab order.
+            let response = ui.add(
+                egui::TextEdit::singleline(self.value_mut(field))
+                    .id(field.id())
+                    .lock_focus(true),
+            );
+            if response.has_focus() {
+                let (tab, shift) = ui.input(|i| (i.key_pressed(egui::Key::Tab), i.modifiers.shift));
+                if tab {
+                    focus = Some(field.next(shift));
+                }
             }
-            if self.last_name_error {
-                ui.label("Invalid last name. Must be at least 2 letters.");
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                submit = true;
             }
 
-            ui.label("Enter the employee ID (format: AA-1234):");
-            let employee_id_response = ui.add(egui::TextEdit::singleline(&mut self.employee_id));
-            if employee_id_response.changed() {
-                self.employee_id_error = false;
-            } else if employee_id_response.lost_focus() && !self.employee_id.is_empty() {
-                self.employee_id_error = !self.is_valid_employee_id();
+            if response.changed() {
+                *self.error_flag_mut(field) = false;
+            } else if response.lost_focus() && !self.value_mut(field).is_empty() {
+                *self.error_flag_mut(field) = !self.is_valid(field);
             }
-            if self.employee_id_error {
-                ui.label("Invalid employee ID. Must be in format AA-1234.");
+            if *self.error_flag_mut(field) {
+                ui.colored_label(egui::Color32::RED, field.error());
             }
+        }
+
+        if ui.button("Submit").clicked() {
+            submit = true;
+        }
+        if submit {
+            focus = match self.submit() {
+                SubmitOutcome::Submitted => Some(Field::FirstName),
+                SubmitOutcome::Invalid(field) => Some(field),
+            };
+        }
+        if let Some(field) = focus {
+            ui.memory_mut(|memory| memory.request_focus(field.id()));
+        }
+        ui.label(&self.status);
+    }
+}
 
-            ui.label("Enter the zipcode (5 digits):");
-            let zipcode_response = ui.add(egui::TextEdit::singleline(&mut self.zipcode));
-            if zipcode_response.changed() {
-                self.zipcode_error = false;
-            } else if zipcode_response.lost_focus() && !self.zipcode.is_empty() {
-                self.zipcode_error = !self.is_valid_zipcode();
+#[derive(Debug, PartialEq)]
+struct RowError {
+    row: u64,
+    field: String,
+    message: String,
+}
+
+#[derive(Debug, Default)]
+struct ImportReport {
+    valid_rows: Vec<csv::StringRecord>,
+    errors: Vec<RowError>,
+}
+
+/// Runs every row of an employee CSV through the form's validation rules. Rows are numbered
+/// by their line in the file, so the first data row after the header is row 2.
+fn validate_csv<R: std::io::Read>(reader: R) -> ImportReport {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+    let mut report = ImportReport::default();
+    for (index, result) in reader.records().enumerate() {
+        let fallback_row = index as u64 + 2;
+        let record = match result {
+            Ok(record) => record,
+            Err(e) => {
+                report.errors.push(RowError {
+                    row: e.position().map_or(fallback_row, |p| p.line()),
+                    field: "row".to_string(),
+                    message: e.to_string(),
+                });
+                continue;
             }
-            if self.zipcode_error {
-                ui.label("Invalid zipcode. Must be exactly 5 digits.");
+        };
+        let row = record.position().map_or(fallback_row, |p| p.line());
+        if record.len() != CSV_HEADERS.len() {
+            report.errors.push(RowError {
+                row,
+                field: "row".to_string(),
+                message: format!(
+                    "Expected {} fields, found {}.",
+                    CSV_HEADERS.len(),
+                    record.len()
+                ),
+            });
+            continue;
+        }
+
+        let info = EmployeeInfo {
+            first_name: record[0].trim().to_string(),
+            last_name: record[1].trim().to_string(),
+            employee_id: record[2].trim().to_string(),
+            zipcode: record[3].trim().to_string(),
+            ..Default::default()
+        };
+        let errors = info.validation_errors();
+        if errors.is_empty() {
+            report.valid_rows.push(record);
+        } else {
+            report
+                .errors
+                .extend(errors.into_iter().map(|(field, message)| RowError {
+                    row,
+                    field: field.to_string(),
+                    message: message.to_string(),
+                }));
+        }
+    }
+    report
+}
+
+fn export_valid_rows<W: std::io::Write>(
+    rows: &[csv::StringRecord],
+    writer: W,
+) -> Result<(), csv::Error> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(CSV_HEADERS)?;
+    for row in rows {
+        writer.write_record(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Path the valid rows of `path` are exported to, e.g. `employees_valid.csv`.
+fn valid_rows_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{}_valid.csv", stem))
+}
+
+struct CsvImport {
+    path: String,
+    report: Option<ImportReport>,
+    status: String,
+}
+
+impl Default for CsvImport {
+    fn default() -> Self {
+        Self {
+            path: "exercises/e27/inputs/employees.csv".to_string(),
+            report: None,
+            status: String::new(),
+        }
+    }
+}
+
+impl CsvImport {
+    fn load(&mut self) {
+        let result = std::fs::File::open(&self.path)
+            .map_err(|e| e.to_string())
+            .map(validate_csv);
+        match result {
+            Ok(report) => {
+                self.status = format!(
+                    "{} valid row(s), {} error(s).",
+                    report.valid_rows.len(),
+                    report.errors.len()
+                );
+                self.report = Some(report);
+            }
+            Err(e) => {
+                self.status = format!("Error loading {}: {}", self.path, e);
+                self.report = None;
+            }
+        }
+    }
+
+    fn export(&mut self) {
+        let Some(report) = &self.report else {
+            return;
+        };
+        let output = valid_rows_path(Path::new(&self.path));
+        let result = std::fs::File::create(&output)
+            .map_err(|e| e.to_string())
+            .and_then(|file| {
+                export_valid_rows(&report.valid_rows, file).map_err(|e| e.to_string())
+            });
+        self.status = match result {
+            Ok(()) => format!(
+                "Exported {} valid row(s) to {}.",
+                report.valid_rows.len(),
+                output.display()
+            ),
+            Err(e) => format!("Error exporting to {}: {}", output.display(), e),
+        };
+    }
+
+    fn show(&mut self, ui: &mut egui::Ui) {
+        ui.label("CSV file:");
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.path);
+            if ui.button("Load").clicked() {
+                self.load();
+            }
+        });
+        let can_export = self
+            .report
+            .as_ref()
+            .is_some_and(|report| !report.valid_rows.is_empty());
+        if ui
+            .add_enabled(can_export, egui::Button::new("Export valid rows"))
+            .clicked()
+        {
+            self.export();
+        }
+        ui.label(&self.status);
+
+        if let Some(report) = &self.report
+            && !report.errors.is_empty()
+        {
+            ui.separator();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                egui::Grid::new("import_errors")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Row");
+                        ui.strong("Field");
+                        ui.strong("Message");
+                        ui.end_row();
+                        for error in &report.errors {
+                            ui.label(error.row.to_string());
+                            ui.label(&error.field);
+                            ui.label(&error.message);
+                            ui.end_row();
+                        }
+                    });
+            });
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+enum Mode {
+    #[default]
+    Form,
+    Import,
+}
+
+struct EmployeeApp {
+    mode: Mode,
+    form: EmployeeInfo,
+    import: CsvImport,
+    draft_path: PathBuf,
+    /// Whether the form was restored from a draft that can still be discarded.
+    draft_restored: bool,
+}
+
+impl EmployeeApp {
+    fn new(draft_path: PathBuf) -> Self {
+        let (form, draft_restored) = match load_draft(&draft_path) {
+            Ok(Some(draft)) => (EmployeeInfo::from_draft(draft), true),
+            Ok(None) => (EmployeeInfo::default(), false),
+            Err(e) => (
+                EmployeeInfo {
+                    status: format!("Error loading draft: {}", e),
+                    ..Default::default()
+                },
+                false,
+            ),
+        };
+        Self {
+            mode: Mode::default(),
+            form,
+            import: CsvImport::default(),
+            draft_path,
+            draft_restored,
+        }
+    }
+
+    /// Saves the unfinished form as the draft, or removes the draft once the form is empty.
+    fn save_or_clear_draft(&self) -> Result<(), Box<dyn std::error::Error>> {
+        match self.form.draft() {
+            Some(draft) => save_draft(&self.draft_path, &draft),
+            None => Ok(discard_draft(&self.draft_path)?),
+        }
+    }
+
+    fn discard(&mut self) {
+        self.form = EmployeeInfo::default();
+        self.draft_restored = false;
+        self.form.status = match discard_draft(&self.draft_path) {
+            Ok(()) => "Draft discarded.".to_string(),
+            Err(e) => format!("Error discarding draft: {}", e),
+        };
+    }
+}
+
+impl eframe::App for EmployeeApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if ctx.input(|i| i.viewport().close_requested())
+            && let Err(e) = self.save_or_clear_draft()
+        {
+            eprintln!("Error saving draft: {}", e);
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.mode, Mode::Form, "Form");
+                ui.selectable_value(&mut self.mode, Mode::Import, "Import CSV");
+                if self.draft_restored && ui.button("Discard draft").clicked() {
+                    self.discard();
+                }
+            });
+            ui.separator();
+            match self.mode {
+                Mode::Form => self.form.show(ui),
+                Mode::Import => self.import.show(ui),
             }
         });
     }
@@ -100,13 +552,13 @@ impl eframe::App for EmployeeInfo {
 
 fn main() -> eframe::Result {
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 250.0]),
+        viewport: egui::ViewportBuilder::default().with_inner_size([500.0, 400.0]),
         ..Default::default()
     };
     eframe::run_native(
         "Employee Information Validator",
         options,
-        Box::new(|_| Ok(Box::<EmployeeInfo>::default())),
+        Box::new(|_| Ok(Box::new(EmployeeApp::new(PathBuf::from(DRAFT_FILE))))),
     )
 }
 
@@ -269,4 +721,137 @@ mod tests {
         };
         assert!(!info.is_valid_zipcode());
     }
+
+    #[test]
+    fn field_next_cycles_in_tab_order() {
+        assert_eq!(Field::FirstName.next(false), Field::LastName);
+        assert_eq!(Field::Zipcode.next(false), Field::FirstName);
+        assert_eq!(Field::FirstName.next(true), Field::Zipcode);
+        assert_eq!(Field::EmployeeId.next(true), Field::LastName);
+    }
+
+    #[test]
+    fn submit_reports_first_invalid_field() {
+        let mut info = EmployeeInfo {
+            first_name: "John".to_string(),
+            last_name: "X".to_string(),
+            employee_id: "AB-12".to_string(),
+            zipcode: "55555".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(info.submit(), SubmitOutcome::Invalid(Field::LastName));
+        assert!(info.last_name_error && info.employee_id_error);
+        assert!(!info.first_name_error && !info.zipcode_error);
+
+        info.last_name = "Smith".to_string();
+        info.employee_id = "AB-1234".to_string();
+        assert_eq!(info.submit(), SubmitOutcome::Submitted);
+        assert!(info.first_name.is_empty());
+        assert_eq!(info.status, "Submitted John Smith (AB-1234).");
+    }
+
+    #[test]
+    fn validate_csv_reports_invalid_rows() {
+        let csv = "first_name,last_name,employee_id,zipcode\n\
+                   John,Smith,AB-1234,55401\n\
+                   J,Johnson,CD-567,55402\n\
+                   Liam,Brown,KL-1516\n";
+        let report = validate_csv(csv.as_bytes());
+
+        assert_eq!(report.valid_rows.len(), 1);
+        assert_eq!(
+            report.errors,
+            vec![
+                RowError {
+                    row: 3,
+                    field: "first_name".to_string(),
+                    message: FIRST_NAME_ERROR.to_string(),
+                },
+                RowError {
+                    row: 3,
+                    field: "employee_id".to_string(),
+                    message: EMPLOYEE_ID_ERROR.to_string(),
+                },
+                RowError {
+                    row: 4,
+                    field: "row".to_string(),
+                    message: "Expected 4 fields, found 3.".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn export_valid_rows_writes_header_and_rows() {
+        let csv = "first_name,last_name,employee_id,zipcode\nJohn,Smith,AB-1234,55401\n";
+        let report = validate_csv(csv.as_bytes());
+        let mut output = Vec::new();
+        export_valid_rows(&report.valid_rows, &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), csv);
+    }
+
+    #[test]
+    fn valid_rows_path_appends_suffix() {
+        assert_eq!(
+            valid_rows_path(Path::new("inputs/employees.csv")),
+            PathBuf::from("inputs/employees_valid.csv")
+        );
+    }
+
+    #[test]
+    fn draft_is_none_for_an_empty_form() {
+        assert_eq!(EmployeeInfo::default().draft(), None);
+        let info = EmployeeInfo {
+            last_name: "Smith".to_string(),
+            status: "ignored".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            info.draft(),
+            Some(Draft {
+                last_name: "Smith".to_string(),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn app_restores_and_discards_a_saved_draft() {
+        let path = std::env::temp_dir().join(format!("e27_draft_{}.json", std::process::id()));
+        let draft = Draft {
+            first_name: "John".to_string(),
+            employee_id: "AB-12".to_string(),
+            ..Default::default()
+        };
+        save_draft(&path, &draft).unwrap();
+
+        let mut app = EmployeeApp::new(path.clone());
+        assert!(app.draft_restored);
+        assert_eq!(app.form.first_name, "John");
+        assert_eq!(app.form.employee_id, "AB-12");
+
+        app.discard();
+        assert!(!path.exists());
+        assert_eq!(app.form.draft(), None);
+        assert!(!EmployeeApp::new(path).draft_restored);
+    }
+
+    #[test]
+    fn save_or_clear_draft_removes_the_draft_of_an_empty_form() {
+        let path = std::env::temp_dir().join(format!("e27_empty_{}.json", std::process::id()));
+        let mut app = EmployeeApp::new(path.clone());
+        app.form.zipcode = "554".to_string();
+        app.save_or_clear_draft().unwrap();
+        assert_eq!(
+            load_draft(&path).unwrap(),
+            Some(Draft {
+                zipcode: "554".to_string(),
+                ..Default::default()
+            })
+        );
+
+        app.form = EmployeeInfo::default();
+        app.save_or_clear_draft().unwrap();
+        assert!(!path.exists());
+    }
 }
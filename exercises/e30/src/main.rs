@@ -11,16 +11,94 @@
 //! - **Visual Formatting**: Uses grid layout with proper headers and striped rows
 //! - **Educational Tool**: Provides clear visual representation of multiplication patterns
 //! - **Responsive Design**: Adapts to window size and maintains usability for larger tables
-use eframe::egui::{self};
+//! - **Visual Overlays**: Toggleable highlighting of the perfect squares on the diagonal,
+//!   shading of cells whose row and column are coprime, and a hover tooltip listing every
+//!   factor pair of a cell's product
+//! - **Frozen Headers**: The header row and column stay visible while the table scrolls, and
+//!   only the visible cells are drawn so large tables stay responsive
+//! - **Auto-sized Cells**: Every cell is as wide as the widest product in the table
+use eframe::egui::{self, Align2, Rect, Sense, Vec2, vec2};
+use std::ops::RangeInclusive;
+
+const SQUARE_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 214, 102);
+const COPRIME_COLOR: egui::Color32 = egui::Color32::from_rgb(153, 204, 255);
+
+const MIN_CELL_WIDTH: f32 = 30.0;
+const CELL_PADDING: Vec2 = vec2(6.0, 5.0);
+
+/// Size of every table cell given the size of the widest product's text.
+fn cell_size(widest_text: Vec2) -> Vec2 {
+    vec2(
+        (widest_text.x + 2.0 * CELL_PADDING.x).max(MIN_CELL_WIDTH),
+        widest_text.y + 2.0 * CELL_PADDING.y,
+    )
+}
+
+/// Returns the 1-based rows (or columns) of a `count`-long table with `cell`-sized cells that
+/// are visible in a viewport starting at `offset` and `extent` long. Index 0 is the header,
+/// which is frozen over the first cell of the viewport, so rows hidden behind it are skipped.
+fn visible_range(offset: f32, extent: f32, cell: f32, count: u32) -> RangeInclusive<u32> {
+    let first = ((offset + cell) / cell).floor().max(1.0) as u32;
+    let last = (((offset + extent) / cell).ceil() as u32)
+        .saturating_sub(1)
+        .min(count);
+    first..=last
+}
+
+#[derive(Debug, Default)]
+struct Overlays {
+    squares: bool,
+    coprime: bool,
+    factor_pairs: bool,
+}
 
 #[derive(Debug)]
 struct MultiplicationTableApp {
     base: u32,
+    overlays: Overlays,
 }
 
 impl Default for MultiplicationTableApp {
     fn default() -> Self {
-        Self { base: 1 }
+        Self {
+            base: 1,
+            overlays: Overlays::default(),
+        }
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Returns every pair `(a, b)` with `a <= b` and `a * b == n`.
+fn factor_pairs(n: u32) -> Vec<(u32, u32)> {
+    (1..)
+        .take_while(|a| a * a <= n)
+        .filter(|a| n.is_multiple_of(*a))
+        .map(|a| (a, n / a))
+        .collect()
+}
+
+fn factor_pairs_text(n: u32) -> String {
+    let pairs: Vec<String> = factor_pairs(n)
+        .iter()
+        .map(|(a, b)| format!("{} × {}", a, b))
+        .collect();
+    format!("{} = {}", n, pairs.join(" = "))
+}
+
+impl Overlays {
+    /// Background color for the cell at row `i` and column `j`, if any overlay applies. The
+    /// square highlight takes precedence over coprime shading.
+    fn cell_color(&self, i: u32, j: u32) -> Option<egui::Color32> {
+        if self.squares && i == j {
+            Some(SQUARE_COLOR)
+        } else if self.coprime && gcd(i, j) == 1 {
+            Some(COPRIME_COLOR)
+        } else {
+            None
+        }
     }
 }
 
@@ -37,40 +115,114 @@ impl eframe::App for MultiplicationTableApp {
                     }
                 });
 
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.overlays.squares, "Squares");
+                ui.checkbox(&mut self.overlays.coprime, "Coprime");
+                ui.checkbox(&mut self.overlays.factor_pairs, "Factor pairs");
+            });
+
             ui.separator();
 
             egui::ScrollArea::both()
                 .auto_shrink([false, false])
-                .show(ui, |ui| {
-                    egui::Grid::new("multiplication_table")
-                        .spacing([10.0, 10.0])
-                        .striped(true)
-                        .min_col_width(30.0)
-                        .show(ui, |ui| {
-                            // Header row with column numbers
-                            ui.label("×"); // Top-left corner indicator
-                            for i in 1..=self.base {
-                                ui.strong(i.to_string());
-                            }
-                            ui.end_row();
-
-                            // Table body with row numbers and calculations
-                            for i in 1..=self.base {
-                                ui.strong(i.to_string()); // Row header
-                                for j in 1..=self.base {
-                                    ui.label((i * j).to_string());
-                                }
-                                ui.end_row();
-                            }
-                        });
-                });
+                .show_viewport(ui, |ui, viewport| self.show_table(ui, viewport));
         });
     }
 }
 
+impl MultiplicationTableApp {
+    /// Draws the visible part of the table, then the header row and column pinned to the top
+    /// and left of the viewport. `viewport` is in table coordinates.
+    fn show_table(&self, ui: &mut egui::Ui, viewport: Rect) {
+        let font_id = egui::TextStyle::Body.resolve(ui.style());
+        let widest = (self.base * self.base).to_string();
+        let text_size = ui
+            .fonts(|fonts| fonts.layout_no_wrap(widest, font_id.clone(), egui::Color32::WHITE))
+            .size();
+        let cell = cell_size(text_size);
+        let (table_rect, _) = ui.allocate_exact_size(cell * (self.base + 1) as f32, Sense::hover());
+        let origin = table_rect.min;
+        let pinned = origin + viewport.min.to_vec2();
+        let cell_rect = |row: f32, col: f32| {
+            Rect::from_min_size(origin + vec2(col * cell.x, row * cell.y), cell)
+        };
+
+        let visuals = ui.visuals().clone();
+        let painter = ui.painter();
+        let rows = visible_range(viewport.min.y, viewport.height(), cell.y, self.base);
+        let cols = visible_range(viewport.min.x, viewport.width(), cell.x, self.base);
+        let body = Rect::from_min_max(pinned + cell, origin + viewport.max.to_vec2());
+
+        for i in rows.clone() {
+            if i % 2 == 0 {
+                let stripe = cell_rect(i as f32, 0.0).with_max_x(table_rect.max.x);
+                painter.rect_filled(stripe.intersect(body), 0.0, visuals.faint_bg_color);
+            }
+            for j in cols.clone() {
+                let rect = cell_rect(i as f32, j as f32);
+                let mut color = visuals.text_color();
+                if let Some(background) = self.overlays.cell_color(i, j) {
+                    painter.rect_filled(rect.shrink(2.0), 2.0, background);
+                    color = egui::Color32::BLACK;
+                }
+                painter.text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    (i * j).to_string(),
+                    font_id.clone(),
+                    color,
+                );
+                if self.overlays.factor_pairs {
+                    let visible = rect.intersect(body);
+                    if visible.is_positive() {
+                        ui.interact(visible, ui.id().with(("cell", i, j)), Sense::hover())
+                            .on_hover_text(factor_pairs_text(i * j));
+                    }
+                }
+            }
+        }
+
+        // The header row and column are painted last so they cover the cells scrolled under
+        // them.
+        let header_fill = visuals.widgets.noninteractive.bg_fill;
+        let header_color = visuals.strong_text_color();
+        for j in cols {
+            let rect = cell_rect(0.0, j as f32).translate(vec2(0.0, viewport.min.y));
+            painter.rect_filled(rect, 0.0, header_fill);
+            painter.text(
+                rect.center(),
+                Align2::CENTER_CENTER,
+                j.to_string(),
+                font_id.clone(),
+                header_color,
+            );
+        }
+        for i in rows {
+            let rect = cell_rect(i as f32, 0.0).translate(vec2(viewport.min.x, 0.0));
+            painter.rect_filled(rect, 0.0, header_fill);
+            painter.text(
+                rect.center(),
+                Align2::CENTER_CENTER,
+                i.to_string(),
+                font_id.clone(),
+                header_color,
+            );
+        }
+        let corner = Rect::from_min_size(pinned, cell);
+        painter.rect_filled(corner, 0.0, header_fill);
+        painter.text(
+            corner.center(),
+            Align2::CENTER_CENTER,
+            "×",
+            font_id,
+            header_color,
+        );
+    }
+}
+
 fn main() -> eframe::Result {
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 250.0]),
+        viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 280.0]),
         ..Default::default()
     };
     eframe::run_native(
@@ -79,3 +231,46 @@ fn main() -> eframe::Result {
         Box::new(|_| Ok(Box::<MultiplicationTableApp>::default())),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factor_pairs_lists_each_pair_once() {
+        assert_eq!(factor_pairs(12), vec![(1, 12), (2, 6), (3, 4)]);
+        assert_eq!(factor_pairs(16), vec![(1, 16), (2, 8), (4, 4)]);
+        assert_eq!(factor_pairs(7), vec![(1, 7)]);
+        assert_eq!(factor_pairs_text(6), "6 = 1 × 6 = 2 × 3");
+    }
+
+    #[test]
+    fn cell_color_applies_enabled_overlays() {
+        let overlays = Overlays {
+            squares: true,
+            coprime: true,
+            factor_pairs: false,
+        };
+        assert_eq!(overlays.cell_color(4, 4), Some(SQUARE_COLOR));
+        assert_eq!(overlays.cell_color(3, 4), Some(COPRIME_COLOR));
+        assert_eq!(overlays.cell_color(4, 6), None);
+        assert_eq!(Overlays::default().cell_color(1, 1), None);
+    }
+
+    #[test]
+    fn cell_size_fits_the_widest_product() {
+        assert_eq!(cell_size(vec2(8.0, 14.0)), vec2(MIN_CELL_WIDTH, 24.0));
+        assert_eq!(cell_size(vec2(40.0, 14.0)), vec2(52.0, 24.0));
+    }
+
+    #[test]
+    fn visible_range_skips_cells_under_the_frozen_header() {
+        // Unscrolled: the header takes the first cell, then rows 1-4 fit in 100 points.
+        assert_eq!(visible_range(0.0, 100.0, 20.0, 12), 1..=4);
+        // Scrolled by 50 points: row 2 is behind the header, row 3 is partly visible.
+        assert_eq!(visible_range(50.0, 100.0, 20.0, 12), 3..=7);
+        // The range never runs past the table.
+        assert_eq!(visible_range(150.0, 200.0, 20.0, 12), 8..=12);
+        assert!(visible_range(0.0, 10.0, 20.0, 12).is_empty());
+    }
+}
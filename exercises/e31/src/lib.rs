@@ -0,0 +1,186 @@
+//! # Karvonen Heart Rate Library
+//!
+//! Target heart rate calculations shared by the Karvonen calculator's GUI and its headless
+//! command-line mode.
+
+/// Training intensities, in percent, shown in the zone table.
+pub const INTENSITIES: std::ops::RangeInclusive<u32> = 55..=95;
+pub const INTENSITY_STEP: usize = 5;
+
+/// How the maximum heart rate is estimated. The classic `220 - age` rule is the default but
+/// can be off by 10+ bpm for many people, so the newer regressions or a value measured during a
+/// stress test can be used instead.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum MaxHrFormula {
+    #[default]
+    Classic,
+    /// Tanaka et al. (2001): `208 - 0.7 × age`.
+    Tanaka,
+    /// Gulati et al. (2010), derived from women: `206 - 0.88 × age`.
+    Gulati,
+    Measured(u32),
+}
+
+impl MaxHrFormula {
+    pub fn max_heart_rate(self, age: u32) -> f64 {
+        let age = f64::from(age);
+        match self {
+            MaxHrFormula::Classic => 220.0 - age,
+            MaxHrFormula::Tanaka => 208.0 - 0.7 * age,
+            MaxHrFormula::Gulati => 206.0 - 0.88 * age,
+            MaxHrFormula::Measured(bpm) => f64::from(bpm),
+        }
+    }
+}
+
+impl std::fmt::Display for MaxHrFormula {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MaxHrFormula::Classic => write!(f, "Classic (220 - age)"),
+            MaxHrFormula::Tanaka => write!(f, "Tanaka (208 - 0.7 x age)"),
+            MaxHrFormula::Gulati => write!(f, "Gulati (206 - 0.88 x age)"),
+            MaxHrFormula::Measured(bpm) => write!(f, "Measured ({} bpm)", bpm),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Zone {
+    pub intensity: u32,
+    pub rate: u32,
+}
+
+pub fn karvonen_target_heart_rate(
+    age: u32,
+    resting_pulse: u32,
+    intensity: f64,
+    formula: MaxHrFormula,
+) -> u32 {
+    let max_heart_rate = formula.max_heart_rate(age);
+    let target_heart_rate =
+        ((max_heart_rate - f64::from(resting_pulse)) * intensity) + f64::from(resting_pulse);
+    target_heart_rate.round() as u32
+}
+
+/// Returns the target heart rate for each intensity in `INTENSITIES`.
+pub fn zone_table(age: u32, resting_pulse: u32, formula: MaxHrFormula) -> Vec<Zone> {
+    INTENSITIES
+        .step_by(INTENSITY_STEP)
+        .map(|intensity| Zone {
+            intensity,
+            rate: karvonen_target_heart_rate(
+                age,
+                resting_pulse,
+                f64::from(intensity) / 100.0,
+                formula,
+            ),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn karvonen_target_heart_rate_calculates_correctly() {
+        // Test case with typical adult values
+        // Expected: (220-30-70)*0.65 + 70 = 78 + 70 = 148
+        assert_eq!(
+            karvonen_target_heart_rate(30, 70, 0.65, MaxHrFormula::Classic),
+            148
+        );
+        assert_eq!(
+            karvonen_target_heart_rate(30, 70, 0.55, MaxHrFormula::Classic),
+            136
+        ); // 55% intensity
+        assert_eq!(
+            karvonen_target_heart_rate(30, 70, 0.95, MaxHrFormula::Classic),
+            184
+        ); // 95% intensity
+    }
+
+    #[test]
+    fn karvonen_target_heart_rate_handles_boundary_cases() {
+        // Test with senior age
+        // Expected: (220-80-65)*0.70 + 65 = 52.5 + 65 = 118 (rounded)
+        assert_eq!(
+            karvonen_target_heart_rate(80, 65, 0.70, MaxHrFormula::Classic),
+            118
+        );
+
+        // Test with child age
+        // Expected: (220-10-80)*0.60 + 80 = 78 + 80 = 158
+        assert_eq!(
+            karvonen_target_heart_rate(10, 80, 0.60, MaxHrFormula::Classic),
+            158
+        );
+    }
+
+    #[test]
+    fn karvonen_target_heart_rate_handles_extreme_intensities() {
+        // At 0% intensity, result should be the resting heart rate
+        // Expected: (220-40-60)*0.0 + 60 = 0 + 60 = 60
+        assert_eq!(
+            karvonen_target_heart_rate(40, 60, 0.0, MaxHrFormula::Classic),
+            60
+        );
+
+        // At 100% intensity, result should be the maximum heart rate
+        // Expected: (220-40-60)*1.0 + 60 = 120 + 60 = 180
+        assert_eq!(
+            karvonen_target_heart_rate(40, 60, 1.0, MaxHrFormula::Classic),
+            180
+        );
+    }
+
+    #[test]
+    fn karvonen_target_heart_rate_handles_rounding() {
+        // This will produce a floating point result that needs rounding
+        // Expected: (220-33-67)*0.75 + 67 = 90 + 67 = 157
+        assert_eq!(
+            karvonen_target_heart_rate(33, 67, 0.75, MaxHrFormula::Classic),
+            157
+        );
+    }
+
+    #[test]
+    fn zone_table_covers_all_intensities() {
+        let zones = zone_table(30, 70, MaxHrFormula::Classic);
+        assert_eq!(zones.len(), 9);
+        assert_eq!(
+            zones[0],
+            Zone {
+                intensity: 55,
+                rate: 136
+            }
+        );
+        assert_eq!(zones[8].intensity, 95);
+    }
+
+    #[test]
+    fn max_heart_rate_formulas_differ_by_age() {
+        assert_eq!(MaxHrFormula::Classic.max_heart_rate(40), 180.0);
+        assert_eq!(MaxHrFormula::Tanaka.max_heart_rate(40), 180.0);
+        assert!((MaxHrFormula::Gulati.max_heart_rate(40) - 170.8).abs() < 1e-9);
+        assert_eq!(MaxHrFormula::Measured(172).max_heart_rate(40), 172.0);
+
+        // Tanaka diverges from the classic rule for older users.
+        assert!((MaxHrFormula::Tanaka.max_heart_rate(70) - 159.0).abs() < 1e-9);
+        assert_eq!(MaxHrFormula::Classic.max_heart_rate(70), 150.0);
+    }
+
+    #[test]
+    fn karvonen_target_heart_rate_uses_selected_formula() {
+        // Expected: (159-60)*0.70 + 60 = 69.3 + 60 = 129
+        assert_eq!(
+            karvonen_target_heart_rate(70, 60, 0.70, MaxHrFormula::Tanaka),
+            129
+        );
+        // Expected: (190-60)*0.50 + 60 = 65 + 60 = 125
+        assert_eq!(
+            karvonen_target_heart_rate(70, 60, 0.50, MaxHrFormula::Measured(190)),
+            125
+        );
+    }
+}
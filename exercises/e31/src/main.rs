@@ -10,23 +10,130 @@
 //! - **Range of Intensities**: Displays rates for training intensities from 55% to 95%
 //! - **Tabular Results**: Presents calculated heart rates in an organized, scrollable grid
 //! - **Scientific Formula**: Implements the Karvonen method for personalized heart rate zones
+//! - **Headless Mode**: `--age <years> --resting <bpm> [--format table|csv]` prints the zone
+//!   table to stdout for use in scripts
+//! - **Max Heart Rate Formulas**: Estimates max heart rate with the classic, Tanaka, or Gulati
+//!   formula, or uses a measured value (`--max-hr classic|tanaka|gulati|<bpm>`); the active
+//!   formula is noted above the zone table
+use e31::{MaxHrFormula, Zone, zone_table};
 use eframe::egui::{self};
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 struct BpmTracker {
     resting_pulse: u32,
     age: u32,
+    formula: MaxHrFormula,
+    /// Remembered so switching away from "Measured" and back keeps the entered value.
+    measured_max: u32,
 }
 
-impl BpmTracker {
-    fn karvonen_target_heart_rate(&self, intensity: f64) -> u32 {
-        let max_heart_rate = f64::from(220 - self.age);
-        let target_heart_rate = ((max_heart_rate - f64::from(self.resting_pulse)) * intensity)
-            + f64::from(self.resting_pulse);
-        target_heart_rate.round() as u32
+impl Default for BpmTracker {
+    fn default() -> Self {
+        Self {
+            resting_pulse: 0,
+            age: 0,
+            formula: MaxHrFormula::default(),
+            measured_max: 180,
+        }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    Table,
+    Csv,
+}
+
+#[derive(Debug, PartialEq)]
+struct HeadlessOptions {
+    age: u32,
+    resting_pulse: u32,
+    format: OutputFormat,
+    formula: MaxHrFormula,
+}
+
+fn parse_formula(value: &str) -> Result<MaxHrFormula, String> {
+    match value {
+        "classic" => Ok(MaxHrFormula::Classic),
+        "tanaka" => Ok(MaxHrFormula::Tanaka),
+        "gulati" => Ok(MaxHrFormula::Gulati),
+        _ => match value.parse::<u32>() {
+            Ok(bpm) if bpm > 0 => Ok(MaxHrFormula::Measured(bpm)),
+            _ => Err(format!("invalid max heart rate '{}'", value)),
+        },
+    }
+}
+
+/// Parses the headless mode arguments, returning `None` when no arguments were given and the
+/// GUI should be shown instead.
+fn parse_args(args: &[String]) -> Result<Option<HeadlessOptions>, String> {
+    if args.is_empty() {
+        return Ok(None);
+    }
+
+    let mut age = None;
+    let mut resting_pulse = None;
+    let mut format = OutputFormat::Table;
+    let mut formula = MaxHrFormula::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} requires a value", arg));
+        match arg.as_str() {
+            "--age" => {
+                let value = value()?;
+                match value.parse::<u32>() {
+                    Ok(years) if (1..220).contains(&years) => age = Some(years),
+                    _ => return Err(format!("invalid age '{}'", value)),
+                }
+            }
+            "--resting" => {
+                let value = value()?;
+                match value.parse::<u32>() {
+                    Ok(bpm) if bpm > 0 => resting_pulse = Some(bpm),
+                    _ => return Err(format!("invalid resting pulse '{}'", value)),
+                }
+            }
+            "--format" => {
+                format = match value()?.as_str() {
+                    "table" => OutputFormat::Table,
+                    "csv" => OutputFormat::Csv,
+                    other => return Err(format!("unknown format '{}'", other)),
+                }
+            }
+            "--max-hr" => formula = parse_formula(value()?)?,
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+
+    Ok(Some(HeadlessOptions {
+        age: age.ok_or("--age is required")?,
+        resting_pulse: resting_pulse.ok_or("--resting is required")?,
+        format,
+        formula,
+    }))
+}
+
+fn format_zones(zones: &[Zone], format: OutputFormat, formula: MaxHrFormula) -> String {
+    let mut output = String::new();
+    match format {
+        OutputFormat::Table => {
+            output.push_str(&format!("Max HR formula: {}\n", formula));
+            output.push_str("Intensity | Rate\n");
+            output.push_str("----------|--------\n");
+            for zone in zones {
+                output.push_str(&format!("{:>8}% | {} bpm\n", zone.intensity, zone.rate));
+            }
+        }
+        OutputFormat::Csv => {
+            output.push_str("intensity,rate\n");
+            for zone in zones {
+                output.push_str(&format!("{},{}\n", zone.intensity, zone.rate));
+            }
+        }
+    }
+    output
+}
+
 impl eframe::App for BpmTracker {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
@@ -36,6 +143,26 @@ impl eframe::App for BpmTracker {
 
             ui.add(egui::Slider::new(&mut self.age, 1..=110).text("age"));
 
+            ui.horizontal(|ui| {
+                ui.label("Max HR:");
+                egui::ComboBox::from_id_salt("max_hr_formula")
+                    .selected_text(self.formula.to_string())
+                    .show_ui(ui, |ui| {
+                        for formula in [
+                            MaxHrFormula::Classic,
+                            MaxHrFormula::Tanaka,
+                            MaxHrFormula::Gulati,
+                            MaxHrFormula::Measured(self.measured_max),
+                        ] {
+                            ui.selectable_value(&mut self.formula, formula, formula.to_string());
+                        }
+                    });
+                if let MaxHrFormula::Measured(bpm) = &mut self.formula {
+                    ui.add(egui::DragValue::new(bpm).range(100..=230).suffix(" bpm"));
+                    self.measured_max = *bpm;
+                }
+            });
+
             ui.separator();
 
             egui::ScrollArea::both()
@@ -49,11 +176,9 @@ impl eframe::App for BpmTracker {
                             ui.label("Intensity");
                             ui.label("Rate");
                             ui.end_row();
-                            for intensity in (55..=95).step_by(5) {
-                                let target_heart_rate =
-                                    self.karvonen_target_heart_rate(intensity as f64 / 100.0);
-                                ui.label(format!("{}%", intensity));
-                                ui.label(format!("{} bpm", target_heart_rate));
+                            for zone in zone_table(self.age, self.resting_pulse, self.formula) {
+                                ui.label(format!("{}%", zone.intensity));
+                                ui.label(format!("{} bpm", zone.rate));
                                 ui.end_row();
                             }
                         });
@@ -63,6 +188,23 @@ impl eframe::App for BpmTracker {
 }
 
 fn main() -> eframe::Result {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match parse_args(&args) {
+        Ok(Some(options)) => {
+            let zones = zone_table(options.age, options.resting_pulse, options.formula);
+            print!("{}", format_zones(&zones, options.format, options.formula));
+            return Ok(());
+        }
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!(
+                "Usage: e31 [--age <years> --resting <bpm> [--format table|csv] [--max-hr <formula|bpm>]]"
+            );
+            std::process::exit(1);
+        }
+    }
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 250.0]),
         ..Default::default()
@@ -78,64 +220,95 @@ fn main() -> eframe::Result {
 mod tests {
     use super::*;
 
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
     #[test]
-    fn karvonen_target_heart_rate_calculates_correctly() {
-        // Test case with typical adult values
-        let tracker = BpmTracker {
-            resting_pulse: 70,
-            age: 30,
-        };
-
-        // Expected: (220-30-70)*0.65 + 70 = 78 + 70 = 148
-        assert_eq!(tracker.karvonen_target_heart_rate(0.65), 148);
-        assert_eq!(tracker.karvonen_target_heart_rate(0.55), 136); // 55% intensity
-        assert_eq!(tracker.karvonen_target_heart_rate(0.95), 184); // 95% intensity
+    fn parse_args_reads_headless_options() {
+        assert_eq!(
+            parse_args(&args(&[
+                "--age",
+                "30",
+                "--resting",
+                "70",
+                "--format",
+                "csv"
+            ])),
+            Ok(Some(HeadlessOptions {
+                age: 30,
+                resting_pulse: 70,
+                format: OutputFormat::Csv,
+                formula: MaxHrFormula::Classic,
+            }))
+        );
+        assert_eq!(parse_args(&[]), Ok(None));
+        assert!(parse_args(&args(&["--age", "30"])).is_err());
+        assert!(parse_args(&args(&["--age", "abc", "--resting", "70"])).is_err());
+        assert!(
+            parse_args(&args(&[
+                "--age",
+                "30",
+                "--resting",
+                "70",
+                "--format",
+                "xml"
+            ]))
+            .is_err()
+        );
     }
 
     #[test]
-    fn karvonen_target_heart_rate_handles_boundary_cases() {
-        // Test with senior age
-        let senior_tracker = BpmTracker {
-            resting_pulse: 65,
-            age: 80,
-        };
-        // Expected: (220-80-65)*0.70 + 65 = 52.5 + 65 = 118 (rounded)
-        assert_eq!(senior_tracker.karvonen_target_heart_rate(0.70), 118);
-
-        // Test with child age
-        let child_tracker = BpmTracker {
-            resting_pulse: 80,
-            age: 10,
-        };
-        // Expected: (220-10-80)*0.60 + 80 = 78 + 80 = 158
-        assert_eq!(child_tracker.karvonen_target_heart_rate(0.60), 158);
+    fn format_zones_writes_csv() {
+        let zones = zone_table(30, 70, MaxHrFormula::Classic);
+        let csv = format_zones(&zones[..2], OutputFormat::Csv, MaxHrFormula::Classic);
+        assert_eq!(csv, "intensity,rate\n55,136\n60,142\n");
     }
 
     #[test]
-    fn karvonen_target_heart_rate_handles_extreme_intensities() {
-        let tracker = BpmTracker {
-            resting_pulse: 60,
-            age: 40,
-        };
-
-        // At 0% intensity, result should be the resting heart rate
-        // Expected: (220-40-60)*0.0 + 60 = 0 + 60 = 60
-        assert_eq!(tracker.karvonen_target_heart_rate(0.0), 60);
-
-        // At 100% intensity, result should be the maximum heart rate
-        // Expected: (220-40-60)*1.0 + 60 = 120 + 60 = 180
-        assert_eq!(tracker.karvonen_target_heart_rate(1.0), 180);
+    fn parse_args_reads_max_hr_formula() {
+        let options = parse_args(&args(&[
+            "--age",
+            "40",
+            "--resting",
+            "60",
+            "--max-hr",
+            "tanaka",
+        ]))
+        .unwrap()
+        .unwrap();
+        assert_eq!(options.formula, MaxHrFormula::Tanaka);
+
+        let options = parse_args(&args(&[
+            "--age",
+            "40",
+            "--resting",
+            "60",
+            "--max-hr",
+            "185",
+        ]))
+        .unwrap()
+        .unwrap();
+        assert_eq!(options.formula, MaxHrFormula::Measured(185));
+
+        assert!(parse_args(&args(&["--age", "40", "--resting", "60", "--max-hr", "0"])).is_err());
+        assert!(
+            parse_args(&args(&[
+                "--age",
+                "40",
+                "--resting",
+                "60",
+                "--max-hr",
+                "fox"
+            ]))
+            .is_err()
+        );
     }
 
     #[test]
-    fn karvonen_target_heart_rate_handles_rounding() {
-        let tracker = BpmTracker {
-            resting_pulse: 67,
-            age: 33,
-        };
-
-        // This will produce a floating point result that needs rounding
-        // Expected: (220-33-67)*0.75 + 67 = 90 + 67 = 157
-        assert_eq!(tracker.karvonen_target_heart_rate(0.75), 157);
+    fn format_zones_notes_formula_in_table() {
+        let zones = zone_table(40, 60, MaxHrFormula::Gulati);
+        let table = format_zones(&zones[..1], OutputFormat::Table, MaxHrFormula::Gulati);
+        assert!(table.starts_with("Max HR formula: Gulati (206 - 0.88 x age)\n"));
     }
 }
@@ -11,11 +11,25 @@
 //! - **Interactive Grid Layout**: Numbers are displayed in a scrollable grid with 10 columns
 //! - **Win Detection**: Shows a congratulatory popup when the correct number is guessed
 //! - **Replayability**: Allows resetting the game to try again with a new target number
+//! - **Session Statistics**: Persists each finished game's difficulty, guess count, and time,
+//!   and shows games played, average guesses per difficulty, and a performance trend
+//! - **Adaptive Difficulty**: After each game, suggests moving up or down a tier based on recent
+//!   guess efficiency; auto mode picks the next game's difficulty the same way
+//! - **Challenge Codes**: Every game is seeded and shows a shareable code such as `M-4F7KQ2X`;
+//!   entering the code starts a game with the same difficulty and hidden number, and codes are
+//!   validated (difficulty, characters, length, and a check character) before play
 use eframe::egui::{self, ahash::HashMap};
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
-#[derive(Debug, PartialEq, Default)]
+const RESULTS_FILE: &str = "exercises/e32/results.jsonl";
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 enum Difficulty {
     #[default]
     Easy,
@@ -23,6 +37,269 @@ enum Difficulty {
     Hard,
 }
 
+const DIFFICULTIES: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+impl Difficulty {
+    fn range(&self) -> std::ops::RangeInclusive<u32> {
+        match self {
+            Difficulty::Easy => 1..=10,
+            Difficulty::Medium => 1..=100,
+            Difficulty::Hard => 1..=1000,
+        }
+    }
+
+    fn harder(&self) -> Option<Difficulty> {
+        match self {
+            Difficulty::Easy => Some(Difficulty::Medium),
+            Difficulty::Medium => Some(Difficulty::Hard),
+            Difficulty::Hard => None,
+        }
+    }
+
+    fn easier(&self) -> Option<Difficulty> {
+        match self {
+            Difficulty::Easy => None,
+            Difficulty::Medium => Some(Difficulty::Easy),
+            Difficulty::Hard => Some(Difficulty::Medium),
+        }
+    }
+
+    /// Worst-case number of guesses a binary search needs for this difficulty's range.
+    fn optimal_guesses(&self) -> u32 {
+        (self.range().end() + 1).next_power_of_two().ilog2()
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "Easy"),
+            Difficulty::Medium => write!(f, "Medium"),
+            Difficulty::Hard => write!(f, "Hard"),
+        }
+    }
+}
+
+// Crockford base32, which leaves out I, L, O, and U so codes are easy to read aloud.
+const CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const SEED_CHARS: usize = 6;
+const SEED_BITS: u32 = 5 * SEED_CHARS as u32;
+
+/// A seeded game that two players can attempt by sharing its code.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Challenge {
+    difficulty: Difficulty,
+    seed: u32,
+}
+
+impl Challenge {
+    fn random(difficulty: Difficulty) -> Self {
+        Challenge {
+            difficulty,
+            seed: rand::rng().random_range(0..1 << SEED_BITS),
+        }
+    }
+
+    fn prefix(difficulty: Difficulty) -> char {
+        match difficulty {
+            Difficulty::Easy => 'E',
+            Difficulty::Medium => 'M',
+            Difficulty::Hard => 'H',
+        }
+    }
+
+    /// Check character over the difficulty and seed digits. The weights are odd, so any single
+    /// mistyped character is rejected rather than silently starting a different game.
+    fn check_digit(difficulty: Difficulty, digits: &[u8]) -> u8 {
+        let sum = digits
+            .iter()
+            .enumerate()
+            .fold(Self::prefix(difficulty) as u32, |sum, (i, &digit)| {
+                sum + (2 * i as u32 + 1) * u32::from(digit)
+            });
+        (sum % 32) as u8
+    }
+
+    fn digits(&self) -> Vec<u8> {
+        (0..SEED_CHARS)
+            .rev()
+            .map(|i| ((self.seed >> (5 * i)) & 31) as u8)
+            .collect()
+    }
+
+    fn code(&self) -> String {
+        let mut digits = self.digits();
+        digits.push(Self::check_digit(self.difficulty, &digits));
+        let body: String = digits
+            .iter()
+            .map(|&digit| CODE_ALPHABET[digit as usize] as char)
+            .collect();
+        format!("{}-{}", Self::prefix(self.difficulty), body)
+    }
+
+    fn parse(code: &str) -> Result<Self, String> {
+        let code = code.trim().to_ascii_uppercase();
+        let (prefix, body) = code.split_once('-').ok_or("Codes look like M-4F7KQ2X.")?;
+        let difficulty = DIFFICULTIES
+            .into_iter()
+            .find(|d| prefix.len() == 1 && prefix.starts_with(Self::prefix(*d)))
+            .ok_or_else(|| format!("Unknown difficulty '{}'; use E, M, or H.", prefix))?;
+        if body.len() != SEED_CHARS + 1 {
+            return Err(format!(
+                "Codes have {} characters after the dash.",
+                SEED_CHARS + 1
+            ));
+        }
+        let digits = body
+            .bytes()
+            .map(|c| {
+                CODE_ALPHABET
+                    .iter()
+                    .position(|&a| a == c)
+                    .map(|digit| digit as u8)
+                    .ok_or_else(|| format!("'{}' is not a valid code character.", c as char))
+            })
+            .collect::<Result<Vec<u8>, String>>()?;
+        let (seed_digits, check) = digits.split_at(SEED_CHARS);
+        if check[0] != Self::check_digit(difficulty, seed_digits) {
+            return Err("That code has a typo; please check it and try again.".to_string());
+        }
+        let seed = seed_digits
+            .iter()
+            .fold(0u32, |seed, &digit| (seed << 5) | u32::from(digit));
+        Ok(Challenge { difficulty, seed })
+    }
+
+    /// Derives the hidden number from the seed with SplitMix64, which unlike the `rand`
+    /// generators is fixed, so a code picks the same number in every build of the game.
+    fn target(&self) -> u32 {
+        let range = self.difficulty.range();
+        let mut z = (u64::from(self.seed) << 2 | self.difficulty as u64)
+            .wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        let span = u64::from(range.end() - range.start() + 1);
+        range.start() + (z % span) as u32
+    }
+}
+
+/// Outcome of a finished game, persisted one JSON object per line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct GameRecord {
+    difficulty: Difficulty,
+    guesses: u32,
+    duration_secs: f64,
+    played_at: u64,
+}
+
+impl GameRecord {
+    /// Guesses relative to a perfect binary search; lower is better.
+    fn score(&self) -> f64 {
+        f64::from(self.guesses) / f64::from(self.difficulty.optimal_guesses())
+    }
+}
+
+fn append_record(
+    results_file: &Path,
+    record: &GameRecord,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(results_file)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
+fn read_records(results_file: &Path) -> Result<Vec<GameRecord>, Box<dyn std::error::Error>> {
+    if !results_file.exists() {
+        return Ok(Vec::new());
+    }
+    let reader = BufReader::new(std::fs::File::open(results_file)?);
+    let mut records = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            records.push(serde_json::from_str(&line)?);
+        }
+    }
+    records.sort_by_key(|r: &GameRecord| r.played_at);
+    Ok(records)
+}
+
+/// Returns the number of games and average guesses for each difficulty that has been played.
+fn average_guesses(records: &[GameRecord]) -> Vec<(Difficulty, usize, f64)> {
+    DIFFICULTIES
+        .iter()
+        .filter_map(|&difficulty| {
+            let guesses: Vec<u32> = records
+                .iter()
+                .filter(|r| r.difficulty == difficulty)
+                .map(|r| r.guesses)
+                .collect();
+            if guesses.is_empty() {
+                return None;
+            }
+            let average = f64::from(guesses.iter().sum::<u32>()) / guesses.len() as f64;
+            Some((difficulty, guesses.len(), average))
+        })
+        .collect()
+}
+
+// Number of most recent games at a difficulty used to judge performance.
+const RECENT_GAMES: usize = 5;
+const MIN_GAMES_FOR_SUGGESTION: usize = 3;
+// Average scores (guesses relative to a binary search) that trigger a tier change.
+const MOVE_UP_SCORE: f64 = 0.8;
+const MOVE_DOWN_SCORE: f64 = 1.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DifficultySuggestion {
+    MoveUp(Difficulty),
+    MoveDown(Difficulty),
+    Stay,
+}
+
+/// Average score of the most recent games played at `difficulty`, if enough have been played.
+fn recent_efficiency(records: &[GameRecord], difficulty: Difficulty) -> Option<f64> {
+    let scores: Vec<f64> = records
+        .iter()
+        .rev()
+        .filter(|r| r.difficulty == difficulty)
+        .take(RECENT_GAMES)
+        .map(GameRecord::score)
+        .collect();
+    if scores.len() < MIN_GAMES_FOR_SUGGESTION {
+        return None;
+    }
+    Some(scores.iter().sum::<f64>() / scores.len() as f64)
+}
+
+fn suggest_difficulty(records: &[GameRecord], current: Difficulty) -> DifficultySuggestion {
+    match recent_efficiency(records, current) {
+        Some(score) if score <= MOVE_UP_SCORE => current
+            .harder()
+            .map_or(DifficultySuggestion::Stay, DifficultySuggestion::MoveUp),
+        Some(score) if score > MOVE_DOWN_SCORE => current
+            .easier()
+            .map_or(DifficultySuggestion::Stay, DifficultySuggestion::MoveDown),
+        _ => DifficultySuggestion::Stay,
+    }
+}
+
+/// Difficulty for the next game in auto mode: the last game's difficulty adjusted by the
+/// current suggestion, starting at Easy.
+fn auto_difficulty(records: &[GameRecord]) -> Difficulty {
+    let current = records.last().map_or(Difficulty::Easy, |r| r.difficulty);
+    match suggest_difficulty(records, current) {
+        DifficultySuggestion::MoveUp(difficulty) | DifficultySuggestion::MoveDown(difficulty) => {
+            difficulty
+        }
+        DifficultySuggestion::Stay => current,
+    }
+}
+
 #[derive(Debug, Default)]
 enum GuessResult {
     #[default]
@@ -46,6 +323,14 @@ struct GuessingGame {
     difficulty: Option<Difficulty>,
     target: u32,
     guesses: HashMap<u32, GuessResult>,
+    started: Option<Instant>,
+    history: Vec<GameRecord>,
+    show_stats: bool,
+    status: Option<String>,
+    auto: bool,
+    challenge: Option<Challenge>,
+    code_input: String,
+    code_error: Option<String>,
 }
 
 impl GuessingGame {
@@ -53,6 +338,109 @@ impl GuessingGame {
         self.difficulty = None;
         self.target = 0;
         self.guesses.clear();
+        self.started = None;
+        self.challenge = None;
+        if self.auto {
+            self.start(auto_difficulty(&self.history));
+        }
+    }
+
+    fn start(&mut self, difficulty: Difficulty) {
+        self.start_challenge(Challenge::random(difficulty));
+    }
+
+    fn start_challenge(&mut self, challenge: Challenge) {
+        self.difficulty = Some(challenge.difficulty);
+        self.target = challenge.target();
+        self.challenge = Some(challenge);
+        self.guesses.clear();
+        self.started = Some(Instant::now());
+        self.code_error = None;
+    }
+
+    fn start_from_code(&mut self) {
+        match Challenge::parse(&self.code_input) {
+            Ok(challenge) => {
+                self.auto = false;
+                self.start_challenge(challenge);
+            }
+            Err(e) => self.code_error = Some(e),
+        }
+    }
+
+    fn suggestion_text(&self) -> Option<String> {
+        let difficulty = self.difficulty?;
+        let text = match suggest_difficulty(&self.history, difficulty) {
+            DifficultySuggestion::MoveUp(harder) => format!(
+                "You're guessing efficiently. Try {} ({}-{}) next!",
+                harder,
+                harder.range().start(),
+                harder.range().end()
+            ),
+            DifficultySuggestion::MoveDown(easier) => format!(
+                "These have been tough. {} ({}-{}) might be more fun.",
+                easier,
+                easier.range().start(),
+                easier.range().end()
+            ),
+            DifficultySuggestion::Stay => return None,
+        };
+        Some(if self.auto {
+            format!("{} Auto mode will switch for the next game.", text)
+        } else {
+            text
+        })
+    }
+
+    fn record_result(&mut self) {
+        let Some(difficulty) = self.difficulty else {
+            return;
+        };
+        let record = GameRecord {
+            difficulty,
+            guesses: self.guesses.len() as u32,
+            duration_secs: self.started.map_or(0.0, |s| s.elapsed().as_secs_f64()),
+            played_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        };
+        self.status = append_record(Path::new(RESULTS_FILE), &record)
+            .err()
+            .map(|e| format!("Could not save result: {}", e));
+        self.history.push(record);
+    }
+
+    fn show_statistics(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Statistics");
+        ui.label(format!("Games played: {}", self.history.len()));
+
+        egui::Grid::new("average_guesses")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("Difficulty");
+                ui.strong("Games");
+                ui.strong("Avg. guesses");
+                ui.end_row();
+                for (difficulty, games, average) in average_guesses(&self.history) {
+                    ui.label(difficulty.to_string());
+                    ui.label(games.to_string());
+                    ui.label(format!("{:.1}", average));
+                    ui.end_row();
+                }
+            });
+
+        ui.separator();
+        ui.label("Performance trend (guesses vs. perfect binary search, lower is better):");
+        let scores: Vec<f64> = self.history.iter().map(GameRecord::score).collect();
+        if scores.len() < 2 {
+            ui.label("Play at least two games to see a trend.");
+        } else {
+            plot_trend(ui, &scores);
+        }
+
+        if ui.button("Back").clicked() {
+            self.show_stats = false;
+        }
     }
 
     fn is_game_over(&self) -> bool {
@@ -61,9 +449,7 @@ impl GuessingGame {
 
     fn get_difficulty_range(&self) -> std::ops::RangeInclusive<u32> {
         match self.difficulty {
-            Some(Difficulty::Easy) => 1..=10,
-            Some(Difficulty::Medium) => 1..=100,
-            Some(Difficulty::Hard) => 1..=1000,
+            Some(difficulty) => difficulty.range(),
             None => 1..=1, // Default range if no difficulty is selected
         }
     }
@@ -77,10 +463,51 @@ impl GuessingGame {
     }
 }
 
+/// Draws `scores` as a line chart scaled to fit the available width.
+fn plot_trend(ui: &mut egui::Ui, scores: &[f64]) {
+    let size = egui::vec2(ui.available_width(), 120.0);
+    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let max_score = scores.iter().cloned().fold(1.0, f64::max);
+    let step = rect.width() / (scores.len() - 1) as f32;
+    let points: Vec<egui::Pos2> = scores
+        .iter()
+        .enumerate()
+        .map(|(i, score)| {
+            let y = rect.bottom() - (score / max_score) as f32 * rect.height();
+            egui::pos2(rect.left() + i as f32 * step, y)
+        })
+        .collect();
+
+    let painter = ui.painter_at(rect);
+    painter.rect_stroke(
+        rect,
+        0.0,
+        ui.visuals().widgets.noninteractive.bg_stroke,
+        egui::StrokeKind::Inside,
+    );
+    painter.add(egui::Shape::line(
+        points,
+        egui::Stroke::new(2.0, ui.visuals().selection.bg_fill),
+    ));
+}
+
 impl eframe::App for GuessingGame {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
-            if let Some(_difficulty) = &self.difficulty {
+            if let Some(status) = &self.status {
+                ui.colored_label(egui::Color32::RED, status);
+            }
+            if self.show_stats {
+                self.show_statistics(ui);
+            } else if let Some(_difficulty) = &self.difficulty {
+                if let Some(challenge) = self.challenge {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Challenge code: {}", challenge.code()));
+                        if ui.small_button("Copy").clicked() {
+                            ui.ctx().copy_text(challenge.code());
+                        }
+                    });
+                }
                 // Construct a range based on the selected difficulty
                 let range = self.get_difficulty_range();
 
@@ -111,6 +538,7 @@ impl eframe::App for GuessingGame {
                                         match result {
                                             GuessResult::Correct => {
                                                 self.guesses.insert(number, GuessResult::Correct);
+                                                self.record_result();
                                             }
                                             GuessResult::TooLow => {
                                                 self.guesses.insert(number, GuessResult::TooLow);
@@ -130,38 +558,68 @@ impl eframe::App for GuessingGame {
                                     ui.end_row();
                                 }
                             });
+                        if self.is_game_over()
+                            && let Some(suggestion) = self.suggestion_text()
+                        {
+                            ui.label(suggestion);
+                        }
                         ui.with_layout(
                             egui::Layout::top_down_justified(egui::Align::Center),
                             |ui| {
-                                if ui.button("Reset Game").clicked() {
+                                let label = if self.auto { "Next Game" } else { "Reset Game" };
+                                if ui.button(label).clicked() {
+                                    self.reset();
+                                }
+                                if self.auto && ui.button("Leave Auto Mode").clicked() {
+                                    self.auto = false;
                                     self.reset();
                                 }
                             },
                         );
                     });
             } else {
+                let mut selected = None;
                 egui::ComboBox::from_label("Difficulty")
-                    .selected_text(self.difficulty.as_ref().map_or(
-                        "Select Difficulty".to_string(),
-                        |d| match d {
-                            Difficulty::Easy => "Easy".to_string(),
-                            Difficulty::Medium => "Medium".to_string(),
-                            Difficulty::Hard => "Hard".to_string(),
-                        },
-                    ))
+                    .selected_text("Select Difficulty")
                     .show_ui(ui, |ui| {
-                        ui.selectable_value(&mut self.difficulty, Some(Difficulty::Easy), "Easy");
-                        ui.selectable_value(
-                            &mut self.difficulty,
-                            Some(Difficulty::Medium),
-                            "Medium",
-                        );
-                        ui.selectable_value(&mut self.difficulty, Some(Difficulty::Hard), "Hard");
+                        ui.selectable_value(&mut selected, Some(Difficulty::Easy), "Easy");
+                        ui.selectable_value(&mut selected, Some(Difficulty::Medium), "Medium");
+                        ui.selectable_value(&mut selected, Some(Difficulty::Hard), "Hard");
                     });
+                if let Some(difficulty) = selected {
+                    self.start(difficulty);
+                    return;
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label("Challenge code:");
+                    let response = ui.text_edit_singleline(&mut self.code_input);
+                    let entered =
+                        response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    if response.changed() {
+                        self.code_error = None;
+                    }
+                    if ui.button("Play").clicked() || entered {
+                        self.start_from_code();
+                    }
+                });
+                if let Some(error) = &self.code_error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                if self.difficulty.is_some() {
+                    return;
+                }
 
-                // Randomly select a target number within the range
-                let mut rng = rand::rng();
-                self.target = rng.random_range(self.get_difficulty_range());
+                let next = auto_difficulty(&self.history);
+                if ui.button(format!("Auto ({})", next)).clicked() {
+                    self.auto = true;
+                    self.start(next);
+                    return;
+                }
+
+                if ui.button("Statistics").clicked() {
+                    self.show_stats = true;
+                }
             }
         });
     }
@@ -172,9 +630,169 @@ fn main() -> eframe::Result {
         viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 400.0]),
         ..Default::default()
     };
-    eframe::run_native(
-        "Guessing Game",
-        options,
-        Box::new(|_| Ok(Box::<GuessingGame>::default())),
-    )
+    let mut game = GuessingGame::default();
+    match read_records(Path::new(RESULTS_FILE)) {
+        Ok(history) => game.history = history,
+        Err(e) => game.status = Some(format!("Could not load past results: {}", e)),
+    }
+    eframe::run_native("Guessing Game", options, Box::new(|_| Ok(Box::new(game))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(difficulty: Difficulty, guesses: u32) -> GameRecord {
+        GameRecord {
+            difficulty,
+            guesses,
+            duration_secs: 1.0,
+            played_at: 0,
+        }
+    }
+
+    #[test]
+    fn optimal_guesses_matches_binary_search() {
+        assert_eq!(Difficulty::Easy.optimal_guesses(), 4);
+        assert_eq!(Difficulty::Medium.optimal_guesses(), 7);
+        assert_eq!(Difficulty::Hard.optimal_guesses(), 10);
+        assert_eq!(record(Difficulty::Hard, 5).score(), 0.5);
+    }
+
+    #[test]
+    fn average_guesses_groups_by_difficulty() {
+        let records = vec![
+            record(Difficulty::Easy, 2),
+            record(Difficulty::Hard, 9),
+            record(Difficulty::Easy, 5),
+        ];
+        assert_eq!(
+            average_guesses(&records),
+            vec![(Difficulty::Easy, 2, 3.5), (Difficulty::Hard, 1, 9.0)]
+        );
+    }
+
+    #[test]
+    fn suggest_difficulty_uses_recent_efficiency() {
+        let mut records = vec![record(Difficulty::Medium, 4); 3];
+        assert_eq!(
+            suggest_difficulty(&records, Difficulty::Medium),
+            DifficultySuggestion::MoveUp(Difficulty::Hard)
+        );
+        assert_eq!(
+            suggest_difficulty(&records[..2], Difficulty::Medium),
+            DifficultySuggestion::Stay
+        );
+
+        // Only the most recent games count.
+        records.extend(vec![record(Difficulty::Medium, 14); RECENT_GAMES]);
+        assert_eq!(
+            suggest_difficulty(&records, Difficulty::Medium),
+            DifficultySuggestion::MoveDown(Difficulty::Easy)
+        );
+        assert_eq!(
+            suggest_difficulty(&vec![record(Difficulty::Hard, 1); 3], Difficulty::Hard),
+            DifficultySuggestion::Stay
+        );
+    }
+
+    #[test]
+    fn auto_difficulty_follows_suggestion() {
+        assert_eq!(auto_difficulty(&[]), Difficulty::Easy);
+        assert_eq!(
+            auto_difficulty(&vec![record(Difficulty::Easy, 2); 3]),
+            Difficulty::Medium
+        );
+        assert_eq!(
+            auto_difficulty(&vec![record(Difficulty::Hard, 10); 3]),
+            Difficulty::Hard
+        );
+    }
+
+    #[test]
+    fn challenge_codes_round_trip() {
+        for difficulty in DIFFICULTIES {
+            for seed in [0, 1, 12_345, (1 << SEED_BITS) - 1] {
+                let challenge = Challenge { difficulty, seed };
+                let code = challenge.code();
+                assert_eq!(code.len(), SEED_CHARS + 3);
+                assert_eq!(Challenge::parse(&code), Ok(challenge));
+                assert_eq!(
+                    Challenge::parse(&format!(" {} ", code.to_lowercase())),
+                    Ok(challenge)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn challenge_targets_are_deterministic_and_in_range() {
+        for difficulty in DIFFICULTIES {
+            for seed in 0..200 {
+                let challenge = Challenge { difficulty, seed };
+                let target = challenge.target();
+                assert!(difficulty.range().contains(&target));
+                assert_eq!(
+                    Challenge::parse(&challenge.code()).unwrap().target(),
+                    target
+                );
+            }
+        }
+        // The derivation is fixed, so shared codes keep working across versions.
+        let challenge = Challenge {
+            difficulty: Difficulty::Hard,
+            seed: 42,
+        };
+        assert_eq!(challenge.target(), 115);
+        let targets: std::collections::HashSet<u32> = (0..50)
+            .map(|seed| {
+                Challenge {
+                    difficulty: Difficulty::Hard,
+                    seed,
+                }
+                .target()
+            })
+            .collect();
+        assert!(targets.len() > 40);
+    }
+
+    #[test]
+    fn challenge_parse_rejects_invalid_codes() {
+        let code = Challenge {
+            difficulty: Difficulty::Medium,
+            seed: 987_654,
+        }
+        .code();
+        assert!(Challenge::parse("").is_err());
+        assert!(Challenge::parse(&code.replacen('M', "X", 1)).is_err());
+        assert!(Challenge::parse(&code[..code.len() - 1]).is_err());
+        assert!(Challenge::parse(&format!("{}U", &code[..code.len() - 1])).is_err());
+        let mut typo = code.into_bytes();
+        typo[3] = if typo[3] == b'0' { b'1' } else { b'0' };
+        assert_eq!(
+            Challenge::parse(std::str::from_utf8(&typo).unwrap()),
+            Err("That code has a typo; please check it and try again.".to_string())
+        );
+    }
+
+    #[test]
+    fn start_from_code_replays_the_same_game() {
+        let challenge = Challenge::random(Difficulty::Medium);
+        let mut game = GuessingGame {
+            code_input: challenge.code(),
+            ..Default::default()
+        };
+        game.start_from_code();
+        assert_eq!(game.difficulty, Some(Difficulty::Medium));
+        assert_eq!(game.target, challenge.target());
+        assert_eq!(game.challenge, Some(challenge));
+
+        let mut game = GuessingGame {
+            code_input: "E-123".to_string(),
+            ..Default::default()
+        };
+        game.start_from_code();
+        assert_eq!(game.difficulty, None);
+        assert!(game.code_error.is_some());
+    }
 }
@@ -8,17 +8,82 @@
 //! - **Question Input**: Type your question in a text field
 //! - **Random Responses**: Get one of several possible answers when you "shake" the ball
 //! - **Simple Interface**: Clean, intuitive UI for asking questions and viewing responses
+//! - **Decision Journal**: Optionally records each question and answer, lets the user mark
+//!   later whether they followed the advice and how it turned out, and reports how often the
+//!   ball was right
 //!
 //! The simulator provides a virtual Magic 8 Ball experience with a set of predefined
 //! responses that are randomly selected when the user submits a question.
 use eframe::egui::{self};
 use rand::seq::IndexedRandom;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const JOURNAL_FILE: &str = "exercises/e33/journal.json";
+
+/// A question asked in journal mode and, once resolved, what the user did and how it went.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct JournalEntry {
+    question: String,
+    answer: String,
+    followed: bool,
+    turned_out_well: Option<bool>,
+}
+
+impl JournalEntry {
+    /// The ball was right if following its advice went well or ignoring it went badly.
+    fn ball_was_right(&self) -> Option<bool> {
+        self.turned_out_well
+            .map(|turned_out_well| turned_out_well == self.followed)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct JournalStats {
+    entries: usize,
+    resolved: usize,
+    right: usize,
+}
+
+impl JournalStats {
+    fn percent_right(&self) -> Option<f64> {
+        (self.resolved > 0).then(|| self.right as f64 / self.resolved as f64 * 100.0)
+    }
+}
+
+fn journal_stats(journal: &[JournalEntry]) -> JournalStats {
+    let verdicts: Vec<bool> = journal.iter().filter_map(|e| e.ball_was_right()).collect();
+    JournalStats {
+        entries: journal.len(),
+        resolved: verdicts.len(),
+        right: verdicts.iter().filter(|&&right| right).count(),
+    }
+}
+
+fn read_journal(file_path: &Path) -> Result<Vec<JournalEntry>, Box<dyn std::error::Error>> {
+    if !file_path.exists() {
+        return Ok(Vec::new());
+    }
+    let file = std::fs::File::open(file_path)?;
+    Ok(serde_json::from_reader(file)?)
+}
+
+fn write_journal(
+    file_path: &Path,
+    journal: &[JournalEntry],
+) -> Result<(), Box<dyn std::error::Error>> {
+    persist::write_json(file_path, journal)?;
+    Ok(())
+}
 
 #[derive(Debug)]
 struct Magic8Ball {
     question: String,
     response: Option<&'static str>,
     responses: [&'static str; 4],
+    journal_mode: bool,
+    journal: Vec<JournalEntry>,
+    status: Option<String>,
 }
 
 impl Default for Magic8Ball {
@@ -27,6 +92,9 @@ impl Default for Magic8Ball {
             question: String::new(),
             response: None,
             responses: ["Yes", "No", "Ask again later", "Definitely not"],
+            journal_mode: false,
+            journal: Vec::new(),
+            status: None,
         }
     }
 }
@@ -40,12 +108,82 @@ impl Magic8Ball {
                 .unwrap_or(&self.responses[0]),
         )
     }
+
+    fn record_in_journal(&mut self) {
+        if let Some(response) = self.response {
+            self.journal.push(JournalEntry {
+                question: self.question.clone(),
+                answer: response.to_string(),
+                followed: false,
+                turned_out_well: None,
+            });
+            self.save_journal();
+        }
+    }
+
+    fn save_journal(&mut self) {
+        self.status = write_journal(Path::new(JOURNAL_FILE), &self.journal)
+            .err()
+            .map(|e| format!("Could not save journal: {}", e));
+    }
+
+    fn show_journal(&mut self, ui: &mut egui::Ui) {
+        let stats = journal_stats(&self.journal);
+        match stats.percent_right() {
+            Some(percent) => ui.label(format!(
+                "The ball was right {:.0}% of the time ({} of {} resolved decisions).",
+                percent, stats.right, stats.resolved
+            )),
+            None => ui.label(format!(
+                "{} question(s) recorded. Resolve a decision to see how the ball did.",
+                stats.entries
+            )),
+        };
+
+        let mut changed = false;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for entry in self.journal.iter_mut().rev() {
+                ui.separator();
+                ui.label(format!("{} → {}", entry.question, entry.answer));
+                match entry.turned_out_well {
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut entry.followed, "Followed the advice");
+                            if ui.button("Turned out well").clicked() {
+                                entry.turned_out_well = Some(true);
+                                changed = true;
+                            }
+                            if ui.button("Turned out badly").clicked() {
+                                entry.turned_out_well = Some(false);
+                                changed = true;
+                            }
+                        });
+                    }
+                    Some(turned_out_well) => {
+                        ui.label(format!(
+                            "{} the advice; it turned out {}.",
+                            if entry.followed {
+                                "Followed"
+                            } else {
+                                "Ignored"
+                            },
+                            if turned_out_well { "well" } else { "badly" }
+                        ));
+                    }
+                }
+            }
+        });
+        if changed {
+            self.save_journal();
+        }
+    }
 }
 
 impl eframe::App for Magic8Ball {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.label("Ask a question and shake the Magic 8 Ball!");
+            ui.checkbox(&mut self.journal_mode, "Decision journal");
             ui.horizontal(|ui| {
                 ui.label("Your Question:");
                 ui.text_edit_singleline(&mut self.question);
@@ -53,10 +191,20 @@ impl eframe::App for Magic8Ball {
 
             if !self.question.is_empty() && ui.button("Shake").clicked() {
                 self.set_rand_response();
+                if self.journal_mode {
+                    self.record_in_journal();
+                }
             }
             if let Some(response) = &self.response {
                 ui.label(format!("Magic 8 Ball says: {}", response));
             }
+            if let Some(status) = &self.status {
+                ui.colored_label(egui::Color32::RED, status);
+            }
+            if self.journal_mode {
+                ui.separator();
+                self.show_journal(ui);
+            }
         });
     }
 }
@@ -66,9 +214,60 @@ fn main() -> eframe::Result {
         viewport: egui::ViewportBuilder::default().with_inner_size([400.0, 400.0]),
         ..Default::default()
     };
+    let mut magic_8_ball = Magic8Ball::default();
+    match read_journal(Path::new(JOURNAL_FILE)) {
+        Ok(journal) => magic_8_ball.journal = journal,
+        Err(e) => magic_8_ball.status = Some(format!("Could not load journal: {}", e)),
+    }
     eframe::run_native(
         "Magic 8 Ball",
         options,
-        Box::new(|_| Ok(Box::<Magic8Ball>::default())),
+        Box::new(|_| Ok(Box::new(magic_8_ball))),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(followed: bool, turned_out_well: Option<bool>) -> JournalEntry {
+        JournalEntry {
+            question: "Should I?".to_string(),
+            answer: "Yes".to_string(),
+            followed,
+            turned_out_well,
+        }
+    }
+
+    #[test]
+    fn ball_was_right_compares_advice_and_outcome() {
+        assert_eq!(entry(true, Some(true)).ball_was_right(), Some(true));
+        assert_eq!(entry(false, Some(false)).ball_was_right(), Some(true));
+        assert_eq!(entry(true, Some(false)).ball_was_right(), Some(false));
+        assert_eq!(entry(false, Some(true)).ball_was_right(), Some(false));
+        assert_eq!(entry(true, None).ball_was_right(), None);
+    }
+
+    #[test]
+    fn journal_stats_counts_resolved_entries() {
+        let journal = vec![
+            entry(true, Some(true)),
+            entry(true, Some(false)),
+            entry(false, None),
+            entry(true, Some(false)),
+            entry(false, Some(true)),
+            entry(false, Some(false)),
+        ];
+        let stats = journal_stats(&journal);
+        assert_eq!(
+            stats,
+            JournalStats {
+                entries: 6,
+                resolved: 5,
+                right: 2,
+            }
+        );
+        assert_eq!(stats.percent_right(), Some(40.0));
+        assert_eq!(journal_stats(&[]).percent_right(), None);
+    }
+}
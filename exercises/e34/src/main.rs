@@ -8,13 +8,24 @@
 //! - **File I/O**: Reads from and writes to a text file to maintain employee records
 //! - **Interactive Interface**: Prompts users to enter names for removal
 //! - **Validation**: Verifies if employees exist before attempting removal
+//! - **Forgiving Matching**: Names match case-insensitively, and when nothing matches, the
+//!   closest existing names are proposed and removed only after confirmation
 //! - **Persistence**: Saves the updated list back to the file when complete
+//! - **Terminal UI**: Optional `--tui` mode with cursor navigation, incremental search,
+//!   multi-select removal, and a confirmation dialog before saving
 //!
 //! The application loads an existing employee list, allows the user to remove employees
-//! interactively, and then saves the updated list when finished.
+//! interactively, and then saves the updated list when finished. Without `--tui`, or when
+//! stdout is not a terminal, the plain prompt loop is used.
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
 use std::collections::HashSet;
 use std::fs::File;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
 
@@ -53,6 +64,92 @@ fn prompt_for_name() -> Option<String> {
     }
 }
 
+// Maximum number of close names proposed when a removal name doesn't match.
+const MAX_SUGGESTIONS: usize = 3;
+
+#[derive(Debug, PartialEq)]
+enum NameMatch {
+    Found(String),
+    Suggestions(Vec<String>),
+    NotFound,
+}
+
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+/// Looks up `input` exactly, then ignoring case and extra whitespace, and finally proposes the
+/// names within a few edits of it, closest first.
+fn find_name(names: &HashSet<String>, input: &str) -> NameMatch {
+    if names.contains(input) {
+        return NameMatch::Found(input.to_string());
+    }
+
+    let normalized = normalize_name(input);
+    if let Some(name) = names.iter().find(|name| normalize_name(name) == normalized) {
+        return NameMatch::Found(name.clone());
+    }
+
+    let max_distance = (normalized.chars().count() / 3).max(2);
+    let mut candidates: Vec<(usize, &String)> = names
+        .iter()
+        .map(|name| (edit_distance(&normalized, &normalize_name(name)), name))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    candidates.sort();
+    if candidates.is_empty() {
+        return NameMatch::NotFound;
+    }
+    NameMatch::Suggestions(
+        candidates
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, name)| name.clone())
+            .collect(),
+    )
+}
+
+fn prompt_for_suggestion(input: &str, suggestions: &[String]) -> Option<String> {
+    println!("Employee '{}' not found. Did you mean:", input);
+    for (i, name) in suggestions.iter().enumerate() {
+        println!("  {}. {}", i + 1, name);
+    }
+    loop {
+        print!("Enter a number to remove that employee (or blank to cancel): ");
+        io::stdout().flush().unwrap();
+
+        let mut choice = String::new();
+        io::stdin().read_line(&mut choice).unwrap();
+        let choice = choice.trim();
+        if choice.is_empty() {
+            return None;
+        }
+        match choice.parse::<usize>() {
+            Ok(n) if (1..=suggestions.len()).contains(&n) => {
+                return Some(suggestions[n - 1].clone());
+            }
+            _ => println!("Please enter a number between 1 and {}.", suggestions.len()),
+        }
+    }
+}
+
 fn print_employees(names: &HashSet<String>) {
     if names.is_empty() {
         println!("No employees found.");
@@ -62,7 +159,249 @@ fn print_employees(names: &HashSet<String>) {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Browse,
+    Search,
+    Confirm,
+}
+
+#[derive(Debug, PartialEq)]
+enum Action {
+    Continue,
+    Save,
+    Quit,
+}
+
+/// State of the terminal UI, kept separate from rendering so key handling can be tested.
+struct EmployeeList {
+    names: Vec<String>,
+    marked: HashSet<String>,
+    removed: usize,
+    cursor: usize,
+    query: String,
+    mode: Mode,
+}
+
+impl EmployeeList {
+    fn new(names: &HashSet<String>) -> Self {
+        let mut names: Vec<String> = names.iter().cloned().collect();
+        names.sort();
+        Self {
+            names,
+            marked: HashSet::new(),
+            removed: 0,
+            cursor: 0,
+            query: String::new(),
+            mode: Mode::Browse,
+        }
+    }
+
+    /// Names matching the current search query, in display order.
+    fn visible(&self) -> Vec<&String> {
+        let query = self.query.to_lowercase();
+        self.names
+            .iter()
+            .filter(|name| name.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    fn current(&self) -> Option<&String> {
+        self.visible().get(self.cursor).copied()
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        let len = self.visible().len();
+        self.cursor = match len {
+            0 => 0,
+            _ => self.cursor.saturating_add_signed(delta).min(len - 1),
+        };
+    }
+
+    fn toggle_marked(&mut self) {
+        if let Some(name) = self.current().cloned() {
+            if !self.marked.remove(&name) {
+                self.marked.insert(name);
+            }
+            self.move_cursor(1);
+        }
+    }
+
+    /// Removes the marked names, or the name under the cursor if nothing is marked.
+    fn remove_marked(&mut self) {
+        if self.marked.is_empty()
+            && let Some(name) = self.current().cloned()
+        {
+            self.marked.insert(name);
+        }
+        let before = self.names.len();
+        self.names.retain(|name| !self.marked.contains(name));
+        self.removed += before - self.names.len();
+        self.marked.clear();
+        self.move_cursor(0);
+    }
+
+    fn remaining(&self) -> HashSet<String> {
+        self.names.iter().cloned().collect()
+    }
+
+    fn handle_key(&mut self, key: KeyCode) -> Action {
+        match self.mode {
+            Mode::Browse => match key {
+                KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1),
+                KeyCode::Char(' ') => self.toggle_marked(),
+                KeyCode::Char('d') | KeyCode::Delete => self.remove_marked(),
+                KeyCode::Char('/') => self.mode = Mode::Search,
+                KeyCode::Char('s') | KeyCode::Char('q') | KeyCode::Esc => {
+                    if self.removed == 0 {
+                        return Action::Quit;
+                    }
+                    self.mode = Mode::Confirm;
+                }
+                _ => {}
+            },
+            Mode::Search => match key {
+                KeyCode::Enter => self.mode = Mode::Browse,
+                KeyCode::Esc => {
+                    self.query.clear();
+                    self.move_cursor(0);
+                    self.mode = Mode::Browse;
+                }
+                KeyCode::Backspace => {
+                    self.query.pop();
+                    self.move_cursor(0);
+                }
+                KeyCode::Up => self.move_cursor(-1),
+                KeyCode::Down => self.move_cursor(1),
+                KeyCode::Char(c) => {
+                    self.query.push(c);
+                    self.cursor = 0;
+                }
+                _ => {}
+            },
+            Mode::Confirm => match key {
+                KeyCode::Char('y') | KeyCode::Enter => return Action::Save,
+                KeyCode::Char('n') => return Action::Quit,
+                KeyCode::Esc => self.mode = Mode::Browse,
+                _ => {}
+            },
+        }
+        Action::Continue
+    }
+}
+
+fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+fn draw(frame: &mut Frame, list: &EmployeeList) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(1),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let search = match list.mode {
+        Mode::Search => format!("Search: {}_", list.query),
+        _ if !list.query.is_empty() => format!("Search: {}", list.query),
+        _ => format!("{} employees", list.names.len()),
+    };
+    frame.render_widget(Line::from(search).bold(), header);
+
+    let items: Vec<ListItem> = list
+        .visible()
+        .into_iter()
+        .map(|name| {
+            let marker = if list.marked.contains(name) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            ListItem::new(format!("{} {}", marker, name))
+        })
+        .collect();
+    let mut state = ListState::default().with_selected(Some(list.cursor));
+    let employees = List::new(items)
+        .block(Block::bordered().title(" Employees "))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(employees, body, &mut state);
+
+    let help = match list.mode {
+        Mode::Search => "type to filter  Enter: done  Esc: clear",
+        _ => "↑/↓: move  Space: mark  d: remove  /: search  s: save  q: quit",
+    };
+    frame.render_widget(Line::from(help).dim(), footer);
+
+    if list.mode == Mode::Confirm {
+        let area = centered_rect(frame.area(), 40, 5);
+        let dialog = Paragraph::new(vec![
+            Line::from(format!("Save after removing {} employee(s)?", list.removed)),
+            Line::from("y: save  n: discard  Esc: back"),
+        ])
+        .centered()
+        .block(Block::bordered().title(" Confirm "));
+        frame.render_widget(Clear, area);
+        frame.render_widget(dialog, area);
+    }
+}
+
+/// Runs the terminal UI, returning the names to save or `None` if changes were discarded.
+fn run_tui(
+    terminal: &mut DefaultTerminal,
+    names: &HashSet<String>,
+) -> io::Result<Option<HashSet<String>>> {
+    let mut list = EmployeeList::new(names);
+    loop {
+        terminal.draw(|frame| draw(frame, &list))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match list.handle_key(key.code) {
+                Action::Continue => {}
+                Action::Save => return Ok(Some(list.remaining())),
+                Action::Quit => return Ok(None),
+            }
+        }
+    }
+}
+
+fn run_prompt_mode(names: &mut HashSet<String>) {
+    print_employees(names);
+
+    while let Some(name) = prompt_for_name() {
+        let selected = match find_name(names, &name) {
+            NameMatch::Found(found) => Some(found),
+            NameMatch::Suggestions(suggestions) => prompt_for_suggestion(&name, &suggestions),
+            NameMatch::NotFound => {
+                println!("Employee '{}' not found in the list.", name);
+                None
+            }
+        };
+        if let Some(selected) = selected {
+            names.remove(&selected);
+            println!("Removed '{}'.", selected);
+        }
+
+        if names.is_empty() {
+            break;
+        }
+
+        print_employees(names);
+    }
+}
+
 fn main() {
+    let use_tui = std::env::args().skip(1).any(|arg| arg == "--tui");
     let file_path = PathBuf::from("exercises/e34/inputs/employees.txt");
 
     if let Ok(mut names) = read_names_from_file(file_path.clone()) {
@@ -70,20 +409,24 @@ fn main() {
             println!("No employees found in the file. Please add some names first.");
             return;
         }
-        print_employees(&names);
 
-        while let Some(name) = prompt_for_name() {
-            if names.contains(&name) {
-                names.remove(&name);
-            } else {
-                println!("Employee '{}' not found in the list.", name);
+        if use_tui && io::stdout().is_terminal() {
+            let mut terminal = ratatui::init();
+            let result = run_tui(&mut terminal, &names);
+            ratatui::restore();
+            match result {
+                Ok(Some(remaining)) => names = remaining,
+                Ok(None) => {
+                    println!("No changes saved.");
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("Error running terminal UI: {}", e);
+                    return;
+                }
             }
-
-            if names.is_empty() {
-                break;
-            }
-
-            print_employees(&names);
+        } else {
+            run_prompt_mode(&mut names);
         }
 
         if let Err(e) = write_names_to_file(file_path, &names) {
@@ -95,3 +438,110 @@ fn main() {
         eprintln!("Error reading from file: {}", e);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn employee_list() -> EmployeeList {
+        let names = [
+            "Ivan Guerra",
+            "Ahyun Jung",
+            "Moraima Guerra",
+            "Alexander Guerra",
+        ];
+        EmployeeList::new(&names.iter().map(|name| name.to_string()).collect())
+    }
+
+    #[test]
+    fn employee_list_filters_incrementally() {
+        let mut list = employee_list();
+        list.handle_key(KeyCode::Char('/'));
+        "GUER".chars().for_each(|c| {
+            list.handle_key(KeyCode::Char(c));
+        });
+        assert_eq!(list.visible().len(), 3);
+
+        list.handle_key(KeyCode::Enter);
+        list.handle_key(KeyCode::Char('/'));
+        assert_eq!(list.visible().len(), 3);
+        (0..4).for_each(|_| {
+            list.handle_key(KeyCode::Backspace);
+        });
+        "ivan".chars().for_each(|c| {
+            list.handle_key(KeyCode::Char(c));
+        });
+        assert_eq!(list.visible(), vec!["Ivan Guerra"]);
+
+        list.handle_key(KeyCode::Esc);
+        assert_eq!(list.mode, Mode::Browse);
+        assert_eq!(list.visible().len(), 4);
+    }
+
+    #[test]
+    fn employee_list_keeps_cursor_in_range_while_editing_search() {
+        let mut list = employee_list();
+        list.handle_key(KeyCode::Char('/'));
+        list.handle_key(KeyCode::Char('z'));
+        assert!(list.current().is_none());
+        list.handle_key(KeyCode::Backspace);
+        assert_eq!(list.current().map(String::as_str), Some("Ahyun Jung"));
+
+        "ivan".chars().for_each(|c| {
+            list.handle_key(KeyCode::Char(c));
+        });
+        (0..4).for_each(|_| {
+            list.handle_key(KeyCode::Backspace);
+        });
+        list.handle_key(KeyCode::Down);
+        assert_eq!(list.current().map(String::as_str), Some("Alexander Guerra"));
+    }
+
+    #[test]
+    fn employee_list_removes_marked_names_after_confirmation() {
+        let mut list = employee_list();
+        list.handle_key(KeyCode::Char(' '));
+        list.handle_key(KeyCode::Down);
+        list.handle_key(KeyCode::Char(' '));
+        list.handle_key(KeyCode::Char('d'));
+        assert_eq!(list.names, vec!["Alexander Guerra", "Moraima Guerra"]);
+
+        assert_eq!(list.handle_key(KeyCode::Char('s')), Action::Continue);
+        assert_eq!(list.mode, Mode::Confirm);
+        assert_eq!(list.handle_key(KeyCode::Char('y')), Action::Save);
+        assert_eq!(list.remaining().len(), 2);
+    }
+
+    #[test]
+    fn employee_list_quits_without_confirmation_when_unchanged() {
+        let mut list = employee_list();
+        assert_eq!(list.handle_key(KeyCode::Char('q')), Action::Quit);
+    }
+
+    fn names(list: &[&str]) -> HashSet<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn find_name_ignores_case_and_whitespace() {
+        let names = names(&["John Smith", "Jackie Jackson"]);
+        assert_eq!(
+            find_name(&names, "John Smith"),
+            NameMatch::Found("John Smith".to_string())
+        );
+        assert_eq!(
+            find_name(&names, "  john   SMITH "),
+            NameMatch::Found("John Smith".to_string())
+        );
+    }
+
+    #[test]
+    fn find_name_suggests_closest_names() {
+        let names = names(&["John Smith", "Jon Smyth", "Jackie Jackson", "Chris Jones"]);
+        assert_eq!(
+            find_name(&names, "Jon Smith"),
+            NameMatch::Suggestions(vec!["John Smith".to_string(), "Jon Smyth".to_string()])
+        );
+        assert_eq!(find_name(&names, "Zed"), NameMatch::NotFound);
+    }
+}
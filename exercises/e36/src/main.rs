@@ -9,6 +9,12 @@
 //! - **Statistical Analysis**: Calculates mean, standard deviation, minimum, and maximum values
 //! - **Error Handling**: Gracefully handles file access and parsing errors
 //! - **NaN Handling**: Properly filters out NaN values when calculating min and max
+//! - **Rolling Statistics**: Summarizes `timestamp,value` series as mean, min, max, and
+//!   95th percentile over fixed-width time windows (`--rolling <window> [--input <file>]`)
+//! - **Warm-up Exclusion**: `--warm-up <n>` skips the first `n` samples, reporting the
+//!   remaining statistics alongside the raw ones
+//! - **Trimmed Mean**: `--trim <percent>` drops the top and bottom `percent` of samples
+//!   before averaging; like `--warm-up`, it cannot be combined with `--rolling`
 //!
 //! The application reads time measurements from a file, computes key statistical metrics,
 //! and presents them in a clear, formatted output for performance analysis.
@@ -63,12 +69,199 @@ fn min(times: &[f64]) -> f64 {
         .fold(f64::INFINITY, f64::min)
 }
 
-fn print_statistics(times: &[f64]) {
-    if times.is_empty() {
-        println!("No times available to compute statistics.");
+/// Returns the `percentile` (0-100) of `times` using the nearest-rank method.
+fn percentile(times: &[f64], percentile: f64) -> f64 {
+    let mut sorted: Vec<f64> = times.iter().cloned().filter(|v| !v.is_nan()).collect();
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let rank = (percentile / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Averages `times` after dropping `trim_percent` (0 to under 50) of the samples from each
+/// end of the sorted values.
+fn trimmed_mean(times: &[f64], trim_percent: f64) -> f64 {
+    let mut sorted: Vec<f64> = times.iter().cloned().filter(|v| !v.is_nan()).collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let trimmed = (sorted.len() as f64 * trim_percent / 100.0).floor() as usize;
+    if trimmed * 2 >= sorted.len() {
+        return compute_mean(&sorted);
+    }
+    compute_mean(&sorted[trimmed..sorted.len() - trimmed])
+}
+
+/// Drops the first `warm_up` samples, leaving nothing if there are not enough.
+fn skip_warm_up<T>(samples: &[T], warm_up: usize) -> &[T] {
+    &samples[warm_up.min(samples.len())..]
+}
+
+#[derive(Debug, PartialEq)]
+struct RollingOptions {
+    window: f64,
+    input: PathBuf,
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    rolling: Option<RollingOptions>,
+    warm_up: usize,
+    trim_percent: f64,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut window = None;
+    let mut input = None;
+    let mut warm_up = 0;
+    let mut trim_percent = 0.0;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rolling" => {
+                let value = args.next().ok_or("--rolling requires a window size")?;
+                match value.parse::<f64>() {
+                    Ok(size) if size > 0.0 => window = Some(size),
+                    _ => return Err(format!("invalid window size '{}'", value)),
+                }
+            }
+            "--input" => {
+                let path = args.next().ok_or("--input requires a file path")?;
+                input = Some(PathBuf::from(path));
+            }
+            "--warm-up" => {
+                let value = args.next().ok_or("--warm-up requires a sample count")?;
+                warm_up = value
+                    .parse()
+                    .map_err(|_| format!("invalid warm-up count '{}'", value))?;
+            }
+            "--trim" => {
+                let value = args.next().ok_or("--trim requires a percentage")?;
+                match value.parse::<f64>() {
+                    Ok(percent) if (0.0..50.0).contains(&percent) => trim_percent = percent,
+                    _ => {
+                        return Err(format!(
+                            "trim percentage must be in [0, 50), got '{}'",
+                            value
+                        ));
+                    }
+                }
+            }
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+    let rolling = match (window, input) {
+        (Some(window), input) => Some(RollingOptions {
+            window,
+            input: input.unwrap_or_else(|| PathBuf::from("exercises/e36/inputs/timeseries.csv")),
+        }),
+        (None, Some(_)) => return Err("--input can only be used with --rolling".to_string()),
+        (None, None) => None,
+    };
+    if rolling.is_some() && (warm_up > 0 || trim_percent > 0.0) {
+        return Err("--warm-up and --trim cannot be used with --rolling".to_string());
+    }
+    Ok(Options {
+        rolling,
+        warm_up,
+        trim_percent,
+    })
+}
+
+/// Parses a `timestamp,value` line, skipping lines whose timestamp is not a finite number.
+fn parse_sample(line: &str) -> Option<(f64, f64)> {
+    let (timestamp, value) = line.split_once(',')?;
+    let timestamp: f64 = timestamp.trim().parse().ok()?;
+    if !timestamp.is_finite() {
+        return None;
+    }
+    Some((timestamp, value.trim().parse().ok()?))
+}
+
+fn read_samples_from_file(file_path: PathBuf) -> Result<Vec<(f64, f64)>, std::io::Error> {
+    let file = File::open(file_path)?;
+    let reader = BufReader::new(file);
+    let mut samples = Vec::new();
+
+    for line in reader.lines() {
+        if let Some(sample) = parse_sample(&line?) {
+            samples.push(sample);
+        }
+    }
+    samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+    Ok(samples)
+}
+
+#[derive(Debug, PartialEq)]
+struct WindowStats {
+    start: f64,
+    count: usize,
+    mean: f64,
+    min: f64,
+    max: f64,
+    p95: f64,
+}
+
+/// Groups time-sorted samples into consecutive windows of `window` width, starting at the
+/// first timestamp. Windows without samples are skipped.
+fn rolling_statistics(samples: &[(f64, f64)], window: f64) -> Vec<WindowStats> {
+    let Some(&(first, _)) = samples.first() else {
+        return Vec::new();
+    };
+    // Windows are numbered by their offset from the first timestamp, so large timestamps whose
+    // spacing is below f64 precision still fall into distinct windows.
+    let window_index = |t: f64| ((t - first) / window).floor();
+
+    let mut stats = Vec::new();
+    let mut remaining = samples;
+    while let Some(&(timestamp, _)) = remaining.first() {
+        let index = window_index(timestamp);
+        let start = first + index * window;
+        // Every window consumes at least one sample so the loop always advances.
+        let len = remaining
+            .iter()
+            .position(|&(t, _)| window_index(t) != index)
+            .unwrap_or(remaining.len())
+            .max(1);
+        let values: Vec<f64> = remaining[..len].iter().map(|&(_, v)| v).collect();
+        stats.push(WindowStats {
+            start,
+            count: values.len(),
+            mean: compute_mean(&values),
+            min: min(&values),
+            max: max(&values),
+            p95: percentile(&values, 95.0),
+        });
+        remaining = &remaining[len..];
+    }
+    stats
+}
+
+fn print_rolling_statistics(stats: &[WindowStats], window: f64) {
+    if stats.is_empty() {
+        println!("No samples available to compute statistics.");
         return;
     }
 
+    println!(
+        "{:>12} {:>12} {:>6} {:>10} {:>10} {:>10} {:>10}",
+        "start", "end", "count", "mean", "min", "max", "p95"
+    );
+    for row in stats {
+        println!(
+            "{:>12.2} {:>12.2} {:>6} {:>10.2} {:>10.2} {:>10.2} {:>10.2}",
+            row.start,
+            row.start + window,
+            row.count,
+            row.mean,
+            row.min,
+            row.max,
+            row.p95
+        );
+    }
+}
+
+fn print_summary(times: &[f64]) {
     let mean = compute_mean(times);
     let std_dev = compute_std_deviation(times, mean);
     let max = max(times);
@@ -80,12 +273,61 @@ fn print_statistics(times: &[f64]) {
     println!("The standard deviation is {:.2}ms", std_dev);
 }
 
+fn print_statistics(times: &[f64], warm_up: usize, trim_percent: f64) {
+    if times.is_empty() {
+        println!("No times available to compute statistics.");
+        return;
+    }
+
+    print_summary(times);
+
+    let measured = skip_warm_up(times, warm_up);
+    if warm_up > 0 {
+        println!("\nExcluding the first {} warm-up samples:", warm_up);
+        if measured.is_empty() {
+            println!("No times remain after the warm-up.");
+            return;
+        }
+        print_summary(measured);
+    }
+    if trim_percent > 0.0 {
+        println!(
+            "The {}% trimmed mean is {:.2}ms",
+            trim_percent,
+            trimmed_mean(measured, trim_percent)
+        );
+    }
+}
+
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!(
+                "Usage: e36 [--rolling <window> [--input <file>] | [--warm-up <n>] [--trim <percent>]]"
+            );
+            std::process::exit(1);
+        }
+    };
+
+    if let Some(rolling) = options.rolling {
+        match read_samples_from_file(rolling.input) {
+            Ok(samples) => {
+                let stats = rolling_statistics(&samples, rolling.window);
+                print_rolling_statistics(&stats, rolling.window);
+            }
+            Err(e) => eprintln!("Error reading samples from file: {}", e),
+        }
+        return;
+    }
+
     let file_path = PathBuf::from("exercises/e36/inputs/times.txt");
 
     match read_times_from_file(file_path) {
         Ok(times) => {
-            print_statistics(&times);
+            print_statistics(&times, options.warm_up, options.trim_percent);
         }
         Err(e) => {
             eprintln!("Error reading times from file: {}", e);
@@ -152,4 +394,113 @@ mod tests {
         let times = vec![3.0, 10.0, f64::NAN, 5.0];
         assert_eq!(max(&times), 10.0);
     }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let times: Vec<f64> = (1..=20).map(f64::from).collect();
+        assert_eq!(percentile(&times, 95.0), 19.0);
+        assert_eq!(percentile(&times, 100.0), 20.0);
+        assert_eq!(percentile(&[4.0, f64::NAN], 95.0), 4.0);
+        assert!(percentile(&[], 95.0).is_nan());
+    }
+
+    #[test]
+    fn parse_sample_reads_timestamp_and_value() {
+        assert_eq!(parse_sample("12, 3.5"), Some((12.0, 3.5)));
+        assert_eq!(parse_sample("12"), None);
+        assert_eq!(parse_sample("timestamp,value"), None);
+    }
+
+    #[test]
+    fn rolling_statistics_advances_on_extreme_timestamps() {
+        assert_eq!(parse_sample("inf,2"), None);
+        assert_eq!(parse_sample("NaN,2"), None);
+        let stats = rolling_statistics(&[(0.0, 1.0), (f64::INFINITY, 2.0)], 10.0);
+        assert_eq!(stats.iter().map(|s| s.count).sum::<usize>(), 2);
+
+        let first = 1.7e18;
+        let samples = [(first, 1.0), (first + 512.0, 2.0), (first + 1024.0, 3.0)];
+        let stats = rolling_statistics(&samples, 100.0);
+        assert_eq!(stats.len(), 3);
+        assert!(stats.iter().all(|s| s.count == 1));
+    }
+
+    #[test]
+    fn rolling_statistics_emits_one_row_per_window() {
+        let samples = vec![
+            (0.0, 1.0),
+            (4.0, 3.0),
+            (10.0, 10.0),
+            (25.0, 6.0),
+            (29.0, 2.0),
+        ];
+        let stats = rolling_statistics(&samples, 10.0);
+
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats[0].start, 0.0);
+        assert_eq!(stats[0].count, 2);
+        assert_eq!(stats[0].mean, 2.0);
+        assert_eq!(stats[1].start, 10.0);
+        assert_eq!(stats[1].count, 1);
+        assert_eq!(stats[2].start, 20.0);
+        assert_eq!(stats[2].min, 2.0);
+        assert_eq!(stats[2].max, 6.0);
+        assert_eq!(stats[2].p95, 6.0);
+    }
+
+    #[test]
+    fn parse_args_reads_rolling_options() {
+        let args: Vec<String> = ["--rolling", "30", "--input", "log.csv"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            parse_args(&args).unwrap().rolling,
+            Some(RollingOptions {
+                window: 30.0,
+                input: PathBuf::from("log.csv"),
+            })
+        );
+        assert_eq!(parse_args(&[]).unwrap().rolling, None);
+        assert!(parse_args(&["--rolling".to_string(), "0".to_string()]).is_err());
+        assert!(parse_args(&["--input".to_string(), "log.csv".to_string()]).is_err());
+    }
+
+    #[test]
+    fn parse_args_reads_warm_up_and_trim() {
+        let args: Vec<String> = ["--warm-up", "3", "--trim", "10"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            parse_args(&args).unwrap(),
+            Options {
+                rolling: None,
+                warm_up: 3,
+                trim_percent: 10.0,
+            }
+        );
+        assert!(parse_args(&["--trim".to_string(), "50".to_string()]).is_err());
+        assert!(parse_args(&["--warm-up".to_string(), "-1".to_string()]).is_err());
+        let rolling_trim: Vec<String> = ["--rolling", "30", "--trim", "10"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert!(parse_args(&rolling_trim).is_err());
+    }
+
+    #[test]
+    fn trimmed_mean_drops_extremes() {
+        let times = vec![100.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 1.0];
+        assert_eq!(trimmed_mean(&times, 10.0), 5.5);
+        assert_eq!(trimmed_mean(&times, 0.0), compute_mean(&times));
+        assert_eq!(trimmed_mean(&[1.0, 3.0], 40.0), 2.0);
+    }
+
+    #[test]
+    fn skip_warm_up_drops_leading_samples() {
+        let times = vec![50.0, 40.0, 10.0, 12.0];
+        assert_eq!(skip_warm_up(&times, 2), &[10.0, 12.0]);
+        assert!(skip_warm_up(&times, 10).is_empty());
+    }
 }
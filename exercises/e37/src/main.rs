@@ -11,40 +11,452 @@
 //! - **Character Shuffling**: Randomizes the order of characters to enhance security
 //! - **Clipboard Integration**: Automatically copies the generated password to the system clipboard
 //! - **Type Safety**: Uses strongly-typed components to represent password character types
+//! - **Policy Presets**: Named presets (`--preset nist|corporate|pin`) expand to component
+//!   counts and constraints, and generated passwords are validated against the active policy
+//! - **Strength Gate**: Scores each password with the shared strength library and regenerates
+//!   (`--attempts <n>`, default 5) until it reaches `--min-entropy <bits>`; a composition too
+//!   short to ever reach the minimum is rejected up front with the number of letters to add
+//! - **Positional Rules**: `--no-repeats` forbids a character repeated consecutively,
+//!   `--no-leading-digit` forbids starting with a digit, and `--max-class-run <n>` limits how
+//!   many characters of the same class appear in a row; passwords are built position by
+//!   position with backtracking so the rules always hold
+//! - **Encrypted Vault**: After generating a password, offers to store it under a label in a
+//!   local vault encrypted with ChaCha20-Poly1305 using a key derived from a master passphrase
+//!   (Argon2id); `list` shows the stored labels and `get <label>` copies an entry to the
+//!   clipboard. `--vault <file>` selects a vault other than `exercises/e37/vault.bin`. The
+//!   vault is replaced atomically and readable by its owner only
 //!
 //! The application prompts the user for password composition requirements, generates a
 //! password that meets those requirements, and automatically copies it to the clipboard
-//! for convenient and secure use.
+//! for convenient and secure use. When a preset is given on the command line, the prompts are
+//! skipped and the preset's composition is used instead.
 use arboard::Clipboard;
-use once_cell::sync::Lazy;
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use e25::{
+    COMPONENT_CLASSES, PasswordComponent, entropy_bits, generate_password, password_strength,
+};
+use rand::Rng;
 use rand::prelude::IndexedRandom;
 use rand::seq::SliceRandom;
+use rpassword::read_password;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::io::{self, Write};
+use std::path::Path;
 
-static SPECIAL_CHARS: Lazy<Vec<char>> = Lazy::new(|| {
-    vec![
-        '!', '@', '#', '$', '%', '^', '&', '*', '(', ')', '-', '_', '=', '+', '{', '}', '[', ']',
-        ':', ';', '"', '\'', '<', '>', ',', '.', '?', '/', '\\',
-    ]
-});
+// Upper bound on the placements tried while searching for a password that satisfies the
+// positional rules, so that impossible rule combinations fail instead of searching forever.
+const MAX_PLACEMENTS: usize = 100_000;
 
-static DIGITS: Lazy<Vec<char>> =
-    Lazy::new(|| vec!['0', '1', '2', '3', '4', '5', '6', '7', '8', '9']);
+/// Rules about where characters may appear in a generated password.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct PositionalRules {
+    no_consecutive_repeats: bool,
+    no_leading_digit: bool,
+    max_class_run: Option<usize>,
+}
 
-static ALPHABET: Lazy<Vec<char>> = Lazy::new(|| {
-    let mut alphabet = Vec::new();
-    for c in 'a'..='z' {
-        alphabet.push(c);
-        alphabet.push(c.to_ascii_uppercase());
+impl PositionalRules {
+    fn is_active(&self) -> bool {
+        *self != PositionalRules::default()
     }
-    alphabet
-});
 
-#[derive(Debug, Clone)]
-enum PasswordComponent {
-    AlphaChar,
-    Digit,
-    SpecialChar,
+    fn validate(&self, password: &str) -> Result<(), String> {
+        let chars: Vec<char> = password.chars().collect();
+        if self.no_leading_digit
+            && chars
+                .first()
+                .is_some_and(|c| PasswordComponent::Digit.chars().contains(c))
+        {
+            return Err("password must not start with a digit".to_string());
+        }
+        if self.no_consecutive_repeats && chars.windows(2).any(|w| w[0] == w[1]) {
+            return Err("password must not repeat a character consecutively".to_string());
+        }
+        if let Some(max_run) = self.max_class_run {
+            let classes: Vec<Option<PasswordComponent>> =
+                chars.iter().map(|&c| PasswordComponent::of(c)).collect();
+            if classes
+                .windows(max_run + 1)
+                .any(|w| w.iter().all(|class| *class == w[0]))
+            {
+                return Err(format!(
+                    "password must not have more than {} characters of the same class in a row",
+                    max_run
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_rule_args(args: &[String]) -> Result<PositionalRules, String> {
+    let max_class_run = match args.iter().position(|arg| arg == "--max-class-run") {
+        None => None,
+        Some(i) => {
+            let value = args.get(i + 1).ok_or("--max-class-run requires a value")?;
+            match value.parse::<usize>() {
+                Ok(run) if run > 0 => Some(run),
+                _ => return Err(format!("invalid maximum class run '{}'", value)),
+            }
+        }
+    };
+    Ok(PositionalRules {
+        no_consecutive_repeats: args.iter().any(|arg| arg == "--no-repeats"),
+        no_leading_digit: args.iter().any(|arg| arg == "--no-leading-digit"),
+        max_class_run,
+    })
+}
+
+struct ConstrainedSearch<'a, R: rand::Rng> {
+    rules: &'a PositionalRules,
+    rng: &'a mut R,
+    remaining: [usize; 3],
+    classes: Vec<PasswordComponent>,
+    placements: usize,
+}
+
+impl<R: rand::Rng> ConstrainedSearch<'_, R> {
+    fn run_length(&self, class: PasswordComponent) -> usize {
+        self.classes
+            .iter()
+            .rev()
+            .take_while(|c| **c == class)
+            .count()
+    }
+
+    /// Checks whether the remaining characters can still be arranged without exceeding the
+    /// maximum class run: a class with `n` characters left needs enough other characters to
+    /// break it into runs.
+    fn is_feasible(&self) -> bool {
+        let Some(max_run) = self.rules.max_class_run else {
+            return true;
+        };
+        let total: usize = self.remaining.iter().sum();
+        self.remaining
+            .iter()
+            .all(|&count| count <= max_run * (total - count + 1))
+    }
+
+    /// Places one class per position, trying the allowed classes in random order and
+    /// backtracking when a choice leaves the rest of the password impossible to complete.
+    fn place(&mut self) -> bool {
+        if self.remaining.iter().all(|&count| count == 0) {
+            return true;
+        }
+        if self.placements >= MAX_PLACEMENTS || !self.is_feasible() {
+            return false;
+        }
+
+        let mut candidates: Vec<usize> = (0..COMPONENT_CLASSES.len())
+            .filter(|&i| self.remaining[i] > 0)
+            .collect();
+        candidates.shuffle(self.rng);
+        for i in candidates {
+            let class = COMPONENT_CLASSES[i];
+            if self.classes.is_empty()
+                && self.rules.no_leading_digit
+                && class == PasswordComponent::Digit
+            {
+                continue;
+            }
+            if self
+                .rules
+                .max_class_run
+                .is_some_and(|max_run| self.run_length(class) >= max_run)
+            {
+                continue;
+            }
+
+            self.placements += 1;
+            self.remaining[i] -= 1;
+            self.classes.push(class);
+            if self.place() {
+                return true;
+            }
+            self.classes.pop();
+            self.remaining[i] += 1;
+        }
+        false
+    }
+}
+
+/// Generates a password with the requested components that satisfies `rules`, or explains why
+/// the rules cannot be met.
+fn generate_constrained_password(
+    components: &[PasswordComponent],
+    rules: &PositionalRules,
+) -> Result<String, String> {
+    let mut rng = rand::rng();
+    let mut remaining = [0; 3];
+    for component in components {
+        let i = COMPONENT_CLASSES
+            .iter()
+            .position(|class| class == component)
+            .unwrap();
+        remaining[i] += 1;
+    }
+
+    let mut search = ConstrainedSearch {
+        rules,
+        rng: &mut rng,
+        remaining,
+        classes: Vec::with_capacity(components.len()),
+        placements: 0,
+    };
+    if !search.place() {
+        return Err("the requested characters cannot satisfy the positional rules".to_string());
+    }
+
+    let classes = search.classes;
+    let mut password = String::with_capacity(classes.len());
+    let mut previous = None;
+    for class in classes {
+        // Every class has more than one character, so a different one is always available.
+        let choices: Vec<char> = class
+            .chars()
+            .iter()
+            .copied()
+            .filter(|c| !rules.no_consecutive_repeats || Some(*c) != previous)
+            .collect();
+        let c = *choices.choose(&mut rng).unwrap();
+        password.push(c);
+        previous = Some(c);
+    }
+    Ok(password)
+}
+
+/// Composition and constraints that a generated password must satisfy.
+#[derive(Debug, Clone, PartialEq)]
+struct PasswordPolicy {
+    name: &'static str,
+    num_alpha: usize,
+    num_digits: usize,
+    num_specials: usize,
+    min_length: usize,
+    min_digits: usize,
+    min_specials: usize,
+    digits_only: bool,
+}
+
+impl PasswordPolicy {
+    /// Long passphrase-style password per NIST SP 800-63B guidance.
+    const NIST: PasswordPolicy = PasswordPolicy {
+        name: "NIST",
+        num_alpha: 11,
+        num_digits: 2,
+        num_specials: 2,
+        min_length: 15,
+        min_digits: 0,
+        min_specials: 0,
+        digits_only: false,
+    };
+
+    /// Typical corporate rule: at least 12 characters with a digit and a symbol.
+    const CORPORATE: PasswordPolicy = PasswordPolicy {
+        name: "Corporate 12+symbol",
+        num_alpha: 8,
+        num_digits: 2,
+        num_specials: 2,
+        min_length: 12,
+        min_digits: 1,
+        min_specials: 1,
+        digits_only: false,
+    };
+
+    /// Six digit numeric PIN.
+    const PIN: PasswordPolicy = PasswordPolicy {
+        name: "PIN",
+        num_alpha: 0,
+        num_digits: 6,
+        num_specials: 0,
+        min_length: 6,
+        min_digits: 6,
+        min_specials: 0,
+        digits_only: true,
+    };
+
+    fn from_preset(preset: &str) -> Option<PasswordPolicy> {
+        match preset.to_lowercase().as_str() {
+            "nist" => Some(Self::NIST),
+            "corporate" => Some(Self::CORPORATE),
+            "pin" => Some(Self::PIN),
+            _ => None,
+        }
+    }
+
+    fn components(&self) -> Vec<PasswordComponent> {
+        let mut components = Vec::new();
+        components.extend(vec![PasswordComponent::AlphaChar; self.num_alpha]);
+        components.extend(vec![PasswordComponent::SpecialChar; self.num_specials]);
+        components.extend(vec![PasswordComponent::Digit; self.num_digits]);
+        components
+    }
+
+    fn validate(&self, password: &str) -> Result<(), String> {
+        let length = password.chars().count();
+        if length < self.min_length {
+            return Err(format!(
+                "password must be at least {} characters long",
+                self.min_length
+            ));
+        }
+
+        let digits = password
+            .chars()
+            .filter(|c| PasswordComponent::Digit.chars().contains(c))
+            .count();
+        if digits < self.min_digits {
+            return Err(format!(
+                "password must contain at least {} digit(s)",
+                self.min_digits
+            ));
+        }
+
+        let specials = password
+            .chars()
+            .filter(|c| PasswordComponent::SpecialChar.chars().contains(c))
+            .count();
+        if specials < self.min_specials {
+            return Err(format!(
+                "password must contain at least {} special character(s)",
+                self.min_specials
+            ));
+        }
+
+        if self.digits_only && digits != length {
+            return Err("password must contain only digits".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_preset_arg(args: &[String]) -> Result<Option<PasswordPolicy>, String> {
+    match args.iter().position(|arg| arg == "--preset") {
+        None => Ok(None),
+        Some(i) => {
+            let name = args
+                .get(i + 1)
+                .ok_or("--preset requires a value (nist, corporate, pin)")?;
+            PasswordPolicy::from_preset(name).map(Some).ok_or(format!(
+                "unknown preset '{}' (expected nist, corporate, pin)",
+                name
+            ))
+        }
+    }
+}
+
+const DEFAULT_ATTEMPTS: usize = 5;
+
+/// Minimum entropy a generated password must reach before it is accepted.
+#[derive(Debug, PartialEq)]
+struct StrengthGate {
+    min_entropy: Option<f64>,
+    max_attempts: usize,
+}
+
+fn parse_strength_args(args: &[String]) -> Result<StrengthGate, String> {
+    let value_of = |flag: &str| -> Result<Option<&String>, String> {
+        match args.iter().position(|arg| arg == flag) {
+            None => Ok(None),
+            Some(i) => args
+                .get(i + 1)
+                .map(Some)
+                .ok_or(format!("{} requires a value", flag)),
+        }
+    };
+
+    let min_entropy = match value_of("--min-entropy")? {
+        None => None,
+        Some(value) => match value.parse::<f64>() {
+            Ok(bits) if bits >= 0.0 => Some(bits),
+            _ => return Err(format!("invalid minimum entropy '{}'", value)),
+        },
+    };
+    let max_attempts = match value_of("--attempts")? {
+        None => DEFAULT_ATTEMPTS,
+        Some(value) => match value.parse::<usize>() {
+            Ok(attempts) if attempts > 0 => attempts,
+            _ => return Err(format!("invalid number of attempts '{}'", value)),
+        },
+    };
+    Ok(StrengthGate {
+        min_entropy,
+        max_attempts,
+    })
+}
+
+/// The highest entropy any password with these components can score. `entropy_bits` only looks
+/// at the length and the character classes present, so this is scored on a sample password that
+/// uses every class the components allow, including both letter cases.
+fn composition_entropy(components: &[PasswordComponent]) -> f64 {
+    let mut letters = ['a', 'A'].into_iter().cycle();
+    let sample: String = components
+        .iter()
+        .map(|component| match component {
+            PasswordComponent::AlphaChar => letters.next().unwrap(),
+            PasswordComponent::Digit => '0',
+            PasswordComponent::SpecialChar => '!',
+        })
+        .collect();
+    entropy_bits(&sample)
+}
+
+/// Explains how many letters must be added to `components` before any password can reach
+/// `min_entropy`, or returns `None` when the composition is already long enough.
+fn composition_shortfall(components: &[PasswordComponent], min_entropy: f64) -> Option<String> {
+    let best = composition_entropy(components);
+    if best >= min_entropy {
+        return None;
+    }
+    let mut extended = components.to_vec();
+    extended.extend([PasswordComponent::AlphaChar; 2]);
+    let bits_per_char = composition_entropy(&extended) / extended.len() as f64;
+    let mut needed = ((min_entropy / bits_per_char).ceil() as usize)
+        .saturating_sub(components.len())
+        .max(1);
+    // A single added letter only counts one case, so it may fall short of the estimate.
+    extended.pop();
+    if needed == 1 && composition_entropy(&extended) < min_entropy {
+        needed = 2;
+    }
+    Some(format!(
+        "the requested composition reaches at most {:.1} bits of entropy; add at least {} more \
+         letter(s) to reach {:.1} bits",
+        best, needed, min_entropy
+    ))
+}
+
+/// Generates passwords until one meets the gate's minimum entropy or the attempts run out.
+/// Returns the last password generated and the number of attempts used. Fails without
+/// generating anything when the composition itself is too short, since no amount of
+/// regenerating can change its score.
+fn generate_gated_password(
+    components: &[PasswordComponent],
+    gate: &StrengthGate,
+    rules: &PositionalRules,
+) -> Result<(String, usize), String> {
+    let min_entropy = gate.min_entropy.unwrap_or(0.0);
+    if let Some(shortfall) = composition_shortfall(components, min_entropy) {
+        return Err(shortfall);
+    }
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let password = if rules.is_active() {
+            generate_constrained_password(components, rules)?
+        } else {
+            generate_password(components, &mut rand::rng())
+        };
+        // A password can fall short of the composition's best score when its letters all
+        // happen to share one case.
+        if entropy_bits(&password) >= min_entropy || attempts >= gate.max_attempts {
+            return Ok((password, attempts));
+        }
+    }
 }
 
 fn prompt_for_components() -> Result<Vec<PasswordComponent>, std::io::Error> {
@@ -74,33 +486,321 @@ fn prompt_for_components() -> Result<Vec<PasswordComponent>, std::io::Error> {
     Ok(components)
 }
 
-fn generate_password(mut components: Vec<PasswordComponent>) -> String {
-    let mut rng = rand::rng();
+const VAULT_FILE: &str = "exercises/e37/vault.bin";
+const VAULT_MAGIC: &[u8] = b"E37V1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
 
-    components.shuffle(&mut rng);
-    components
-        .iter()
-        .map(|component| match component {
-            PasswordComponent::AlphaChar => *ALPHABET.choose(&mut rng).unwrap(),
-            PasswordComponent::Digit => *DIGITS.choose(&mut rng).unwrap(),
-            PasswordComponent::SpecialChar => *SPECIAL_CHARS.choose(&mut rng).unwrap(),
-        })
-        .collect()
+/// Labelled passwords, stored on disk as the magic bytes, a random salt and nonce, and the
+/// ChaCha20-Poly1305 encrypted JSON of the entries.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Vault {
+    entries: BTreeMap<String, String>,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key, String> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Failed to derive the vault key: {}", e))?;
+    Ok(key)
+}
+
+impl Vault {
+    fn encrypt(&self, passphrase: &str) -> Result<Vec<u8>, String> {
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        let mut rng = rand::rng();
+        rng.fill(&mut salt);
+        rng.fill(&mut nonce);
+
+        let plaintext = serde_json::to_vec(self).map_err(|e| e.to_string())?;
+        let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|_| "Failed to encrypt the vault".to_string())?;
+
+        let mut data = VAULT_MAGIC.to_vec();
+        data.extend_from_slice(&salt);
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&ciphertext);
+        Ok(data)
+    }
+
+    fn decrypt(data: &[u8], passphrase: &str) -> Result<Self, String> {
+        let data = data
+            .strip_prefix(VAULT_MAGIC)
+            .ok_or("The file is not a password vault")?;
+        if data.len() < SALT_LEN + NONCE_LEN {
+            return Err("The vault file is truncated".to_string());
+        }
+        let (salt, data) = data.split_at(SALT_LEN);
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+
+        let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, salt)?);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| "Wrong passphrase or corrupted vault".to_string())?;
+        serde_json::from_slice(&plaintext).map_err(|e| e.to_string())
+    }
+
+    fn load(path: &Path, passphrase: &str) -> Result<Self, String> {
+        let data =
+            std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::decrypt(&data, passphrase)
+    }
+
+    fn save(&self, path: &Path, passphrase: &str) -> Result<(), String> {
+        persist::write_private(path, &self.encrypt(passphrase)?)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum VaultCommand {
+    List,
+    Get(String),
+}
+
+/// Flags that take a value, so the value is not mistaken for a subcommand.
+const VALUE_FLAGS: [&str; 5] = [
+    "--vault",
+    "--preset",
+    "--min-entropy",
+    "--attempts",
+    "--max-class-run",
+];
+
+/// The arguments that are neither flags nor flag values, in order.
+fn positional_args(args: &[String]) -> Vec<&str> {
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if VALUE_FLAGS.contains(&arg.as_str()) {
+            args.next();
+        } else if !arg.starts_with("--") {
+            positional.push(arg.as_str());
+        }
+    }
+    positional
+}
+
+fn parse_vault_command(args: &[String]) -> Result<Option<VaultCommand>, String> {
+    let positional = positional_args(args);
+    match positional.first().copied() {
+        Some("list") => Ok(Some(VaultCommand::List)),
+        Some("get") => match positional.get(1) {
+            Some(label) => Ok(Some(VaultCommand::Get(label.to_string()))),
+            None => Err("get requires a label".to_string()),
+        },
+        _ => Ok(None),
+    }
+}
+
+fn parse_vault_arg(args: &[String]) -> Result<&Path, String> {
+    match args.iter().position(|arg| arg == "--vault") {
+        None => Ok(Path::new(VAULT_FILE)),
+        Some(i) => args
+            .get(i + 1)
+            .map(Path::new)
+            .ok_or_else(|| "--vault requires a file".to_string()),
+    }
+}
+
+/// Prompts for the master passphrase, asking twice when it will protect a new vault.
+fn prompt_for_passphrase(new_vault: bool) -> Result<String, String> {
+    loop {
+        println!("Enter the vault passphrase:");
+        let passphrase = read_password().map_err(|e| e.to_string())?;
+        if passphrase.is_empty() {
+            println!("The passphrase cannot be empty.");
+            continue;
+        }
+        if new_vault {
+            println!("Confirm the vault passphrase:");
+            if read_password().map_err(|e| e.to_string())? != passphrase {
+                println!("The passphrases do not match. Please try again.");
+                continue;
+            }
+        }
+        return Ok(passphrase);
+    }
+}
+
+fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| format!("Failed to copy to the clipboard: {}", e))
+}
+
+fn run_vault_command(command: &VaultCommand, path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Err(format!("No vault found at {}", path.display()));
+    }
+    let vault = Vault::load(path, &prompt_for_passphrase(false)?)?;
+    match command {
+        VaultCommand::List if vault.entries.is_empty() => println!("The vault is empty."),
+        VaultCommand::List => {
+            for label in vault.entries.keys() {
+                println!("{}", label);
+            }
+        }
+        VaultCommand::Get(label) => {
+            let password = vault
+                .entries
+                .get(label)
+                .ok_or_else(|| format!("No entry labelled '{}'", label))?;
+            copy_to_clipboard(password)?;
+            println!("password for '{}' copied to clipboard", label);
+        }
+    }
+    Ok(())
+}
+
+/// Offers to store `password` in the vault at `path`, creating the vault if needed.
+fn offer_to_store(password: &str, path: &Path) -> Result<(), String> {
+    let mut input = String::new();
+    print!("Store this password in the vault? (y/N): ");
+    io::stdout().flush().map_err(|e| e.to_string())?;
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(|e| e.to_string())?;
+    if !input.trim().eq_ignore_ascii_case("y") {
+        return Ok(());
+    }
+
+    let label = loop {
+        input.clear();
+        print!("Label: ");
+        io::stdout().flush().map_err(|e| e.to_string())?;
+        io::stdin()
+            .read_line(&mut input)
+            .map_err(|e| e.to_string())?;
+        match input.trim() {
+            "" => println!("The label cannot be empty."),
+            label => break label.to_string(),
+        }
+    };
+
+    let new_vault = !path.exists();
+    let passphrase = prompt_for_passphrase(new_vault)?;
+    let mut vault = if new_vault {
+        Vault::default()
+    } else {
+        Vault::load(path, &passphrase)?
+    };
+    if vault
+        .entries
+        .insert(label.clone(), password.to_string())
+        .is_some()
+    {
+        println!("Replaced the existing entry for '{}'.", label);
+    }
+    vault.save(path, &passphrase)?;
+    println!("Stored '{}' in {}", label, path.display());
+    Ok(())
 }
 
 fn main() {
-    let components = prompt_for_components();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let vault_path = match parse_vault_arg(&args) {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+    match parse_vault_command(&args) {
+        Ok(Some(command)) => {
+            if let Err(e) = run_vault_command(&command, vault_path) {
+                eprintln!("Error: {}", e);
+            }
+            return;
+        }
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    }
+    let policy = match parse_preset_arg(&args) {
+        Ok(policy) => policy,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+    let gate = match parse_strength_args(&args) {
+        Ok(gate) => gate,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+    let rules = match parse_rule_args(&args) {
+        Ok(rules) => rules,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let components = match &policy {
+        Some(policy) => {
+            println!("Using the {} password policy.", policy.name);
+            Ok(policy.components())
+        }
+        None => prompt_for_components(),
+    };
     if let Ok(components) = components {
         if components.is_empty() {
             println!("No components selected. Exiting.");
             return;
         }
 
-        let password = generate_password(components);
-        let mut clipboard = Clipboard::new().expect("Failed to access clipboard");
-        clipboard
-            .set_text(password)
-            .expect("Failed to set clipboard text");
+        let (password, attempts) = match generate_gated_password(&components, &gate, &rules) {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return;
+            }
+        };
+        let entropy = entropy_bits(&password);
+        println!(
+            "Password strength: {} ({:.1} bits of entropy, {} attempt(s))",
+            password_strength(&password),
+            entropy,
+            attempts
+        );
+        if let Some(min_entropy) = gate.min_entropy
+            && entropy < min_entropy
+        {
+            eprintln!(
+                "Could not reach {:.1} bits of entropy in {} attempt(s); try again or add more characters.",
+                min_entropy, gate.max_attempts
+            );
+            return;
+        }
+        if let Err(e) = rules.validate(&password) {
+            eprintln!("Generated password violates the positional rules: {}", e);
+            return;
+        }
+        if let Some(policy) = &policy
+            && let Err(e) = policy.validate(&password)
+        {
+            eprintln!(
+                "Generated password violates the {} policy: {}",
+                policy.name, e
+            );
+            return;
+        }
+        if let Err(e) = offer_to_store(&password, vault_path) {
+            eprintln!("Error: {}", e);
+        }
+        if let Err(e) = copy_to_clipboard(&password) {
+            eprintln!("Error: {}", e);
+            return;
+        }
         println!("password copied to clipboard");
     } else if let Err(e) = components {
         eprintln!("Error reading components: {}", e);
@@ -119,7 +819,7 @@ mod tests {
             PasswordComponent::SpecialChar,
         ];
 
-        let password = generate_password(components);
+        let password = generate_password(&components, &mut rand::rng());
         assert_eq!(password.len(), 3);
     }
 
@@ -132,7 +832,7 @@ mod tests {
             PasswordComponent::AlphaChar,
         ];
 
-        let password = generate_password(components);
+        let password = generate_password(&components, &mut rand::rng());
 
         assert_eq!(password.len(), 4);
 
@@ -148,10 +848,233 @@ mod tests {
     #[test]
     fn generate_password_handles_empty_components() {
         let components = vec![];
-        let password = generate_password(components);
+        let password = generate_password(&components, &mut rand::rng());
         assert!(
             password.is_empty(),
             "Password should be empty when no components are provided"
         );
     }
+
+    #[test]
+    fn presets_generate_passwords_that_satisfy_their_policy() {
+        for policy in [
+            PasswordPolicy::NIST,
+            PasswordPolicy::CORPORATE,
+            PasswordPolicy::PIN,
+        ] {
+            let password = generate_password(&policy.components(), &mut rand::rng());
+            assert_eq!(policy.validate(&password), Ok(()), "{}", policy.name);
+        }
+    }
+
+    #[test]
+    fn validate_rejects_passwords_that_violate_the_policy() {
+        assert!(PasswordPolicy::CORPORATE.validate("short1!").is_err());
+        assert!(PasswordPolicy::CORPORATE.validate("longpassword1").is_err());
+        assert!(PasswordPolicy::CORPORATE.validate("longpassword!").is_err());
+        assert!(PasswordPolicy::PIN.validate("12a456").is_err());
+        assert!(PasswordPolicy::NIST.validate("tooshort").is_err());
+    }
+
+    #[test]
+    fn parse_preset_arg_selects_named_preset() {
+        let args = vec!["--preset".to_string(), "PIN".to_string()];
+        assert_eq!(parse_preset_arg(&args), Ok(Some(PasswordPolicy::PIN)));
+        assert_eq!(parse_preset_arg(&[]), Ok(None));
+        assert!(parse_preset_arg(&["--preset".to_string()]).is_err());
+        assert!(parse_preset_arg(&["--preset".to_string(), "bogus".to_string()]).is_err());
+    }
+
+    #[test]
+    fn parse_strength_args_reads_gate_options() {
+        let args: Vec<String> = ["--min-entropy", "60", "--attempts", "10"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            parse_strength_args(&args),
+            Ok(StrengthGate {
+                min_entropy: Some(60.0),
+                max_attempts: 10,
+            })
+        );
+        assert_eq!(
+            parse_strength_args(&[]),
+            Ok(StrengthGate {
+                min_entropy: None,
+                max_attempts: DEFAULT_ATTEMPTS,
+            })
+        );
+        assert!(parse_strength_args(&["--min-entropy".to_string()]).is_err());
+        assert!(parse_strength_args(&["--attempts".to_string(), "0".to_string()]).is_err());
+    }
+
+    #[test]
+    fn generate_gated_password_fails_fast_when_composition_is_too_short() {
+        let gate = StrengthGate {
+            min_entropy: Some(40.0),
+            max_attempts: 3,
+        };
+        let e = generate_gated_password(
+            &PasswordPolicy::PIN.components(),
+            &gate,
+            &PositionalRules::default(),
+        )
+        .unwrap_err();
+        assert!(e.contains("at most 19.9 bits"));
+        assert!(e.contains("add at least 2 more letter(s)"));
+    }
+
+    #[test]
+    fn composition_shortfall_names_the_letters_needed() {
+        let pin = PasswordPolicy::PIN.components();
+        assert!(composition_entropy(&pin) < 20.0);
+        assert_eq!(composition_shortfall(&pin, 19.0), None);
+
+        let mut extended = pin.clone();
+        extended.extend([PasswordComponent::AlphaChar; 2]);
+        assert!(composition_entropy(&extended) >= 40.0);
+    }
+
+    #[test]
+    fn generate_gated_password_accepts_strong_password() {
+        let gate = StrengthGate {
+            min_entropy: Some(10.0),
+            max_attempts: 3,
+        };
+        let (password, attempts) = generate_gated_password(
+            &PasswordPolicy::NIST.components(),
+            &gate,
+            &PositionalRules::default(),
+        )
+        .unwrap();
+        assert_eq!(attempts, 1);
+        assert!(entropy_bits(&password) >= 10.0);
+    }
+
+    #[test]
+    fn generate_constrained_password_satisfies_rules() {
+        let rules = PositionalRules {
+            no_consecutive_repeats: true,
+            no_leading_digit: true,
+            max_class_run: Some(2),
+        };
+        let mut components = vec![PasswordComponent::Digit; 6];
+        components.extend(vec![PasswordComponent::AlphaChar; 3]);
+        for _ in 0..50 {
+            let password = generate_constrained_password(&components, &rules).unwrap();
+            assert_eq!(password.len(), 9);
+            assert_eq!(rules.validate(&password), Ok(()), "{}", password);
+            assert_eq!(
+                password
+                    .chars()
+                    .filter(|c| PasswordComponent::Digit.chars().contains(c))
+                    .count(),
+                6
+            );
+        }
+    }
+
+    #[test]
+    fn generate_constrained_password_reports_impossible_rules() {
+        let rules = PositionalRules {
+            no_leading_digit: true,
+            ..PositionalRules::default()
+        };
+        assert!(generate_constrained_password(&PasswordPolicy::PIN.components(), &rules).is_err());
+
+        let rules = PositionalRules {
+            max_class_run: Some(2),
+            ..PositionalRules::default()
+        };
+        let mut components = vec![PasswordComponent::Digit; 40];
+        components.push(PasswordComponent::AlphaChar);
+        assert!(generate_constrained_password(&components, &rules).is_err());
+    }
+
+    #[test]
+    fn positional_rules_validate_and_parse() {
+        let rules = parse_rule_args(&[
+            "--no-repeats".to_string(),
+            "--max-class-run".to_string(),
+            "3".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(
+            rules,
+            PositionalRules {
+                no_consecutive_repeats: true,
+                no_leading_digit: false,
+                max_class_run: Some(3),
+            }
+        );
+        assert!(rules.validate("ab1c2!").is_ok());
+        assert!(rules.validate("aab").is_err());
+        assert!(rules.validate("abcd").is_err());
+        assert!(parse_rule_args(&["--max-class-run".to_string(), "0".to_string()]).is_err());
+    }
+
+    #[test]
+    fn vault_round_trips_with_the_right_passphrase() {
+        let mut vault = Vault::default();
+        vault
+            .entries
+            .insert("email".to_string(), "h7$kQ2!x".to_string());
+        let data = vault.encrypt("correct horse").unwrap();
+        assert!(data.starts_with(VAULT_MAGIC));
+        assert!(!String::from_utf8_lossy(&data).contains("h7$kQ2!x"));
+        assert_eq!(Vault::decrypt(&data, "correct horse"), Ok(vault));
+    }
+
+    #[test]
+    fn vault_rejects_wrong_passphrase_and_bad_files() {
+        let data = Vault::default().encrypt("correct horse").unwrap();
+        assert_eq!(
+            Vault::decrypt(&data, "battery staple"),
+            Err("Wrong passphrase or corrupted vault".to_string())
+        );
+        assert!(Vault::decrypt(b"not a vault", "correct horse").is_err());
+        assert!(Vault::decrypt(&data[..VAULT_MAGIC.len() + 4], "correct horse").is_err());
+    }
+
+    #[test]
+    fn vault_saves_and_loads_from_disk() {
+        let path = std::env::temp_dir().join(format!("e37_vault_{}.bin", std::process::id()));
+        let mut vault = Vault::default();
+        vault.entries.insert("bank".to_string(), "9!aZ".to_string());
+        vault.save(&path, "passphrase").unwrap();
+        let loaded = Vault::load(&path, "passphrase");
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, Ok(vault));
+    }
+
+    #[test]
+    fn parse_vault_command_reads_subcommands() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            parse_vault_command(&args(&["list"])),
+            Ok(Some(VaultCommand::List))
+        );
+        assert_eq!(
+            parse_vault_command(&args(&["get", "email", "--vault", "v.bin"])),
+            Ok(Some(VaultCommand::Get("email".to_string())))
+        );
+        assert_eq!(
+            parse_vault_command(&args(&["--vault", "v.bin", "list"])),
+            Ok(Some(VaultCommand::List))
+        );
+        assert_eq!(
+            parse_vault_command(&args(&["--no-repeats", "get", "--vault", "v.bin", "email"])),
+            Ok(Some(VaultCommand::Get("email".to_string())))
+        );
+        assert_eq!(parse_vault_command(&args(&["--preset", "pin"])), Ok(None));
+        assert_eq!(parse_vault_command(&args(&["--vault", "list"])), Ok(None));
+        assert!(parse_vault_command(&args(&["get"])).is_err());
+        assert!(parse_vault_command(&args(&["get", "--vault", "v.bin"])).is_err());
+        assert_eq!(
+            parse_vault_arg(&args(&["list", "--vault", "v.bin"])),
+            Ok(Path::new("v.bin"))
+        );
+        assert_eq!(parse_vault_arg(&[]), Ok(Path::new(VAULT_FILE)));
+    }
 }
@@ -24,10 +24,10 @@ fn print_even_nums(file_path: PathBuf) -> Result<Vec<u32>, std::io::Error> {
 
     for line in reader.lines() {
         let time_str = line?;
-        if let Ok(time) = time_str.trim().parse::<u32>() {
-            if time & 1 != 1 {
-                times.push(time);
-            }
+        if let Ok(time) = time_str.trim().parse::<u32>()
+            && time & 1 != 1
+        {
+            times.push(time);
         }
     }
     Ok(times)
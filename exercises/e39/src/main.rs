@@ -9,13 +9,35 @@
 //! - **Interactive Sorting**: Allows users to select different sort criteria
 //! - **Multiple Sort Options**: Sort by name, position, or separation date
 //! - **Formatted Output**: Presents employee data in a clean, tabular format
+//! - **Natural Collation**: Text columns sort case-insensitively with accents folded, so
+//!   "Álvarez" sorts with "Alvarez" and "de la Cruz" with "Davis", and numeric suffixes sort
+//!   numerically ("Engineer 2" before "Engineer 10")
+//! - **Validation Mode**: `--validate` checks the CSV for missing fields, duplicate rows, and
+//!   unparsable or future separation dates, exiting with a non-zero status on failure
+//! - **Anonymizer**: `--scrub <output> [--seed <n>]` writes a copy of the CSV with every name
+//!   replaced by a generated one, keeping positions and dates, so it can be shared as test data
 //!
 //! The application loads employee data from a CSV file, prompts the user to select
 //! a sorting criterion, and displays the sorted results in a formatted table.
-use chrono::NaiveDate;
+use chrono::{Local, NaiveDate};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
 use serde::Deserialize;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
+const REQUIRED_FIELDS: [&str; 3] = ["first_name", "last_name", "position"];
+const FAKE_FIRST_NAMES: [&str; 16] = [
+    "Avery", "Blake", "Casey", "Dana", "Emerson", "Finley", "Harper", "Jordan", "Kendall", "Logan",
+    "Morgan", "Parker", "Quinn", "Riley", "Sawyer", "Taylor",
+];
+const FAKE_LAST_NAMES: [&str; 16] = [
+    "Abbott", "Barnes", "Carver", "Dalton", "Ellis", "Foster", "Garner", "Hayes", "Irwin",
+    "Keller", "Lambert", "Mercer", "Norris", "Porter", "Reeves", "Sutton",
+];
+
 enum SortCriterion {
     FirstName,
     LastName,
@@ -84,19 +106,322 @@ fn print_employee_table(employees: &[Employee]) {
     }
 }
 
+fn fold_accents(c: char) -> char {
+    match c {
+        'á' | 'à' | 'â' | 'ä' | 'ã' | 'å' => 'a',
+        'ç' => 'c',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'í' | 'ì' | 'î' | 'ï' => 'i',
+        'ñ' => 'n',
+        'ó' | 'ò' | 'ô' | 'ö' | 'õ' | 'ø' => 'o',
+        'ú' | 'ù' | 'û' | 'ü' => 'u',
+        'ý' | 'ÿ' => 'y',
+        _ => c,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum KeyPart {
+    Number(u128),
+    Text(String),
+}
+
+/// Splits `text` into runs of digits and non-digits, lowercasing and folding accents in the
+/// text runs so that it can be compared in natural order.
+fn collation_key(text: &str) -> Vec<KeyPart> {
+    let mut parts = Vec::new();
+    let mut chars = text.trim().chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_ascii_digit() {
+            let mut digits = String::new();
+            while let Some(d) = chars.next_if(char::is_ascii_digit) {
+                digits.push(d);
+            }
+            parts.push(KeyPart::Number(digits.parse().unwrap_or(u128::MAX)));
+        } else {
+            let mut run = String::new();
+            while let Some(t) = chars.next_if(|t| !t.is_ascii_digit()) {
+                run.extend(t.to_lowercase().map(fold_accents));
+            }
+            parts.push(KeyPart::Text(run));
+        }
+    }
+    parts
+}
+
+/// Compares strings case-insensitively with accents folded and digit runs compared by value.
+/// Strings that collate equally fall back to byte order so the result is deterministic.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    collation_key(a)
+        .cmp(&collation_key(b))
+        .then_with(|| a.cmp(b))
+}
+
 fn sort_employees(employees: &mut [Employee], criterion: SortCriterion) {
     match criterion {
-        SortCriterion::FirstName => employees.sort_by_key(|e| e.first_name.clone()),
-        SortCriterion::LastName => employees.sort_by_key(|e| e.last_name.clone()),
-        SortCriterion::Position => employees.sort_by_key(|e| e.position.clone()),
+        SortCriterion::FirstName => {
+            employees.sort_by(|a, b| natural_cmp(&a.first_name, &b.first_name))
+        }
+        SortCriterion::LastName => {
+            employees.sort_by(|a, b| natural_cmp(&a.last_name, &b.last_name))
+        }
+        SortCriterion::Position => employees.sort_by(|a, b| natural_cmp(&a.position, &b.position)),
         SortCriterion::SeparationDate => {
             employees.sort_by_key(|e| e.separation_date);
         }
     }
 }
 
+#[derive(Debug, PartialEq)]
+struct ValidationIssue {
+    line: u64,
+    message: String,
+}
+
+/// Checks every record in `rdr` and returns the problems found, in file order.
+fn validate_records<R: std::io::Read>(
+    rdr: &mut csv::Reader<R>,
+    today: NaiveDate,
+) -> Result<Vec<ValidationIssue>, csv::Error> {
+    let headers = rdr.headers()?.clone();
+    let column = |name: &str| headers.iter().position(|h| h == name);
+    let mut issues = Vec::new();
+    let mut seen: HashMap<Vec<String>, u64> = HashMap::new();
+
+    for result in rdr.records() {
+        let record = result?;
+        let line = record.position().map_or(0, |p| p.line());
+        let mut report = |message: String| issues.push(ValidationIssue { line, message });
+
+        if record.len() != headers.len() {
+            report(format!(
+                "expected {} fields but found {}",
+                headers.len(),
+                record.len()
+            ));
+        }
+
+        for field in REQUIRED_FIELDS {
+            let value = column(field).and_then(|i| record.get(i)).unwrap_or("");
+            if value.trim().is_empty() {
+                report(format!("missing {}", field));
+            }
+        }
+
+        let date = column("separation_date")
+            .and_then(|i| record.get(i))
+            .unwrap_or("")
+            .trim();
+        if !date.is_empty() {
+            match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                Ok(date) if date > today => {
+                    report(format!("separation date {} is in the future", date))
+                }
+                Ok(_) => {}
+                Err(_) => report(format!("unparsable separation date '{}'", date)),
+            }
+        }
+
+        let key: Vec<String> = record.iter().map(|f| f.trim().to_string()).collect();
+        if let Some(first_line) = seen.get(&key) {
+            report(format!("duplicate of line {}", first_line));
+        } else {
+            seen.insert(key, line);
+        }
+    }
+    Ok(issues)
+}
+
+fn run_validation(file_path: PathBuf) -> i32 {
+    let result = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_path(&file_path)
+        .and_then(|mut rdr| validate_records(&mut rdr, Local::now().date_naive()));
+    match result {
+        Ok(issues) if issues.is_empty() => {
+            println!("{}: no problems found.", file_path.display());
+            0
+        }
+        Ok(issues) => {
+            for issue in &issues {
+                println!("{}:{}: {}", file_path.display(), issue.line, issue.message);
+            }
+            println!("{} problem(s) found.", issues.len());
+            1
+        }
+        Err(e) => {
+            eprintln!("Error reading file: {}", e);
+            2
+        }
+    }
+}
+
+/// Replaces names with fake ones drawn from `rng`. Each distinct real name always maps to the
+/// same fake name so repeated employees stay recognizable as repeats, and distinct names in a
+/// column never share a fake one. Once a column has used every fake name, further names get a
+/// numeric suffix ("Avery 2").
+struct Scrubber {
+    rng: StdRng,
+    replacements: HashMap<(usize, String), String>,
+    used: HashMap<usize, HashSet<String>>,
+}
+
+impl Scrubber {
+    fn new(seed: u64) -> Self {
+        Scrubber {
+            rng: StdRng::seed_from_u64(seed),
+            replacements: HashMap::new(),
+            used: HashMap::new(),
+        }
+    }
+
+    fn fresh_fake(&mut self, column: usize, fakes: &[&str]) -> String {
+        let used = self.used.entry(column).or_default();
+        let round = used.len() / fakes.len().max(1);
+        let available: Vec<String> = fakes
+            .iter()
+            .map(|fake| match round {
+                0 => fake.to_string(),
+                _ => format!("{} {}", fake, round + 1),
+            })
+            .filter(|fake| !used.contains(fake))
+            .collect();
+        let fake = available.choose(&mut self.rng).cloned().unwrap_or_default();
+        used.insert(fake.clone());
+        fake
+    }
+
+    fn replace(&mut self, column: usize, name: &str, fakes: &[&str]) -> &str {
+        if name.trim().is_empty() {
+            return "";
+        }
+        let key = (column, name.to_string());
+        if !self.replacements.contains_key(&key) {
+            let fake = self.fresh_fake(column, fakes);
+            self.replacements.insert(key.clone(), fake);
+        }
+        &self.replacements[&key]
+    }
+}
+
+/// Copies every record from `rdr` to `wtr`, scrubbing the name columns and leaving the rest,
+/// including dates, exactly as written.
+fn scrub_records<R: std::io::Read, W: std::io::Write>(
+    rdr: &mut csv::Reader<R>,
+    wtr: &mut csv::Writer<W>,
+    seed: u64,
+) -> Result<usize, csv::Error> {
+    let headers = rdr.headers()?.clone();
+    let name_columns: Vec<(usize, &'static [&'static str])> = headers
+        .iter()
+        .enumerate()
+        .filter_map(|(i, header)| match header {
+            "first_name" => Some((i, &FAKE_FIRST_NAMES[..])),
+            "last_name" => Some((i, &FAKE_LAST_NAMES[..])),
+            _ => None,
+        })
+        .collect();
+    wtr.write_record(&headers)?;
+
+    let mut scrubber = Scrubber::new(seed);
+    let mut count = 0;
+    for result in rdr.records() {
+        let record = result?;
+        let scrubbed: Vec<String> = record
+            .iter()
+            .enumerate()
+            .map(
+                |(i, field)| match name_columns.iter().find(|(c, _)| *c == i) {
+                    Some(&(column, fakes)) => scrubber.replace(column, field, fakes).to_string(),
+                    None => field.to_string(),
+                },
+            )
+            .collect();
+        wtr.write_record(&scrubbed)?;
+        count += 1;
+    }
+    wtr.flush()?;
+    Ok(count)
+}
+
+#[derive(Debug, PartialEq)]
+struct ScrubOptions {
+    output: PathBuf,
+    seed: Option<u64>,
+}
+
+/// Parses the `--scrub` arguments, returning `None` when scrubbing wasn't requested.
+fn parse_scrub_args(args: &[String]) -> Result<Option<ScrubOptions>, String> {
+    let mut output = None;
+    let mut seed = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--scrub" => {
+                output = Some(PathBuf::from(
+                    args.next().ok_or("--scrub requires an output file")?,
+                ))
+            }
+            "--seed" => {
+                let value = args.next().ok_or("--seed requires a value")?;
+                seed = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid seed '{}'", value))?,
+                );
+            }
+            _ => {}
+        }
+    }
+    match output {
+        Some(output) => Ok(Some(ScrubOptions { output, seed })),
+        None if seed.is_some() => Err("--seed is only used with --scrub".to_string()),
+        None => Ok(None),
+    }
+}
+
+fn run_scrub(file_path: PathBuf, options: ScrubOptions) -> i32 {
+    // Without an explicit seed pick one, and report it so the output can be reproduced.
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let result = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_path(&file_path)
+        .and_then(|mut rdr| {
+            let mut wtr = csv::Writer::from_path(&options.output)?;
+            scrub_records(&mut rdr, &mut wtr, seed)
+        });
+    match result {
+        Ok(count) => {
+            println!(
+                "Wrote {} scrubbed record(s) to {} (seed {}).",
+                count,
+                options.output.display(),
+                seed
+            );
+            0
+        }
+        Err(e) => {
+            eprintln!("Error scrubbing file: {}", e);
+            1
+        }
+    }
+}
+
 fn main() {
     let file_path = PathBuf::from("exercises/e39/inputs/employees.csv");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--validate") {
+        std::process::exit(run_validation(file_path));
+    }
+    match parse_scrub_args(&args) {
+        Ok(Some(options)) => std::process::exit(run_scrub(file_path, options)),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(2);
+        }
+    }
+
     match load_employees(file_path) {
         Ok(mut employees) => {
             let sort_criterion = prompt_for_sort_criterion();
@@ -203,4 +528,155 @@ mod tests {
         assert_eq!(employees[0].separation_date, Some(date1));
         assert_eq!(employees[1].separation_date, Some(date2));
     }
+
+    #[test]
+    fn natural_cmp_ignores_case_and_accents() {
+        let mut names = vec![
+            "Zimmer",
+            "de la Cruz",
+            "Álvarez",
+            "Baker",
+            "Davis",
+            "alvarez",
+        ];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(
+            names,
+            vec![
+                "alvarez",
+                "Álvarez",
+                "Baker",
+                "Davis",
+                "de la Cruz",
+                "Zimmer"
+            ]
+        );
+    }
+
+    #[test]
+    fn natural_cmp_orders_numeric_suffixes_by_value() {
+        let mut positions = vec!["Engineer 10", "Engineer 2", "engineer 1", "Engineer"];
+        positions.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(
+            positions,
+            vec!["Engineer", "engineer 1", "Engineer 2", "Engineer 10"]
+        );
+    }
+
+    #[test]
+    fn validate_records_reports_problems_by_line() {
+        let data = "first_name,last_name,position,separation_date\n\
+                    John,Johnson,Manager,2016-12-31\n\
+                    ,Xiong,Software Engineer,2016-10-05\n\
+                    Jake,Jacobson,Programmer,2016-13-40\n\
+                    Sally,Weber,Web Developer,2030-01-01\n\
+                    John,Johnson,Manager,2016-12-31\n\
+                    Jacquelyn,Jackson\n";
+        let mut rdr = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(data.as_bytes());
+        let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+
+        let issues = validate_records(&mut rdr, today).unwrap();
+        let summary: Vec<(u64, &str)> = issues
+            .iter()
+            .map(|i| (i.line, i.message.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (3, "missing first_name"),
+                (4, "unparsable separation date '2016-13-40'"),
+                (5, "separation date 2030-01-01 is in the future"),
+                (6, "duplicate of line 2"),
+                (7, "expected 4 fields but found 2"),
+                (7, "missing position"),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_records_accepts_clean_data() {
+        let mut rdr = csv::Reader::from_path("inputs/employees.csv").unwrap();
+        let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        assert!(validate_records(&mut rdr, today).unwrap().is_empty());
+    }
+
+    fn scrub(data: &str, seed: u64) -> String {
+        let mut rdr = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(data.as_bytes());
+        let mut wtr = csv::Writer::from_writer(Vec::new());
+        scrub_records(&mut rdr, &mut wtr, seed).unwrap();
+        String::from_utf8(wtr.into_inner().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn scrub_records_replaces_names_but_keeps_other_fields() {
+        let data = "first_name,last_name,position,separation_date\n\
+                    John,Johnson,Manager,2016-12-31\n\
+                    Tou,Xiong,Software Engineer,\n\
+                    John,Johnson,Manager,2016-12-31\n";
+        let output = scrub(data, 42);
+        let rows: Vec<Vec<&str>> = output
+            .lines()
+            .map(|line| line.split(',').collect())
+            .collect();
+
+        assert_eq!(
+            rows[0],
+            ["first_name", "last_name", "position", "separation_date"]
+        );
+        assert!(FAKE_FIRST_NAMES.contains(&rows[1][0]));
+        assert!(FAKE_LAST_NAMES.contains(&rows[1][1]));
+        assert_eq!(rows[1][2..], ["Manager", "2016-12-31"]);
+        assert_eq!(rows[2][2..], ["Software Engineer", ""]);
+        // The same employee is scrubbed to the same fake name.
+        assert_eq!(rows[3], rows[1]);
+        assert!(!output.contains("John") && !output.contains("Xiong"));
+    }
+
+    #[test]
+    fn scrub_records_keeps_distinct_employees_distinct() {
+        let mut data = String::from("first_name,last_name,position,separation_date\n");
+        for i in 0..40 {
+            data.push_str(&format!("Name{},Smith,Engineer,\n", i));
+            data.push_str(&format!("Name{},Jones,Engineer,\n", i));
+        }
+        let distinct = |csv: &str| csv.lines().collect::<HashSet<&str>>().len();
+
+        let output = scrub(&data, 42);
+        assert_eq!(distinct(&output), distinct(&data));
+        let mut rdr = csv::Reader::from_reader(output.as_bytes());
+        assert!(
+            validate_records(&mut rdr, NaiveDate::MAX)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn scrub_records_is_deterministic_for_a_seed() {
+        let data = std::fs::read_to_string("inputs/employees.csv").unwrap();
+        assert_eq!(scrub(&data, 7), scrub(&data, 7));
+        assert_ne!(scrub(&data, 7), scrub(&data, 8));
+    }
+
+    #[test]
+    fn parse_scrub_args_reads_output_and_seed() {
+        let args: Vec<String> = ["--scrub", "out.csv", "--seed", "7"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            parse_scrub_args(&args),
+            Ok(Some(ScrubOptions {
+                output: PathBuf::from("out.csv"),
+                seed: Some(7),
+            }))
+        );
+        assert_eq!(parse_scrub_args(&[]), Ok(None));
+        assert!(parse_scrub_args(&args[2..]).is_err());
+        assert!(parse_scrub_args(&args[..1]).is_err());
+    }
 }
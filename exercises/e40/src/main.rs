@@ -10,14 +10,31 @@
 //! - **Flexible Matching**: Uses case-insensitive partial matching for text searches
 //! - **Date-based Filtering**: Finds employees who left within the last six months
 //! - **Formatted Output**: Displays results in a clear, tabular format
+//! - **CSV Export**: Optionally writes the matched records to a new CSV file
+//! - **Pagination**: Large result sets are shown a page at a time (`--page-size <n>`, default
+//!   20) with next/prev/quit controls
+//! - **Column Selection**: `--columns name,position,...` chooses which fields appear in the
+//!   output table
+//! - **Watch Mode**: `--watch` keeps running after the search and re-prints the results whenever
+//!   the employee file changes
 //!
 //! The application loads employee data, prompts the user to select a search criterion,
 //! accepts search parameters, and displays matching records in a formatted table.
 use chrono::{Local, Months, NaiveDate};
-use serde::Deserialize;
-use std::path::PathBuf;
-
-#[derive(Debug, Deserialize)]
+use notify::{RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+const DEFAULT_PAGE_SIZE: usize = 20;
+const EMPLOYEES_FILE: &str = "exercises/e39/inputs/employees.csv";
+/// Editors and exports often write a file in several steps, so changes arriving this close
+/// together are treated as one.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Deserialize, Serialize)]
 struct Employee {
     first_name: String,
     last_name: String,
@@ -66,7 +83,35 @@ enum SearchCriterion {
     SeparationDate,
 }
 
-fn load_employees(file_path: PathBuf) -> Result<Vec<Employee>, std::io::Error> {
+/// A search with its parameters, kept so it can be re-run against a reloaded file.
+#[derive(Debug, Clone, PartialEq)]
+enum Query {
+    Name(String),
+    Position(String),
+    SeparationDate,
+}
+
+impl Query {
+    fn run<'a>(&self, employees: &'a [Employee]) -> Vec<&'a Employee> {
+        match self {
+            Query::Name(name) => search_by_name(employees, name),
+            Query::Position(position) => search_by_position(employees, position),
+            Query::SeparationDate => search_by_separation_date(employees),
+        }
+    }
+
+    fn not_found_message(&self) -> &'static str {
+        match self {
+            Query::Name(_) => "No employees found with that name.",
+            Query::Position(_) => "No employees found with that position.",
+            Query::SeparationDate => {
+                "No employees found with a separation date in the last 6 months."
+            }
+        }
+    }
+}
+
+fn load_employees(file_path: &Path) -> Result<Vec<Employee>, std::io::Error> {
     let mut rdr = csv::Reader::from_path(file_path)?;
     let mut employees = Vec::new();
 
@@ -97,69 +142,343 @@ fn prompt_for_search_criterion() -> SearchCriterion {
     }
 }
 
-fn print_employee_table(employees: &[&Employee]) {
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Column {
+    Name,
+    FirstName,
+    LastName,
+    Position,
+    SeparationDate,
+}
+
+const DEFAULT_COLUMNS: [Column; 3] = [Column::Name, Column::Position, Column::SeparationDate];
+
+impl Column {
+    fn parse(name: &str) -> Option<Column> {
+        match name.trim().to_lowercase().as_str() {
+            "name" => Some(Column::Name),
+            "first_name" | "first" => Some(Column::FirstName),
+            "last_name" | "last" => Some(Column::LastName),
+            "position" => Some(Column::Position),
+            "separation_date" | "date" => Some(Column::SeparationDate),
+            _ => None,
+        }
+    }
+
+    fn header(&self) -> &'static str {
+        match self {
+            Column::Name => "Name",
+            Column::FirstName => "First Name",
+            Column::LastName => "Last Name",
+            Column::Position => "Position",
+            Column::SeparationDate => "Separation Date",
+        }
+    }
+
+    fn width(&self) -> usize {
+        match self {
+            Column::Name | Column::Position => 20,
+            Column::FirstName | Column::LastName | Column::SeparationDate => 15,
+        }
+    }
+
+    fn value(&self, employee: &Employee) -> String {
+        match self {
+            Column::Name => format!("{} {}", employee.first_name, employee.last_name),
+            Column::FirstName => employee.first_name.clone(),
+            Column::LastName => employee.last_name.clone(),
+            Column::Position => employee.position.clone(),
+            Column::SeparationDate => employee
+                .separation_date
+                .map_or("N/A".to_string(), |d| d.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    columns: Vec<Column>,
+    page_size: usize,
+    watch: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            columns: DEFAULT_COLUMNS.to_vec(),
+            page_size: DEFAULT_PAGE_SIZE,
+            watch: false,
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--columns" => {
+                let value = args.next().ok_or("--columns requires a value")?;
+                options.columns = value
+                    .split(',')
+                    .map(|name| Column::parse(name).ok_or(format!("unknown column '{}'", name)))
+                    .collect::<Result<Vec<Column>, String>>()?;
+            }
+            "--page-size" => {
+                let value = args.next().ok_or("--page-size requires a value")?;
+                options.page_size = match value.parse() {
+                    Ok(size) if size > 0 => size,
+                    _ => return Err(format!("invalid page size '{}'", value)),
+                };
+            }
+            "--watch" => options.watch = true,
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+    Ok(options)
+}
+
+fn format_row(cells: &[String], columns: &[Column]) -> String {
+    cells
+        .iter()
+        .zip(columns)
+        .map(|(cell, column)| format!("{:<width$}", cell, width = column.width()))
+        .collect::<Vec<String>>()
+        .join(" | ")
+        .trim_end()
+        .to_string()
+}
+
+fn print_employee_table(employees: &[&Employee], columns: &[Column]) {
     // Print the header row
-    println!("{:<20} | {:<20} | Separation Date", "Name", "Position");
+    let headers: Vec<String> = columns.iter().map(|c| c.header().to_string()).collect();
+    println!("{}", format_row(&headers, columns));
 
     // Print the separator line under the header
-    println!("{:-<20} | {:-<20} | {:-<15}", "", "", "");
+    let separators: Vec<String> = columns.iter().map(|c| "-".repeat(c.width())).collect();
+    println!("{}", format_row(&separators, columns));
 
     // Print each employee row
     for employee in employees {
-        let full_name = format!("{} {}", employee.first_name, employee.last_name);
-        let separation_date = employee
-            .separation_date
-            .map_or("N/A".to_string(), |d| d.to_string());
-        println!(
-            "{:<20} | {:<20} | {}",
-            full_name, employee.position, separation_date
-        );
+        let cells: Vec<String> = columns.iter().map(|c| c.value(employee)).collect();
+        println!("{}", format_row(&cells, columns));
     }
 }
 
-fn main() {
-    let file_path = PathBuf::from("exercises/e39/inputs/employees.csv");
-    match load_employees(file_path) {
-        Ok(employees) => {
-            let search_criterion = prompt_for_search_criterion();
-            match search_criterion {
-                SearchCriterion::Name => {
-                    println!("Enter a name to search for:");
-                    let mut name = String::new();
-                    std::io::stdin()
-                        .read_line(&mut name)
-                        .expect("Failed to read line");
-                    let results = search_by_name(&employees, name.trim());
-                    if results.is_empty() {
-                        println!("No employees found with that name.");
-                    } else {
-                        print_employee_table(&results);
-                    }
-                }
-                SearchCriterion::Position => {
-                    println!("Enter a position to search for:");
-                    let mut position = String::new();
-                    std::io::stdin()
-                        .read_line(&mut position)
-                        .expect("Failed to read line");
-                    let results = search_by_position(&employees, position.trim());
-                    if results.is_empty() {
-                        println!("No employees found with that position.");
-                    } else {
-                        print_employee_table(&results);
-                    }
+fn page_count(len: usize, page_size: usize) -> usize {
+    len.div_ceil(page_size).max(1)
+}
+
+fn page_range(page: usize, page_size: usize, len: usize) -> Range<usize> {
+    let start = (page * page_size).min(len);
+    start..(start + page_size).min(len)
+}
+
+#[derive(Debug, PartialEq)]
+enum PageCommand {
+    Next,
+    Prev,
+    Quit,
+}
+
+fn parse_page_command(input: &str) -> Option<PageCommand> {
+    match input.trim().to_lowercase().as_str() {
+        "" | "n" | "next" => Some(PageCommand::Next),
+        "p" | "prev" => Some(PageCommand::Prev),
+        "q" | "quit" => Some(PageCommand::Quit),
+        _ => None,
+    }
+}
+
+/// Reads one pagination command, treating end of input or a read error as `Quit` so a closed
+/// stdin cannot leave the pager looping on the last page.
+fn read_page_command<R: std::io::BufRead>(reader: &mut R) -> Option<PageCommand> {
+    let mut input = String::new();
+    match reader.read_line(&mut input) {
+        Ok(0) | Err(_) => Some(PageCommand::Quit),
+        Ok(_) => parse_page_command(&input),
+    }
+}
+
+fn print_paginated(employees: &[&Employee], options: &Options) {
+    let pages = page_count(employees.len(), options.page_size);
+    let mut page = 0;
+    loop {
+        print_employee_table(
+            &employees[page_range(page, options.page_size, employees.len())],
+            &options.columns,
+        );
+        if pages == 1 {
+            return;
+        }
+
+        loop {
+            println!(
+                "Page {} of {} ({} results) - (n)ext, (p)rev, (q)uit:",
+                page + 1,
+                pages,
+                employees.len()
+            );
+            match read_page_command(&mut std::io::stdin().lock()) {
+                Some(PageCommand::Next) if page + 1 < pages => page += 1,
+                Some(PageCommand::Prev) if page > 0 => page -= 1,
+                Some(PageCommand::Quit) => return,
+                Some(_) => {
+                    println!("No more pages in that direction.");
+                    continue;
                 }
-                SearchCriterion::SeparationDate => {
-                    let results = search_by_separation_date(&employees);
-                    if results.is_empty() {
-                        println!("No employees found with a separation date in the last 6 months.");
-                    } else {
-                        print_employee_table(&results);
-                    }
+                None => {
+                    println!("Invalid choice, please try again.");
+                    continue;
                 }
             }
+            break;
         }
-        Err(e) => eprintln!("Error reading file: {}", e),
+    }
+}
+
+fn export_employees<W: std::io::Write>(
+    employees: &[&Employee],
+    writer: W,
+) -> Result<(), csv::Error> {
+    let mut wtr = csv::Writer::from_writer(writer);
+    for employee in employees {
+        wtr.serialize(employee)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+fn prompt_for_export(employees: &[&Employee]) {
+    println!("Enter a file path to export these results to CSV (leave blank to skip):");
+    let mut path = String::new();
+    std::io::stdin()
+        .read_line(&mut path)
+        .expect("Failed to read line");
+    let path = path.trim();
+    if path.is_empty() {
+        return;
+    }
+
+    let result = std::fs::File::create(path)
+        .map_err(csv::Error::from)
+        .and_then(|file| export_employees(employees, file));
+    match result {
+        Ok(()) => println!("Exported {} employee(s) to {}.", employees.len(), path),
+        Err(e) => eprintln!("Error writing file: {}", e),
+    }
+}
+
+fn prompt_for_query() -> Query {
+    match prompt_for_search_criterion() {
+        SearchCriterion::Name => {
+            println!("Enter a name to search for:");
+            let mut name = String::new();
+            std::io::stdin()
+                .read_line(&mut name)
+                .expect("Failed to read line");
+            Query::Name(name.trim().to_string())
+        }
+        SearchCriterion::Position => {
+            println!("Enter a position to search for:");
+            let mut position = String::new();
+            std::io::stdin()
+                .read_line(&mut position)
+                .expect("Failed to read line");
+            Query::Position(position.trim().to_string())
+        }
+        SearchCriterion::SeparationDate => Query::SeparationDate,
+    }
+}
+
+/// Whether a watcher event reports new contents for `file_path`.
+fn is_change_to(event: &notify::Event, file_path: &Path) -> bool {
+    (event.kind.is_modify() || event.kind.is_create())
+        && event
+            .paths
+            .iter()
+            .any(|path| path.file_name() == file_path.file_name())
+}
+
+fn print_query_results(employees: &[Employee], query: &Query, columns: &[Column]) {
+    let results = query.run(employees);
+    if results.is_empty() {
+        println!("{}", query.not_found_message());
+    } else {
+        print_employee_table(&results, columns);
+    }
+}
+
+/// Re-runs `query` every time `file_path` changes until the process is interrupted.
+fn watch_employees(
+    file_path: &Path,
+    query: &Query,
+    columns: &[Column],
+) -> Result<(), notify::Error> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    // Watch the directory rather than the file so that exports which replace the file are
+    // still seen.
+    let dir = match file_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+    println!(
+        "Watching {} for changes (Ctrl+C to stop)...",
+        file_path.display()
+    );
+
+    for event in &rx {
+        if !is_change_to(&event?, file_path) {
+            continue;
+        }
+        while rx.recv_timeout(WATCH_DEBOUNCE).is_ok() {}
+
+        println!();
+        println!("{} changed, searching again:", file_path.display());
+        match load_employees(file_path) {
+            Ok(employees) => print_query_results(&employees, query, columns),
+            Err(e) => eprintln!("Error reading file: {}", e),
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+
+    let file_path = PathBuf::from(EMPLOYEES_FILE);
+    let employees = match load_employees(&file_path) {
+        Ok(employees) => employees,
+        Err(e) => {
+            eprintln!("Error reading file: {}", e);
+            return;
+        }
+    };
+
+    let query = prompt_for_query();
+    if options.watch {
+        print_query_results(&employees, &query, &options.columns);
+        if let Err(e) = watch_employees(&file_path, &query, &options.columns) {
+            eprintln!("Error watching file: {}", e);
+        }
+        return;
+    }
+
+    let results = query.run(&employees);
+    if results.is_empty() {
+        println!("{}", query.not_found_message());
+    } else {
+        print_paginated(&results, &options);
+        prompt_for_export(&results);
     }
 }
 
@@ -215,9 +534,11 @@ mod tests {
         // Multiple matches by first name
         let results = search_by_name(&employees, "john");
         assert_eq!(results.len(), 4);
-        assert!(results
-            .iter()
-            .all(|e| e.first_name.contains("John") || e.last_name.contains("John")));
+        assert!(
+            results
+                .iter()
+                .all(|e| e.first_name.contains("John") || e.last_name.contains("John"))
+        );
 
         // Multiple matches by last name
         let results = search_by_name(&employees, "son");
@@ -276,16 +597,20 @@ mod tests {
         // Multiple partial matches
         let results = search_by_position(&employees, "dev");
         assert_eq!(results.len(), 3);
-        assert!(results
-            .iter()
-            .all(|e| e.position.to_lowercase().contains("dev")));
+        assert!(
+            results
+                .iter()
+                .all(|e| e.position.to_lowercase().contains("dev"))
+        );
 
         // Multiple matches with different capitalizations
         let results = search_by_position(&employees, "manager");
         assert_eq!(results.len(), 2);
-        assert!(results
-            .iter()
-            .all(|e| e.position.to_lowercase().contains("manager")));
+        assert!(
+            results
+                .iter()
+                .all(|e| e.position.to_lowercase().contains("manager"))
+        );
 
         // Single match
         let results = search_by_position(&employees, "senior");
@@ -370,4 +695,133 @@ mod tests {
         assert!(!result_names.contains(&"John".to_string())); // Outside window
         assert!(!result_names.contains(&"Alice".to_string())); // No separation date
     }
+
+    #[test]
+    fn export_employees_writes_csv_with_header() {
+        let employees = [
+            Employee {
+                first_name: "John".to_string(),
+                last_name: "Johnson".to_string(),
+                position: "Manager".to_string(),
+                separation_date: NaiveDate::from_ymd_opt(2016, 12, 31),
+            },
+            Employee {
+                first_name: "Jake".to_string(),
+                last_name: "Jacobson".to_string(),
+                position: "Programmer".to_string(),
+                separation_date: None,
+            },
+        ];
+        let matched: Vec<&Employee> = employees.iter().collect();
+
+        let mut output = Vec::new();
+        export_employees(&matched, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "first_name,last_name,position,separation_date\n\
+             John,Johnson,Manager,2016-12-31\n\
+             Jake,Jacobson,Programmer,\n"
+        );
+    }
+
+    #[test]
+    fn parse_args_reads_columns_and_page_size() {
+        let args: Vec<String> = ["--columns", "last_name,position", "--page-size", "5"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            parse_args(&args),
+            Ok(Options {
+                columns: vec![Column::LastName, Column::Position],
+                page_size: 5,
+                watch: false,
+            })
+        );
+        assert_eq!(parse_args(&[]), Ok(Options::default()));
+        assert!(parse_args(&["--columns".to_string(), "salary".to_string()]).is_err());
+        assert!(parse_args(&["--page-size".to_string(), "0".to_string()]).is_err());
+    }
+
+    #[test]
+    fn page_range_splits_results_into_pages() {
+        assert_eq!(page_count(45, 20), 3);
+        assert_eq!(page_count(0, 20), 1);
+        assert_eq!(page_range(0, 20, 45), 0..20);
+        assert_eq!(page_range(2, 20, 45), 40..45);
+        assert_eq!(parse_page_command(""), Some(PageCommand::Next));
+        assert_eq!(parse_page_command("P"), Some(PageCommand::Prev));
+        assert_eq!(parse_page_command("x"), None);
+    }
+
+    #[test]
+    fn read_page_command_quits_at_end_of_input() {
+        assert_eq!(
+            read_page_command(&mut "\n".as_bytes()),
+            Some(PageCommand::Next)
+        );
+        assert_eq!(
+            read_page_command(&mut "".as_bytes()),
+            Some(PageCommand::Quit)
+        );
+    }
+
+    #[test]
+    fn format_row_pads_selected_columns() {
+        let employee = Employee {
+            first_name: "Jake".to_string(),
+            last_name: "Jacobson".to_string(),
+            position: "Programmer".to_string(),
+            separation_date: None,
+        };
+        let columns = [Column::LastName, Column::SeparationDate];
+        let cells: Vec<String> = columns.iter().map(|c| c.value(&employee)).collect();
+        assert_eq!(format_row(&cells, &columns), "Jacobson        | N/A");
+    }
+
+    #[test]
+    fn parse_args_reads_watch_flag() {
+        let options = parse_args(&["--watch".to_string()]).unwrap();
+        assert!(options.watch);
+        assert_eq!(options.page_size, DEFAULT_PAGE_SIZE);
+    }
+
+    #[test]
+    fn query_reruns_against_reloaded_employees() {
+        let query = Query::Position("dev".to_string());
+        let employees = [Employee {
+            first_name: "Jake".to_string(),
+            last_name: "Jacobson".to_string(),
+            position: "Programmer".to_string(),
+            separation_date: None,
+        }];
+        assert!(query.run(&employees).is_empty());
+
+        let reloaded = [Employee {
+            first_name: "Jake".to_string(),
+            last_name: "Jacobson".to_string(),
+            position: "Developer".to_string(),
+            separation_date: None,
+        }];
+        assert_eq!(query.run(&reloaded).len(), 1);
+    }
+
+    #[test]
+    fn is_change_to_matches_events_for_the_watched_file() {
+        use notify::event::{CreateKind, EventKind, ModifyKind};
+
+        let file_path = Path::new("inputs/employees.csv");
+        let modified = notify::Event::new(EventKind::Modify(ModifyKind::Any))
+            .add_path(PathBuf::from("/data/inputs/employees.csv"));
+        assert!(is_change_to(&modified, file_path));
+
+        let other_file = notify::Event::new(EventKind::Create(CreateKind::File))
+            .add_path(PathBuf::from("/data/inputs/notes.txt"));
+        assert!(!is_change_to(&other_file, file_path));
+
+        let accessed = notify::Event::new(EventKind::Access(notify::event::AccessKind::Any))
+            .add_path(PathBuf::from("/data/inputs/employees.csv"));
+        assert!(!is_change_to(&accessed, file_path));
+    }
 }
@@ -7,25 +7,60 @@
 //! - **Name Parsing**: Processes comma-separated name entries (last name, first name)
 //! - **Case-Insensitive Sorting**: Sorts names alphabetically by last name, then by first name
 //! - **Structured Data**: Maintains first and last name as separate fields
+//! - **Name Statistics**: `--stats` reports duplicate surnames, the most common last names,
+//!   and a histogram of last-name initials
+//! - **Progress Reporting**: Progress bars for the read, sort, and write phases so large
+//!   inputs don't appear hung, silenced with `--quiet`
+//! - **Streaming Output**: Sorted names are written through a buffered writer
 //!
 //! The application reads names from a specified input file, sorts them alphabetically
 //! in a case-insensitive manner (primary sort by last name, secondary by first name),
 //! and writes the sorted list to an output file.
-use std::io::{BufRead, Write};
+use e46::plot_histogram;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::collections::HashMap;
+use std::io::{BufRead, BufWriter, Write};
 use std::path::PathBuf;
+use std::time::Duration;
 
 struct Name {
     first_name: String,
     last_name: String,
 }
 
-fn read_names(file_path: &PathBuf) -> Result<Vec<Name>, std::io::Error> {
+/// Creates a progress bar over `len` units, or a hidden one when `quiet` is set.
+fn progress_bar(len: u64, message: &'static str, quiet: bool) -> ProgressBar {
+    if quiet {
+        return ProgressBar::hidden();
+    }
+    let bar = ProgressBar::new(len).with_message(message);
+    bar.set_style(
+        ProgressStyle::with_template("{msg:<8} [{bar:40}] {percent:>3}% ({elapsed})")
+            .unwrap()
+            .progress_chars("=> "),
+    );
+    bar
+}
+
+fn spinner(message: &'static str, quiet: bool) -> ProgressBar {
+    if quiet {
+        return ProgressBar::hidden();
+    }
+    let spinner = ProgressBar::new_spinner().with_message(message);
+    spinner.enable_steady_tick(Duration::from_millis(100));
+    spinner
+}
+
+fn read_names(file_path: &PathBuf, quiet: bool) -> Result<Vec<Name>, std::io::Error> {
     let file = std::fs::File::open(file_path)?;
+    let progress = progress_bar(file.metadata()?.len(), "Reading", quiet);
     let reader = std::io::BufReader::new(file);
     let mut names = Vec::new();
 
     for line in reader.lines() {
         let line = line?;
+        // Count the newline stripped by `lines()` so the bar ends at the file size.
+        progress.inc(line.len() as u64 + 1);
         let parts: Vec<&str> = line.split(',').collect();
         if parts.len() == 2 {
             names.push(Name {
@@ -34,14 +69,29 @@ fn read_names(file_path: &PathBuf) -> Result<Vec<Name>, std::io::Error> {
             });
         }
     }
+    progress.finish();
     Ok(names)
 }
 
-fn write_names(file_path: &PathBuf, names: &[Name]) -> Result<(), std::io::Error> {
-    let mut file = std::fs::File::create(file_path)?;
+/// Streams `names` to `writer`, advancing `progress` by one per name.
+fn write_names_to<W: Write>(
+    writer: W,
+    names: &[Name],
+    progress: &ProgressBar,
+) -> Result<(), std::io::Error> {
+    let mut writer = BufWriter::new(writer);
     for name in names {
-        writeln!(file, "{}, {}", name.last_name, name.first_name)?;
+        writeln!(writer, "{}, {}", name.last_name, name.first_name)?;
+        progress.inc(1);
     }
+    writer.flush()
+}
+
+fn write_names(file_path: &PathBuf, names: &[Name], quiet: bool) -> Result<(), std::io::Error> {
+    let file = std::fs::File::create(file_path)?;
+    let progress = progress_bar(names.len() as u64, "Writing", quiet);
+    write_names_to(file, names, &progress)?;
+    progress.finish();
     Ok(())
 }
 
@@ -58,26 +108,139 @@ fn sort_names(names: &mut [Name]) {
     });
 }
 
+const TOP_SURNAMES: usize = 10;
+
+/// Counts each last name, most common first and ties in alphabetical order.
+fn surname_counts(names: &[Name]) -> Vec<(String, u32)> {
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    for name in names {
+        *counts.entry(name.last_name.clone()).or_insert(0) += 1;
+    }
+    let mut counts: Vec<(String, u32)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    counts
+}
+
+fn initial_counts(names: &[Name]) -> HashMap<String, u32> {
+    let mut counts = HashMap::new();
+    for name in names {
+        if let Some(initial) = name.last_name.chars().next() {
+            *counts
+                .entry(initial.to_uppercase().to_string())
+                .or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+fn print_statistics(names: &[Name]) {
+    let surnames = surname_counts(names);
+    let duplicates: Vec<&(String, u32)> = surnames.iter().filter(|(_, count)| *count > 1).collect();
+
+    println!("\nDuplicate surnames: {}", duplicates.len());
+    for (surname, count) in &duplicates {
+        println!("  {} ({})", surname, count);
+    }
+
+    println!("\nTop {} last names:", TOP_SURNAMES);
+    for (rank, (surname, count)) in surnames.iter().take(TOP_SURNAMES).enumerate() {
+        println!("  {:>2}. {} ({})", rank + 1, surname, count);
+    }
+
+    println!("\nLast names by initial:");
+    plot_histogram(&initial_counts(names));
+}
+
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let show_stats = args.iter().any(|arg| arg == "--stats");
+    let quiet = args.iter().any(|arg| arg == "--quiet");
+
     let file_path = PathBuf::from("exercises/e41/data/names.txt");
 
-    match read_names(&file_path) {
+    match read_names(&file_path, quiet) {
         Ok(mut names) => {
             if names.is_empty() {
                 println!("No names found in the file.");
                 return;
             }
+            let sorting = spinner("Sorting", quiet);
             sort_names(&mut names);
+            sorting.finish();
             let output_file_path = PathBuf::from("exercises/e41/data/sorted_names.txt");
-            if let Err(e) = write_names(&output_file_path, &names) {
+            if let Err(e) = write_names(&output_file_path, &names, quiet) {
                 eprintln!("Error writing sorted names to file: {}", e);
-            } else {
+            } else if !quiet {
                 println!(
                     "Names sorted and written to {:?} successfully.",
                     output_file_path
                 );
             }
+            if show_stats {
+                print_statistics(&names);
+            }
         }
         Err(e) => eprintln!("Error reading names from file: {}", e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(first_name: &str, last_name: &str) -> Name {
+        Name {
+            first_name: first_name.to_string(),
+            last_name: last_name.to_string(),
+        }
+    }
+
+    #[test]
+    fn surname_counts_orders_by_frequency() {
+        let names = vec![
+            name("Alice", "Smith"),
+            name("Bob", "Jones"),
+            name("Clara", "Smith"),
+            name("David", "Brown"),
+        ];
+        assert_eq!(
+            surname_counts(&names),
+            vec![
+                ("Smith".to_string(), 2),
+                ("Brown".to_string(), 1),
+                ("Jones".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn initial_counts_groups_by_uppercase_initial() {
+        let names = vec![
+            name("Alice", "smith"),
+            name("Bob", "Sanchez"),
+            name("Eve", ""),
+        ];
+        let counts = initial_counts(&names);
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts["S"], 2);
+    }
+
+    #[test]
+    fn write_names_to_streams_every_name() {
+        let names = vec![name("Alice", "Smith"), name("Bob", "Jones")];
+        let progress = progress_bar(names.len() as u64, "Writing", true);
+        let mut output = Vec::new();
+        write_names_to(&mut output, &names, &progress).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Smith, Alice\nJones, Bob\n"
+        );
+        assert_eq!(progress.position(), 2);
+    }
+
+    #[test]
+    fn quiet_hides_progress() {
+        assert!(progress_bar(10, "Reading", true).is_hidden());
+        assert!(spinner("Sorting", true).is_hidden());
+    }
+}
@@ -8,24 +8,214 @@
 //! - **Salary-Based Sorting**: Sorts employees in descending order by salary
 //! - **Formatted Output**: Presents employee data in a clean, properly aligned tabular format
 //! - **Number Formatting**: Uses locale-aware formatting for salary values
+//! - **Raise Modeling**: Applies a what-if raise scenario (`--raise-percent <pct>`,
+//!   `--raise-amount <amount>`, or `--raise-config <file>` with per-position percentages) and
+//!   shows old vs. new salaries plus the change in total payroll
+//! - **Department Subtotals**: When records carry an optional fifth department column, the
+//!   report is grouped by department with per-department headcount and payroll subtotals
+//!   (`--input <file>` selects a different CSV file)
+//! - **Pay Band Analysis**: `--pay-bands <file>` reads a salary range per position, flags who
+//!   falls below or above their band and by how much, and summarizes each band's salary spread
+//!   with percentiles
 //!
-//! The application reads employee records (first name, last name, and salary),
+//! The application reads employee records (first name, last name, salary, and an optional
+//! position and department),
 //! sorts them from highest to lowest salary, and displays the results in a
 //! formatted table with dynamically sized columns.
 use num_format::{Locale, ToFormattedString};
 use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::io::BufRead;
 
 #[derive(Debug, Deserialize)]
 struct Employee {
     first_name: String,
     last_name: String,
     salary: u32,
+    #[serde(default)]
+    position: Option<String>,
+    #[serde(default)]
+    department: Option<String>,
+}
+
+impl Employee {
+    /// Records from files without a position column have an empty position.
+    fn position(&self) -> &str {
+        self.position.as_deref().unwrap_or("")
+    }
+}
+
+const DEFAULT_INPUT: &str = "exercises/e42/inputs/employees.csv";
+const UNASSIGNED_DEPARTMENT: &str = "Unassigned";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PayBand {
+    min: u32,
+    max: u32,
+}
+
+#[derive(Debug, PartialEq)]
+enum BandStatus {
+    Below(u32),
+    Within,
+    Above(u32),
+    NoBand,
+}
+
+impl PayBand {
+    fn status(&self, salary: u32) -> BandStatus {
+        if salary < self.min {
+            BandStatus::Below(self.min - salary)
+        } else if salary > self.max {
+            BandStatus::Above(salary - self.max)
+        } else {
+            BandStatus::Within
+        }
+    }
+}
+
+/// Reads `Position: min-max` lines, matching positions case-insensitively like the raise
+/// config.
+fn read_pay_bands(file_path: &str) -> Result<HashMap<String, PayBand>, Box<dyn std::error::Error>> {
+    let file = std::fs::File::open(file_path)?;
+    let reader = std::io::BufReader::new(file);
+    let mut bands = HashMap::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        if let Some((position, range)) = line.split_once(':') {
+            let (min, max) = range
+                .split_once('-')
+                .ok_or_else(|| format!("expected min-max for {}", position.trim()))?;
+            let band = PayBand {
+                min: min.trim().parse()?,
+                max: max.trim().parse()?,
+            };
+            if band.min > band.max {
+                return Err(format!("band for {} has min above max", position.trim()).into());
+            }
+            bands.insert(position.trim().to_lowercase(), band);
+        }
+    }
+    Ok(bands)
+}
+
+fn parse_pay_bands_path(args: &[String]) -> Result<Option<&str>, String> {
+    match args.iter().position(|arg| arg == "--pay-bands") {
+        None => Ok(None),
+        Some(i) => args
+            .get(i + 1)
+            .map(|path| Some(path.as_str()))
+            .ok_or_else(|| "--pay-bands requires a file path".to_string()),
+    }
+}
+
+/// Linearly interpolated percentile of salaries sorted in ascending order.
+fn percentile(sorted: &[u32], percent: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = percent / 100.0 * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    let fraction = rank - lower as f64;
+    sorted[lower] as f64 + (sorted[upper] as f64 - sorted[lower] as f64) * fraction
+}
+
+#[derive(Debug, PartialEq)]
+struct BandSpread {
+    count: usize,
+    min: u32,
+    p25: f64,
+    median: f64,
+    p75: f64,
+    max: u32,
+}
+
+fn band_spread(salaries: &mut [u32]) -> Option<BandSpread> {
+    salaries.sort_unstable();
+    Some(BandSpread {
+        count: salaries.len(),
+        min: *salaries.first()?,
+        p25: percentile(salaries, 25.0),
+        median: percentile(salaries, 50.0),
+        p75: percentile(salaries, 75.0),
+        max: *salaries.last()?,
+    })
+}
+
+#[derive(Debug, PartialEq)]
+enum RaiseScenario {
+    FlatPercent(f64),
+    PerPosition(HashMap<String, f64>),
+    FixedAmount(u32),
+}
+
+impl RaiseScenario {
+    fn new_salary(&self, employee: &Employee) -> u32 {
+        let percent = match self {
+            RaiseScenario::FixedAmount(amount) => return employee.salary.saturating_add(*amount),
+            RaiseScenario::FlatPercent(percent) => *percent,
+            // Positions missing from the config receive no raise.
+            RaiseScenario::PerPosition(percents) => percents
+                .get(&employee.position().to_lowercase())
+                .copied()
+                .unwrap_or(0.0),
+        };
+        (employee.salary as f64 * (1.0 + percent / 100.0)).round() as u32
+    }
+}
+
+fn read_raise_config(file_path: &str) -> Result<HashMap<String, f64>, Box<dyn std::error::Error>> {
+    let file = std::fs::File::open(file_path)?;
+    let reader = std::io::BufReader::new(file);
+    let mut percents = HashMap::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        if let Some((position, percent)) = line.split_once(':') {
+            percents.insert(position.trim().to_lowercase(), percent.trim().parse()?);
+        }
+    }
+    Ok(percents)
+}
+
+fn parse_raise_scenario(
+    args: &[String],
+) -> Result<Option<RaiseScenario>, Box<dyn std::error::Error>> {
+    let value = |i: usize| {
+        args.get(i + 1)
+            .ok_or_else(|| format!("{} requires a value", args[i]))
+    };
+    for (i, arg) in args.iter().enumerate() {
+        match arg.as_str() {
+            "--raise-percent" => return Ok(Some(RaiseScenario::FlatPercent(value(i)?.parse()?))),
+            "--raise-amount" => return Ok(Some(RaiseScenario::FixedAmount(value(i)?.parse()?))),
+            "--raise-config" => {
+                return Ok(Some(RaiseScenario::PerPosition(read_raise_config(value(
+                    i,
+                )?)?)));
+            }
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
+fn parse_input_path(args: &[String]) -> Result<&str, String> {
+    match args.iter().position(|arg| arg == "--input") {
+        None => Ok(DEFAULT_INPUT),
+        Some(i) => args
+            .get(i + 1)
+            .map(|path| path.as_str())
+            .ok_or_else(|| "--input requires a file path".to_string()),
+    }
 }
 
 fn read_employees_csv(file_path: &str) -> Result<Vec<Employee>, Box<dyn std::error::Error>> {
     let file = std::fs::File::open(file_path)?;
     let mut rdr = csv::ReaderBuilder::new()
         .has_headers(false)
+        .flexible(true)
         .from_reader(file);
 
     let mut employees = Vec::new();
@@ -46,7 +236,7 @@ fn sort_by_salary(employees: &mut [Employee]) {
     });
 }
 
-fn print_employees(employees: &[Employee]) {
+fn print_employees(employees: &[&Employee]) {
     // Find the maximum width needed for each column
     let max_last_width = employees
         .iter()
@@ -108,8 +298,211 @@ fn print_employees(employees: &[Employee]) {
     }
 }
 
+/// Groups employees by department, keeping each group in the order given. Returns `None` when
+/// no employee has a department, in which case the flat report should be used.
+fn group_by_department(employees: &[Employee]) -> Option<BTreeMap<&str, Vec<&Employee>>> {
+    if employees.iter().all(|e| e.department.is_none()) {
+        return None;
+    }
+
+    let mut departments: BTreeMap<&str, Vec<&Employee>> = BTreeMap::new();
+    for employee in employees {
+        let department = match employee.department.as_deref().map(str::trim) {
+            Some(department) if !department.is_empty() => department,
+            _ => UNASSIGNED_DEPARTMENT,
+        };
+        departments.entry(department).or_default().push(employee);
+    }
+    Some(departments)
+}
+
+fn payroll(employees: &[&Employee]) -> u64 {
+    employees.iter().map(|e| e.salary as u64).sum()
+}
+
+fn print_department_report(departments: &BTreeMap<&str, Vec<&Employee>>) {
+    let (mut headcount, mut total) = (0, 0);
+    for (department, employees) in departments {
+        println!("{}", department);
+        println!();
+        print_employees(employees);
+        println!(
+            "Subtotal: {} employee(s), payroll ${}",
+            employees.len(),
+            payroll(employees).to_formatted_string(&Locale::en)
+        );
+        println!();
+        headcount += employees.len();
+        total += payroll(employees);
+    }
+    println!(
+        "Total: {} employee(s), payroll ${}",
+        headcount,
+        total.to_formatted_string(&Locale::en)
+    );
+}
+
+fn format_change(change: i64) -> String {
+    let sign = if change < 0 { "-" } else { "+" };
+    format!("{}${}", sign, change.abs().to_formatted_string(&Locale::en))
+}
+
+/// Widths of the name and position columns, wide enough for their headers and every employee.
+fn column_widths(employees: &[Employee]) -> (usize, usize) {
+    let name_width = employees
+        .iter()
+        .map(|e| e.first_name.len() + e.last_name.len() + 1)
+        .max()
+        .unwrap_or(4)
+        .max(4)
+        + 1;
+    let position_width = employees
+        .iter()
+        .map(|e| e.position().len())
+        .max()
+        .unwrap_or(8)
+        .max(8)
+        + 1;
+    (name_width, position_width)
+}
+
+fn print_raise_report(employees: &[Employee], scenario: &RaiseScenario) {
+    let (name_width, position_width) = column_widths(employees);
+
+    println!(
+        "{:<name_width$}{:<position_width$}{:>12}{:>12}{:>10}",
+        "Name", "Position", "Old Salary", "New Salary", "Change"
+    );
+    println!(
+        "{:-<name_width$}{:-<position_width$}{:->12}{:->12}{:->10}",
+        "", "", "", "", ""
+    );
+
+    let (mut old_total, mut new_total) = (0i64, 0i64);
+    for employee in employees {
+        let new_salary = scenario.new_salary(employee);
+        old_total += employee.salary as i64;
+        new_total += new_salary as i64;
+        println!(
+            "{:<name_width$}{:<position_width$}{:>12}{:>12}{:>10}",
+            format!("{} {}", employee.last_name, employee.first_name),
+            employee.position(),
+            format!("${}", employee.salary.to_formatted_string(&Locale::en)),
+            format!("${}", new_salary.to_formatted_string(&Locale::en)),
+            format_change(new_salary as i64 - employee.salary as i64),
+        );
+    }
+
+    println!();
+    println!(
+        "Total payroll: ${} -> ${} ({})",
+        old_total.to_formatted_string(&Locale::en),
+        new_total.to_formatted_string(&Locale::en),
+        format_change(new_total - old_total)
+    );
+}
+
+fn format_dollars(amount: u32) -> String {
+    format!("${}", amount.to_formatted_string(&Locale::en))
+}
+
+fn print_pay_band_report(employees: &[Employee], bands: &HashMap<String, PayBand>) {
+    let (name_width, position_width) = column_widths(employees);
+
+    println!(
+        "{:<name_width$}{:<position_width$}{:>10}{:>20}  Status",
+        "Name", "Position", "Salary", "Band"
+    );
+    println!(
+        "{:-<name_width$}{:-<position_width$}{:->10}{:->20}  {:-<6}",
+        "", "", "", "", ""
+    );
+
+    let mut salaries_by_band: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+    for employee in employees {
+        let band = bands.get(&employee.position().to_lowercase());
+        let status = band.map_or(BandStatus::NoBand, |band| band.status(employee.salary));
+        println!(
+            "{:<name_width$}{:<position_width$}{:>10}{:>20}  {}",
+            format!("{} {}", employee.last_name, employee.first_name),
+            employee.position(),
+            format_dollars(employee.salary),
+            band.map_or("-".to_string(), |band| format!(
+                "{}-{}",
+                format_dollars(band.min),
+                format_dollars(band.max)
+            )),
+            match status {
+                BandStatus::Below(gap) => format!("Below by {}", format_dollars(gap)),
+                BandStatus::Within => "Within".to_string(),
+                BandStatus::Above(gap) => format!("Above by {}", format_dollars(gap)),
+                BandStatus::NoBand => "No band".to_string(),
+            }
+        );
+        if band.is_some() {
+            salaries_by_band
+                .entry(employee.position().to_string())
+                .or_default()
+                .push(employee.salary);
+        }
+    }
+
+    println!();
+    println!(
+        "{:<position_width$}{:>6}{:>10}{:>10}{:>10}{:>10}{:>10}",
+        "Position", "Count", "Min", "25th", "Median", "75th", "Max"
+    );
+    println!(
+        "{:-<position_width$}{:->6}{:->10}{:->10}{:->10}{:->10}{:->10}",
+        "", "", "", "", "", "", ""
+    );
+    for (position, salaries) in &mut salaries_by_band {
+        if let Some(spread) = band_spread(salaries) {
+            println!(
+                "{:<position_width$}{:>6}{:>10}{:>10}{:>10}{:>10}{:>10}",
+                position,
+                spread.count,
+                format_dollars(spread.min),
+                format_dollars(spread.p25.round() as u32),
+                format_dollars(spread.median.round() as u32),
+                format_dollars(spread.p75.round() as u32),
+                format_dollars(spread.max),
+            );
+        }
+    }
+}
+
 fn main() {
-    let file_path = "exercises/e42/inputs/employees.csv";
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let file_path = match parse_input_path(&args) {
+        Ok(file_path) => file_path,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+    let scenario = match parse_raise_scenario(&args) {
+        Ok(scenario) => scenario,
+        Err(e) => {
+            eprintln!("Error reading raise scenario: {}", e);
+            return;
+        }
+    };
+
+    let pay_bands = match parse_pay_bands_path(&args) {
+        Ok(Some(path)) => match read_pay_bands(path) {
+            Ok(bands) => Some(bands),
+            Err(e) => {
+                eprintln!("Error reading pay bands: {}", e);
+                return;
+            }
+        },
+        Ok(None) => None,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
 
     match read_employees_csv(file_path) {
         Ok(mut employees) => {
@@ -118,8 +511,170 @@ fn main() {
                 return;
             }
             sort_by_salary(&mut employees);
-            print_employees(&employees);
+            if let Some(bands) = pay_bands {
+                print_pay_band_report(&employees, &bands);
+                return;
+            }
+            match scenario {
+                Some(scenario) => print_raise_report(&employees, &scenario),
+                None => match group_by_department(&employees) {
+                    Some(departments) => print_department_report(&departments),
+                    None => print_employees(&employees.iter().collect::<Vec<&Employee>>()),
+                },
+            }
         }
         Err(e) => eprintln!("Error reading employees: {}", e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn employee(salary: u32, position: &str) -> Employee {
+        Employee {
+            first_name: "Alice".to_string(),
+            last_name: "Smith".to_string(),
+            salary,
+            position: Some(position.to_string()),
+            department: None,
+        }
+    }
+
+    #[test]
+    fn new_salary_applies_flat_percentage_and_fixed_amount() {
+        let alice = employee(72000, "Manager");
+        assert_eq!(RaiseScenario::FlatPercent(3.0).new_salary(&alice), 74160);
+        assert_eq!(RaiseScenario::FlatPercent(0.0).new_salary(&alice), 72000);
+        assert_eq!(RaiseScenario::FixedAmount(2500).new_salary(&alice), 74500);
+        assert_eq!(
+            RaiseScenario::FixedAmount(u32::MAX).new_salary(&alice),
+            u32::MAX
+        );
+    }
+
+    #[test]
+    fn new_salary_applies_per_position_percentages() {
+        let percents = HashMap::from([("developer".to_string(), 4.5)]);
+        let scenario = RaiseScenario::PerPosition(percents);
+        assert_eq!(scenario.new_salary(&employee(54000, "Developer")), 56430);
+        assert_eq!(scenario.new_salary(&employee(46000, "Support")), 46000);
+    }
+
+    #[test]
+    fn parse_raise_scenario_reads_flags() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        assert_eq!(
+            parse_raise_scenario(&args(&["--raise-percent", "2.5"])).unwrap(),
+            Some(RaiseScenario::FlatPercent(2.5))
+        );
+        assert_eq!(
+            parse_raise_scenario(&args(&["--raise-amount", "1000"])).unwrap(),
+            Some(RaiseScenario::FixedAmount(1000))
+        );
+        assert_eq!(parse_raise_scenario(&args(&[])).unwrap(), None);
+        assert!(parse_raise_scenario(&args(&["--raise-amount"])).is_err());
+        assert!(parse_raise_scenario(&args(&["--raise-percent", "lots"])).is_err());
+    }
+
+    #[test]
+    fn format_change_includes_sign() {
+        assert_eq!(format_change(2160), "+$2,160");
+        assert_eq!(format_change(-1500), "-$1,500");
+        assert_eq!(format_change(0), "+$0");
+    }
+
+    #[test]
+    fn read_raise_config_parses_position_percentages() {
+        let percents = read_raise_config("inputs/raises.txt").unwrap();
+        assert_eq!(percents.get("developer"), Some(&4.5));
+        assert_eq!(percents.get("director"), Some(&2.5));
+    }
+
+    #[test]
+    fn read_employees_csv_accepts_files_without_a_position() {
+        let path = std::env::temp_dir().join(format!("e42_employees_{}.csv", std::process::id()));
+        std::fs::write(&path, "Smith,Alice,72000\nJohnson,Bob,54000,Developer\n").unwrap();
+        let employees = read_employees_csv(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(employees[0].position, None);
+        assert_eq!(employees[0].position(), "");
+        assert_eq!(employees[1].position(), "Developer");
+    }
+
+    #[test]
+    fn read_employees_csv_accepts_optional_department() {
+        let employees = read_employees_csv("inputs/employees_by_department.csv").unwrap();
+        assert_eq!(employees[0].department.as_deref(), Some("Operations"));
+        assert_eq!(employees[4].department, None);
+
+        let employees = read_employees_csv("inputs/employees.csv").unwrap();
+        assert!(employees.iter().all(|e| e.department.is_none()));
+        assert!(group_by_department(&employees).is_none());
+    }
+
+    #[test]
+    fn group_by_department_subtotals_each_department() {
+        let mut employees = read_employees_csv("inputs/employees_by_department.csv").unwrap();
+        sort_by_salary(&mut employees);
+        let departments = group_by_department(&employees).unwrap();
+
+        let summary: Vec<(&str, usize, u64)> = departments
+            .iter()
+            .map(|(name, members)| (*name, members.len(), payroll(members)))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Customer Success", 1, 61000),
+                ("Engineering", 2, 143000),
+                ("Operations", 2, 185000),
+                ("Unassigned", 1, 46000),
+            ]
+        );
+        assert_eq!(departments["Engineering"][0].salary, 89000);
+    }
+
+    #[test]
+    fn read_pay_bands_parses_ranges() {
+        let bands = read_pay_bands("inputs/pay_bands.txt").unwrap();
+        assert_eq!(
+            bands.get("developer"),
+            Some(&PayBand {
+                min: 60000,
+                max: 85000
+            })
+        );
+        assert_eq!(bands.len(), 4);
+    }
+
+    #[test]
+    fn pay_band_status_reports_gap() {
+        let band = PayBand {
+            min: 60000,
+            max: 85000,
+        };
+        assert_eq!(band.status(54000), BandStatus::Below(6000));
+        assert_eq!(band.status(60000), BandStatus::Within);
+        assert_eq!(band.status(89000), BandStatus::Above(4000));
+    }
+
+    #[test]
+    fn band_spread_interpolates_percentiles() {
+        let mut salaries = vec![89000, 54000, 83000, 78000];
+        let spread = band_spread(&mut salaries).unwrap();
+        assert_eq!(
+            spread,
+            BandSpread {
+                count: 4,
+                min: 54000,
+                p25: 72000.0,
+                median: 80500.0,
+                p75: 84500.0,
+                max: 89000,
+            }
+        );
+        assert_eq!(band_spread(&mut []), None);
+    }
+}
@@ -10,20 +10,49 @@
 //! - **Product Details**: Displays formatted product information including price and quantity
 //! - **Inventory Expansion**: Supports adding new products when items aren't found
 //! - **Data Validation**: Ensures proper input formats for prices and quantities
+//! - **Checkout**: A `sell` command sells several products at once, validates stock, prints a
+//!   receipt using the e10 receipt logic, and saves the updated inventory atomically
+//! - **Valuation**: A `value` command reports the total inventory value (price × quantity) and
+//!   each product's share of it
+//! - **Stocktake**: A `stocktake` command prints a checklist sorted by location and name for
+//!   physical counts
+//! - **Transaction Log**: Every lookup, addition, quantity update, and sale is appended to a
+//!   JSONL log, and a `summary` command totals the day's units added, units sold, and revenue
+//!   per product
 //!
 //! The application loads a product inventory from JSON, enables users to search for
 //! specific items by name, shows detailed product information, and offers the option
 //! to add missing products with the system maintaining persistence across sessions.
+use chrono::{DateTime, Local, NaiveDate};
+use e10::{PurchaseItem, PurchaseReceipt};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Display;
-use std::io::{self, Write};
-use std::path::PathBuf;
+use std::fs::OpenOptions;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Serialize, Deserialize)]
+const TRANSACTION_LOG: &str = "exercises/e44/transactions.jsonl";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct Product {
     name: String,
     price: f64,
     quantity: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    location: Option<String>,
+}
+
+const UNASSIGNED_LOCATION: &str = "Unassigned";
+
+impl Product {
+    fn value(&self) -> f64 {
+        self.price * self.quantity as f64
+    }
+
+    fn location(&self) -> &str {
+        self.location.as_deref().unwrap_or(UNASSIGNED_LOCATION)
+    }
 }
 
 impl Display for Product {
@@ -36,7 +65,7 @@ impl Display for Product {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct ProductList {
     products: Vec<Product>,
 }
@@ -48,12 +77,310 @@ fn read_products_json(file_path: &PathBuf) -> Result<ProductList, std::io::Error
     Ok(products)
 }
 
-fn write_products_json(file_path: &PathBuf, products: &ProductList) -> Result<(), std::io::Error> {
-    let file = std::fs::File::create(file_path)?;
-    serde_json::to_writer(file, products)?;
+fn write_products_json(file_path: &Path, products: &ProductList) -> Result<(), std::io::Error> {
+    persist::write_atomic(file_path, |writer| {
+        Ok(serde_json::to_writer(writer, products)?)
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum Event {
+    Lookup {
+        product: String,
+        found: bool,
+    },
+    Add {
+        product: String,
+        price: f64,
+        quantity: u32,
+    },
+    Update {
+        product: String,
+        old_quantity: u32,
+        new_quantity: u32,
+    },
+    Sale {
+        product: String,
+        quantity: u32,
+        price: f64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Transaction {
+    timestamp: DateTime<Local>,
+    #[serde(flatten)]
+    event: Event,
+}
+
+fn append_transaction(log_file: &Path, transaction: &Transaction) -> Result<(), std::io::Error> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_file)?;
+    writeln!(file, "{}", serde_json::to_string(transaction)?)?;
     Ok(())
 }
 
+/// Appends `event` to the transaction log. A failed write is reported but doesn't interrupt
+/// the sale or lookup that caused it.
+fn record(event: Event) {
+    let transaction = Transaction {
+        timestamp: Local::now(),
+        event,
+    };
+    if let Err(e) = append_transaction(Path::new(TRANSACTION_LOG), &transaction) {
+        eprintln!("Failed to record transaction: {e}");
+    }
+}
+
+fn read_transactions(log_file: &Path) -> Result<Vec<Transaction>, std::io::Error> {
+    let file = match std::fs::File::open(log_file) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut transactions = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            transactions.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(transactions)
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct ProductSummary {
+    lookups: u32,
+    added: u32,
+    sold: u32,
+    revenue: f64,
+}
+
+/// Totals the events recorded on `date` per product. Quantity updates count towards units
+/// added when they increase stock.
+fn daily_summary(
+    transactions: &[Transaction],
+    date: NaiveDate,
+) -> BTreeMap<String, ProductSummary> {
+    let mut summary: BTreeMap<String, ProductSummary> = BTreeMap::new();
+    for transaction in transactions
+        .iter()
+        .filter(|t| t.timestamp.date_naive() == date)
+    {
+        match &transaction.event {
+            Event::Lookup { product, .. } => {
+                summary.entry(product.clone()).or_default().lookups += 1;
+            }
+            Event::Add {
+                product, quantity, ..
+            } => {
+                summary.entry(product.clone()).or_default().added += quantity;
+            }
+            Event::Update {
+                product,
+                old_quantity,
+                new_quantity,
+            } => {
+                summary.entry(product.clone()).or_default().added +=
+                    new_quantity.saturating_sub(*old_quantity);
+            }
+            Event::Sale {
+                product,
+                quantity,
+                price,
+            } => {
+                let entry = summary.entry(product.clone()).or_default();
+                entry.sold += quantity;
+                entry.revenue += price * *quantity as f64;
+            }
+        }
+    }
+    summary
+}
+
+fn print_summary(transactions: &[Transaction], date: NaiveDate) {
+    let summary = daily_summary(transactions, date);
+    if summary.is_empty() {
+        println!("No transactions recorded on {date}.");
+        return;
+    }
+    println!("Summary for {date}");
+    println!(
+        "{:<20} {:>8} {:>8} {:>8} {:>12}",
+        "Product", "Lookups", "Added", "Sold", "Revenue"
+    );
+    println!("{:-<20} {:->8} {:->8} {:->8} {:->12}", "", "", "", "", "");
+    for (name, line) in &summary {
+        println!(
+            "{:<20} {:>8} {:>8} {:>8} {:>12}",
+            name,
+            line.lookups,
+            line.added,
+            line.sold,
+            format!("${:.2}", line.revenue)
+        );
+    }
+    let revenue: f64 = summary.values().map(|line| line.revenue).sum();
+    let sold: u32 = summary.values().map(|line| line.sold).sum();
+    println!(
+        "{:<20} {:>8} {:>8} {:>8} {:>12}",
+        "Total",
+        "",
+        "",
+        sold,
+        format!("${:.2}", revenue)
+    );
+}
+
+fn parse_order_line(line: &str) -> Option<(String, u32)> {
+    let (name, quantity) = line.trim().rsplit_once(' ')?;
+    let quantity = quantity.parse().ok().filter(|&q| q > 0)?;
+    Some((name.trim().to_string(), quantity))
+}
+
+/// Sells every item in `order`, returning the receipt. The inventory is only modified when
+/// all products exist and have enough stock.
+fn sell(
+    product_list: &mut ProductList,
+    order: &[(String, u32)],
+) -> Result<PurchaseReceipt, String> {
+    let mut requested: HashMap<usize, u32> = HashMap::new();
+    for (name, quantity) in order {
+        let index = product_list
+            .products
+            .iter()
+            .position(|p| p.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("Product '{name}' not found."))?;
+        *requested.entry(index).or_insert(0) += quantity;
+    }
+
+    for (&index, &quantity) in &requested {
+        let product = &product_list.products[index];
+        if quantity > product.quantity {
+            return Err(format!(
+                "Not enough {} in stock: requested {}, available {}.",
+                product.name, quantity, product.quantity
+            ));
+        }
+    }
+
+    let mut items = Vec::new();
+    for (index, quantity) in requested {
+        let product = &mut product_list.products[index];
+        product.quantity -= quantity;
+        items.push(PurchaseItem::new(quantity, product.price));
+    }
+    Ok(PurchaseReceipt::new(items))
+}
+
+/// Sells `order` and saves the updated inventory to `file_path`. The in-memory inventory is only
+/// changed once the save succeeds, so it never gets ahead of the file.
+fn checkout(
+    product_list: &mut ProductList,
+    order: &[(String, u32)],
+    file_path: &Path,
+) -> Result<PurchaseReceipt, String> {
+    let mut updated = product_list.clone();
+    let receipt = sell(&mut updated, order)?;
+    write_products_json(file_path, &updated)
+        .map_err(|e| format!("Failed to save the inventory: {e}"))?;
+    *product_list = updated;
+    Ok(receipt)
+}
+
+#[derive(Debug, PartialEq)]
+struct ValuationLine<'a> {
+    name: &'a str,
+    value: f64,
+    percent: f64,
+}
+
+/// Returns each product's value and share of the total, most valuable first, along with the
+/// total inventory value.
+fn valuation(product_list: &ProductList) -> (Vec<ValuationLine<'_>>, f64) {
+    let total: f64 = product_list.products.iter().map(Product::value).sum();
+    let mut lines: Vec<ValuationLine> = product_list
+        .products
+        .iter()
+        .map(|p| ValuationLine {
+            name: &p.name,
+            value: p.value(),
+            percent: if total > 0.0 {
+                p.value() / total * 100.0
+            } else {
+                0.0
+            },
+        })
+        .collect();
+    lines.sort_by(|a, b| b.value.total_cmp(&a.value).then(a.name.cmp(b.name)));
+    (lines, total)
+}
+
+fn print_valuation(product_list: &ProductList) {
+    let (lines, total) = valuation(product_list);
+    println!("{:<20} {:>14} {:>8}", "Product", "Value", "Share");
+    println!("{:-<20} {:->14} {:->8}", "", "", "");
+    for line in lines {
+        println!(
+            "{:<20} {:>14} {:>7.1}%",
+            line.name,
+            format!("${:.2}", line.value),
+            line.percent
+        );
+    }
+    println!("{:<20} {:>14}", "Total", format!("${:.2}", total));
+}
+
+/// Returns the products ordered by location and then name, the order in which they are
+/// counted during a stocktake. Products without a location are counted last.
+fn stocktake_order(product_list: &ProductList) -> Vec<&Product> {
+    let mut products: Vec<&Product> = product_list.products.iter().collect();
+    products.sort_by(|a, b| {
+        a.location
+            .is_none()
+            .cmp(&b.location.is_none())
+            .then_with(|| {
+                a.location()
+                    .to_lowercase()
+                    .cmp(&b.location().to_lowercase())
+            })
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    products
+}
+
+fn print_stocktake(product_list: &ProductList) {
+    println!(
+        "    {:<12} {:<20} {:>8}  Counted",
+        "Location", "Product", "Expected"
+    );
+    for product in stocktake_order(product_list) {
+        println!(
+            "[ ] {:<12} {:<20} {:>8}  ________",
+            product.location(),
+            product.name,
+            product.quantity
+        );
+    }
+}
+
+fn prompt_for_order() -> Vec<(String, u32)> {
+    let mut order = Vec::new();
+    loop {
+        let line = prompt_for_str("Enter product name and quantity (e.g. 'Laptop 2'), or 'done': ");
+        if line.eq_ignore_ascii_case("done") {
+            return order;
+        }
+        match parse_order_line(&line) {
+            Some(item) => order.push(item),
+            None => println!("Invalid entry. Please enter a product name followed by a quantity."),
+        }
+    }
+}
+
 fn prompt_for_str(prompt: &str) -> String {
     print!("{}", prompt);
     io::stdout().flush().unwrap();
@@ -74,6 +401,29 @@ fn prompt_for_yes_no(prompt: &str) -> bool {
     }
 }
 
+const COMMANDS: [&str; 6] = ["exit", "sell", "update", "value", "stocktake", "summary"];
+
+/// Returns the command `input` names, if any. A product that shares its name with a command can
+/// still be looked up: the user is asked which one they meant.
+fn command_for(input: &str, product_list: &ProductList) -> Option<String> {
+    let command = input.to_lowercase();
+    if !COMMANDS.contains(&command.as_str()) {
+        return None;
+    }
+    let is_product = product_list
+        .products
+        .iter()
+        .any(|p| p.name.eq_ignore_ascii_case(input));
+    if is_product
+        && !prompt_for_yes_no(&format!(
+            "Run the '{command}' command instead of looking up the product? (yes/no): "
+        ))
+    {
+        return None;
+    }
+    Some(command)
+}
+
 fn prompt_for_product(name: &str) -> Product {
     let price: f64 = loop {
         let input = prompt_for_str("Enter product price: ");
@@ -91,10 +441,13 @@ fn prompt_for_product(name: &str) -> Product {
         }
     };
 
+    let location = prompt_for_str("Enter product location (leave blank if none): ");
+
     Product {
         name: name.to_string(),
         price,
         quantity,
+        location: (!location.is_empty()).then_some(location),
     }
 }
 
@@ -102,25 +455,118 @@ fn main() {
     let file_path = PathBuf::from("exercises/e44/inputs/products.json");
     match read_products_json(&file_path) {
         Ok(mut product_list) => loop {
-            let product_name = prompt_for_str("Enter product name (or 'exit' to quit): ");
-            if product_name.to_lowercase() == "exit" {
+            let product_name = prompt_for_str(
+                "Enter product name ('sell' to check out, 'update', 'value', 'stocktake', \
+                 'summary', 'exit' to quit): ",
+            );
+            let command = command_for(&product_name, &product_list);
+            if command.as_deref() == Some("exit") {
                 break;
             }
 
+            if command.as_deref() == Some("value") {
+                print_valuation(&product_list);
+                continue;
+            }
+
+            if command.as_deref() == Some("stocktake") {
+                print_stocktake(&product_list);
+                continue;
+            }
+
+            if command.as_deref() == Some("summary") {
+                match read_transactions(Path::new(TRANSACTION_LOG)) {
+                    Ok(transactions) => print_summary(&transactions, Local::now().date_naive()),
+                    Err(e) => println!("Failed to read the transaction log: {e}"),
+                }
+                continue;
+            }
+
+            if command.as_deref() == Some("update") {
+                let name = prompt_for_str("Enter product name: ");
+                let Some(product) = product_list
+                    .products
+                    .iter_mut()
+                    .find(|p| p.name.eq_ignore_ascii_case(&name))
+                else {
+                    println!("Product '{name}' not found.");
+                    continue;
+                };
+                let new_quantity: u32 = loop {
+                    let input = prompt_for_str("Enter new quantity on hand: ");
+                    match input.parse() {
+                        Ok(value) => break value,
+                        Err(_) => println!("Invalid quantity. Please enter a valid number."),
+                    }
+                };
+                record(Event::Update {
+                    product: product.name.clone(),
+                    old_quantity: product.quantity,
+                    new_quantity,
+                });
+                product.quantity = new_quantity;
+                if let Err(e) = write_products_json(&file_path, &product_list) {
+                    println!("Failed to save the inventory: {e}");
+                }
+                continue;
+            }
+
+            if command.as_deref() == Some("sell") {
+                let order = prompt_for_order();
+                if order.is_empty() {
+                    continue;
+                }
+                match checkout(&mut product_list, &order, &file_path) {
+                    Ok(receipt) => {
+                        for (name, quantity) in &order {
+                            println!("{quantity} x {name}");
+                            if let Some(product) = product_list
+                                .products
+                                .iter()
+                                .find(|p| p.name.eq_ignore_ascii_case(name))
+                            {
+                                record(Event::Sale {
+                                    product: product.name.clone(),
+                                    quantity: *quantity,
+                                    price: product.price,
+                                });
+                            }
+                        }
+                        println!("{receipt}");
+                    }
+                    Err(e) => println!("{e}"),
+                }
+                continue;
+            }
+
             if let Some(product) = product_list
                 .products
                 .iter_mut()
                 .find(|p| p.name.to_lowercase() == product_name.to_lowercase())
             {
                 println!("{product}");
+                record(Event::Lookup {
+                    product: product.name.clone(),
+                    found: true,
+                });
             } else {
                 println!("Product '{product_name}' not found.");
+                record(Event::Lookup {
+                    product: product_name.clone(),
+                    found: false,
+                });
                 let add_item = prompt_for_yes_no("Would you like to add this product? (yes/no): ");
                 if add_item {
                     let new_product = prompt_for_product(&product_name);
+                    record(Event::Add {
+                        product: new_product.name.clone(),
+                        price: new_product.price,
+                        quantity: new_product.quantity,
+                    });
                     product_list.products.push(new_product);
-                    write_products_json(&file_path, &product_list)
-                        .expect("Failed to write product");
+                    if let Err(e) = write_products_json(&file_path, &product_list) {
+                        println!("Failed to save the inventory: {e}");
+                    }
                 }
             }
         },
@@ -133,3 +579,252 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn product(name: &str, price: f64, quantity: u32, location: Option<&str>) -> Product {
+        Product {
+            name: name.to_string(),
+            price,
+            quantity,
+            location: location.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn valuation_computes_total_and_shares() {
+        let product_list = ProductList {
+            products: vec![
+                product("Cable", 5.0, 20, None),
+                product("Laptop", 900.0, 1, None),
+            ],
+        };
+        let (lines, total) = valuation(&product_list);
+        assert_eq!(total, 1000.0);
+        assert_eq!(
+            lines,
+            vec![
+                ValuationLine {
+                    name: "Laptop",
+                    value: 900.0,
+                    percent: 90.0
+                },
+                ValuationLine {
+                    name: "Cable",
+                    value: 100.0,
+                    percent: 10.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn stocktake_order_sorts_by_location_then_name() {
+        let product_list = ProductList {
+            products: vec![
+                product("Tablet", 1.0, 1, Some("Aisle 2")),
+                product("Mouse", 1.0, 1, None),
+                product("Laptop", 1.0, 1, Some("Aisle 2")),
+                product("Phone", 1.0, 1, Some("aisle 1")),
+            ],
+        };
+        let names: Vec<&str> = stocktake_order(&product_list)
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Phone", "Laptop", "Tablet", "Mouse"]);
+    }
+
+    fn transaction(timestamp: &str, event: Event) -> Transaction {
+        Transaction {
+            timestamp: DateTime::parse_from_rfc3339(timestamp)
+                .unwrap()
+                .with_timezone(&Local),
+            event,
+        }
+    }
+
+    #[test]
+    fn transaction_round_trips_as_tagged_json() {
+        let sale = transaction(
+            "2024-03-01T10:00:00+00:00",
+            Event::Sale {
+                product: "Laptop".to_string(),
+                quantity: 2,
+                price: 900.0,
+            },
+        );
+        let json = serde_json::to_string(&sale).unwrap();
+        assert!(json.contains(r#""event":"sale""#));
+        assert_eq!(serde_json::from_str::<Transaction>(&json).unwrap(), sale);
+    }
+
+    #[test]
+    fn daily_summary_totals_events_for_the_day() {
+        let first = transaction(
+            "2024-03-01T12:00:00+00:00",
+            Event::Add {
+                product: "Cable".to_string(),
+                price: 5.0,
+                quantity: 10,
+            },
+        );
+        let date = first.timestamp.date_naive();
+        let transactions = vec![
+            first,
+            transaction(
+                "2024-03-01T12:05:00+00:00",
+                Event::Lookup {
+                    product: "Cable".to_string(),
+                    found: true,
+                },
+            ),
+            transaction(
+                "2024-03-01T12:10:00+00:00",
+                Event::Sale {
+                    product: "Cable".to_string(),
+                    quantity: 3,
+                    price: 5.0,
+                },
+            ),
+            transaction(
+                "2024-03-01T12:15:00+00:00",
+                Event::Update {
+                    product: "Cable".to_string(),
+                    old_quantity: 7,
+                    new_quantity: 12,
+                },
+            ),
+            transaction(
+                "2024-03-01T12:20:00+00:00",
+                Event::Update {
+                    product: "Laptop".to_string(),
+                    old_quantity: 4,
+                    new_quantity: 1,
+                },
+            ),
+            transaction(
+                "2024-03-05T12:00:00+00:00",
+                Event::Sale {
+                    product: "Cable".to_string(),
+                    quantity: 100,
+                    price: 5.0,
+                },
+            ),
+        ];
+
+        let summary = daily_summary(&transactions, date);
+        assert_eq!(
+            summary["Cable"],
+            ProductSummary {
+                lookups: 1,
+                added: 15,
+                sold: 3,
+                revenue: 15.0
+            }
+        );
+        assert_eq!(summary["Laptop"], ProductSummary::default());
+        assert_eq!(summary.len(), 2);
+    }
+
+    #[test]
+    fn read_transactions_treats_missing_log_as_empty() {
+        let path = std::env::temp_dir().join("e44_missing_transactions.jsonl");
+        let _ = std::fs::remove_file(&path);
+        assert!(read_transactions(&path).unwrap().is_empty());
+
+        let lookup = transaction(
+            "2024-03-01T10:00:00+00:00",
+            Event::Lookup {
+                product: "Mouse".to_string(),
+                found: false,
+            },
+        );
+        append_transaction(&path, &lookup).unwrap();
+        append_transaction(&path, &lookup).unwrap();
+        assert_eq!(
+            read_transactions(&path).unwrap(),
+            vec![lookup.clone(), lookup]
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn parse_order_line_rejects_malformed_lines() {
+        assert_eq!(
+            parse_order_line("Wireless Mouse 3"),
+            Some(("Wireless Mouse".to_string(), 3))
+        );
+        assert_eq!(parse_order_line("Laptop"), None);
+        assert_eq!(parse_order_line("Laptop two"), None);
+        assert_eq!(parse_order_line("Laptop 0"), None);
+        assert_eq!(parse_order_line("Laptop -1"), None);
+    }
+
+    #[test]
+    fn sell_totals_multi_item_orders_and_decrements_stock() {
+        let mut products = ProductList {
+            products: vec![
+                product("Laptop", 500.0, 3, None),
+                product("Mouse", 20.0, 10, None),
+            ],
+        };
+        let order = [
+            ("laptop".to_string(), 1),
+            ("Mouse".to_string(), 2),
+            ("Laptop".to_string(), 1),
+        ];
+
+        let receipt = sell(&mut products, &order).unwrap();
+        assert_eq!(receipt.total_cost(), 1040.0);
+        assert_eq!(products.products[0].quantity, 1);
+        assert_eq!(products.products[1].quantity, 8);
+    }
+
+    #[test]
+    fn sell_leaves_stock_untouched_when_an_item_is_unavailable() {
+        let mut products = ProductList {
+            products: vec![
+                product("Laptop", 500.0, 1, None),
+                product("Mouse", 20.0, 10, None),
+            ],
+        };
+
+        let short = [("Mouse".to_string(), 1), ("Laptop".to_string(), 2)];
+        assert!(
+            sell(&mut products, &short)
+                .err()
+                .unwrap()
+                .contains("Not enough Laptop")
+        );
+        let missing = [("Mouse".to_string(), 1), ("Keyboard".to_string(), 1)];
+        assert!(
+            sell(&mut products, &missing)
+                .err()
+                .unwrap()
+                .contains("not found")
+        );
+        assert_eq!(products.products[0].quantity, 1);
+        assert_eq!(products.products[1].quantity, 10);
+    }
+
+    #[test]
+    fn checkout_keeps_inventory_when_the_save_fails() {
+        let mut products = ProductList {
+            products: vec![product("Mouse", 20.0, 10, None)],
+        };
+        let order = [("Mouse".to_string(), 2)];
+
+        let unwritable = std::env::temp_dir().join("e44_missing_dir/products.json");
+        assert!(checkout(&mut products, &order, &unwritable).is_err());
+        assert_eq!(products.products[0].quantity, 10);
+
+        let path = std::env::temp_dir().join(format!("e44_products_{}.json", std::process::id()));
+        checkout(&mut products, &order, &path).unwrap();
+        assert_eq!(products.products[0].quantity, 8);
+        assert_eq!(read_products_json(&path).unwrap().products[0].quantity, 8);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
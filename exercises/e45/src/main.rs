@@ -10,14 +10,20 @@
 //! - **Configuration File**: Reads replacement pairs from a dedicated configuration file
 //! - **Recursive Directory Traversal**: Handles nested directory structures
 //! - **Replacement Tracking**: Counts and reports the number of replacements for each word
+//! - **Interactive Mode**: `--interactive` shows each match in context and asks whether to
+//!   replace it (y/n/a/q), applying only the accepted replacements
+//! - **Traversal Filters**: Skips hidden files and paths excluded by `.gitignore` and does not
+//!   follow symlinks by default; `--hidden`, `--no-ignore`, and `--follow-links` opt in to each,
+//!   with symlink cycles reported and skipped
 //!
 //! The application reads a list of word replacements from a configuration file,
 //! traverses a specified directory structure, applies the word replacements to all
 //! text files, and provides a summary report of the replacements made.
 use anyhow::anyhow;
+use ignore::WalkBuilder;
 use std::collections::HashMap;
 use std::io::{BufRead, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 fn read_replacement_file(file_path: &PathBuf) -> Result<HashMap<String, String>, std::io::Error> {
     let file = std::fs::File::open(file_path)?;
@@ -71,25 +77,218 @@ fn replace_words(
     (result, replacement_counts)
 }
 
+#[derive(Debug, Clone, PartialEq)]
+struct WordMatch {
+    start: usize,
+    end: usize,
+    old_word: String,
+    new_word: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Decision {
+    Yes,
+    No,
+    All,
+    Quit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    ReplaceAll,
+    Confirm,
+    Stopped,
+}
+
+/// Finds every whole-word occurrence of the words in `replacements`, ordered by position.
+/// Overlapping matches are dropped in favor of the earliest one.
+fn find_matches(content: &str, replacements: &HashMap<String, String>) -> Vec<WordMatch> {
+    let mut matches = Vec::new();
+    for (old_word, new_word) in replacements {
+        let old_word_pattern = format!(r"\b{}\b", regex::escape(old_word));
+        let re = regex::Regex::new(&old_word_pattern).unwrap();
+        matches.extend(re.find_iter(content).map(|m| WordMatch {
+            start: m.start(),
+            end: m.end(),
+            old_word: old_word.clone(),
+            new_word: new_word.clone(),
+        }));
+    }
+    matches.sort_by_key(|m| (m.start, std::cmp::Reverse(m.end)));
+
+    let mut last_end = 0;
+    matches.retain(|m| {
+        let keep = m.start >= last_end;
+        if keep {
+            last_end = m.end;
+        }
+        keep
+    });
+    matches
+}
+
+/// Returns the line containing `word_match` with the match highlighted as `[old -> new]`.
+fn match_context(content: &str, word_match: &WordMatch) -> String {
+    let line_start = content[..word_match.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = content[word_match.end..]
+        .find('\n')
+        .map_or(content.len(), |i| word_match.end + i);
+    format!(
+        "{}[{} -> {}]{}",
+        &content[line_start..word_match.start],
+        word_match.old_word,
+        word_match.new_word,
+        &content[word_match.end..line_end]
+    )
+}
+
+/// Replaces only the matches accepted by `confirm`. The returned flag is `true` when the user
+/// asked to quit.
+fn replace_words_confirmed(
+    content: &str,
+    replacements: &HashMap<String, String>,
+    mut confirm: impl FnMut(&WordMatch) -> Decision,
+) -> (String, HashMap<String, u32>, bool) {
+    let mut result = String::with_capacity(content.len());
+    let mut replacement_counts: HashMap<String, u32> =
+        replacements.keys().map(|word| (word.clone(), 0)).collect();
+    let mut last_end = 0;
+    let mut accept_all = false;
+    let mut quit = false;
+
+    for word_match in find_matches(content, replacements) {
+        let accepted = accept_all
+            || match confirm(&word_match) {
+                Decision::Yes => true,
+                Decision::No => false,
+                Decision::All => {
+                    accept_all = true;
+                    true
+                }
+                Decision::Quit => {
+                    quit = true;
+                    break;
+                }
+            };
+
+        if accepted {
+            result.push_str(&content[last_end..word_match.start]);
+            result.push_str(&word_match.new_word);
+            last_end = word_match.end;
+            *replacement_counts.get_mut(&word_match.old_word).unwrap() += 1;
+        }
+    }
+    result.push_str(&content[last_end..]);
+
+    (result, replacement_counts, quit)
+}
+
+fn prompt_for_decision(path: &Path, context: &str) -> Decision {
+    loop {
+        println!("{}:\n  {}", path.display(), context);
+        print!("Replace this occurrence? [y,n,a,q] ");
+        std::io::stdout().flush().ok();
+
+        // Stop replacing once input ends rather than asking again forever.
+        let mut input = String::new();
+        if matches!(std::io::stdin().read_line(&mut input), Ok(0) | Err(_)) {
+            return Decision::Quit;
+        }
+        match input.trim().to_lowercase().as_str() {
+            "y" => return Decision::Yes,
+            "n" => return Decision::No,
+            "a" => return Decision::All,
+            "q" => return Decision::Quit,
+            _ => println!("y - replace, n - skip, a - replace all in this file, q - quit"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct WalkOptions {
+    include_hidden: bool,
+    respect_gitignore: bool,
+    follow_links: bool,
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        WalkOptions {
+            include_hidden: false,
+            respect_gitignore: true,
+            follow_links: false,
+        }
+    }
+}
+
+impl WalkOptions {
+    fn from_args(args: &[String]) -> Self {
+        let has_flag = |flag: &str| args.iter().any(|arg| arg == flag);
+        WalkOptions {
+            include_hidden: has_flag("--hidden"),
+            respect_gitignore: !has_flag("--no-ignore"),
+            follow_links: has_flag("--follow-links"),
+        }
+    }
+}
+
+/// Returns the files under `dir_path` in a stable order. Entries that cannot be read, such as
+/// symlinks that loop back to an ancestor, are reported and skipped.
+fn collect_files(dir_path: &Path, options: &WalkOptions) -> Vec<PathBuf> {
+    let walker = WalkBuilder::new(dir_path)
+        .hidden(!options.include_hidden)
+        .git_ignore(options.respect_gitignore)
+        .git_global(options.respect_gitignore)
+        .git_exclude(options.respect_gitignore)
+        .require_git(false)
+        .ignore(false)
+        .parents(options.respect_gitignore)
+        .follow_links(options.follow_links)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+
+    let mut files = Vec::new();
+    for entry in walker {
+        match entry {
+            Ok(entry) if entry.file_type().is_some_and(|t| t.is_file()) => {
+                files.push(entry.into_path())
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("Warning: skipping entry: {}", e),
+        }
+    }
+    files
+}
+
 fn replace_words_in_dir(
-    dir_path: &PathBuf,
+    dir_path: &Path,
     replacements: &HashMap<String, String>,
     replacement_cnts: &mut HashMap<String, u32>,
+    mode: &mut Mode,
+    walk_options: &WalkOptions,
 ) -> anyhow::Result<()> {
-    for entry in std::fs::read_dir(dir_path)? {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_file() {
-            let content = read_text_file(&path)?;
-            let (updated_content, local_replacements) = replace_words(&content, replacements);
+    for path in collect_files(dir_path, walk_options) {
+        if *mode == Mode::Stopped {
+            break;
+        }
 
-            write_text_file(&path, &updated_content)?;
-            for (word, count) in local_replacements {
-                *replacement_cnts.entry(word).or_insert(0) += count;
+        let content = read_text_file(&path)?;
+        let (updated_content, local_replacements) = if *mode == Mode::Confirm {
+            let (updated_content, local_replacements, quit) =
+                replace_words_confirmed(&content, replacements, |word_match| {
+                    prompt_for_decision(&path, &match_context(&content, word_match))
+                });
+            if quit {
+                *mode = Mode::Stopped;
             }
-        } else if path.is_dir() {
-            replace_words_in_dir(&path, replacements, replacement_cnts)?;
+            (updated_content, local_replacements)
+        } else {
+            replace_words(&content, replacements)
+        };
+
+        write_text_file(&path, &updated_content)?;
+        for (word, count) in local_replacements {
+            *replacement_cnts.entry(word).or_insert(0) += count;
         }
     }
     Ok(())
@@ -98,13 +297,26 @@ fn replace_words_in_dir(
 fn main() -> anyhow::Result<()> {
     let replacement_file = PathBuf::from("exercises/e45/inputs/replacements.txt");
     let input_dir = PathBuf::from("exercises/e45/inputs/test");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let walk_options = WalkOptions::from_args(&args);
+    let mut mode = if args.iter().any(|arg| arg == "--interactive") {
+        Mode::Confirm
+    } else {
+        Mode::ReplaceAll
+    };
 
     let replacements = read_replacement_file(&replacement_file)
         .map_err(|e| anyhow!("Error reading replacement file: {}", e))?;
     let mut replacement_counts = HashMap::new();
 
-    replace_words_in_dir(&input_dir, &replacements, &mut replacement_counts)
-        .map_err(|e| anyhow!("Error processing directory: {}", e))?;
+    replace_words_in_dir(
+        &input_dir,
+        &replacements,
+        &mut replacement_counts,
+        &mut mode,
+        &walk_options,
+    )
+    .map_err(|e| anyhow!("Error processing directory: {}", e))?;
     for (word, count) in &replacement_counts {
         println!("Replaced '{}' {} time(s).", word, count);
     }
@@ -196,4 +408,132 @@ mod tests {
         assert_eq!(counts.get("Hello"), Some(&1));
         assert_eq!(counts.get("world"), Some(&1));
     }
+
+    #[test]
+    fn test_find_matches_orders_by_position() {
+        let content = "foo utilize foo";
+        let mut replacements = HashMap::new();
+        replacements.insert("foo".to_string(), "bar".to_string());
+        replacements.insert("utilize".to_string(), "use".to_string());
+
+        let matches = find_matches(content, &replacements);
+        let starts: Vec<(usize, &str)> = matches
+            .iter()
+            .map(|m| (m.start, m.old_word.as_str()))
+            .collect();
+        assert_eq!(starts, vec![(0, "foo"), (4, "utilize"), (12, "foo")]);
+    }
+
+    #[test]
+    fn test_replace_words_confirmed_applies_only_accepted_matches() {
+        let content = "cat dog cat dog cat";
+        let mut replacements = HashMap::new();
+        replacements.insert("cat".to_string(), "lion".to_string());
+
+        let mut answers = vec![Decision::No, Decision::Yes, Decision::No].into_iter();
+        let (result, counts, quit) =
+            replace_words_confirmed(content, &replacements, |_| answers.next().unwrap());
+
+        assert_eq!(result, "cat dog lion dog cat");
+        assert_eq!(counts.get("cat"), Some(&1));
+        assert!(!quit);
+    }
+
+    #[test]
+    fn test_replace_words_confirmed_handles_all_and_quit() {
+        let content = "a cat, a cat, a cat";
+        let mut replacements = HashMap::new();
+        replacements.insert("cat".to_string(), "dog".to_string());
+
+        let mut prompts = 0;
+        let (result, counts, _) = replace_words_confirmed(content, &replacements, |_| {
+            prompts += 1;
+            Decision::All
+        });
+        assert_eq!(result, "a dog, a dog, a dog");
+        assert_eq!(counts.get("cat"), Some(&3));
+        assert_eq!(prompts, 1);
+
+        let mut answers = vec![Decision::Yes, Decision::Quit].into_iter();
+        let (result, counts, quit) =
+            replace_words_confirmed(content, &replacements, |_| answers.next().unwrap());
+        assert_eq!(result, "a dog, a cat, a cat");
+        assert_eq!(counts.get("cat"), Some(&1));
+        assert!(quit);
+    }
+
+    #[test]
+    fn test_match_context_highlights_match_on_its_line() {
+        let content = "first line\nthe cat sat\nlast line";
+        let mut replacements = HashMap::new();
+        replacements.insert("cat".to_string(), "dog".to_string());
+
+        let matches = find_matches(content, &replacements);
+        assert_eq!(match_context(content, &matches[0]), "the [cat -> dog] sat");
+    }
+
+    fn file_names(root: &Path, files: &[PathBuf]) -> Vec<String> {
+        files
+            .iter()
+            .map(|f| f.strip_prefix(root).unwrap().display().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_collect_files_skips_hidden_and_ignored_files_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join(".gitignore"), "ignored.txt\n").unwrap();
+        std::fs::write(root.join("a.txt"), "a").unwrap();
+        std::fs::write(root.join("ignored.txt"), "b").unwrap();
+        std::fs::write(root.join(".hidden.txt"), "c").unwrap();
+
+        let files = collect_files(root, &WalkOptions::default());
+        assert_eq!(file_names(root, &files), vec!["a.txt"]);
+
+        let options = WalkOptions {
+            include_hidden: true,
+            respect_gitignore: false,
+            follow_links: false,
+        };
+        let files = collect_files(root, &options);
+        assert_eq!(
+            file_names(root, &files),
+            vec![".gitignore", ".hidden.txt", "a.txt", "ignored.txt"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_collect_files_survives_symlink_cycles() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir(root.join("sub")).unwrap();
+        std::fs::write(root.join("sub/a.txt"), "a").unwrap();
+        std::os::unix::fs::symlink(root, root.join("sub/loop")).unwrap();
+
+        let options = WalkOptions {
+            follow_links: true,
+            ..WalkOptions::default()
+        };
+        let files = collect_files(root, &options);
+        assert_eq!(file_names(root, &files), vec!["sub/a.txt"]);
+        assert_eq!(
+            file_names(root, &collect_files(root, &WalkOptions::default())),
+            vec!["sub/a.txt"]
+        );
+    }
+
+    #[test]
+    fn test_walk_options_from_args() {
+        let args = vec!["--hidden".to_string(), "--follow-links".to_string()];
+        assert_eq!(
+            WalkOptions::from_args(&args),
+            WalkOptions {
+                include_hidden: true,
+                respect_gitignore: true,
+                follow_links: true,
+            }
+        );
+    }
 }
@@ -0,0 +1,17 @@
+//! # Histogram Library
+//!
+//! Text histogram rendering shared by the word frequency counter and other exercises that
+//! chart counts.
+use std::collections::HashMap;
+
+/// Prints one `*` bar per entry, largest counts first.
+pub fn plot_histogram(word_freq: &HashMap<String, u32>) {
+    let mut counts: Vec<(&String, &u32)> = word_freq.iter().collect();
+    let max_length = counts.iter().map(|kv| kv.0.len()).max().unwrap_or(0);
+
+    counts.sort_by(|a, b| b.1.cmp(a.1));
+    for kv in &counts {
+        let padding = " ".repeat(max_length - kv.0.len() + 1);
+        println!("{}{}: {}", kv.0, padding, "*".repeat(*kv.1 as usize));
+    }
+}
@@ -10,16 +10,29 @@
 //! - **Word Frequency Analysis**: Counts occurrences of each unique word in the text
 //! - **Visual Output**: Displays word frequencies as horizontal histogram bars
 //! - **Sorted Results**: Presents words in descending order by frequency
+//! - **Concordance View**: `--context <word>` prints each occurrence of a word with its
+//!   surrounding words (KWIC) and a table of the words that most often appear near it within a
+//!   configurable window (`--window <n>`, default 5)
+//! - **Stemming**: `--stem` counts words by their Porter stem so "run", "runs", and "running"
+//!   are counted together; `--verbose` lists the original surface forms under each stem
+//! - **Language Detection**: Detects the document's language, strips elided articles such as
+//!   French "l'" and "d'", and stems with the matching algorithm; `--lang <code>` overrides the
+//!   detected language
+//! - **Stop Words**: `--drop-stop-words` drops the detected language's stop words from the
+//!   histogram
 //!
 //! The application reads text from an input file, processes and normalizes the words,
 //! counts their frequencies, and then displays a formatted histogram that visually
 //! represents the relative frequency of each word in the document.
 use anyhow::anyhow;
+use e46::plot_histogram;
 use regex::Regex;
-use std::collections::HashMap;
+use rust_stemmers::{Algorithm, Stemmer};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::Read;
 use std::path::PathBuf;
+use whatlang::Lang;
 
 fn read_file_content(file_path: &PathBuf) -> anyhow::Result<String> {
     let mut file =
@@ -36,34 +49,330 @@ fn clean_suffix(word: &str) -> String {
     re.replace(word, "").to_string()
 }
 
-fn count_word_freq(content: &str) -> HashMap<String, u32> {
-    let words: Vec<String> = content
-        .split_whitespace()
-        .map(|s| clean_suffix(s.to_lowercase().as_str()))
-        .collect();
-    let mut word_freq = HashMap::new();
+/// A language the counter knows how to clean up: its stop words are looked up by ISO 639-1
+/// code and `elisions` are prefixes stripped from the front of words.
+#[derive(Debug, PartialEq)]
+struct Language {
+    name: &'static str,
+    lang: Lang,
+    code: &'static str,
+    stemmer: Algorithm,
+    elisions: &'static [&'static str],
+}
+
+const FRENCH_ELISIONS: &[&str] = &[
+    "l'", "d'", "j'", "m'", "n'", "s'", "t'", "c'", "qu'", "jusqu'", "lorsqu'", "puisqu'",
+];
+const ITALIAN_ELISIONS: &[&str] = &[
+    "l'", "un'", "d'", "dell'", "all'", "dall'", "nell'", "sull'",
+];
+
+const fn language(
+    name: &'static str,
+    lang: Lang,
+    code: &'static str,
+    stemmer: Algorithm,
+) -> Language {
+    Language {
+        name,
+        lang,
+        code,
+        stemmer,
+        elisions: &[],
+    }
+}
+
+const LANGUAGES: &[Language] = &[
+    language("English", Lang::Eng, "en", Algorithm::English),
+    Language {
+        elisions: FRENCH_ELISIONS,
+        ..language("French", Lang::Fra, "fr", Algorithm::French)
+    },
+    language("German", Lang::Deu, "de", Algorithm::German),
+    language("Spanish", Lang::Spa, "es", Algorithm::Spanish),
+    Language {
+        elisions: ITALIAN_ELISIONS,
+        ..language("Italian", Lang::Ita, "it", Algorithm::Italian)
+    },
+    language("Portuguese", Lang::Por, "pt", Algorithm::Portuguese),
+    language("Dutch", Lang::Nld, "nl", Algorithm::Dutch),
+    language("Danish", Lang::Dan, "da", Algorithm::Danish),
+    language("Norwegian", Lang::Nob, "no", Algorithm::Norwegian),
+    language("Swedish", Lang::Swe, "sv", Algorithm::Swedish),
+    language("Finnish", Lang::Fin, "fi", Algorithm::Finnish),
+    language("Hungarian", Lang::Hun, "hu", Algorithm::Hungarian),
+    language("Romanian", Lang::Ron, "ro", Algorithm::Romanian),
+    language("Turkish", Lang::Tur, "tr", Algorithm::Turkish),
+    language("Russian", Lang::Rus, "ru", Algorithm::Russian),
+    language("Greek", Lang::Ell, "el", Algorithm::Greek),
+    language("Arabic", Lang::Ara, "ar", Algorithm::Arabic),
+];
+
+const ENGLISH: &Language = &LANGUAGES[0];
+
+impl Language {
+    /// Looks a language up by ISO 639-1 code (`fr`), ISO 639-3 code (`fra`), or English name.
+    fn find(name: &str) -> Option<&'static Language> {
+        LANGUAGES.iter().find(|language| {
+            language.code.eq_ignore_ascii_case(name)
+                || language.lang.code().eq_ignore_ascii_case(name)
+                || language.name.eq_ignore_ascii_case(name)
+        })
+    }
+
+    fn stop_words(&self) -> HashSet<&'static str> {
+        stop_words::get(self.code).iter().copied().collect()
+    }
+}
 
-    for word in words {
+/// Detects the language of `content`, returning `None` when detection isn't reliable or the
+/// language isn't supported.
+fn detect_language(content: &str) -> Option<&'static Language> {
+    let info = whatlang::detect(content).filter(|info| info.is_reliable())?;
+    LANGUAGES
+        .iter()
+        .find(|language| language.lang == info.lang())
+}
+
+fn normalize_word(raw: &str, language: &Language) -> String {
+    let word = raw
+        .to_lowercase()
+        .replace('’', "'")
+        .trim_matches(|c: char| {
+            matches!(c, '"' | '«' | '»' | '„' | '“' | '”' | '¿' | '¡' | '(' | ')')
+        })
+        .to_string();
+    let word = language
+        .elisions
+        .iter()
+        .find_map(|elision| word.strip_prefix(elision))
+        .unwrap_or(&word);
+    clean_suffix(word)
+}
+
+fn count_word_freq(content: &str, language: &Language) -> HashMap<String, u32> {
+    let mut word_freq = HashMap::new();
+    for word in content
+        .split_whitespace()
+        .map(|raw| normalize_word(raw, language))
+    {
         *word_freq.entry(word).or_insert(0) += 1;
     }
     word_freq
 }
 
-fn plot_histogram(word_freq: &HashMap<String, u32>) {
-    let mut counts: Vec<(&String, &u32)> = word_freq.iter().collect();
-    let max_length = counts.iter().map(|kv| kv.0.len()).max().unwrap_or(0);
+fn remove_stop_words(word_freq: &mut HashMap<String, u32>, language: &Language) {
+    let stop_words = language.stop_words();
+    word_freq.retain(|word, _| !word.is_empty() && !stop_words.contains(word.as_str()));
+}
+
+/// Maps each stem to the surface forms that reduced to it and how often each form occurred.
+type StemGroups = HashMap<String, BTreeMap<String, u32>>;
 
-    counts.sort_by(|a, b| b.1.cmp(a.1));
-    for kv in &counts {
-        let padding = " ".repeat(max_length - kv.0.len() + 1);
-        println!("{}{}: {}", kv.0, padding, "*".repeat(*kv.1 as usize));
+fn group_by_stem(word_freq: &HashMap<String, u32>, stemmer: &Stemmer) -> StemGroups {
+    let mut groups = StemGroups::new();
+    for (word, count) in word_freq {
+        groups
+            .entry(stemmer.stem(word).into_owned())
+            .or_default()
+            .insert(word.clone(), *count);
     }
+    groups
+}
+
+fn stem_freq(groups: &StemGroups) -> HashMap<String, u32> {
+    groups
+        .iter()
+        .map(|(stem, forms)| (stem.clone(), forms.values().sum()))
+        .collect()
+}
+
+fn print_stem_groups(groups: &StemGroups) {
+    let mut stems: Vec<(&String, &BTreeMap<String, u32>)> = groups.iter().collect();
+    stems.sort_by_key(|(stem, _)| *stem);
+    for (stem, forms) in stems {
+        let forms: Vec<String> = forms
+            .iter()
+            .map(|(form, count)| format!("{} ({})", form, count))
+            .collect();
+        println!("{:<16} {}", stem, forms.join(", "));
+    }
+}
+
+const DEFAULT_WINDOW: usize = 5;
+const MAX_CO_OCCURRENCES: usize = 10;
+
+#[derive(Debug, PartialEq)]
+struct Token<'a> {
+    line: usize,
+    raw: &'a str,
+    word: String,
+}
+
+fn tokenize<'a>(content: &'a str, language: &Language) -> Vec<Token<'a>> {
+    content
+        .lines()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            line.split_whitespace().map(move |raw| Token {
+                line: i + 1,
+                raw,
+                word: normalize_word(raw, language),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+struct ConcordanceLine {
+    line: usize,
+    left: String,
+    keyword: String,
+    right: String,
+}
+
+/// Returns every occurrence of `word` with up to `window` words of context on either side.
+fn concordance(tokens: &[Token], word: &str, window: usize) -> Vec<ConcordanceLine> {
+    let word = word.to_lowercase();
+    let join = |range: &[Token]| range.iter().map(|t| t.raw).collect::<Vec<&str>>().join(" ");
+
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| token.word == word)
+        .map(|(i, token)| ConcordanceLine {
+            line: token.line,
+            left: join(&tokens[i.saturating_sub(window)..i]),
+            keyword: token.raw.to_string(),
+            right: join(&tokens[i + 1..(i + 1 + window).min(tokens.len())]),
+        })
+        .collect()
+}
+
+/// Counts the words appearing within `window` words of each occurrence of `word`, most
+/// frequent first.
+fn co_occurrences(tokens: &[Token], word: &str, window: usize) -> Vec<(String, u32)> {
+    let word = word.to_lowercase();
+    let mut counts: HashMap<String, u32> = HashMap::new();
+
+    for (i, _) in tokens.iter().enumerate().filter(|(_, t)| t.word == word) {
+        let start = i.saturating_sub(window);
+        let end = (i + 1 + window).min(tokens.len());
+        for (j, neighbor) in tokens.iter().enumerate().take(end).skip(start) {
+            if j != i && neighbor.word != word && !neighbor.word.is_empty() {
+                *counts.entry(neighbor.word.clone()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut counts: Vec<(String, u32)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+fn print_concordance(content: &str, query: &str, window: usize, language: &Language) {
+    let tokens = tokenize(content, language);
+    // Clean the query the same way as the text so "war." or "l'homme" still match.
+    let word = normalize_word(query, language);
+    let word = word.as_str();
+    let lines = concordance(&tokens, word, window);
+    if lines.is_empty() {
+        println!("'{}' does not appear in the text.", word);
+        return;
+    }
+
+    let left_width = lines.iter().map(|l| l.left.len()).max().unwrap_or(0);
+    for line in &lines {
+        println!(
+            "{:>4}: {:>left_width$} [{}] {}",
+            line.line, line.left, line.keyword, line.right
+        );
+    }
+
+    println!();
+    println!("Words appearing within {} words of '{}':", window, word);
+    for (neighbor, count) in co_occurrences(&tokens, word, window)
+        .iter()
+        .take(MAX_CO_OCCURRENCES)
+    {
+        println!("{:<20} {}", neighbor, count);
+    }
+}
+
+fn parse_context_args(args: &[String]) -> anyhow::Result<Option<(String, usize)>> {
+    let value = |flag: &str| -> anyhow::Result<Option<&String>> {
+        match args.iter().position(|arg| arg == flag) {
+            None => Ok(None),
+            Some(i) => args
+                .get(i + 1)
+                .map(Some)
+                .ok_or_else(|| anyhow!("{} requires a value", flag)),
+        }
+    };
+
+    let Some(word) = value("--context")? else {
+        return Ok(None);
+    };
+    let window = match value("--window")? {
+        Some(window) => window
+            .parse()
+            .map_err(|_| anyhow!("Invalid window size '{}'", window))?,
+        None => DEFAULT_WINDOW,
+    };
+    Ok(Some((word.clone(), window)))
+}
+
+fn parse_lang_arg(args: &[String]) -> anyhow::Result<Option<&'static Language>> {
+    let Some(i) = args.iter().position(|arg| arg == "--lang") else {
+        return Ok(None);
+    };
+    let name = args
+        .get(i + 1)
+        .ok_or_else(|| anyhow!("--lang requires a value"))?;
+    Language::find(name)
+        .map(Some)
+        .ok_or_else(|| anyhow!("Unsupported language '{}'", name))
 }
 
 fn main() -> anyhow::Result<()> {
     let file_path = PathBuf::from("exercises/e46/inputs/words.txt");
     let content = read_file_content(&file_path)?;
-    let word_freq = count_word_freq(&content);
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let context = parse_context_args(&args)?;
+    let language = match parse_lang_arg(&args)? {
+        Some(language) => language,
+        None => match detect_language(&content) {
+            Some(language) => {
+                println!("Detected language: {}", language.name);
+                language
+            }
+            None => {
+                println!("Could not reliably detect the language, assuming English.");
+                ENGLISH
+            }
+        },
+    };
+
+    if let Some((word, window)) = context {
+        print_concordance(&content, &word, window, language);
+        return Ok(());
+    }
+
+    let mut word_freq = count_word_freq(&content, language);
+    if args.iter().any(|arg| arg == "--drop-stop-words") {
+        remove_stop_words(&mut word_freq, language);
+    }
+
+    if args.iter().any(|arg| arg == "--stem") {
+        let groups = group_by_stem(&word_freq, &Stemmer::create(language.stemmer));
+        plot_histogram(&stem_freq(&groups));
+        if args.iter().any(|arg| arg == "--verbose") {
+            println!();
+            print_stem_groups(&groups);
+        }
+        return Ok(());
+    }
 
     plot_histogram(&word_freq);
 
@@ -137,7 +446,7 @@ mod tests {
     #[test]
     fn test_count_word_freq_with_single_occurrence() {
         let content = "hello world";
-        let word_freq = count_word_freq(content);
+        let word_freq = count_word_freq(content, ENGLISH);
 
         assert_eq!(word_freq.len(), 2);
         assert_eq!(word_freq.get("hello"), Some(&1));
@@ -147,7 +456,7 @@ mod tests {
     #[test]
     fn test_count_word_freq_with_multiple_occurrences() {
         let content = "hello hello world";
-        let word_freq = count_word_freq(content);
+        let word_freq = count_word_freq(content, ENGLISH);
 
         assert_eq!(word_freq.len(), 2);
         assert_eq!(word_freq.get("hello"), Some(&2));
@@ -157,7 +466,7 @@ mod tests {
     #[test]
     fn test_count_word_freq_with_punctuation() {
         let content = "hello, world! hello.";
-        let word_freq = count_word_freq(content);
+        let word_freq = count_word_freq(content, ENGLISH);
 
         assert_eq!(word_freq.len(), 2);
         assert_eq!(word_freq.get("hello"), Some(&2));
@@ -167,7 +476,7 @@ mod tests {
     #[test]
     fn test_count_word_freq_with_capitalization() {
         let content = "Hello World hello";
-        let word_freq = count_word_freq(content);
+        let word_freq = count_word_freq(content, ENGLISH);
 
         assert_eq!(word_freq.len(), 2);
         assert_eq!(word_freq.get("hello"), Some(&2));
@@ -177,7 +486,7 @@ mod tests {
     #[test]
     fn test_count_word_freq_with_possessive() {
         let content = "John's book and Mary's pen";
-        let word_freq = count_word_freq(content);
+        let word_freq = count_word_freq(content, ENGLISH);
 
         assert_eq!(word_freq.len(), 5);
         assert_eq!(word_freq.get("john"), Some(&1));
@@ -190,7 +499,7 @@ mod tests {
     #[test]
     fn test_count_word_freq_with_empty_string() {
         let content = "";
-        let word_freq = count_word_freq(content);
+        let word_freq = count_word_freq(content, ENGLISH);
 
         assert_eq!(word_freq.len(), 0);
     }
@@ -198,7 +507,7 @@ mod tests {
     #[test]
     fn test_count_word_freq_with_whitespace_only() {
         let content = "   \n\t   ";
-        let word_freq = count_word_freq(content);
+        let word_freq = count_word_freq(content, ENGLISH);
 
         assert_eq!(word_freq.len(), 0);
     }
@@ -206,7 +515,7 @@ mod tests {
     #[test]
     fn test_count_word_freq_with_mixed_content() {
         let content = "The quick brown fox jumps over the lazy dog. The fox is quick!";
-        let word_freq = count_word_freq(content);
+        let word_freq = count_word_freq(content, ENGLISH);
 
         assert_eq!(word_freq.len(), 9);
         assert_eq!(word_freq.get("the"), Some(&3));
@@ -219,4 +528,150 @@ mod tests {
         assert_eq!(word_freq.get("dog"), Some(&1));
         assert_eq!(word_freq.get("is"), Some(&1));
     }
+
+    #[test]
+    fn test_concordance_includes_window_of_context() {
+        let content = "the quick brown fox\njumps over the lazy dog.";
+        let tokens = tokenize(content, ENGLISH);
+        let lines = concordance(&tokens, "The", 2);
+
+        assert_eq!(
+            lines,
+            vec![
+                ConcordanceLine {
+                    line: 1,
+                    left: "".to_string(),
+                    keyword: "the".to_string(),
+                    right: "quick brown".to_string(),
+                },
+                ConcordanceLine {
+                    line: 2,
+                    left: "jumps over".to_string(),
+                    keyword: "the".to_string(),
+                    right: "lazy dog.".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_co_occurrences_counts_neighbors_within_window() {
+        let content = "snake badger mushroom badger snake snake";
+        let tokens = tokenize(content, ENGLISH);
+
+        assert_eq!(
+            co_occurrences(&tokens, "badger", 1),
+            vec![("mushroom".to_string(), 2), ("snake".to_string(), 2)]
+        );
+        assert!(co_occurrences(&tokens, "missing", 3).is_empty());
+    }
+
+    #[test]
+    fn test_concordance_matches_normalized_words() {
+        let french = Language::find("fr").unwrap();
+        let content = "Il vit l'homme. Puis l’homme partit.";
+        let tokens = tokenize(content, french);
+        let word = normalize_word("homme", french);
+        assert_eq!(concordance(&tokens, &word, 1).len(), 2);
+
+        let content = "They spoke of “war” and of war.";
+        let tokens = tokenize(content, ENGLISH);
+        let word = normalize_word("war.", ENGLISH);
+        assert_eq!(word, "war");
+        assert_eq!(concordance(&tokens, &word, 1).len(), 2);
+    }
+
+    #[test]
+    fn test_parse_context_args() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+
+        assert_eq!(parse_context_args(&args(&[])).unwrap(), None);
+        assert_eq!(
+            parse_context_args(&args(&["--context", "badger"])).unwrap(),
+            Some(("badger".to_string(), DEFAULT_WINDOW))
+        );
+        assert_eq!(
+            parse_context_args(&args(&["--context", "badger", "--window", "2"])).unwrap(),
+            Some(("badger".to_string(), 2))
+        );
+        assert!(parse_context_args(&args(&["--context"])).is_err());
+        assert!(parse_context_args(&args(&["--context", "a", "--window", "x"])).is_err());
+    }
+
+    #[test]
+    fn test_group_by_stem_merges_inflections() {
+        let word_freq = count_word_freq("run runs running Running walked walk", ENGLISH);
+        let groups = group_by_stem(&word_freq, &Stemmer::create(Algorithm::English));
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups["run"],
+            BTreeMap::from([
+                ("run".to_string(), 1),
+                ("running".to_string(), 2),
+                ("runs".to_string(), 1)
+            ])
+        );
+
+        let freq = stem_freq(&groups);
+        assert_eq!(freq.get("run"), Some(&4));
+        assert_eq!(freq.get("walk"), Some(&2));
+    }
+
+    #[test]
+    fn test_detect_language() {
+        let french = "Le petit prince est un conte poétique et philosophique sous l'apparence \
+                      d'un conte pour enfants. C'est l'histoire d'un aviateur qui rencontre un \
+                      petit garçon venu d'une autre planète.";
+        assert_eq!(detect_language(french), Some(Language::find("fr").unwrap()));
+
+        let english = "The quick brown fox jumps over the lazy dog while the farmer watches \
+                       from the porch and wonders where his chickens have gone.";
+        assert_eq!(detect_language(english), Some(ENGLISH));
+        assert_eq!(detect_language("42"), None);
+    }
+
+    #[test]
+    fn test_language_find_accepts_codes_and_names() {
+        assert_eq!(Language::find("de").unwrap().name, "German");
+        assert_eq!(Language::find("DEU").unwrap().name, "German");
+        assert_eq!(Language::find("german").unwrap().code, "de");
+        assert_eq!(Language::find("xx"), None);
+
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        assert_eq!(parse_lang_arg(&args(&[])).unwrap(), None);
+        assert_eq!(
+            parse_lang_arg(&args(&["--lang", "es"])).unwrap(),
+            Language::find("Spanish")
+        );
+        assert!(parse_lang_arg(&args(&["--lang"])).is_err());
+        assert!(parse_lang_arg(&args(&["--lang", "klingon"])).is_err());
+    }
+
+    #[test]
+    fn test_french_counts_strip_elisions_and_stop_words() {
+        let french = Language::find("fr").unwrap();
+        let mut word_freq = count_word_freq(
+            "L’histoire d'un « prince » et l'histoire du prince.",
+            french,
+        );
+        assert_eq!(word_freq.get("histoire"), Some(&2));
+        assert_eq!(word_freq.get("prince"), Some(&2));
+
+        remove_stop_words(&mut word_freq, french);
+        assert_eq!(
+            word_freq,
+            HashMap::from([("histoire".to_string(), 2), ("prince".to_string(), 2)])
+        );
+    }
+
+    #[test]
+    fn test_english_stop_words_are_removed() {
+        let mut word_freq = count_word_freq("The badger and the snake.", ENGLISH);
+        remove_stop_words(&mut word_freq, ENGLISH);
+        assert_eq!(
+            word_freq,
+            HashMap::from([("badger".to_string(), 1), ("snake".to_string(), 1)])
+        );
+    }
 }
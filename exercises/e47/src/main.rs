@@ -9,9 +9,28 @@
 //! - **Sorted Display**: Presents astronauts sorted by last name
 //! - **Formatted Output**: Shows data in a clean, tabular format with proper alignment
 //! - **Spacecraft Information**: Includes details about which spacecraft each astronaut is on
-use serde::Deserialize;
+//! - **Crew History**: Appends every roster snapshot to a local history file
+//! - **Crew Timeline**: The `timeline` subcommand reconstructs arrivals and departures from
+//!   consecutive snapshots and prints each astronaut's observed time aboard
+//! - **Serve Mode**: `--serve [addr]` runs a small HTTP server exposing the cached roster as
+//!   JSON (`/astros.json`) and an SVG badge with the current count (`/badge.svg`) for dashboards
+//! - **Astronaut Metadata**: Joins the roster with a user-maintained JSON file of agencies,
+//!   nationalities, and roles (`--metadata <file>`, defaulting to `inputs/astronauts.json`
+//!   when present), adding those columns and listing names that don't match
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Deserialize, Clone)]
+const HISTORY_FILE: &str = "exercises/e47/history.jsonl";
+const METADATA_FILE: &str = "exercises/e47/inputs/astronauts.json";
+const DEFAULT_SERVE_ADDR: &str = "127.0.0.1:8047";
+// How long a fetched roster is served before the API is queried again.
+const CACHE_MAX_AGE_MINUTES: i64 = 5;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 struct Astronaut {
     name: String,
     craft: String,
@@ -31,7 +50,333 @@ fn get_astronauts() -> anyhow::Result<SpaceInfo> {
     Ok(response)
 }
 
-fn print_astronauts(space_info: &SpaceInfo) {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Snapshot {
+    timestamp: DateTime<Utc>,
+    people: Vec<Astronaut>,
+}
+
+#[derive(Debug, PartialEq)]
+enum CrewEvent {
+    Arrival(DateTime<Utc>, Astronaut),
+    Departure(DateTime<Utc>, Astronaut),
+}
+
+fn append_snapshot(history_file: &Path, snapshot: &Snapshot) -> anyhow::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(history_file)?;
+    writeln!(file, "{}", serde_json::to_string(snapshot)?)?;
+    Ok(())
+}
+
+fn read_snapshots(history_file: &Path) -> anyhow::Result<Vec<Snapshot>> {
+    let reader = BufReader::new(std::fs::File::open(history_file)?);
+    let mut snapshots = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            snapshots.push(serde_json::from_str(&line)?);
+        }
+    }
+    snapshots.sort_by_key(|s: &Snapshot| s.timestamp);
+    Ok(snapshots)
+}
+
+/// Compares consecutive snapshots to find who arrived and who departed between them. Everyone
+/// in the first snapshot is treated as arriving when that snapshot was taken. Astronauts are
+/// matched by name, so moving to another craft doesn't end their stay.
+fn crew_events(snapshots: &[Snapshot]) -> Vec<CrewEvent> {
+    let mut events = Vec::new();
+    let mut previous: &[Astronaut] = &[];
+    for snapshot in snapshots {
+        let aboard = |people: &[Astronaut], name: &str| people.iter().any(|a| a.name == name);
+        for astronaut in previous
+            .iter()
+            .filter(|a| !aboard(&snapshot.people, &a.name))
+        {
+            events.push(CrewEvent::Departure(snapshot.timestamp, astronaut.clone()));
+        }
+        for astronaut in snapshot
+            .people
+            .iter()
+            .filter(|a| !aboard(previous, &a.name))
+        {
+            events.push(CrewEvent::Arrival(snapshot.timestamp, astronaut.clone()));
+        }
+        previous = &snapshot.people;
+    }
+    events
+}
+
+/// Pairs each arrival with the matching departure. Astronauts without one are measured up to
+/// `until` and flagged as still aboard. Longest stays come first.
+fn time_aboard(events: &[CrewEvent], until: DateTime<Utc>) -> Vec<(String, TimeDelta, bool)> {
+    let mut arrivals: HashMap<&str, DateTime<Utc>> = HashMap::new();
+    let mut durations = Vec::new();
+    for event in events {
+        match event {
+            CrewEvent::Arrival(at, astronaut) => {
+                arrivals.insert(&astronaut.name, *at);
+            }
+            CrewEvent::Departure(at, astronaut) => {
+                if let Some(arrived) = arrivals.remove(astronaut.name.as_str()) {
+                    durations.push((astronaut.name.clone(), *at - arrived, false));
+                }
+            }
+        }
+    }
+    for (name, arrived) in arrivals {
+        durations.push((name.to_string(), until - arrived, true));
+    }
+    durations.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    durations
+}
+
+fn print_timeline(snapshots: &[Snapshot]) {
+    let Some(last) = snapshots.last() else {
+        println!("No crew history recorded yet.");
+        return;
+    };
+
+    let events = crew_events(snapshots);
+    for event in &events {
+        match event {
+            CrewEvent::Arrival(at, astronaut) => println!(
+                "{}  + {} arrived on {}",
+                at.format("%Y-%m-%d %H:%M"),
+                astronaut.name,
+                astronaut.craft
+            ),
+            CrewEvent::Departure(at, astronaut) => println!(
+                "{}  - {} left {}",
+                at.format("%Y-%m-%d %H:%M"),
+                astronaut.name,
+                astronaut.craft
+            ),
+        }
+    }
+
+    println!();
+    println!("Observed time aboard:");
+    for (name, duration, still_aboard) in time_aboard(&events, last.timestamp) {
+        println!(
+            "{:<25} {:>4} days {:>2} hours{}",
+            name,
+            duration.num_days(),
+            duration.num_hours() % 24,
+            if still_aboard { " (still aboard)" } else { "" }
+        );
+    }
+}
+
+/// Holds the most recent roster, refreshing it from the API once it is older than `max_age` and
+/// recording each fresh roster in `history_file`. A failed refresh keeps serving the previous
+/// roster and is not retried until another `max_age` has passed.
+struct CrewCache {
+    snapshot: Option<Snapshot>,
+    history_file: PathBuf,
+    max_age: TimeDelta,
+    failed_at: Option<DateTime<Utc>>,
+}
+
+impl CrewCache {
+    fn current(
+        &mut self,
+        now: DateTime<Utc>,
+        fetch: impl FnOnce() -> anyhow::Result<Vec<Astronaut>>,
+    ) -> Option<&Snapshot> {
+        let stale = self
+            .snapshot
+            .as_ref()
+            .is_none_or(|s| now - s.timestamp >= self.max_age);
+        let backing_off = self
+            .failed_at
+            .is_some_and(|failed_at| now - failed_at < self.max_age);
+        if stale && !backing_off {
+            match fetch() {
+                Ok(people) => {
+                    let snapshot = Snapshot {
+                        timestamp: now,
+                        people,
+                    };
+                    if let Err(e) = append_snapshot(&self.history_file, &snapshot) {
+                        eprintln!("Unable to record crew history: {}", e);
+                    }
+                    self.snapshot = Some(snapshot);
+                    self.failed_at = None;
+                }
+                Err(e) => {
+                    eprintln!("Unable to refresh astronaut data: {}", e);
+                    self.failed_at = Some(now);
+                }
+            }
+        }
+        self.snapshot.as_ref()
+    }
+}
+
+/// Details about an astronaut that the API doesn't provide, maintained by the user.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct AstronautMetadata {
+    name: String,
+    agency: Option<String>,
+    nationality: Option<String>,
+    role: Option<String>,
+}
+
+fn load_metadata(file_path: &Path) -> anyhow::Result<Vec<AstronautMetadata>> {
+    let content = std::fs::read_to_string(file_path)
+        .map_err(|e| anyhow::anyhow!("Unable to read {}: {}", file_path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Invalid metadata file {}: {}", file_path.display(), e))
+}
+
+/// Names are compared ignoring case and extra whitespace.
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+struct MetadataJoin<'a> {
+    matched: HashMap<String, &'a AstronautMetadata>,
+    /// Astronauts in space without a metadata entry.
+    missing: Vec<&'a str>,
+    /// Metadata entries that don't match anyone in space.
+    unused: Vec<&'a str>,
+}
+
+fn join_metadata<'a>(
+    people: &'a [Astronaut],
+    metadata: &'a [AstronautMetadata],
+) -> MetadataJoin<'a> {
+    let by_name: HashMap<String, &AstronautMetadata> = metadata
+        .iter()
+        .map(|entry| (normalize_name(&entry.name), entry))
+        .collect();
+    let mut join = MetadataJoin {
+        matched: HashMap::new(),
+        missing: Vec::new(),
+        unused: Vec::new(),
+    };
+    for astronaut in people {
+        let name = normalize_name(&astronaut.name);
+        match by_name.get(&name) {
+            Some(entry) => {
+                join.matched.insert(name, *entry);
+            }
+            None => join.missing.push(&astronaut.name),
+        }
+    }
+    join.unused = metadata
+        .iter()
+        .filter(|entry| !join.matched.contains_key(&normalize_name(&entry.name)))
+        .map(|entry| entry.name.as_str())
+        .collect();
+    join
+}
+
+#[derive(Debug, Serialize)]
+struct CrewReport<'a> {
+    number: usize,
+    updated: DateTime<Utc>,
+    people: &'a [Astronaut],
+}
+
+fn crew_json(snapshot: &Snapshot) -> anyhow::Result<String> {
+    Ok(serde_json::to_string(&CrewReport {
+        number: snapshot.people.len(),
+        updated: snapshot.timestamp,
+        people: &snapshot.people,
+    })?)
+}
+
+/// Renders a flat two-part badge reading "people in space | <count>".
+fn badge_svg(count: usize) -> String {
+    const LABEL: &str = "people in space";
+    // Approximate width of a character in 11px Verdana.
+    let text_width = |text: &str| text.chars().count() * 7 + 10;
+    let value = count.to_string();
+    let (label_width, value_width) = (text_width(LABEL), text_width(&value));
+    let width = label_width + value_width;
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{LABEL}: {value}">
+  <rect width="{label_width}" height="20" fill="#555"/>
+  <rect x="{label_width}" width="{value_width}" height="20" fill="#007ec6"/>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,sans-serif" font-size="11">
+    <text x="{label_x}" y="14">{LABEL}</text>
+    <text x="{value_x}" y="14">{value}</text>
+  </g>
+</svg>
+"##,
+        label_x = label_width / 2,
+        value_x = label_width + value_width / 2,
+    )
+}
+
+fn respond(request: tiny_http::Request, status: u16, content_type: &str, body: String) {
+    let header = tiny_http::Header::from_bytes("Content-Type", content_type)
+        .expect("content type header is valid");
+    let response = tiny_http::Response::from_string(body)
+        .with_status_code(status)
+        .with_header(header);
+    if let Err(e) = request.respond(response) {
+        eprintln!("Unable to send response: {}", e);
+    }
+}
+
+fn serve(addr: &str, history_file: &Path) -> anyhow::Result<()> {
+    let server = tiny_http::Server::http(addr).map_err(|e| anyhow::anyhow!(e))?;
+    let mut cache = CrewCache {
+        snapshot: if history_file.exists() {
+            read_snapshots(history_file)?.pop()
+        } else {
+            None
+        },
+        history_file: history_file.to_path_buf(),
+        max_age: TimeDelta::minutes(CACHE_MAX_AGE_MINUTES),
+        failed_at: None,
+    };
+    println!("Serving /astros.json and /badge.svg on http://{}", addr);
+
+    for request in server.incoming_requests() {
+        let path = request.url().split('?').next().unwrap_or("").to_string();
+        if path != "/astros.json" && path != "/badge.svg" {
+            respond(request, 404, "text/plain", "Not found\n".to_string());
+            continue;
+        }
+
+        let Some(snapshot) = cache.current(Utc::now(), || Ok(get_astronauts()?.people)) else {
+            respond(
+                request,
+                503,
+                "text/plain",
+                "Astronaut data unavailable\n".to_string(),
+            );
+            continue;
+        };
+        if path == "/badge.svg" {
+            respond(
+                request,
+                200,
+                "image/svg+xml",
+                badge_svg(snapshot.people.len()),
+            );
+        } else {
+            match crew_json(snapshot) {
+                Ok(body) => respond(request, 200, "application/json", body),
+                Err(e) => respond(request, 500, "text/plain", format!("{}\n", e)),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_astronauts(space_info: &SpaceInfo, metadata: Option<&[AstronautMetadata]>) {
     let mut sorted_people = space_info.people.clone();
     sorted_people.sort_by(|a, b| {
         let a_last = a
@@ -49,49 +394,269 @@ fn print_astronauts(space_info: &SpaceInfo) {
         a_last.cmp(&b_last)
     });
 
-    let name_width = space_info
-        .people
+    let join = metadata.map(|metadata| join_metadata(&space_info.people, metadata));
+    let mut headers = vec!["Name", "Craft"];
+    if join.is_some() {
+        headers.extend(["Agency", "Nationality", "Role"]);
+    }
+    let rows: Vec<Vec<String>> = sorted_people
         .iter()
-        .map(|a| a.name.len())
-        .max()
-        .unwrap_or(0)
-        + 1;
-    let craft_width = space_info
-        .people
+        .map(|astronaut| {
+            let mut row = vec![astronaut.name.clone(), astronaut.craft.clone()];
+            if let Some(join) = &join {
+                let entry = join.matched.get(&normalize_name(&astronaut.name));
+                let field = |value: Option<&Option<String>>| {
+                    value
+                        .and_then(|v| v.clone())
+                        .unwrap_or_else(|| "-".to_string())
+                };
+                row.push(field(entry.map(|e| &e.agency)));
+                row.push(field(entry.map(|e| &e.nationality)));
+                row.push(field(entry.map(|e| &e.role)));
+            }
+            row
+        })
+        .collect();
+
+    let widths: Vec<usize> = headers
         .iter()
-        .map(|a| a.craft.len())
-        .max()
-        .unwrap_or(0)
-        + 1;
-    println!(
-        "{:<width$} | {:<craft_width$}",
-        "Name",
-        "Craft",
-        width = name_width,
-        craft_width = craft_width
-    );
-    println!(
-        "{:-<width$} | {:-<craft_width$}",
-        "",
-        "",
-        width = name_width,
-        craft_width = craft_width
-    );
-    for astronaut in &sorted_people {
-        println!(
-            "{:<width$} | {:<craft_width$}",
-            astronaut.name,
-            astronaut.craft,
-            width = name_width,
-            craft_width = craft_width
-        );
+        .enumerate()
+        .map(|(i, header)| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .chain([header.len()])
+                .max()
+                .unwrap_or(0)
+                + 1
+        })
+        .collect();
+    let format_row = |cells: &[String]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join(" | ")
+    };
+    let headers: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+    println!("{}", format_row(&headers));
+    let dashes: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    println!("{}", dashes.join(" | "));
+    for row in &rows {
+        println!("{}", format_row(row));
+    }
+
+    if let Some(join) = join {
+        if !join.missing.is_empty() {
+            println!();
+            println!("No metadata for: {}", join.missing.join(", "));
+        }
+        if !join.unused.is_empty() {
+            println!();
+            println!("Metadata entries not in space: {}", join.unused.join(", "));
+        }
+    }
+}
+
+/// Loads the metadata named by `--metadata`, or the default file when it exists.
+fn metadata_from_args(args: &[String]) -> anyhow::Result<Option<Vec<AstronautMetadata>>> {
+    match args.iter().position(|arg| arg == "--metadata") {
+        Some(i) => {
+            let path = args
+                .get(i + 1)
+                .ok_or_else(|| anyhow::anyhow!("--metadata requires a file"))?;
+            load_metadata(Path::new(path)).map(Some)
+        }
+        None if Path::new(METADATA_FILE).exists() => {
+            load_metadata(Path::new(METADATA_FILE)).map(Some)
+        }
+        None => Ok(None),
     }
 }
 
 fn main() -> anyhow::Result<()> {
+    let history_file = Path::new(HISTORY_FILE);
+    if std::env::args().nth(1).as_deref() == Some("timeline") {
+        let snapshots = if history_file.exists() {
+            read_snapshots(history_file)?
+        } else {
+            Vec::new()
+        };
+        print_timeline(&snapshots);
+        return Ok(());
+    }
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(i) = args.iter().position(|arg| arg == "--serve") {
+        let addr = args.get(i + 1).map_or(DEFAULT_SERVE_ADDR, |a| a.as_str());
+        return serve(addr, history_file);
+    }
+
+    let metadata = metadata_from_args(&args)?;
     let space_info = get_astronauts()?;
 
-    print_astronauts(&space_info);
+    print_astronauts(&space_info, metadata.as_deref());
+    let snapshot = Snapshot {
+        timestamp: Utc::now(),
+        people: space_info.people.clone(),
+    };
+    if let Err(e) = append_snapshot(history_file, &snapshot) {
+        eprintln!("Unable to record crew history: {}", e);
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn astronaut(name: &str) -> Astronaut {
+        Astronaut {
+            name: name.to_string(),
+            craft: "ISS".to_string(),
+        }
+    }
+
+    fn snapshot(hours: i64, people: Vec<Astronaut>) -> Snapshot {
+        Snapshot {
+            timestamp: DateTime::UNIX_EPOCH + TimeDelta::hours(hours),
+            people,
+        }
+    }
+
+    #[test]
+    fn crew_events_reports_arrivals_and_departures() {
+        let snapshots = [
+            snapshot(0, vec![astronaut("Sunita Williams")]),
+            snapshot(24, vec![astronaut("Butch Wilmore")]),
+        ];
+        let at = |hours| DateTime::UNIX_EPOCH + TimeDelta::hours(hours);
+        assert_eq!(
+            crew_events(&snapshots),
+            vec![
+                CrewEvent::Arrival(at(0), astronaut("Sunita Williams")),
+                CrewEvent::Departure(at(24), astronaut("Sunita Williams")),
+                CrewEvent::Arrival(at(24), astronaut("Butch Wilmore")),
+            ]
+        );
+    }
+
+    #[test]
+    fn time_aboard_measures_completed_and_ongoing_stays() {
+        let snapshots = [
+            snapshot(0, vec![astronaut("Sunita Williams")]),
+            snapshot(
+                24,
+                vec![astronaut("Sunita Williams"), astronaut("Butch Wilmore")],
+            ),
+            snapshot(72, vec![astronaut("Butch Wilmore")]),
+        ];
+        let last = snapshots.last().unwrap().timestamp;
+        assert_eq!(
+            time_aboard(&crew_events(&snapshots), last),
+            vec![
+                ("Sunita Williams".to_string(), TimeDelta::hours(72), false),
+                ("Butch Wilmore".to_string(), TimeDelta::hours(48), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn changing_craft_does_not_end_a_stay() {
+        let moved = Astronaut {
+            name: "Sunita Williams".to_string(),
+            craft: "Tiangong".to_string(),
+        };
+        let snapshots = [
+            snapshot(0, vec![astronaut("Sunita Williams")]),
+            snapshot(24, vec![moved]),
+        ];
+        let events = crew_events(&snapshots);
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            time_aboard(&events, snapshots[1].timestamp),
+            vec![("Sunita Williams".to_string(), TimeDelta::hours(24), true)]
+        );
+    }
+
+    #[test]
+    fn badge_svg_shows_count() {
+        let svg = badge_svg(7);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(">7</text>"));
+        assert!(svg.contains(r#"aria-label="people in space: 7""#));
+    }
+
+    #[test]
+    fn crew_cache_serves_fresh_snapshot_without_fetching() {
+        let now = Utc::now();
+        let mut cache = CrewCache {
+            snapshot: Some(Snapshot {
+                timestamp: now - TimeDelta::minutes(1),
+                people: vec![astronaut("Sunita Williams")],
+            }),
+            history_file: std::env::temp_dir()
+                .join(format!("e47_cache_history_{}.jsonl", std::process::id())),
+            max_age: TimeDelta::minutes(5),
+            failed_at: None,
+        };
+
+        let snapshot = cache
+            .current(now, || panic!("fresh data must not be refetched"))
+            .unwrap();
+        assert_eq!(snapshot.people.len(), 1);
+
+        let later = now + TimeDelta::minutes(10);
+        let snapshot = cache
+            .current(later, || Err(anyhow::anyhow!("offline")))
+            .unwrap();
+        assert_eq!(snapshot.people, vec![astronaut("Sunita Williams")]);
+
+        let json = crew_json(snapshot).unwrap();
+        assert!(json.starts_with(r#"{"number":1,"#));
+
+        // The failure is remembered until another max-age window has passed.
+        cache.current(later + TimeDelta::minutes(1), || {
+            panic!("a failed refresh must not be retried immediately")
+        });
+        let retry = later + TimeDelta::minutes(5);
+        let snapshot = cache
+            .current(retry, || Ok(vec![astronaut("Butch Wilmore")]))
+            .unwrap();
+        assert_eq!(snapshot.timestamp, retry);
+        assert_eq!(snapshot.people, vec![astronaut("Butch Wilmore")]);
+        let history = read_snapshots(&cache.history_file).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].people, vec![astronaut("Butch Wilmore")]);
+        std::fs::remove_file(&cache.history_file).unwrap();
+    }
+
+    #[test]
+    fn join_metadata_matches_names_loosely_and_reports_unmatched() {
+        let people = vec![astronaut("Sunita Williams"), astronaut("Butch Wilmore")];
+        let metadata: Vec<AstronautMetadata> = serde_json::from_str(
+            r#"[
+                {"name": "sunita  WILLIAMS", "agency": "NASA", "role": "Pilot"},
+                {"name": "Oleg Kononenko", "agency": "Roscosmos"}
+            ]"#,
+        )
+        .unwrap();
+
+        let join = join_metadata(&people, &metadata);
+        let entry = join.matched[&normalize_name("Sunita Williams")];
+        assert_eq!(entry.agency.as_deref(), Some("NASA"));
+        assert_eq!(entry.nationality, None);
+        assert_eq!(join.matched.len(), 1);
+        assert_eq!(join.missing, vec!["Butch Wilmore"]);
+        assert_eq!(join.unused, vec!["Oleg Kononenko"]);
+    }
+
+    #[test]
+    fn bundled_metadata_file_parses() {
+        let metadata = load_metadata(Path::new("inputs/astronauts.json")).unwrap();
+        assert!(!metadata.is_empty());
+        assert!(metadata.iter().all(|entry| entry.agency.is_some()));
+        assert!(load_metadata(Path::new("inputs/missing.json")).is_err());
+    }
+}
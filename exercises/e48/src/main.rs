@@ -8,12 +8,21 @@
 //! - **Location-based Weather**: Fetches weather data for user-specified locations
 //! - **Temperature Display**: Shows temperature in both Fahrenheit and Celsius
 //! - **Wind Direction**: Provides detailed wind direction using compass points
-//! - **Weather Recommendations**: Suggests whether to bring an umbrella or wear a coat
+//! - **Weather Recommendations**: Produces a ranked list of clothing recommendations from a
+//!   rules engine whose rules (conditions over temperature, rain, snow, wind, and UV) are
+//!   loaded from a TOML file; the UV index comes from Open-Meteo
 //! - **Geocoding**: Converts city names to coordinates for accurate weather data
+//! - **Notifications**: `--notify` keeps running and checks the forecast every `--interval`
+//!   minutes, sending a desktop notification when rain is expected within `--hours` hours or
+//!   the temperature will drop below `--min-temp` or rise above `--max-temp`
 use anyhow::anyhow;
+use notify_rust::Notification;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::HashSet;
 use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 static OPENWEATHERMAP_API_KEY: &str = "680daa2576713c28bf8c20fd8fe7798b";
 
@@ -259,6 +268,43 @@ fn get_weather(location: &Coord) -> anyhow::Result<CurrentWeather> {
     Ok(response.json::<CurrentWeather>()?)
 }
 
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Forecast {
+    pub list: Vec<ForecastEntry>,
+}
+
+/// One three-hour step of the 5 day forecast.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForecastEntry {
+    pub dt: i64,
+    pub main: ForecastMain,
+    pub weather: Vec<Weather>,
+    /// Probability of precipitation, from 0 to 1.
+    #[serde(default)]
+    pub pop: f64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForecastMain {
+    pub temp: f64,
+}
+
+fn get_forecast(location: &Coord) -> anyhow::Result<Forecast> {
+    let url = format!(
+        "https://api.openweathermap.org/data/2.5/forecast?lat={}&lon={}&units=imperial&appid={}",
+        location.lat, location.lon, OPENWEATHERMAP_API_KEY
+    );
+
+    let response = reqwest::blocking::get(&url)
+        .map_err(|e| anyhow!("Failed to fetch forecast data: {}", e))?;
+    if !response.status().is_success() {
+        let error_response = response.json::<InvalidRequest>()?;
+        return Err(anyhow!(error_response.message));
+    }
+
+    Ok(response.json::<Forecast>()?)
+}
+
 fn get_coord(location: &Location) -> anyhow::Result<Option<Coord>> {
     let url = format!(
         "http://api.openweathermap.org/geo/1.0/direct?q={},{},USA&limit=5&appid={}",
@@ -330,20 +376,311 @@ fn display_wind_direction(weather: &CurrentWeather) {
     println!("Wind direction: {}", direction);
 }
 
-fn recommend_umbrella(weather: &CurrentWeather) -> bool {
-    let has_weather = !weather.weather.is_empty();
-    let has_rain = ["rain", "drizzle", "thunderstorm"].iter().any(|w| {
+/// Weather observations that clothing rules are evaluated against.
+#[derive(Debug, Clone, PartialEq)]
+struct Conditions {
+    temp: f64,
+    rain: bool,
+    snow: bool,
+    wind_speed: f64,
+    uv_index: Option<f64>,
+}
+
+impl Conditions {
+    fn precipitation(&self) -> bool {
+        self.rain || self.snow
+    }
+}
+
+const RAIN_KINDS: [&str; 3] = ["rain", "drizzle", "thunderstorm"];
+const SNOW_KINDS: [&str; 1] = ["snow"];
+
+fn has_weather(weather: &[Weather], kinds: &[&str]) -> bool {
+    kinds.iter().any(|w| {
         weather
-            .weather
             .iter()
             .any(|weather| weather.main.to_lowercase() == *w)
-    });
+    })
+}
+
+fn is_precipitation(weather: &[Weather]) -> bool {
+    has_weather(weather, &RAIN_KINDS) || has_weather(weather, &SNOW_KINDS)
+}
+
+impl From<&CurrentWeather> for Conditions {
+    fn from(weather: &CurrentWeather) -> Self {
+        Conditions {
+            temp: weather.main.temp,
+            rain: has_weather(&weather.weather, &RAIN_KINDS),
+            snow: has_weather(&weather.weather, &SNOW_KINDS),
+            wind_speed: weather.wind.speed,
+            // The current weather endpoint does not report the UV index; it is fetched
+            // separately with `get_uv_index`.
+            uv_index: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UvResponse {
+    current: UvCurrent,
+}
 
-    has_weather && has_rain
+#[derive(Debug, Deserialize)]
+struct UvCurrent {
+    uv_index: f64,
 }
 
-fn recommend_coat(weather: &CurrentWeather) -> bool {
-    weather.main.temp < 60.0
+/// OpenWeatherMap only reports the UV index through its paid One Call API, so it is read from
+/// Open-Meteo, which needs no API key.
+fn get_uv_index(location: &Coord) -> anyhow::Result<f64> {
+    let url = format!(
+        "https://api.open-meteo.com/v1/forecast?latitude={}&longitude={}&current=uv_index",
+        location.lat, location.lon
+    );
+
+    let response =
+        reqwest::blocking::get(&url).map_err(|e| anyhow!("Failed to fetch UV index: {}", e))?;
+    if !response.status().is_success() {
+        return Err(anyhow!("Failed to fetch UV index: {}", response.status()));
+    }
+
+    Ok(response.json::<UvResponse>()?.current.uv_index)
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+struct Rule {
+    recommendation: String,
+    #[serde(default)]
+    priority: u32,
+    min_temp: Option<f64>,
+    max_temp: Option<f64>,
+    precipitation: Option<bool>,
+    rain: Option<bool>,
+    snow: Option<bool>,
+    min_wind: Option<f64>,
+    max_wind: Option<f64>,
+    min_uv: Option<f64>,
+    max_uv: Option<f64>,
+}
+
+impl Rule {
+    fn matches(&self, conditions: &Conditions) -> bool {
+        let at_least = |min: Option<f64>, value: f64| min.is_none_or(|min| value >= min);
+        let below = |max: Option<f64>, value: f64| max.is_none_or(|max| value < max);
+        let uv_ok = match (self.min_uv, self.max_uv) {
+            (None, None) => true,
+            _ => conditions
+                .uv_index
+                .is_some_and(|uv| at_least(self.min_uv, uv) && below(self.max_uv, uv)),
+        };
+
+        at_least(self.min_temp, conditions.temp)
+            && below(self.max_temp, conditions.temp)
+            && self
+                .precipitation
+                .is_none_or(|p| p == conditions.precipitation())
+            && self.rain.is_none_or(|rain| rain == conditions.rain)
+            && self.snow.is_none_or(|snow| snow == conditions.snow)
+            && at_least(self.min_wind, conditions.wind_speed)
+            && below(self.max_wind, conditions.wind_speed)
+            && uv_ok
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleSet {
+    #[serde(rename = "rule", default)]
+    rules: Vec<Rule>,
+}
+
+fn load_rules(file_path: &Path) -> anyhow::Result<Vec<Rule>> {
+    let content = std::fs::read_to_string(file_path)
+        .map_err(|e| anyhow!("Unable to read rules file {:?}: {}", file_path, e))?;
+    let rule_set: RuleSet =
+        toml::from_str(&content).map_err(|e| anyhow!("Invalid rules file: {}", e))?;
+    Ok(rule_set.rules)
+}
+
+/// Returns the recommendations of all matching rules, highest priority first.
+fn recommend(rules: &[Rule], conditions: &Conditions) -> Vec<String> {
+    let mut matching: Vec<&Rule> = rules.iter().filter(|r| r.matches(conditions)).collect();
+    matching.sort_by_key(|r| std::cmp::Reverse(r.priority));
+    matching
+        .into_iter()
+        .map(|r| r.recommendation.clone())
+        .collect()
+}
+
+const DEFAULT_INTERVAL_MINUTES: u64 = 30;
+const DEFAULT_LOOKAHEAD_HOURS: i64 = 3;
+/// Forecast steps at least this likely to see precipitation count as rain.
+const RAIN_PROBABILITY: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+struct NotifyConfig {
+    interval_minutes: u64,
+    lookahead_hours: i64,
+    min_temp: Option<f64>,
+    max_temp: Option<f64>,
+}
+
+fn parse_notify_args(args: &[String]) -> anyhow::Result<Option<NotifyConfig>> {
+    if !args.iter().any(|arg| arg == "--notify") {
+        return Ok(None);
+    }
+    let value = |flag: &str| -> anyhow::Result<Option<&String>> {
+        match args.iter().position(|arg| arg == flag) {
+            None => Ok(None),
+            Some(i) => args
+                .get(i + 1)
+                .map(Some)
+                .ok_or_else(|| anyhow!("{} requires a value", flag)),
+        }
+    };
+    fn parse<T: std::str::FromStr>(
+        flag: &str,
+        value: Option<&String>,
+    ) -> anyhow::Result<Option<T>> {
+        value
+            .map(|v| {
+                v.parse()
+                    .map_err(|_| anyhow!("Invalid value '{}' for {}", v, flag))
+            })
+            .transpose()
+    }
+
+    let interval_minutes =
+        parse("--interval", value("--interval")?)?.unwrap_or(DEFAULT_INTERVAL_MINUTES);
+    if interval_minutes == 0 {
+        return Err(anyhow!("--interval must be at least 1 minute"));
+    }
+    Ok(Some(NotifyConfig {
+        interval_minutes,
+        lookahead_hours: parse("--hours", value("--hours")?)?.unwrap_or(DEFAULT_LOOKAHEAD_HOURS),
+        min_temp: parse("--min-temp", value("--min-temp")?)?,
+        max_temp: parse("--max-temp", value("--max-temp")?)?,
+    }))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Alert {
+    Rain { dt: i64, description: String },
+    Cold { dt: i64, temp: f64 },
+    Hot { dt: i64, temp: f64 },
+}
+
+impl Alert {
+    /// Identifies the alert so the same forecast step isn't announced on every check.
+    fn key(&self) -> (&'static str, i64) {
+        match self {
+            Alert::Rain { dt, .. } => ("rain", *dt),
+            Alert::Cold { dt, .. } => ("cold", *dt),
+            Alert::Hot { dt, .. } => ("hot", *dt),
+        }
+    }
+
+    fn summary(&self) -> &'static str {
+        match self {
+            Alert::Rain { .. } => "Rain expected",
+            Alert::Cold { .. } => "Temperature dropping",
+            Alert::Hot { .. } => "Temperature rising",
+        }
+    }
+
+    fn body(&self, now: i64) -> String {
+        let when = |dt: i64| match (dt - now).max(0) / 3600 {
+            0 => "within the hour".to_string(),
+            1 => "in about 1 hour".to_string(),
+            hours => format!("in about {} hours", hours),
+        };
+        match self {
+            Alert::Rain { dt, description } => format!("{} {}", description, when(*dt)),
+            Alert::Cold { dt, temp } | Alert::Hot { dt, temp } => {
+                format!("{:.0}°F {}", temp, when(*dt))
+            }
+        }
+    }
+}
+
+/// Returns the first rain, cold, and hot forecast steps between `now` and the lookahead.
+fn forecast_alerts(forecast: &Forecast, now: i64, config: &NotifyConfig) -> Vec<Alert> {
+    let upcoming: Vec<&ForecastEntry> = forecast
+        .list
+        .iter()
+        .filter(|entry| {
+            entry.dt + 3 * 3600 > now && entry.dt <= now + config.lookahead_hours * 3600
+        })
+        .collect();
+
+    let mut alerts = Vec::new();
+    if let Some(entry) = upcoming
+        .iter()
+        .find(|entry| is_precipitation(&entry.weather) || entry.pop >= RAIN_PROBABILITY)
+    {
+        let description = entry
+            .weather
+            .first()
+            .map_or("Rain".to_string(), |weather| weather.description.clone());
+        alerts.push(Alert::Rain {
+            dt: entry.dt,
+            description,
+        });
+    }
+    if let Some(min_temp) = config.min_temp
+        && let Some(entry) = upcoming.iter().find(|entry| entry.main.temp < min_temp)
+    {
+        alerts.push(Alert::Cold {
+            dt: entry.dt,
+            temp: entry.main.temp,
+        });
+    }
+    if let Some(max_temp) = config.max_temp
+        && let Some(entry) = upcoming.iter().find(|entry| entry.main.temp > max_temp)
+    {
+        alerts.push(Alert::Hot {
+            dt: entry.dt,
+            temp: entry.main.temp,
+        });
+    }
+    alerts
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+fn run_notifier(coord: &Coord, config: &NotifyConfig) -> ! {
+    println!(
+        "Checking the forecast every {} minutes. Press Ctrl+C to stop.",
+        config.interval_minutes
+    );
+    let mut sent: HashSet<(&'static str, i64)> = HashSet::new();
+    loop {
+        match get_forecast(coord) {
+            Ok(forecast) => {
+                let now = unix_now();
+                for alert in forecast_alerts(&forecast, now, config) {
+                    if !sent.insert(alert.key()) {
+                        continue;
+                    }
+                    println!("{}: {}", alert.summary(), alert.body(now));
+                    if let Err(e) = Notification::new()
+                        .appname("e48")
+                        .summary(alert.summary())
+                        .body(&alert.body(now))
+                        .show()
+                    {
+                        eprintln!("Failed to send notification: {}", e);
+                    }
+                }
+            }
+            Err(e) => eprintln!("Error: {}", e),
+        }
+        std::thread::sleep(Duration::from_secs(config.interval_minutes * 60));
+    }
 }
 
 fn prompt_for_location() -> anyhow::Result<Location> {
@@ -364,6 +701,9 @@ fn prompt_for_location() -> anyhow::Result<Location> {
 }
 
 fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let notify = parse_notify_args(&args)?;
+    let rules = load_rules(Path::new("exercises/e48/inputs/rules.toml"))?;
     let location = prompt_for_location()?;
     let coord = get_coord(&location)?.ok_or_else(|| {
         anyhow!(
@@ -372,16 +712,260 @@ fn main() -> anyhow::Result<()> {
             location.state
         )
     })?;
+    if let Some(config) = notify {
+        run_notifier(&coord, &config);
+    }
     let weather = get_weather(&coord)?;
 
     display_temp(&weather);
     display_wind_direction(&weather);
-    if recommend_umbrella(&weather) {
-        println!("You might need an umbrella today.");
+    let mut conditions = Conditions::from(&weather);
+    match get_uv_index(&coord) {
+        Ok(uv_index) => {
+            println!("UV index: {:.1}", uv_index);
+            conditions.uv_index = Some(uv_index);
+        }
+        Err(e) => eprintln!("{}; skipping UV rules.", e),
     }
-    if recommend_coat(&weather) {
-        println!("You might need a coat today.");
+    let recommendations = recommend(&rules, &conditions);
+    if !recommendations.is_empty() {
+        println!("Recommended for today:");
+        for (i, recommendation) in recommendations.iter().enumerate() {
+            println!("{}. {}", i + 1, recommendation);
+        }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conditions(temp: f64, rain: bool, wind_speed: f64, uv_index: Option<f64>) -> Conditions {
+        Conditions {
+            temp,
+            rain,
+            snow: false,
+            wind_speed,
+            uv_index,
+        }
+    }
+
+    fn default_rules() -> Vec<Rule> {
+        load_rules(Path::new("inputs/rules.toml")).unwrap()
+    }
+
+    #[test]
+    fn recommend_ranks_matching_rules_by_priority() {
+        let rules = default_rules();
+        assert_eq!(
+            recommend(&rules, &conditions(35.0, true, 5.0, None)),
+            vec!["umbrella", "gloves", "coat"]
+        );
+    }
+
+    #[test]
+    fn recommend_handles_hot_sunny_weather() {
+        let rules = default_rules();
+        assert_eq!(
+            recommend(&rules, &conditions(88.0, false, 3.0, Some(9.0))),
+            vec!["sunscreen", "shorts"]
+        );
+    }
+
+    #[test]
+    fn recommend_handles_windy_and_freezing_weather() {
+        let rules = default_rules();
+        assert_eq!(
+            recommend(&rules, &conditions(55.0, false, 20.0, None)),
+            vec!["coat", "windbreaker"]
+        );
+        assert_eq!(
+            recommend(&rules, &conditions(10.0, false, 0.0, None)),
+            vec!["heavy coat", "gloves"]
+        );
+    }
+
+    #[test]
+    fn rule_with_uv_condition_requires_known_uv_index() {
+        let rule = Rule {
+            recommendation: "sunscreen".to_string(),
+            min_uv: Some(6.0),
+            ..Default::default()
+        };
+        assert!(!rule.matches(&conditions(75.0, false, 0.0, None)));
+        assert!(!rule.matches(&conditions(75.0, false, 0.0, Some(3.0))));
+        assert!(rule.matches(&conditions(75.0, false, 0.0, Some(6.0))));
+    }
+
+    #[test]
+    fn conditions_from_weather_detects_precipitation() {
+        let mut weather = CurrentWeather::default();
+        weather.main.temp = 50.0;
+        weather.wind.speed = 12.0;
+        weather.weather.push(Weather {
+            main: "Drizzle".to_string(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            Conditions::from(&weather),
+            conditions(50.0, true, 12.0, None)
+        );
+        assert!(!Conditions::from(&CurrentWeather::default()).precipitation());
+
+        weather.weather[0].main = "Snow".to_string();
+        let snowy = Conditions::from(&weather);
+        assert!(snowy.snow && !snowy.rain && snowy.precipitation());
+    }
+
+    #[test]
+    fn recommend_suggests_boots_not_umbrella_for_snow() {
+        let snowy = Conditions {
+            snow: true,
+            ..conditions(28.0, false, 5.0, None)
+        };
+        assert_eq!(
+            recommend(&default_rules(), &snowy),
+            vec!["snow boots", "heavy coat", "gloves"]
+        );
+    }
+
+    #[test]
+    fn uv_response_parses_current_index() {
+        let response: UvResponse = serde_json::from_str(
+            r#"{"latitude": 40.7, "longitude": -74.0, "current": {"time": "2024-06-01T12:00", "interval": 900, "uv_index": 7.35}}"#,
+        )
+        .unwrap();
+        assert_eq!(response.current.uv_index, 7.35);
+    }
+
+    #[test]
+    fn load_rules_parses_toml() {
+        let rule_set: RuleSet = toml::from_str(
+            r#"
+            [[rule]]
+            recommendation = "hat"
+            priority = 5
+            min_temp = 70.0
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            rule_set.rules,
+            vec![Rule {
+                recommendation: "hat".to_string(),
+                priority: 5,
+                min_temp: Some(70.0),
+                ..Default::default()
+            }]
+        );
+    }
+
+    fn entry(dt: i64, temp: f64, main: &str, pop: f64) -> ForecastEntry {
+        ForecastEntry {
+            dt,
+            main: ForecastMain { temp },
+            weather: vec![Weather {
+                main: main.to_string(),
+                description: main.to_lowercase(),
+                ..Default::default()
+            }],
+            pop,
+        }
+    }
+
+    fn notify_config(
+        lookahead_hours: i64,
+        min_temp: Option<f64>,
+        max_temp: Option<f64>,
+    ) -> NotifyConfig {
+        NotifyConfig {
+            interval_minutes: DEFAULT_INTERVAL_MINUTES,
+            lookahead_hours,
+            min_temp,
+            max_temp,
+        }
+    }
+
+    #[test]
+    fn forecast_alerts_finds_rain_within_lookahead() {
+        let hour = 3600;
+        let forecast = Forecast {
+            list: vec![
+                entry(0, 60.0, "Clear", 0.0),
+                entry(3 * hour, 58.0, "Clouds", 0.6),
+                entry(6 * hour, 55.0, "Rain", 1.0),
+            ],
+        };
+        let alerts = forecast_alerts(&forecast, 0, &notify_config(3, None, None));
+        assert_eq!(
+            alerts,
+            vec![Alert::Rain {
+                dt: 3 * hour,
+                description: "clouds".to_string()
+            }]
+        );
+        assert_eq!(alerts[0].body(0), "clouds in about 3 hours");
+        assert!(forecast_alerts(&forecast, 0, &notify_config(2, None, None)).is_empty());
+    }
+
+    #[test]
+    fn forecast_alerts_checks_temperature_thresholds() {
+        let hour = 3600;
+        let forecast = Forecast {
+            list: vec![
+                entry(0, 35.0, "Clear", 0.0),
+                entry(3 * hour, 31.0, "Clear", 0.0),
+                entry(6 * hour, 95.0, "Clear", 0.0),
+            ],
+        };
+        let alerts = forecast_alerts(&forecast, hour, &notify_config(6, Some(32.0), Some(90.0)));
+        assert_eq!(
+            alerts,
+            vec![
+                Alert::Cold {
+                    dt: 3 * hour,
+                    temp: 31.0
+                },
+                Alert::Hot {
+                    dt: 6 * hour,
+                    temp: 95.0
+                },
+            ]
+        );
+        assert_eq!(alerts[0].body(hour), "31°F in about 2 hours");
+        assert_ne!(alerts[0].key(), alerts[1].key());
+    }
+
+    #[test]
+    fn parse_notify_args_reads_flags() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+
+        assert_eq!(parse_notify_args(&args(&[])).unwrap(), None);
+        assert_eq!(
+            parse_notify_args(&args(&["--notify"])).unwrap(),
+            Some(notify_config(DEFAULT_LOOKAHEAD_HOURS, None, None))
+        );
+        assert_eq!(
+            parse_notify_args(&args(&[
+                "--notify",
+                "--interval",
+                "10",
+                "--hours",
+                "6",
+                "--min-temp",
+                "32"
+            ]))
+            .unwrap(),
+            Some(NotifyConfig {
+                interval_minutes: 10,
+                ..notify_config(6, Some(32.0), None)
+            })
+        );
+        assert!(parse_notify_args(&args(&["--notify", "--interval", "0"])).is_err());
+        assert!(parse_notify_args(&args(&["--notify", "--max-temp", "hot"])).is_err());
+        assert!(parse_notify_args(&args(&["--notify", "--hours"])).is_err());
+    }
+}
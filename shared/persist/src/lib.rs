@@ -0,0 +1,119 @@
+//! # Atomic File Writes
+//!
+//! Saves files by writing a temporary sibling and renaming it over the original, shared by the
+//! exercises that persist their state between runs.
+//!
+//! ## Features
+//!
+//! - **Atomic Replace**: A failed or interrupted write never leaves a truncated file behind; the
+//!   previous contents stay in place until the new ones are complete
+//! - **JSON Helper**: Saves any serializable value as pretty-printed JSON
+//! - **Private Files**: Saves secrets readable and writable by the owner only (mode 0600 on Unix)
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// The temporary file a save to `path` is written to before it is renamed into place.
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+/// Replaces `path` with whatever `write` produces, leaving the original untouched if it fails.
+pub fn write_atomic(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+) -> io::Result<()> {
+    write_via_temp(path, false, write)
+}
+
+/// Replaces `path` with `contents`, restricting the file to its owner before anything is written.
+pub fn write_private(path: &Path, contents: &[u8]) -> io::Result<()> {
+    write_via_temp(path, true, |writer| writer.write_all(contents))
+}
+
+fn write_via_temp(
+    path: &Path,
+    private: bool,
+    write: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+) -> io::Result<()> {
+    let tmp_path = temp_path(path);
+    let result = File::create(&tmp_path).and_then(|file| {
+        if private {
+            restrict_to_owner(&file)?;
+        }
+        let mut writer = BufWriter::new(file);
+        write(&mut writer)?;
+        writer.flush()
+    });
+    match result {
+        Ok(()) => std::fs::rename(&tmp_path, path),
+        Err(e) => {
+            let _ = std::fs::remove_file(&tmp_path);
+            Err(e)
+        }
+    }
+}
+
+#[cfg(unix)]
+fn restrict_to_owner(file: &File) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))
+}
+
+#[cfg(not(unix))]
+fn restrict_to_owner(_file: &File) -> io::Result<()> {
+    Ok(())
+}
+
+/// Replaces `path` with `value` serialized as pretty-printed JSON.
+pub fn write_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> io::Result<()> {
+    write_atomic(path, |writer| {
+        Ok(serde_json::to_writer_pretty(writer, value)?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_json_replaces_the_file() {
+        let path = std::env::temp_dir().join(format!("persist_json_{}.json", std::process::id()));
+        write_json(&path, &[1, 2]).unwrap();
+        write_json(&path, &[3]).unwrap();
+        let saved: Vec<u32> = serde_json::from_reader(File::open(&path).unwrap()).unwrap();
+        assert_eq!(saved, vec![3]);
+        assert!(!temp_path(&path).exists());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_private_restricts_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+        let path = std::env::temp_dir().join(format!("persist_private_{}.bin", std::process::id()));
+        std::fs::write(&path, "old").unwrap();
+        write_private(&path, b"secret").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(std::fs::read(&path).unwrap(), b"secret");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn failed_write_keeps_the_original() {
+        let path = std::env::temp_dir().join(format!("persist_fail_{}.txt", std::process::id()));
+        std::fs::write(&path, "original").unwrap();
+        let result = write_atomic(&path, |writer| {
+            writer.write_all(b"partial")?;
+            Err(io::Error::other("disk full"))
+        });
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "original");
+        assert!(!temp_path(&path).exists());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
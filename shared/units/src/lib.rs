@@ -0,0 +1,52 @@
+//! # Unit Conversion Tables
+//!
+//! Length and area conversion factors shared by the exercises that convert between units, so
+//! every exercise agrees on the same exact definitions.
+//!
+//! ## Features
+//!
+//! - **Length Factors**: Meters per millimeter, centimeter, kilometer, inch, foot, yard, and mile
+//! - **Area Factors**: Square meters per square centimeter, square foot, square yard, acre, and
+//!   hectare
+
+/// Meters per unit of length.
+pub mod length {
+    pub const MILLIMETER: f64 = 0.001;
+    pub const CENTIMETER: f64 = 0.01;
+    pub const METER: f64 = 1.0;
+    pub const KILOMETER: f64 = 1000.0;
+    pub const INCH: f64 = 0.0254;
+    pub const FOOT: f64 = 0.3048;
+    pub const YARD: f64 = 0.9144;
+    pub const MILE: f64 = 1609.344;
+}
+
+/// Square meters per unit of area.
+pub mod area {
+    pub const SQUARE_CENTIMETER: f64 = 0.0001;
+    pub const SQUARE_FOOT: f64 = 0.09290304;
+    pub const SQUARE_YARD: f64 = 0.83612736;
+    pub const SQUARE_METER: f64 = 1.0;
+    pub const ACRE: f64 = 4046.8564224;
+    pub const HECTARE: f64 = 10_000.0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn square_units_are_squares_of_their_lengths() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12 * b.max(1.0);
+        assert!(close(
+            area::SQUARE_CENTIMETER,
+            length::CENTIMETER * length::CENTIMETER
+        ));
+        assert!(close(area::SQUARE_FOOT, length::FOOT * length::FOOT));
+        assert!(close(area::SQUARE_YARD, length::YARD * length::YARD));
+        assert!(close(area::SQUARE_METER, length::METER * length::METER));
+        // An acre is 43,560 square feet; a hectare is a square 100 m on a side.
+        assert!(close(area::ACRE, 43_560.0 * area::SQUARE_FOOT));
+        assert!(close(area::HECTARE, 100.0 * 100.0 * area::SQUARE_METER));
+    }
+}